@@ -0,0 +1,144 @@
+// Human-readable coin amounts
+// Converts amounts written in whole coins, e.g. "100.5 SUI", to and from
+// on-chain units using the coin's decimals, without going through floats
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use serde::Serialize;
+
+/// Symbol and decimals of a coin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoinUnits {
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+impl CoinUnits {
+    pub fn new(symbol: impl Into<String>, decimals: u32) -> Self {
+        Self {
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+
+    /// Units for a coin whose on-chain scalar is `scalar` (10^decimals)
+    pub fn from_scalar(symbol: impl Into<String>, scalar: u64) -> Result<Self, AggrError> {
+        let symbol = symbol.into();
+        let decimals = scalar.checked_ilog10().unwrap_or(0);
+        if 10u64.pow(decimals) != scalar {
+            return Err(AggrError::InvalidAmount(format!(
+                "{symbol} scalar {scalar} is not a power of ten"
+            )));
+        }
+        Ok(Self::new(symbol, decimals))
+    }
+}
+
+/// An amount in whole coins and in the coin's smallest on-chain units
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoinAmount {
+    /// Whole coins without trailing zeros, e.g. "100.5"
+    pub human: String,
+    /// Smallest units, e.g. 100500000000 for 100.5 SUI
+    pub raw: u64,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+impl CoinAmount {
+    /// Parse `text` as whole coins of `units`: a decimal number optionally
+    /// followed by the coin's symbol ("100.5", "100.5 SUI"). More fractional
+    /// digits than the coin carries are rejected rather than rounded.
+    pub fn parse(text: &str, units: &CoinUnits) -> Result<Self, AggrError> {
+        let invalid = |reason: &str| AggrError::InvalidAmount(format!("{text:?}: {reason}"));
+        let mut parts = text.split_whitespace();
+        let number = parts.next().ok_or_else(|| invalid("empty amount"))?;
+        if let Some(symbol) = parts.next() {
+            if !symbol.eq_ignore_ascii_case(&units.symbol) {
+                return Err(invalid(&format!("expected an amount in {}", units.symbol)));
+            }
+        }
+        if parts.next().is_some() {
+            return Err(invalid("expected \"<amount> [symbol]\""));
+        }
+
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(invalid("not a non-negative decimal number"));
+        }
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > units.decimals as usize {
+            return Err(invalid(&format!(
+                "{} carries at most {} decimals",
+                units.symbol, units.decimals
+            )));
+        }
+
+        let digits = format!(
+            "{whole}{fraction:0<width$}",
+            width = units.decimals as usize
+        );
+        let raw = if digits.is_empty() {
+            0
+        } else {
+            digits
+                .parse::<u64>()
+                .map_err(|_| invalid("too large for the coin"))?
+        };
+        Ok(Self::from_raw(raw, units))
+    }
+
+    /// Amount of `raw` smallest units of `units`
+    pub fn from_raw(raw: u64, units: &CoinUnits) -> Self {
+        Self {
+            human: format_units(raw, units.decimals),
+            raw,
+            symbol: units.symbol.clone(),
+            decimals: units.decimals,
+        }
+    }
+
+    /// Whole coins as a float, the form the DeepBook SDK takes
+    pub fn value(&self) -> f64 {
+        self.human.parse().unwrap_or(0.0)
+    }
+}
+
+/// `raw` smallest units as whole coins, without trailing zeros
+pub fn format_units(raw: u64, decimals: u32) -> String {
+    let digits = format!("{raw:0>width$}", width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Coins a pool trades: `base` is the quantity coin, `quote` the price coin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoolUnits {
+    pub base: CoinUnits,
+    pub quote: CoinUnits,
+}
+
+impl PoolUnits {
+    /// Parse a price in quote coins and a quantity in base coins
+    pub fn order_amounts(&self, price: &str, quantity: &str) -> Result<OrderAmounts, AggrError> {
+        Ok(OrderAmounts {
+            price: CoinAmount::parse(price, &self.quote)?,
+            quantity: CoinAmount::parse(quantity, &self.base)?,
+        })
+    }
+}
+
+/// Price and quantity of an order in human and raw form. The raw price is
+/// quote units per whole base coin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrderAmounts {
+    pub price: CoinAmount,
+    pub quantity: CoinAmount,
+}
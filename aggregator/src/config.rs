@@ -4,15 +4,53 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::control::{
+    AdaptiveRate, BreakerScope, OrderCeiling, DEFAULT_CEILING_WINDOW, DEFAULT_RATE_PER_SEC,
+};
+use crate::quant::PoolParams;
+use crate::retry::RetryPolicy;
+use crate::router::children::{ChildFailurePolicy, ChildSubmissionPolicy};
+use crate::router::digests::{DigestStore, FileDigestStore, MemoryDigestStore, DEFAULT_DIGEST_TTL};
+use crate::router::execution::{DEFAULT_GAS_SAFETY_FACTOR, DEFAULT_STATS_FLUSH_INTERVAL};
+use crate::router::fanout::{
+    FanOutLimits, DEFAULT_BULK_QUOTE_POOL_TIMEOUT, DEFAULT_BULK_QUOTE_WORKERS,
+};
+use crate::router::maintenance::MaintenanceSchedule;
+use crate::router::middleware::{DEFAULT_MAX_BODY_BYTES, DEFAULT_MAX_ORDER_BODY_BYTES};
+use crate::router::quotes::DEFAULT_QUOTE_TTL;
+use crate::router::routes::ReplaceMode;
+use crate::router::selector::EvaluationPolicy;
+use crate::router::sessions::DEFAULT_SESSION_TIMEOUT;
+use crate::signing::{address_for, local_signer, KeyScheme, RemoteSigner, Signer};
+use crate::sponsorship::{
+    GasCoinRefresh, GaslessFallback, DEFAULT_DEBIT_IDEMPOTENCY_TTL, DEFAULT_MAX_TRACKED_USERS,
+};
+use crate::transport::http::{HttpClientConfig, DEFAULT_MAX_RESPONSE_BYTES};
+use crate::venues::adapter::{DEFAULT_MID_PRICE_TTL, DEFAULT_POOL_PARAMS_TTL};
+use crate::venues::amm::CetusPool;
+use crate::venues::book::{DEFAULT_IMPACT_COEFFICIENT, DEFAULT_PARTIAL_FILL_PENALTY};
+use crate::venues::book_export::{
+    BookExportSettings, SnapshotFormat, DEFAULT_EXPORT_MAX_FILES, DEFAULT_EXPORT_MIN_INTERVAL,
+    DEFAULT_EXPORT_TICKS, MAX_EXPORT_TICKS,
+};
+use crate::venues::gas_price::DEFAULT_GAS_PRICE_TTL;
+use crate::venues::snapshots::BookCacheSettings;
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use sui_deepbookv3::utils::config::Environment;
-use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::TypeTag;
 use url::Url;
 
+/// Address the HTTP API listens on when none is configured
+pub const DEFAULT_API_LISTEN_ADDR: &str = "0.0.0.0:8080";
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
     /// gRPC fullnode endpoint, e.g. https://fullnode.mainnet.sui.io:443
@@ -21,27 +59,193 @@ pub struct AppConfig {
     pub jsonrpc_endpoint: Url,
     /// GraphQL RPC + General-Purpose Indexer endpoint (optional)
     pub graphql_endpoint: Option<Url>,
+    /// Seconds to keep retrying transient GraphQL failures (default 30, 0 disables)
+    pub graphql_retry_max_elapsed_secs: Option<u64>,
+    /// Seconds between GraphQL health checks (default 30)
+    pub graphql_health_interval_secs: Option<u64>,
+    /// Report the service unhealthy while a configured GraphQL endpoint is unreachable (default false)
+    pub graphql_required: Option<bool>,
+    /// Seconds to keep resubmitting JSON-RPC executions that return a null result (default 30, 0 disables)
+    pub jsonrpc_empty_result_retry_secs: Option<u64>,
+    /// Open a JSON-RPC connection at startup so the first submission skips connection setup (default true)
+    pub jsonrpc_warm_up: Option<bool>,
     /// DeepBook public indexer (optional; defaults to Mysten Labs public indexer)
     pub deepbook_indexer: Option<Url>,
-    /// Sui address of the trading account
-    pub address: String,
-    /// Hex-encoded 32-byte Ed25519 private key (do not use in prod; replace with HSM)
-    pub ed25519_secret_hex: String,
+    /// Sui address of the trading account; derived from `ed25519_secret_hex` when omitted
+    pub address: Option<String>,
+    /// Hex-encoded 32-byte Ed25519 private key (do not use in prod; replace with HSM).
+    /// Holds a secp256k1 secret instead when `key_scheme` is `secp256k1`.
+    /// Optional only in observer mode.
+    pub ed25519_secret_hex: Option<String>,
+    /// Scheme of `ed25519_secret_hex`: `ed25519` or `secp256k1` (default ed25519)
+    pub key_scheme: Option<KeyScheme>,
+    /// External signing service (e.g. an HSM front end) that signs transactions in
+    /// place of `ed25519_secret_hex`; `address` is then required
+    pub remote_signer_url: Option<Url>,
+    /// Run read-only: no signing key, execution endpoints disabled, no sponsorship
+    pub observer_mode: Option<bool>,
+    /// Compile, sign and simulate every execution but never submit (staging)
+    pub dry_run: Option<bool>,
+    /// Simulate before submitting and size the gas budget from the simulation
+    pub simulate_first: Option<bool>,
+    /// Simulate every transaction before submitting it and refuse those that abort (default false)
+    pub simulate_before_execute: Option<bool>,
+    /// Multiplier on simulated gas when `simulate_first` is set (default 1.2, at least 1)
+    pub gas_safety_factor: Option<f64>,
+    /// Address the HTTP API listens on, e.g. 127.0.0.1:9000 (default 0.0.0.0:8080)
+    pub api_listen_addr: Option<String>,
     /// Concurrency control
     pub max_inflight: usize,
+    /// Orders admitted per second (default 200)
+    pub admission_rate_per_sec: Option<u32>,
+    /// Orders accepted across all callers per rolling window before the rest are shed with 429 (optional)
+    pub order_ceiling: Option<u32>,
+    /// Seconds in the order ceiling's rolling window (default 60)
+    pub order_ceiling_window_secs: Option<u64>,
+    /// Lower the admission rate while the upstream error rate is high (default false)
+    pub adaptive_rate_limit: Option<bool>,
+    /// Error rate in [0, 1] above which the adaptive rate is cut (default 0.1)
+    pub adaptive_rate_error_threshold: Option<f64>,
+    /// Multiplier in (0, 1) applied to the rate while degraded (default 0.5)
+    pub adaptive_rate_decrease_factor: Option<f64>,
+    /// Admissions per second restored each healthy interval (default 10)
+    pub adaptive_rate_increase_step: Option<u32>,
+    /// Floor for the adaptive rate in admissions per second (default 10)
+    pub adaptive_rate_min_per_sec: Option<u32>,
+    /// Milliseconds between adaptive rate adjustments (default 1000)
+    pub adaptive_rate_interval_ms: Option<u64>,
+    /// Server-side cap on the `timeout_ms` query parameter (default 30000)
+    pub max_request_timeout_ms: Option<u64>,
+    /// Warn about HTTP requests slower than this many milliseconds (optional)
+    pub slow_request_threshold_ms: Option<u64>,
+    /// Largest HTTP request body accepted by the API in bytes (default 256 KiB)
+    pub max_body_bytes: Option<usize>,
+    /// Largest body accepted by single-order endpoints in bytes (default 16 KiB)
+    pub max_order_body_bytes: Option<usize>,
+    /// Upper bound on JSON-RPC and GraphQL response bodies in bytes (default 16 MiB)
+    pub max_response_bytes: Option<usize>,
+    /// File used to persist learned route latencies across restarts (optional)
+    pub latency_state_path: Option<PathBuf>,
+    /// File used to persist lifetime execution statistics across restarts (optional)
+    pub execution_stats_path: Option<PathBuf>,
+    /// File of submitted transaction digests kept across restarts; in memory only when unset
+    pub seen_digests_path: Option<PathBuf>,
+    /// Seconds a submitted digest is remembered for duplicate detection (default 3600)
+    pub seen_digests_ttl_secs: Option<u64>,
+    /// Directory /api/v1/admin/book_snapshot writes level2 dumps to; the endpoint is disabled without it
+    pub book_export_dir: Option<PathBuf>,
+    /// Ticks from mid per side in book dumps (default 50)
+    pub book_export_ticks: Option<u64>,
+    /// Book dump format: json or csv (default json)
+    pub book_export_format: Option<SnapshotFormat>,
+    /// Bearer token callers of /api/v1/admin/book_snapshot must present; required with a directory
+    pub book_export_token: Option<String>,
+    /// Seconds between two book dumps (default 10)
+    pub book_export_min_interval_secs: Option<u64>,
+    /// Book dumps refused once the directory holds this many (default 1000)
+    pub book_export_max_files: Option<usize>,
+    /// Seconds between execution statistics flushes (default 60)
+    pub execution_stats_flush_secs: Option<u64>,
+    /// Rebuilds allowed after object version conflicts before giving up (default 3)
+    pub max_conflict_rebuilds: Option<u32>,
     /// Feature switch: use gRPC ExecuteTransaction
     pub use_grpc_execute: Option<bool>,
+    /// Milliseconds of streamed checkpoints batched per confirmation pass (default 0: every checkpoint)
+    pub confirmation_batch_window_ms: Option<u64>,
+    /// Healthy, recently seen validators required to accept orders (default 0: no check)
+    pub min_healthy_validators: Option<usize>,
+    /// Seconds without a checkpoint before the stream is reported unhealthy (optional)
+    pub checkpoint_stale_after_secs: Option<u64>,
+    /// Reject orders while the reference gas price (MIST per unit) is above this (optional)
+    pub max_gas_price: Option<u64>,
+    /// Check before each order that the sender owns or holds a TradeCap for its BalanceManager
+    pub verify_trade_authority: Option<bool>,
+    /// Serve /api/v1/sign_message, signing arbitrary messages with the trading key (default false)
+    pub sign_message_enabled: Option<bool>,
+    /// Bearer token callers of /api/v1/sign_message must present; required when enabled
+    pub sign_message_token: Option<String>,
+    /// Serve /api/v1/build_signed, returning signed orders for external broadcast (default false)
+    pub build_signed_enabled: Option<bool>,
+    /// Bearer token callers of /api/v1/build_signed must present; required when enabled
+    pub build_signed_token: Option<String>,
+    /// Child orders a slicing strategy may have in flight at once (default 1)
+    pub max_parallel_children: Option<usize>,
+    /// What a strategy does after a child order fails: abort or continue (default abort)
+    pub child_failure_policy: Option<ChildFailurePolicy>,
+    /// Circuit breaker granularity: pool, venue or hierarchical (default pool)
+    pub circuit_breaker_scope: Option<BreakerScope>,
+    /// Milliseconds a quote may be committed after it was priced (default 5000)
+    pub quote_ttl_ms: Option<u64>,
+    /// Milliseconds without a session heartbeat before the session's orders are cancelled (default 10000)
+    pub session_timeout_ms: Option<u64>,
+    /// Windows during which new orders are refused, separated by `;`: `<start_ms>-<end_ms>`,
+    /// `daily HH:MM-HH:MM` or `weekly <day> HH:MM-HH:MM`, all UTC (optional)
+    pub maintenance_windows: Option<String>,
+    /// Cancel-replace sequencing: atomic (one PTB) or place_then_cancel (default atomic)
+    pub cancel_replace_mode: Option<ReplaceMode>,
+    /// Venues evaluated per pool, in order, as comma-separated names (optional; default every venue)
+    #[serde(default)]
+    pub route_venue_order: HashMap<String, String>,
+    /// Viable routes after which a selection stops evaluating venues (optional)
+    pub max_route_alternatives: Option<usize>,
+    /// Stop evaluating venues once a route's costs beyond the price are within this many bps of notional (optional)
+    pub route_good_enough_bps: Option<f64>,
+    /// Price move, as a share of price, per multiple of visible depth an order takes beyond the book (default 0.01)
+    pub slippage_impact_coefficient: Option<f64>,
+    /// Share of notional charged for the part of a marketable order visible depth is not expected to fill (default 0.005)
+    pub partial_fill_penalty: Option<f64>,
+    /// Pools a bulk quote evaluates at once (default 8)
+    pub bulk_quote_workers: Option<usize>,
+    /// Milliseconds one pool in a bulk quote may take before it is reported as timed out (default 2000)
+    pub bulk_quote_pool_timeout_ms: Option<u64>,
+    /// Whole-request timeout for JSON-RPC and GraphQL HTTP calls (default 30000)
+    pub http_timeout_ms: Option<u64>,
+    /// Extra headers sent on every JSON-RPC and GraphQL request (optional)
+    #[serde(default)]
+    pub http_headers: HashMap<String, String>,
+    /// Seconds idle pooled HTTP connections are kept open (default 90)
+    pub http_pool_idle_timeout_secs: Option<u64>,
+    /// Seconds between TCP keep-alive probes on HTTP connections (default 60)
+    pub http_tcp_keepalive_secs: Option<u64>,
+    /// Seconds between runtime gRPC readiness probes (default 15)
+    pub grpc_probe_interval_secs: Option<u64>,
+    /// Consecutive probe failures before the endpoint leaves rotation (default 3)
+    pub grpc_probe_failure_threshold: Option<u32>,
     /// DeepBook environment selector (mainnet/testnet)
     pub deepbook_env: Option<String>,
     /// BalanceManager object id (0x...)
     pub deepbook_manager_object: Option<String>,
     /// Label used when registering the BalanceManager with the SDK (defaults MANAGER_1)
     pub deepbook_manager_label: Option<String>,
+    /// TradeCap object id granting the sender trading rights when it does not own the manager
+    pub deepbook_manager_trade_cap: Option<String>,
     /// Optional DeepBook configuration overrides and telemetry toggles
     #[serde(default)]
     pub deepbook_config: Option<DeepBookConfigSection>,
+    /// Cetus CLMM pools routed alongside DeepBook (optional)
+    #[serde(default)]
+    pub cetus: Option<CetusConfigSection>,
     /// Sponsored transaction configuration (optional)
     pub sponsorship: Option<SponsorshipConfig>,
+    /// When a gasless build fails: fail the order or fall back to user_paid (default fail)
+    pub gasless_fallback: Option<GaslessFallback>,
+    /// Additional sponsors that orders may select by alias (optional)
+    #[serde(default)]
+    pub sponsors: Option<HashMap<String, SponsorshipConfig>>,
+    /// Startup self-test order, simulated before serving traffic (optional)
+    pub warmup: Option<WarmupConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WarmupConfig {
+    /// Pool to build the test order against
+    pub pool: String,
+    /// Limit price of the test order
+    pub price: f64,
+    /// Quantity of the test order (keep at the pool minimum)
+    pub quantity: f64,
+    /// Side of the test order (defaults to bid)
+    pub is_bid: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -56,6 +260,9 @@ pub struct DeepBookConfigSection {
     pub balance_managers: Vec<DeepBookBalanceManagerSection>,
     #[serde(default)]
     pub monitored_pools: Vec<String>,
+    /// Extra pool spellings accepted at the API, alias -> canonical key
+    #[serde(default)]
+    pub pool_aliases: HashMap<String, String>,
     pub reconcile_interval_secs: Option<u64>,
     pub indexer_timeout_ms: Option<u64>,
     pub retry_initial_backoff_ms: Option<u64>,
@@ -63,6 +270,21 @@ pub struct DeepBookConfigSection {
     pub retry_multiplier: Option<f64>,
     pub retry_max_elapsed_secs: Option<u64>,
     pub fallback_use_fullnode: Option<bool>,
+    /// Milliseconds a level2 snapshot is reused across quotes; 0 disables (default 250)
+    pub book_cache_ttl_ms: Option<u64>,
+    /// Drop a pool's cached snapshots when a checkpoint changes the pool (default true)
+    pub book_cache_invalidate_on_checkpoint: Option<bool>,
+    /// Seconds the reference gas price is reused before refetching; 0 disables (default 60).
+    /// The epoch watcher reseeds it when the epoch turns over.
+    pub gas_price_ttl_secs: Option<u64>,
+    /// Seconds a pool's tick, lot and minimum size are reused; 0 disables (default 300)
+    pub pool_params_ttl_secs: Option<u64>,
+    /// Milliseconds a pool's mid price is reused across quotes; 0 disables (default 250).
+    /// Dropped early, with the book snapshots, when a checkpoint changes the pool.
+    pub mid_price_ttl_ms: Option<u64>,
+    /// Tick, lot and minimum size used for a pool when the chain or indexer reports invalid ones
+    #[serde(default)]
+    pub pool_params: HashMap<String, PoolParamsSection>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -88,6 +310,37 @@ pub struct DeepBookPoolSection {
     pub quote_coin: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolParamsSection {
+    pub tick_size: f64,
+    pub lot_size: f64,
+    pub min_size: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CetusConfigSection {
+    /// Cetus integrate package whose `pool_script_v2` performs swaps
+    pub package_id: String,
+    /// Cetus `GlobalConfig` shared object
+    pub global_config: String,
+    /// Cetus pool per DeepBook pool key it trades alongside
+    #[serde(default)]
+    pub pools: HashMap<String, CetusPoolSection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CetusPoolSection {
+    pub pool_id: String,
+    /// Full Move type of the pool's coin A
+    pub coin_a: String,
+    /// Full Move type of the pool's coin B
+    pub coin_b: String,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// Whether coin A is the pair's base coin (default true)
+    pub a_is_base: Option<bool>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct DeepBookBalanceManagerSection {
     pub key: String,
@@ -155,6 +408,54 @@ impl DeepBookConfigSection {
         self.fallback_use_fullnode.unwrap_or(true)
     }
 
+    fn pool_param_overrides(&self) -> Result<HashMap<String, PoolParams>> {
+        self.pool_params
+            .iter()
+            .map(|(pool, section)| {
+                let params = PoolParams {
+                    tick_size: section.tick_size,
+                    lot_size: section.lot_size,
+                    min_size: section.min_size,
+                };
+                params
+                    .validate()
+                    .with_context(|| format!("pool params override for {pool}"))?;
+                Ok((pool.clone(), params))
+            })
+            .collect()
+    }
+
+    fn book_cache_settings(&self) -> BookCacheSettings {
+        let defaults = BookCacheSettings::default();
+        BookCacheSettings {
+            ttl: self
+                .book_cache_ttl_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.ttl),
+            invalidate_on_checkpoint: self
+                .book_cache_invalidate_on_checkpoint
+                .unwrap_or(defaults.invalidate_on_checkpoint),
+        }
+    }
+
+    fn gas_price_ttl(&self) -> Duration {
+        self.gas_price_ttl_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_GAS_PRICE_TTL)
+    }
+
+    fn pool_params_ttl(&self) -> Duration {
+        self.pool_params_ttl_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_POOL_PARAMS_TTL)
+    }
+
+    fn mid_price_ttl(&self) -> Duration {
+        self.mid_price_ttl_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_MID_PRICE_TTL)
+    }
+
     fn build_overrides(&self) -> Result<Option<DeepBookOverrideSettings>> {
         let mut overrides = DeepBookOverrideSettings {
             package_ids: self
@@ -299,9 +600,518 @@ impl AppConfig {
         Ok(cfg.try_deserialize()?)
     }
 
+    /// Trading address. Derived from the signing key when `address` is omitted;
+    /// when both are set they must agree, or every order would be signed by a
+    /// key that does not own the sender.
     pub fn sui_address(&self) -> Result<SuiAddress> {
-        SuiAddress::from_str(&self.address)
-            .with_context(|| format!("invalid Sui address: {}", self.address))
+        let configured = self
+            .address
+            .as_deref()
+            .map(|address| {
+                SuiAddress::from_str(address)
+                    .with_context(|| format!("invalid Sui address: {address}"))
+            })
+            .transpose()?;
+        let derived = self
+            .ed25519_secret_hex
+            .as_deref()
+            .map(|secret| address_for(self.key_scheme(), secret))
+            .transpose()
+            .context("derive address from ed25519_secret_hex")?;
+        match (configured, derived) {
+            (Some(configured), Some(derived)) if configured != derived => bail!(
+                "address {configured} does not match {derived}, the address of ed25519_secret_hex"
+            ),
+            (Some(address), _) | (None, Some(address)) => Ok(address),
+            (None, None) => bail!("address is required when ed25519_secret_hex is not set"),
+        }
+    }
+
+    pub fn observer_mode(&self) -> bool {
+        self.observer_mode.unwrap_or(false)
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false)
+    }
+
+    /// Safety factor for simulated gas budgets, `None` unless `simulate_first` is set
+    pub fn simulated_gas_safety_factor(&self) -> Result<Option<f64>> {
+        if !self.simulate_first.unwrap_or(false) {
+            return Ok(None);
+        }
+        match self.gas_safety_factor {
+            Some(factor) if !factor.is_finite() || factor < 1.0 => {
+                bail!("gas safety factor must be at least 1")
+            }
+            Some(factor) => Ok(Some(factor)),
+            None => Ok(Some(DEFAULT_GAS_SAFETY_FACTOR)),
+        }
+    }
+
+    /// Which venues route selection evaluates per pool and when it stops early
+    pub fn route_evaluation_policy(&self) -> Result<EvaluationPolicy> {
+        if self.max_route_alternatives == Some(0) {
+            bail!("max route alternatives must be greater than zero");
+        }
+        if let Some(bps) = self.route_good_enough_bps {
+            if !bps.is_finite() || bps < 0.0 {
+                bail!("route good-enough threshold must be a non-negative number of bps");
+            }
+        }
+        let mut venue_order = HashMap::new();
+        for (pool, venues) in &self.route_venue_order {
+            let venues: Vec<String> = venues
+                .split(',')
+                .map(|venue| venue.trim().to_ascii_lowercase())
+                .filter(|venue| !venue.is_empty())
+                .collect();
+            if venues.is_empty() {
+                bail!("route venue order for {pool} lists no venues");
+            }
+            venue_order.insert(pool.clone(), venues);
+        }
+        Ok(EvaluationPolicy {
+            venue_order,
+            max_alternatives: self.max_route_alternatives,
+            good_enough_bps: self.route_good_enough_bps,
+        })
+    }
+
+    /// Linear impact used to extrapolate slippage past visible depth
+    pub fn slippage_impact_coefficient(&self) -> Result<f64> {
+        match self.slippage_impact_coefficient {
+            Some(coefficient) if !coefficient.is_finite() || coefficient < 0.0 => {
+                bail!("slippage impact coefficient must be a non-negative number")
+            }
+            Some(coefficient) => Ok(coefficient),
+            None => Ok(DEFAULT_IMPACT_COEFFICIENT),
+        }
+    }
+
+    /// Route cost charged per unit of notional a marketable order leaves unfilled
+    pub fn partial_fill_penalty(&self) -> Result<f64> {
+        match self.partial_fill_penalty {
+            Some(penalty) if !penalty.is_finite() || penalty < 0.0 => {
+                bail!("partial fill penalty must be a non-negative number")
+            }
+            Some(penalty) => Ok(penalty),
+            None => Ok(DEFAULT_PARTIAL_FILL_PENALTY),
+        }
+    }
+
+    /// Concurrency and per-pool timeout for bulk quotes
+    pub fn bulk_quote_limits(&self) -> Result<FanOutLimits> {
+        let workers = self
+            .bulk_quote_workers
+            .unwrap_or(DEFAULT_BULK_QUOTE_WORKERS);
+        if workers == 0 {
+            bail!("bulk quote workers must be greater than zero");
+        }
+        let item_timeout = match self.bulk_quote_pool_timeout_ms {
+            Some(0) => bail!("bulk quote pool timeout must be greater than zero"),
+            Some(ms) => Duration::from_millis(ms),
+            None => DEFAULT_BULK_QUOTE_POOL_TIMEOUT,
+        };
+        Ok(FanOutLimits {
+            workers,
+            item_timeout,
+        })
+    }
+
+    /// Bearer token for the message-signing endpoint, `None` when it is disabled
+    pub fn sign_message_token(&self) -> Result<Option<String>> {
+        if !self.sign_message_enabled.unwrap_or(false) {
+            return Ok(None);
+        }
+        if self.observer_mode() {
+            bail!("message signing cannot be enabled in observer mode");
+        }
+        match self.sign_message_token.as_deref().map(str::trim) {
+            Some(token) if token.len() >= 16 => Ok(Some(token.to_string())),
+            Some(_) => bail!("sign_message_token must be at least 16 characters"),
+            None => bail!("sign_message_token is required when sign_message_enabled is set"),
+        }
+    }
+
+    /// Bearer token for the build-signed endpoint, `None` when it is disabled
+    pub fn build_signed_token(&self) -> Result<Option<String>> {
+        if !self.build_signed_enabled.unwrap_or(false) {
+            return Ok(None);
+        }
+        if self.observer_mode() {
+            bail!("build_signed cannot be enabled in observer mode");
+        }
+        match self.build_signed_token.as_deref().map(str::trim) {
+            Some(token) if token.len() >= 16 => Ok(Some(token.to_string())),
+            Some(_) => bail!("build_signed_token must be at least 16 characters"),
+            None => bail!("build_signed_token is required when build_signed_enabled is set"),
+        }
+    }
+
+    /// Scheme of the trading key
+    pub fn key_scheme(&self) -> KeyScheme {
+        self.key_scheme.unwrap_or_default()
+    }
+
+    /// Signing key for execution; empty in observer mode, where nothing is signed
+    pub fn signing_key_hex(&self) -> Result<String> {
+        match (&self.ed25519_secret_hex, self.observer_mode()) {
+            (Some(key), _) => Ok(key.clone()),
+            (None, true) => Ok(String::new()),
+            (None, false) => bail!("ed25519_secret_hex is required unless observer_mode is set"),
+        }
+    }
+
+    /// Transaction signer: the remote signer when configured, else the local key
+    pub fn signer(&self) -> Result<Arc<dyn Signer>> {
+        match &self.remote_signer_url {
+            Some(url) => Ok(Arc::new(
+                RemoteSigner::new(url.clone(), self.sui_address()?)
+                    .context("build remote signer client")?,
+            )),
+            None => Ok(local_signer(self.key_scheme(), self.signing_key_hex()?)),
+        }
+    }
+
+    /// Retry policy for transient GraphQL failures, `None` when disabled
+    pub fn graphql_retry_policy(&self) -> Option<RetryPolicy> {
+        match self.graphql_retry_max_elapsed_secs {
+            Some(0) => None,
+            Some(secs) => Some(RetryPolicy::new(
+                Duration::from_millis(200),
+                Duration::from_secs(5),
+                Duration::from_secs(secs),
+                2.0,
+            )),
+            None => Some(RetryPolicy::default()),
+        }
+    }
+
+    /// Retry policy for null JSON-RPC execution results, `None` when disabled
+    pub fn jsonrpc_empty_result_retry_policy(&self) -> Option<RetryPolicy> {
+        match self.jsonrpc_empty_result_retry_secs {
+            Some(0) => None,
+            Some(secs) => Some(RetryPolicy::new(
+                Duration::from_millis(200),
+                Duration::from_secs(5),
+                Duration::from_secs(secs),
+                2.0,
+            )),
+            None => Some(RetryPolicy::default()),
+        }
+    }
+
+    /// Socket address for the HTTP API listener
+    pub fn api_listen_addr(&self) -> Result<SocketAddr> {
+        let addr = self
+            .api_listen_addr
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or(DEFAULT_API_LISTEN_ADDR);
+        addr.parse()
+            .with_context(|| format!("invalid API listen address {addr:?}; expected host:port"))
+    }
+
+    pub fn max_response_bytes(&self) -> Result<usize> {
+        match self.max_response_bytes {
+            Some(0) => bail!("max response bytes must be greater than zero"),
+            Some(bytes) => Ok(bytes),
+            None => Ok(DEFAULT_MAX_RESPONSE_BYTES),
+        }
+    }
+
+    pub fn max_request_timeout(&self) -> Result<Duration> {
+        match self.max_request_timeout_ms {
+            Some(0) => bail!("max request timeout must be greater than zero"),
+            Some(ms) => Ok(Duration::from_millis(ms)),
+            None => Ok(Duration::from_secs(30)),
+        }
+    }
+
+    pub fn confirmation_batch_window(&self) -> Duration {
+        Duration::from_millis(self.confirmation_batch_window_ms.unwrap_or(0))
+    }
+
+    /// How often lifetime execution statistics are written to disk
+    pub fn execution_stats_flush_interval(&self) -> Result<Duration> {
+        match self.execution_stats_flush_secs {
+            Some(0) => bail!("execution stats flush interval must be greater than zero"),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Ok(DEFAULT_STATS_FLUSH_INTERVAL),
+        }
+    }
+
+    /// How long submitted digests are remembered
+    pub fn seen_digests_ttl(&self) -> Result<Duration> {
+        match self.seen_digests_ttl_secs {
+            Some(0) => bail!("seen digests TTL must be greater than zero"),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Ok(DEFAULT_DIGEST_TTL),
+        }
+    }
+
+    /// Digest store for duplicate detection: file-backed when a path is set
+    pub fn digest_store(&self) -> Result<Arc<dyn DigestStore>> {
+        let ttl = self.seen_digests_ttl()?;
+        Ok(match &self.seen_digests_path {
+            Some(path) => Arc::new(
+                FileDigestStore::open(path, ttl)
+                    .with_context(|| format!("open seen digests file {}", path.display()))?,
+            ),
+            None => Arc::new(MemoryDigestStore::new(ttl)),
+        })
+    }
+
+    pub fn slow_request_threshold(&self) -> Result<Option<Duration>> {
+        match self.slow_request_threshold_ms {
+            Some(0) => bail!("slow request threshold must be greater than zero"),
+            ms => Ok(ms.map(Duration::from_millis)),
+        }
+    }
+
+    pub fn admission_rate_per_sec(&self) -> Result<u32> {
+        match self.admission_rate_per_sec {
+            Some(0) => bail!("admission rate must be greater than zero"),
+            Some(rate) => Ok(rate),
+            None => Ok(DEFAULT_RATE_PER_SEC),
+        }
+    }
+
+    /// Global order ceiling, `None` unless one is configured
+    pub fn order_ceiling(&self) -> Result<Option<OrderCeiling>> {
+        let window = match self.order_ceiling_window_secs {
+            Some(0) => bail!("order ceiling window must be greater than zero"),
+            Some(secs) => Duration::from_secs(secs),
+            None => DEFAULT_CEILING_WINDOW,
+        };
+        match self.order_ceiling {
+            Some(0) => bail!("order ceiling must be greater than zero"),
+            Some(max_orders) => Ok(Some(OrderCeiling::new(max_orders, window))),
+            None => Ok(None),
+        }
+    }
+
+    /// AIMD parameters when the adaptive rate limit is enabled
+    pub fn adaptive_rate(&self) -> Result<Option<AdaptiveRate>> {
+        if !self.adaptive_rate_limit.unwrap_or(false) {
+            return Ok(None);
+        }
+        let defaults = AdaptiveRate::default();
+        let error_threshold = self
+            .adaptive_rate_error_threshold
+            .unwrap_or(defaults.error_threshold);
+        if !(0.0..1.0).contains(&error_threshold) {
+            bail!("adaptive rate error threshold must be in [0, 1)");
+        }
+        let decrease_factor = self
+            .adaptive_rate_decrease_factor
+            .unwrap_or(defaults.decrease_factor);
+        if !(0.0..1.0).contains(&decrease_factor) || decrease_factor == 0.0 {
+            bail!("adaptive rate decrease factor must be between 0 and 1");
+        }
+        let increase_step = self
+            .adaptive_rate_increase_step
+            .unwrap_or(defaults.increase_step);
+        if increase_step == 0 {
+            bail!("adaptive rate increase step must be greater than zero");
+        }
+        let min_rate_per_sec = self
+            .adaptive_rate_min_per_sec
+            .unwrap_or(defaults.min_rate_per_sec);
+        if min_rate_per_sec == 0 {
+            bail!("adaptive rate floor must be greater than zero");
+        }
+        let interval = match self.adaptive_rate_interval_ms {
+            Some(0) => bail!("adaptive rate interval must be greater than zero"),
+            Some(ms) => Duration::from_millis(ms),
+            None => defaults.interval,
+        };
+        Ok(Some(AdaptiveRate {
+            error_threshold,
+            decrease_factor,
+            increase_step,
+            min_rate_per_sec,
+            interval,
+        }))
+    }
+
+    /// Book snapshot export settings, `None` unless a directory is configured
+    pub fn book_export(&self) -> Result<Option<BookExportSettings>> {
+        let Some(dir) = &self.book_export_dir else {
+            return Ok(None);
+        };
+        let ticks = self.book_export_ticks.unwrap_or(DEFAULT_EXPORT_TICKS);
+        if ticks == 0 || ticks > MAX_EXPORT_TICKS {
+            bail!("book export ticks must be between 1 and {MAX_EXPORT_TICKS}");
+        }
+        let token = match self.book_export_token.as_deref().map(str::trim) {
+            Some(token) if token.len() >= 16 => token.to_string(),
+            Some(_) => bail!("book_export_token must be at least 16 characters"),
+            None => bail!("book_export_token is required when book_export_dir is set"),
+        };
+        let max_files = match self.book_export_max_files {
+            Some(0) => bail!("book export max files must be greater than zero"),
+            Some(max) => max,
+            None => DEFAULT_EXPORT_MAX_FILES,
+        };
+        Ok(Some(BookExportSettings {
+            dir: dir.clone(),
+            ticks,
+            format: self.book_export_format.unwrap_or_default(),
+            token,
+            min_interval: self
+                .book_export_min_interval_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_EXPORT_MIN_INTERVAL),
+            max_files,
+        }))
+    }
+
+    /// Router-wide and single-order request body caps
+    pub fn body_limits(&self) -> Result<(usize, usize)> {
+        let body = match self.max_body_bytes {
+            Some(0) => bail!("max body bytes must be greater than zero"),
+            Some(bytes) => bytes,
+            None => DEFAULT_MAX_BODY_BYTES,
+        };
+        let order = match self.max_order_body_bytes {
+            Some(0) => bail!("max order body bytes must be greater than zero"),
+            Some(bytes) => bytes,
+            None => DEFAULT_MAX_ORDER_BODY_BYTES.min(body),
+        };
+        Ok((body, order))
+    }
+
+    pub fn checkpoint_stale_after(&self) -> Result<Option<Duration>> {
+        match self.checkpoint_stale_after_secs {
+            Some(0) => bail!("checkpoint staleness interval must be greater than zero"),
+            secs => Ok(secs.map(Duration::from_secs)),
+        }
+    }
+
+    pub fn max_gas_price(&self) -> Result<Option<u64>> {
+        match self.max_gas_price {
+            Some(0) => bail!("max gas price must be greater than zero"),
+            price => Ok(price),
+        }
+    }
+
+    pub fn child_submission_policy(&self) -> Result<ChildSubmissionPolicy> {
+        let defaults = ChildSubmissionPolicy::default();
+        let max_parallel = match self.max_parallel_children {
+            Some(0) => bail!("max parallel children must be greater than zero"),
+            Some(limit) => limit,
+            None => defaults.max_parallel,
+        };
+        Ok(ChildSubmissionPolicy {
+            max_parallel,
+            on_failure: self.child_failure_policy.unwrap_or(defaults.on_failure),
+        })
+    }
+
+    pub fn quote_ttl(&self) -> Result<Duration> {
+        match self.quote_ttl_ms {
+            Some(0) => bail!("quote TTL must be greater than zero"),
+            Some(ms) => Ok(Duration::from_millis(ms)),
+            None => Ok(DEFAULT_QUOTE_TTL),
+        }
+    }
+
+    pub fn session_timeout(&self) -> Result<Duration> {
+        match self.session_timeout_ms {
+            Some(0) => bail!("session timeout must be greater than zero"),
+            Some(ms) => Ok(Duration::from_millis(ms)),
+            None => Ok(DEFAULT_SESSION_TIMEOUT),
+        }
+    }
+
+    pub fn maintenance_schedule(&self) -> Result<MaintenanceSchedule> {
+        match &self.maintenance_windows {
+            Some(windows) => windows.parse().context("invalid maintenance windows"),
+            None => Ok(MaintenanceSchedule::default()),
+        }
+    }
+
+    /// Shared settings for the JSON-RPC and GraphQL HTTP clients
+    pub fn http_client_config(&self) -> Result<HttpClientConfig> {
+        let mut config = HttpClientConfig::default();
+        match self.http_timeout_ms {
+            Some(0) => bail!("HTTP timeout must be greater than zero"),
+            Some(ms) => config = config.with_timeout(Duration::from_millis(ms)),
+            None => {}
+        }
+        if let Some(secs) = self.http_pool_idle_timeout_secs {
+            config = config.with_pool_idle_timeout(Duration::from_secs(secs));
+        }
+        match self.http_tcp_keepalive_secs {
+            Some(0) => bail!("HTTP TCP keep-alive interval must be greater than zero"),
+            Some(secs) => config = config.with_tcp_keepalive(Duration::from_secs(secs)),
+            None => {}
+        }
+        for (name, value) in &self.http_headers {
+            config = config.with_header(name.clone(), value.clone());
+        }
+        Ok(config)
+    }
+
+    pub fn graphql_health_interval(&self) -> Result<Duration> {
+        match self.graphql_health_interval_secs {
+            Some(0) => bail!("GraphQL health check interval must be greater than zero"),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Ok(Duration::from_secs(30)),
+        }
+    }
+
+    pub fn grpc_probe_interval(&self) -> Result<Duration> {
+        match self.grpc_probe_interval_secs {
+            Some(0) => bail!("gRPC probe interval must be greater than zero"),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Ok(Duration::from_secs(15)),
+        }
+    }
+
+    pub fn grpc_probe_failure_threshold(&self) -> Result<u32> {
+        match self.grpc_probe_failure_threshold {
+            Some(0) => bail!("gRPC probe failure threshold must be greater than zero"),
+            Some(threshold) => Ok(threshold),
+            None => Ok(3),
+        }
+    }
+
+    pub fn cetus_settings(&self) -> Result<Option<CetusSettings>> {
+        let Some(section) = &self.cetus else {
+            return Ok(None);
+        };
+        let object_id = |id: &str, what: &str| {
+            ObjectID::from_hex_literal(id).with_context(|| format!("invalid Cetus {what} {id}"))
+        };
+        let mut pools = HashMap::new();
+        for (key, pool) in &section.pools {
+            for coin in [&pool.coin_a, &pool.coin_b] {
+                TypeTag::from_str(coin)
+                    .with_context(|| format!("invalid coin type {coin} for Cetus pool {key}"))?;
+            }
+            pools.insert(
+                key.clone(),
+                CetusPool {
+                    pool_id: object_id(&pool.pool_id, "pool")?,
+                    coin_a: pool.coin_a.clone(),
+                    coin_b: pool.coin_b.clone(),
+                    decimals_a: pool.decimals_a,
+                    decimals_b: pool.decimals_b,
+                    a_is_base: pool.a_is_base.unwrap_or(true),
+                },
+            );
+        }
+        if pools.is_empty() {
+            bail!("Cetus is configured without any pools");
+        }
+        Ok(Some(CetusSettings {
+            package_id: object_id(&section.package_id, "package")?,
+            global_config: object_id(&section.global_config, "global config")?,
+            pools,
+        }))
     }
 
     pub fn deepbook_settings(&self) -> Result<Option<DeepBookSettings>> {
@@ -333,33 +1143,57 @@ impl AppConfig {
         let (
             overrides,
             monitored_pools,
+            pool_aliases,
             reconcile_interval,
             indexer_timeout,
             retry,
             fallback_use_fullnode,
+            book_cache,
+            gas_price_ttl,
+            pool_params_ttl,
+            mid_price_ttl,
+            pool_params,
         ) = if let Some(section) = &self.deepbook_config {
             let overrides = section.build_overrides()?;
             let monitored_pools = section.monitored_pool_keys();
+            let pool_aliases = section.pool_aliases.clone();
             let reconcile_interval = section.reconcile_interval()?;
             let indexer_timeout = section.indexer_timeout()?;
             let retry = section.retry_settings()?;
             let fallback_use_fullnode = section.fallback_use_fullnode();
+            let book_cache = section.book_cache_settings();
+            let gas_price_ttl = section.gas_price_ttl();
+            let pool_params_ttl = section.pool_params_ttl();
+            let mid_price_ttl = section.mid_price_ttl();
+            let pool_params = section.pool_param_overrides()?;
             (
                 overrides,
                 monitored_pools,
+                pool_aliases,
                 reconcile_interval,
                 indexer_timeout,
                 retry,
                 fallback_use_fullnode,
+                book_cache,
+                gas_price_ttl,
+                pool_params_ttl,
+                mid_price_ttl,
+                pool_params,
             )
         } else {
             (
                 None,
                 Vec::new(),
+                HashMap::new(),
                 Duration::from_secs(300),
                 Duration::from_secs(5),
                 DeepBookRetrySettings::default(),
                 true,
+                BookCacheSettings::default(),
+                DEFAULT_GAS_PRICE_TTL,
+                DEFAULT_POOL_PARAMS_TTL,
+                DEFAULT_MID_PRICE_TTL,
+                HashMap::new(),
             )
         };
 
@@ -368,12 +1202,19 @@ impl AppConfig {
             environment,
             balance_manager_object: manager_object,
             balance_manager_label: manager_label,
+            balance_manager_trade_cap: self.deepbook_manager_trade_cap.clone(),
             overrides,
             monitored_pools,
+            pool_aliases,
             reconcile_interval,
             indexer_timeout,
             retry,
             fallback_use_fullnode,
+            book_cache,
+            gas_price_ttl,
+            pool_params_ttl,
+            mid_price_ttl,
+            pool_params,
         }))
     }
 }
@@ -384,20 +1225,45 @@ pub struct DeepBookSettings {
     pub environment: Environment,
     pub balance_manager_object: String,
     pub balance_manager_label: String,
+    pub balance_manager_trade_cap: Option<String>,
     pub overrides: Option<DeepBookOverrideSettings>,
     pub monitored_pools: Vec<String>,
+    pub pool_aliases: HashMap<String, String>,
     pub reconcile_interval: Duration,
     pub indexer_timeout: Duration,
     pub retry: DeepBookRetrySettings,
     pub fallback_use_fullnode: bool,
+    pub book_cache: BookCacheSettings,
+    /// How long the reference gas price is cached
+    pub gas_price_ttl: Duration,
+    /// How long a pool's tick, lot and minimum size are cached
+    pub pool_params_ttl: Duration,
+    /// How long a pool's mid price is cached
+    pub mid_price_ttl: Duration,
+    /// Per-pool params used when the fetched ones are invalid
+    pub pool_params: HashMap<String, PoolParams>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CetusSettings {
+    pub package_id: ObjectID,
+    pub global_config: ObjectID,
+    /// Cetus pool by DeepBook pool key
+    pub pools: HashMap<String, CetusPool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SponsorshipConfig {
     /// Sponsor's Sui address
     pub sponsor_address: String,
-    /// Hex-encoded 32-byte Ed25519 private key for sponsor (do not use in prod; replace with HSM)
+    /// Hex-encoded 32-byte private key for sponsor (do not use in prod; replace with HSM).
+    /// May be omitted when `remote_signer_url` is set.
+    #[serde(default)]
     pub sponsor_key_hex: String,
+    /// Scheme of `sponsor_key_hex`: `ed25519` or `secp256k1` (default ed25519)
+    pub key_scheme: Option<KeyScheme>,
+    /// External signing service holding the sponsor key, used instead of `sponsor_key_hex`
+    pub remote_signer_url: Option<Url>,
     /// Per-user budget (in gas units)
     pub per_user_budget: Option<u64>,
     /// Per-transaction limit (in gas units)
@@ -410,11 +1276,68 @@ pub struct SponsorshipConfig {
     pub max_gas_per_window: Option<u64>,
     /// Abuse detection window duration in seconds
     pub abuse_window_seconds: Option<u64>,
+    /// Most users with tracked abuse metrics (default 100000)
+    pub max_tracked_users: Option<usize>,
+    /// Seconds an idempotency key is remembered so retries are not debited twice (default 600)
+    pub debit_idempotency_ttl_secs: Option<u64>,
+    /// Seconds between refreshes of the sponsor's gas coins (default 30)
+    pub gas_coin_refresh_secs: Option<u64>,
+    /// Smallest coin balance in MIST used for sponsored gas (default 50000000)
+    pub min_gas_coin_balance: Option<u64>,
+    /// Usable gas coins below which refreshes log a warning (default 2)
+    pub gas_coin_low_water: Option<usize>,
 }
 
 impl SponsorshipConfig {
+    /// Sponsor signer: the remote signer when configured, else the local key
+    pub fn signer(&self) -> Result<Arc<dyn Signer>> {
+        match &self.remote_signer_url {
+            Some(url) => Ok(Arc::new(
+                RemoteSigner::new(url.clone(), self.sponsor_address_parsed()?)
+                    .context("build sponsor remote signer client")?,
+            )),
+            None if self.sponsor_key_hex.is_empty() => {
+                bail!("sponsor_key_hex is required unless remote_signer_url is set")
+            }
+            None => Ok(local_signer(
+                self.key_scheme.unwrap_or_default(),
+                self.sponsor_key_hex.clone(),
+            )),
+        }
+    }
+
     pub fn sponsor_address_parsed(&self) -> Result<SuiAddress> {
         SuiAddress::from_str(&self.sponsor_address)
             .with_context(|| format!("invalid sponsor address: {}", self.sponsor_address))
     }
+
+    pub fn max_tracked_users(&self) -> Result<usize> {
+        match self.max_tracked_users {
+            Some(0) => bail!("max tracked users must be greater than zero"),
+            Some(users) => Ok(users),
+            None => Ok(DEFAULT_MAX_TRACKED_USERS),
+        }
+    }
+
+    pub fn debit_idempotency_ttl(&self) -> Result<Duration> {
+        match self.debit_idempotency_ttl_secs {
+            Some(0) => bail!("debit idempotency TTL must be greater than zero"),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Ok(DEFAULT_DEBIT_IDEMPOTENCY_TTL),
+        }
+    }
+
+    pub fn gas_coin_refresh(&self) -> Result<GasCoinRefresh> {
+        let defaults = GasCoinRefresh::default();
+        let interval = match self.gas_coin_refresh_secs {
+            Some(0) => bail!("gas coin refresh interval must be greater than zero"),
+            Some(secs) => Duration::from_secs(secs),
+            None => defaults.interval,
+        };
+        Ok(GasCoinRefresh {
+            interval,
+            min_balance: self.min_gas_coin_balance.unwrap_or(defaults.min_balance),
+            low_water: self.gas_coin_low_water.unwrap_or(defaults.low_water),
+        })
+    }
 }
@@ -1,42 +1,148 @@
 // Control plane: admission control and circuit breakers
 //
 // Provides simple concurrency limiting, rate limiting, and per-route-class
-// circuit breakers with sliding-window failure tracking.
+// circuit breakers with sliding-window failure tracking. Breaker classes are
+// keyed `venue:pool` or `venue` depending on the configured scope. The rate
+// limit can adapt (AIMD) to the upstream error rate the breakers observe.
+// A global order ceiling sheds bursts outright instead of queuing them.
 //
 // Numan Thabit 2025 Nov
 
+use crate::errors::AggrError;
+use crate::metrics::{ADMISSION_RATE_PER_SEC, INFLIGHT_PERMITS, MAX_INFLIGHT_PERMITS, ORDERS_SHED};
+use serde::Deserialize;
 use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
-use tracing::debug;
+use tracing::{debug, info};
+
+/// Admissions per second when no rate is configured
+pub const DEFAULT_RATE_PER_SEC: u32 = 200;
+/// Breaker outcomes kept for the aggregate upstream error rate
+const ERROR_RATE_WINDOW: usize = 200;
+/// Outcomes needed before the aggregate error rate is reported
+const ERROR_RATE_MIN_SAMPLES: usize = 20;
+
+/// AIMD parameters for adapting the admission rate to the upstream error rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveRate {
+    /// Error rate in [0, 1] above which the rate is cut
+    pub error_threshold: f64,
+    /// Multiplier in (0, 1) applied to the rate on each degraded interval
+    pub decrease_factor: f64,
+    /// Admissions per second added back on each healthy interval
+    pub increase_step: u32,
+    /// Floor the rate is never cut below
+    pub min_rate_per_sec: u32,
+    /// How often the rate is re-evaluated
+    pub interval: Duration,
+}
+
+impl Default for AdaptiveRate {
+    fn default() -> Self {
+        Self {
+            error_threshold: 0.1,
+            decrease_factor: 0.5,
+            increase_step: 10,
+            min_rate_per_sec: 10,
+            interval: Duration::from_secs(1),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AdmissionControl {
     max_inflight: Arc<Semaphore>,
+    limit: usize,
     // Simple rate limiter: allow up to rate_per_sec within a 1s sliding window
     inner: Arc<Mutex<RateLimiter>>,
+    adaptive: Option<AdaptiveRate>,
 }
 
 struct RateLimiter {
+    /// Effective rate; below `ceiling` while adaptation is backing off
     rate_per_sec: u32,
+    /// Configured rate
+    ceiling: u32,
     timestamps: VecDeque<Instant>,
     window: Duration,
 }
 
 impl AdmissionControl {
     pub fn new(max_inflight: usize, rate_per_sec: Option<u32>) -> Self {
+        let rate_per_sec = rate_per_sec.unwrap_or(DEFAULT_RATE_PER_SEC);
         let rl = RateLimiter {
-            rate_per_sec: rate_per_sec.unwrap_or(200),
+            rate_per_sec,
+            ceiling: rate_per_sec,
             timestamps: VecDeque::with_capacity(256),
             window: Duration::from_secs(1),
         };
+        MAX_INFLIGHT_PERMITS.set(max_inflight as i64);
+        INFLIGHT_PERMITS.set(0);
+        ADMISSION_RATE_PER_SEC.set(rate_per_sec as i64);
         Self {
             max_inflight: Arc::new(Semaphore::new(max_inflight)),
+            limit: max_inflight,
             inner: Arc::new(Mutex::new(rl)),
+            adaptive: None,
         }
     }
 
+    /// Adapt the rate limit to the upstream error rate with `params`
+    pub fn with_adaptive_rate(mut self, params: AdaptiveRate) -> Self {
+        self.adaptive = Some(params);
+        self
+    }
+
+    pub fn adaptive_rate(&self) -> Option<&AdaptiveRate> {
+        self.adaptive.as_ref()
+    }
+
+    /// Admissions per second currently allowed
+    pub async fn effective_rate(&self) -> u32 {
+        self.inner.lock().await.rate_per_sec
+    }
+
+    /// One AIMD step: cut the rate multiplicatively while `error_rate` exceeds
+    /// the threshold, otherwise restore it additively up to the configured rate.
+    /// `None` (too few samples to judge) counts as healthy. Returns the new rate;
+    /// a no-op unless adaptation is enabled.
+    pub async fn adapt(&self, error_rate: Option<f64>) -> u32 {
+        let mut guard = self.inner.lock().await;
+        let Some(params) = &self.adaptive else {
+            return guard.rate_per_sec;
+        };
+        let previous = guard.rate_per_sec;
+        let floor = params.min_rate_per_sec.min(guard.ceiling);
+        guard.rate_per_sec = match error_rate {
+            Some(rate) if rate > params.error_threshold => {
+                ((previous as f64 * params.decrease_factor) as u32).max(floor)
+            }
+            _ => previous
+                .saturating_add(params.increase_step)
+                .min(guard.ceiling),
+        };
+        if guard.rate_per_sec != previous {
+            ADMISSION_RATE_PER_SEC.set(guard.rate_per_sec as i64);
+            info!(
+                previous,
+                rate_per_sec = guard.rate_per_sec,
+                error_rate = ?error_rate,
+                "adapted admission rate"
+            );
+        }
+        guard.rate_per_sec
+    }
+
+    /// Permits currently held
+    pub fn inflight(&self) -> usize {
+        self.limit
+            .saturating_sub(self.max_inflight.available_permits())
+    }
+
     /// Acquire an admission permit respecting max inflight and rate limit.
     pub async fn acquire(&self) -> AdmissionPermit {
         // Rate limit loop
@@ -63,17 +169,187 @@ impl AdmissionControl {
             .acquire_owned()
             .await
             .expect("semaphore not closed");
-        AdmissionPermit { _permit: permit }
+        INFLIGHT_PERMITS.set(self.inflight() as i64);
+        AdmissionPermit {
+            permit: Some(permit),
+            admission: self.clone(),
+        }
+    }
+
+    /// Run `work` holding an admission permit. The permit is released when
+    /// `work` finishes, whatever its outcome, or when it is dropped unfinished.
+    pub async fn admit<Fut: Future>(&self, work: Fut) -> Fut::Output {
+        let _permit = self.acquire().await;
+        work.await
     }
 }
 
 pub struct AdmissionPermit {
-    _permit: tokio::sync::OwnedSemaphorePermit,
+    permit: Option<tokio::sync::OwnedSemaphorePermit>,
+    admission: AdmissionControl,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        // Release first so the gauge reflects the freed permit
+        drop(self.permit.take());
+        INFLIGHT_PERMITS.set(self.admission.inflight() as i64);
+    }
+}
+
+/// Rolling window of the order ceiling when none is configured
+pub const DEFAULT_CEILING_WINDOW: Duration = Duration::from_secs(60);
+
+/// Hard cap on orders across all callers per rolling window. Unlike the rate
+/// limiter, which delays admissions, orders over the ceiling are refused at
+/// once so a runaway client cannot build a queue.
+#[derive(Clone)]
+pub struct OrderCeiling {
+    max_orders: u32,
+    window: Duration,
+    admitted: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+impl OrderCeiling {
+    pub fn new(max_orders: u32, window: Duration) -> Self {
+        Self {
+            max_orders,
+            window,
+            admitted: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    pub fn max_orders(&self) -> u32 {
+        self.max_orders
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Count one order, or refuse it with `AggrError::Overloaded` when the
+    /// window is already full
+    pub async fn try_admit(&self) -> Result<(), AggrError> {
+        self.try_admit_at(Instant::now()).await
+    }
+
+    /// `try_admit` as of `now`
+    pub async fn try_admit_at(&self, now: Instant) -> Result<(), AggrError> {
+        let mut admitted = self.admitted.lock().await;
+        while let Some(front) = admitted.front() {
+            if now.saturating_duration_since(*front) >= self.window {
+                admitted.pop_front();
+            } else {
+                break;
+            }
+        }
+        if (admitted.len() as u32) < self.max_orders {
+            admitted.push_back(now);
+            return Ok(());
+        }
+        ORDERS_SHED.inc();
+        let retry_after = admitted
+            .front()
+            .map(|oldest| {
+                self.window
+                    .saturating_sub(now.saturating_duration_since(*oldest))
+            })
+            .unwrap_or(self.window);
+        Err(AggrError::Overloaded {
+            limit: self.max_orders,
+            window_ms: self.window.as_millis() as u64,
+            retry_after_ms: retry_after.as_millis() as u64,
+        })
+    }
+}
+
+/// Granularity at which execution failures trip circuit breakers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerScope {
+    /// One breaker per `venue:pool`; a failing pool does not affect others
+    #[default]
+    Pool,
+    /// One breaker per venue shared by all of its pools
+    Venue,
+    /// Both: pool failures count against the pool and its venue, and either
+    /// being open blocks the route
+    Hierarchical,
+}
+
+impl BreakerScope {
+    /// Breaker keys `class` is tracked under
+    pub fn keys(&self, class: &BreakerClass) -> Vec<String> {
+        match (self, class.pool_key()) {
+            (BreakerScope::Pool, Some(pool)) => vec![pool],
+            (BreakerScope::Hierarchical, Some(pool)) => vec![pool, class.venue_key()],
+            _ => vec![class.venue_key()],
+        }
+    }
+}
+
+/// Venue and optional pool a route executes against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakerClass {
+    pub venue: String,
+    pub pool: Option<String>,
+}
+
+impl BreakerClass {
+    pub fn new(venue: &str, pool: Option<&str>) -> Self {
+        Self {
+            venue: venue.to_string(),
+            pool: pool.map(str::to_owned),
+        }
+    }
+
+    /// `venue`
+    pub fn venue_key(&self) -> String {
+        self.venue.clone()
+    }
+
+    /// `venue:pool`, when the route is tied to a pool
+    pub fn pool_key(&self) -> Option<String> {
+        self.pool
+            .as_ref()
+            .map(|pool| format!("{}:{}", self.venue, pool))
+    }
+}
+
+impl fmt::Display for BreakerClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pool_key() {
+            Some(key) => f.write_str(&key),
+            None => f.write_str(&self.venue),
+        }
+    }
+}
+
+/// Whether `err` reflects on the route class: a transport, submission or
+/// execution failure. Rejections of the request itself (validation, policy,
+/// simulation, sponsor budgets, the healthy-validator precondition) are
+/// passed through without counting. Untyped errors come from building,
+/// submitting or executing the transaction and count.
+pub fn counts_as_breaker_failure(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<AggrError>() {
+        Some(
+            AggrError::Transport(_)
+            | AggrError::Provider(_)
+            | AggrError::BuildTx(_)
+            | AggrError::ResponseTooLarge(_)
+            | AggrError::BackoffExhausted,
+        ) => true,
+        Some(_) => false,
+        None => true,
+    }
 }
 
 #[derive(Clone)]
 pub struct CircuitBreakers {
     inner: Arc<Mutex<HashMap<String, Breaker>>>,
+    scope: BreakerScope,
+    // Recent outcomes across every class, true=failure
+    recent: Arc<Mutex<VecDeque<bool>>>,
 }
 
 #[derive(Clone)]
@@ -90,6 +366,8 @@ impl Default for CircuitBreakers {
     fn default() -> Self {
         Self {
             inner: Arc::new(Mutex::new(HashMap::new())),
+            scope: BreakerScope::default(),
+            recent: Arc::new(Mutex::new(VecDeque::with_capacity(ERROR_RATE_WINDOW))),
         }
     }
 }
@@ -99,6 +377,79 @@ impl CircuitBreakers {
         Self::default()
     }
 
+    /// Track breakers at `scope` granularity
+    pub fn with_scope(mut self, scope: BreakerScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn scope(&self) -> BreakerScope {
+        self.scope
+    }
+
+    /// Whether any breaker covering `class` at the configured scope is open
+    pub async fn is_open_for(&self, class: &BreakerClass) -> bool {
+        let mut open = false;
+        for key in self.scope.keys(class) {
+            // Check every key so expired breakers are reset consistently
+            open |= self.is_open(&key).await;
+        }
+        open
+    }
+
+    pub async fn record_success_for(&self, class: &BreakerClass) {
+        self.observe(false).await;
+        for key in self.scope.keys(class) {
+            self.record(&key, false).await;
+        }
+    }
+
+    pub async fn record_failure_for(&self, class: &BreakerClass) {
+        self.observe(true).await;
+        for key in self.scope.keys(class) {
+            self.record(&key, true).await;
+        }
+    }
+
+    /// Run `attempt` behind the breakers for `class`. While one is open the
+    /// attempt is skipped and `AggrError::CircuitOpen` returned; otherwise
+    /// its outcome is recorded, unless it failed in a way that says nothing
+    /// about the route (see `counts_as_breaker_failure`).
+    pub async fn guard<T, Fut>(&self, class: &BreakerClass, attempt: Fut) -> anyhow::Result<T>
+    where
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if self.is_open_for(class).await {
+            return Err(AggrError::CircuitOpen(class.to_string()).into());
+        }
+        let result = attempt.await;
+        match &result {
+            Ok(_) => self.record_success_for(class).await,
+            Err(err) if counts_as_breaker_failure(err) => self.record_failure_for(class).await,
+            Err(err) => debug!(class = %class, error = %err, "rejection not counted by breaker"),
+        }
+        result
+    }
+
+    /// Failure share of recent outcomes across all classes, `None` until
+    /// enough outcomes have been seen
+    pub async fn recent_error_rate(&self) -> Option<f64> {
+        let recent = self.recent.lock().await;
+        if recent.len() < ERROR_RATE_MIN_SAMPLES {
+            return None;
+        }
+        let failures = recent.iter().filter(|failure| **failure).count();
+        Some(failures as f64 / recent.len() as f64)
+    }
+
+    async fn observe(&self, failure: bool) {
+        let mut recent = self.recent.lock().await;
+        if recent.len() == ERROR_RATE_WINDOW {
+            recent.pop_front();
+        }
+        recent.push_back(failure);
+    }
+
     pub async fn is_open(&self, class: &str) -> bool {
         let mut inner = self.inner.lock().await;
         let b = inner
@@ -115,10 +466,12 @@ impl CircuitBreakers {
     }
 
     pub async fn record_success(&self, class: &str) {
+        self.observe(false).await;
         self.record(class, false).await;
     }
 
     pub async fn record_failure(&self, class: &str) {
+        self.observe(true).await;
         self.record(class, true).await;
     }
 
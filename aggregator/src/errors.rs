@@ -4,6 +4,7 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::quant::QuantConstraint;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -16,6 +17,68 @@ pub enum AggrError {
     Signing(String),
     #[error("build tx error: {0}")]
     BuildTx(String),
+    #[error("risk check rejected order: {0}")]
+    RiskCheck(String),
+    #[error("no healthy validators: {healthy} available, {required} required")]
+    NoHealthyValidators { healthy: usize, required: usize },
+    #[error("unknown pool: {0}")]
+    UnknownPool(String),
+    #[error("pool {pool} is {status} and not accepting orders")]
+    PoolNotTradable { pool: String, status: String },
+    #[error("unknown sponsor: {0}")]
+    UnknownSponsor(String),
+    #[error("unknown balance manager: {0}")]
+    UnknownManager(String),
+    #[error(
+        "{sender} cannot trade from balance manager {manager}: {reason}; have the manager's \
+         owner call balance_manager::mint_trade_cap, transfer the TradeCap to {sender} and set \
+         its object id as the manager's trade_cap"
+    )]
+    MissingTradeCap {
+        manager: String,
+        sender: String,
+        reason: String,
+    },
+    #[error("unknown or already committed quote: {0}")]
+    UnknownQuote(String),
+    #[error("quote expired at {valid_until_ms} ms")]
+    QuoteExpired { valid_until_ms: u64 },
+    #[error("{value} is below the pool's {constraint} of {limit}")]
+    Quantization {
+        constraint: QuantConstraint,
+        value: f64,
+        limit: f64,
+    },
+    #[error("invalid amount {0}")]
+    InvalidAmount(String),
+    #[error("post-only order at {price} would cross the best opposite price {best}")]
+    PostOnlyWouldCross { price: f64, best: f64 },
+    #[error("expected slippage of {expected_bps:.1} bps exceeds the {limit_bps:.1} bps bound")]
+    SlippageExceeded { expected_bps: f64, limit_bps: f64 },
+    #[error("unknown or finished strategy: {0}")]
+    UnknownStrategy(String),
+    #[error("reference gas price {price} exceeds ceiling {ceiling}")]
+    GasPriceTooHigh { price: u64, ceiling: u64 },
+    #[error("scheduled maintenance until {until_ms} ms; new orders are paused")]
+    Maintenance { until_ms: u64 },
+    #[error("simulation rejected transaction: {0}")]
+    SimulationRejected(String),
+    #[error("circuit breaker open for route class: {0}")]
+    CircuitOpen(String),
+    #[error("transaction placed {0} orders in the pool; pass client_order_id to pick one")]
+    AmbiguousOrder(usize),
+    #[error("order ceiling of {limit} per {window_ms} ms reached; retry in {retry_after_ms} ms")]
+    Overloaded {
+        limit: u32,
+        window_ms: u64,
+        retry_after_ms: u64,
+    },
+    #[error("sponsor gas coins hold {available} MIST, below the {required} MIST gas budget")]
+    InsufficientSponsorGas { available: u64, required: u64 },
+    #[error("no open orders in {0} to cancel")]
+    NoOpenOrders(String),
+    #[error("response exceeded {0} byte limit")]
+    ResponseTooLarge(usize),
     #[error("backoff exhausted")]
     BackoffExhausted,
 }
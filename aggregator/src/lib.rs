@@ -4,14 +4,17 @@
 //
 // Numan Thabit 2025 Nov
 
+pub mod amounts;
 pub mod config;
 pub mod control;
 pub mod errors;
 pub mod metrics;
 pub mod quant;
+pub mod retry;
 pub mod router;
 pub mod signing;
 pub mod sponsorship;
 pub mod state;
+pub mod telemetry;
 pub mod transport;
 pub mod venues;
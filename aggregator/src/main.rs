@@ -1,16 +1,32 @@
 use anyhow::{anyhow, Context, Result};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_sdk::types::base_types::SuiAddress;
+use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
-use ultra_aggr::config::AppConfig;
+use ultra_aggr::config::{AppConfig, SponsorshipConfig};
 use ultra_aggr::control::{AdmissionControl, CircuitBreakers};
-use ultra_aggr::router::{ExecutionEngine, RouteSelector, Router, ValidatorSelector};
-use ultra_aggr::state::{start_checkpoint_streaming, CheckpointState};
-use ultra_aggr::transport::graphql::GraphQLRpc;
+use ultra_aggr::router::execution::DEFAULT_MAX_CONFLICT_REBUILDS;
+use ultra_aggr::router::validator::spawn_readiness_probe;
+use ultra_aggr::router::{ApiSettings, ExecutionEngine, RouteSelector, Router, ValidatorSelector};
+use ultra_aggr::sponsorship::{
+    spawn_gas_coin_refresher, AbuseConfig, SponsorRegistry, SponsorshipManager,
+};
+use ultra_aggr::state::{
+    start_checkpoint_streaming, start_staleness_watchdog, CheckpointState, EpochTracker,
+};
+use ultra_aggr::telemetry;
+use ultra_aggr::transport::graphql::{
+    check_graphql_health, spawn_graphql_health_check, GraphQLHealth, GraphQLRpc,
+};
 use ultra_aggr::transport::grpc::GrpcClients;
 use ultra_aggr::transport::jsonrpc::JsonRpc;
-use ultra_aggr::venues::adapter::DeepBookAdapter;
+use ultra_aggr::venues::adapter::{DeepBookAdapter, LimitOrderType, LimitReq};
+use ultra_aggr::venues::amm::CetusAdapter;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -18,23 +34,50 @@ async fn main() -> Result<()> {
 
     if let Err(err) = run().await {
         tracing::error!(error = ?err, "fatal aggregator error");
+        telemetry::shutdown();
         std::process::exit(1);
     }
+    telemetry::shutdown();
     Ok(())
 }
 
 async fn run() -> Result<()> {
     let config = AppConfig::load().context("load configuration from environment")?;
     let sui_address = config.sui_address().context("parse Sui address")?;
+    let api_addr = config.api_listen_addr()?;
 
     let grpc = GrpcClients::new(config.grpc_endpoint.as_str())
         .await
         .with_context(|| format!("connect gRPC endpoint {}", config.grpc_endpoint))?;
 
-    let jsonrpc = JsonRpc::new(config.jsonrpc_endpoint.to_string());
+    let max_response_bytes = config.max_response_bytes()?;
+    let http_config = config.http_client_config()?;
+    let jsonrpc = JsonRpc::new(config.jsonrpc_endpoint.to_string())
+        .with_http_config(&http_config)
+        .context("initialize JSON-RPC client")?
+        .with_max_response_bytes(max_response_bytes);
+    let jsonrpc = match config.jsonrpc_empty_result_retry_policy() {
+        Some(policy) => jsonrpc.with_empty_result_retry(policy),
+        None => jsonrpc.without_empty_result_retry(),
+    };
+    if config.jsonrpc_warm_up.unwrap_or(true) {
+        match jsonrpc.warm_up().await {
+            Ok(chain_id) => info!(chain_id = %chain_id, "JSON-RPC connection warmed up"),
+            Err(err) => {
+                warn!(error = %err, "JSON-RPC warm-up failed; first submission will connect")
+            }
+        }
+    }
 
     let graphql = if let Some(endpoint) = &config.graphql_endpoint {
-        Some(GraphQLRpc::new(endpoint.clone()).context("initialize GraphQL RPC client")?)
+        let client = GraphQLRpc::new(endpoint.clone())
+            .and_then(|client| client.with_http_config(&http_config))
+            .context("initialize GraphQL RPC client")?
+            .with_max_response_bytes(max_response_bytes);
+        Some(match config.graphql_retry_policy() {
+            Some(policy) => client.with_retry_policy(policy),
+            None => client.without_retry(),
+        })
     } else {
         warn!("GraphQL endpoint not provided; GraphQL RPC disabled");
         None
@@ -51,6 +94,16 @@ async fn run() -> Result<()> {
         None
     };
 
+    let cetus = if let Some(settings) = config.cetus_settings()? {
+        Some(Arc::new(
+            CetusAdapter::new(config.jsonrpc_endpoint.as_str(), sui_address, &settings)
+                .await
+                .context("initialize Cetus adapter")?,
+        ))
+    } else {
+        None
+    };
+
     // Initialize router components
     let validator_selector = Arc::new(ValidatorSelector::default());
 
@@ -63,11 +116,31 @@ async fn run() -> Result<()> {
     // Base latency for fast-path (owned objects): ~100ms
     // Shared-object latency (consensus): ~400ms (Mysticeti v2 target)
     let deepbook_arc = deepbook.clone().map(Arc::new);
-    let route_selector = RouteSelector::new(
+    let mut route_selector = RouteSelector::new(
         deepbook_arc.as_ref().map(Arc::clone),
         100, // base_latency_ms
         400, // shared_object_latency_ms
-    );
+    )
+    .with_evaluation_policy(config.route_evaluation_policy()?)
+    .with_impact_coefficient(config.slippage_impact_coefficient()?)
+    .with_partial_fill_penalty(config.partial_fill_penalty()?);
+    if let Some(cetus) = &cetus {
+        route_selector = route_selector.with_cetus(Arc::clone(cetus));
+    }
+    if let Some(path) = &config.latency_state_path {
+        match route_selector.load_state(path).await {
+            Ok(true) => info!(path = %path.display(), "restored learned latency estimates"),
+            Ok(false) => {}
+            Err(err) => warn!(error = %err, "ignoring unreadable latency state"),
+        }
+    }
+
+    // Checkpoint stream state; also resolves pending execution confirmations
+    let mut checkpoint_state =
+        CheckpointState::new(1024).with_confirmation_window(config.confirmation_batch_window());
+    if let Some(stale_after) = config.checkpoint_stale_after()? {
+        checkpoint_state = checkpoint_state.with_stale_after(stale_after);
+    }
 
     // Initialize execution engine
     let mut execution_engine = ExecutionEngine::new(
@@ -75,91 +148,208 @@ async fn run() -> Result<()> {
         grpc.clone(),
         jsonrpc.clone(),
         validator_selector.clone(),
-        config.ed25519_secret_hex.clone(),
+        config.signer()?,
         sui_address,
         config.use_grpc_execute.unwrap_or(false),
+    )
+    .with_confirmations(checkpoint_state.pending().clone())
+    .with_min_healthy_validators(config.min_healthy_validators.unwrap_or(0))
+    .with_dry_run(config.dry_run())
+    .with_simulation(config.simulate_before_execute.unwrap_or(false))
+    .with_digest_store(config.digest_store()?)
+    .with_max_conflict_rebuilds(
+        config
+            .max_conflict_rebuilds
+            .unwrap_or(DEFAULT_MAX_CONFLICT_REBUILDS),
     );
 
-    // Set up sponsorship if configured
-    if let Some(sponsorship_config) = &config.sponsorship {
-        use ultra_aggr::sponsorship::{AbuseConfig, SponsorshipManager};
-        let sponsor_address = sponsorship_config
-            .sponsor_address_parsed()
-            .context("parse sponsor address")?;
-
-        let abuse_config = AbuseConfig {
-            max_tx_per_window: sponsorship_config.max_tx_per_window.unwrap_or(1000),
-            max_gas_per_window: sponsorship_config
-                .max_gas_per_window
-                .unwrap_or(1_000_000_000),
-            window_duration: Duration::from_secs(
-                sponsorship_config.abuse_window_seconds.unwrap_or(3600),
-            ),
-        };
+    if let Some(cetus) = &cetus {
+        execution_engine = execution_engine.with_cetus(Arc::clone(cetus));
+    }
+
+    if let Some(factor) = config.simulated_gas_safety_factor()? {
+        execution_engine = execution_engine.with_simulated_gas_budget(factor);
+    }
+
+    let observer_mode = config.observer_mode();
+    if observer_mode {
+        info!("observer mode: execution endpoints disabled, sponsorship skipped");
+    }
+    if config.dry_run() {
+        warn!("DRY RUN: orders are compiled, signed and simulated but never submitted");
+    }
 
-        // Get reference gas price for sponsorship manager
+    // Set up sponsorship if configured
+    let named_sponsors = config
+        .sponsors
+        .as_ref()
+        .filter(|sponsors| !sponsors.is_empty());
+    if !observer_mode && (config.sponsorship.is_some() || named_sponsors.is_some()) {
+        // Get reference gas price for sponsorship managers
         let gas_price = if let Some(adapter) = &deepbook_arc {
             adapter.reference_gas_price().await.unwrap_or(1000)
         } else {
             1000 // Default fallback
         };
 
-        let sponsorship_manager = Arc::new(
-            SponsorshipManager::new(
-                sponsorship_config.sponsor_key_hex.clone(),
-                sponsor_address,
+        if let Some(sponsorship_config) = &config.sponsorship {
+            let sponsorship_manager = build_sponsorship_manager(
+                sponsorship_config,
                 gas_price,
-                abuse_config,
+                sui_address,
+                deepbook_arc.as_ref(),
             )
-            .context("initialize sponsorship manager")?,
-        );
+            .await?;
+            execution_engine = execution_engine.with_sponsorship(sponsorship_manager);
+            info!("sponsorship manager initialized");
+        }
 
-        // Set per-user budget if configured
-        if let Some(per_user_budget) = sponsorship_config.per_user_budget {
-            let window = sponsorship_config
-                .budget_window_seconds
-                .map(Duration::from_secs);
-            sponsorship_manager
-                .set_user_budget(
+        if let Some(sponsors) = named_sponsors {
+            let mut registry = SponsorRegistry::new();
+            for (alias, sponsor_config) in sponsors {
+                let manager = build_sponsorship_manager(
+                    sponsor_config,
+                    gas_price,
                     sui_address,
-                    per_user_budget,
-                    sponsorship_config.per_tx_limit.unwrap_or(10_000_000),
-                    window,
+                    deepbook_arc.as_ref(),
                 )
-                .await;
+                .await
+                .with_context(|| format!("initialize sponsor {alias}"))?;
+                registry = registry.with_sponsor(alias.clone(), manager);
+            }
+            info!(aliases = ?registry.aliases(), "per-request sponsors initialized");
+            execution_engine = execution_engine.with_sponsor_registry(registry);
         }
-
-        execution_engine = execution_engine.with_sponsorship(sponsorship_manager);
-        info!("sponsorship manager initialized");
+        execution_engine =
+            execution_engine.with_gasless_fallback(config.gasless_fallback.unwrap_or_default());
     }
 
+    if let Some(path) = &config.execution_stats_path {
+        match execution_engine.load_stats(path).await {
+            Ok(true) => info!(path = %path.display(), "restored lifetime execution statistics"),
+            Ok(false) => {}
+            Err(err) => warn!(error = %err, "ignoring unreadable execution statistics"),
+        }
+    }
     let execution_engine = Arc::new(execution_engine);
 
+    if let Some(warmup) = config.warmup.as_ref().filter(|_| !observer_mode) {
+        let req = LimitReq {
+            pool: warmup.pool.clone(),
+            price: warmup.price,
+            quantity: warmup.quantity,
+            is_bid: warmup.is_bid.unwrap_or(true),
+            client_order_id: "0".to_string(),
+            pay_with_deep: false,
+            expiration_ms: None,
+            reduce_only: false,
+            post_only: None,
+            order_type: LimitOrderType::Gtc,
+            manager: None,
+        };
+        execution_engine
+            .self_test(req)
+            .await
+            .context("startup warmup self-test failed")?;
+    }
+
     // Initialize control plane
-    let admission = Arc::new(AdmissionControl::new(config.max_inflight, None));
-    let breakers = Arc::new(CircuitBreakers::new());
+    let mut admission =
+        AdmissionControl::new(config.max_inflight, Some(config.admission_rate_per_sec()?));
+    if let Some(adaptive) = config.adaptive_rate()? {
+        admission = admission.with_adaptive_rate(adaptive);
+    }
+    let admission = Arc::new(admission);
+    let breakers = Arc::new(
+        CircuitBreakers::new().with_scope(config.circuit_breaker_scope.unwrap_or_default()),
+    );
+    spawn_rate_adapter(admission.clone(), breakers.clone());
 
     // Create Router instance for order execution
     let route_selector_arc = Arc::new(route_selector);
-    let router = Arc::new(
-        Router::new(route_selector_arc.clone(), execution_engine.clone())
-            .with_control(admission.clone(), breakers.clone()),
-    );
+    let (max_body_bytes, max_order_body_bytes) = config.body_limits()?;
+    let mut router = Router::new(route_selector_arc.clone(), execution_engine.clone())
+        .with_control(admission.clone(), breakers.clone())
+        .with_api_settings(ApiSettings {
+            max_request_timeout: config.max_request_timeout()?,
+            observer_mode,
+            slow_request_threshold: config.slow_request_threshold()?,
+            max_body_bytes,
+            max_order_body_bytes,
+        });
+    if let Some(adapter) = &deepbook_arc {
+        router = router.with_pool_normalizer(adapter.pool_normalizer().clone());
+    }
+    router = router
+        .with_checkpoint_health(checkpoint_state.clone())
+        .with_quote_ttl(config.quote_ttl()?)
+        .with_session_timeout(config.session_timeout()?)
+        .with_bulk_quote_limits(config.bulk_quote_limits()?)
+        .with_child_policy(config.child_submission_policy()?)
+        .with_trade_authority_check(config.verify_trade_authority.unwrap_or(false));
+    if let Some(max_gas_price) = config.max_gas_price()? {
+        router = router.with_max_gas_price(max_gas_price);
+    }
+    let graphql_health = graphql.as_ref().map(|_| GraphQLHealth::default());
+    if let Some(graphql) = &graphql {
+        router = router.with_history(graphql.clone());
+    }
+    if let Some(health) = &graphql_health {
+        router =
+            router.with_graphql_health(health.clone(), config.graphql_required.unwrap_or(false));
+    }
+    if let Some(token) = config.build_signed_token()? {
+        warn!("build_signed endpoint enabled; it returns spendable signed transactions");
+        router = router.with_build_signed(token);
+    }
+    if let Some(export) = config.book_export()? {
+        info!(
+            dir = %export.dir.display(),
+            min_interval_secs = export.min_interval.as_secs(),
+            max_files = export.max_files,
+            "book snapshot export enabled"
+        );
+        router = router.with_book_export(export);
+    }
+    let maintenance = config.maintenance_schedule()?;
+    for window in maintenance.windows() {
+        info!(%window, "new orders paused during maintenance window");
+    }
+    router = router.with_maintenance_schedule(maintenance);
+    router = router.with_replace_mode(config.cancel_replace_mode.unwrap_or_default());
+    if let Some(ceiling) = config.order_ceiling()? {
+        info!(
+            max_orders = ceiling.max_orders(),
+            window_secs = ceiling.window().as_secs(),
+            "global order ceiling enabled"
+        );
+        router = router.with_order_ceiling(ceiling);
+    }
+    if let Some(token) = config.sign_message_token()? {
+        warn!("message signing endpoint enabled; it signs arbitrary payloads with the trading key");
+        router = router.with_message_signing(config.signer()?, token);
+    }
+    let router = Arc::new(router);
+    spawn_session_watchdog(Arc::clone(&router));
 
     let app = App {
         config: Arc::new(config),
+        api_addr,
         grpc,
         jsonrpc,
         graphql,
+        graphql_health,
         deepbook,
         router,
         route_selector: route_selector_arc,
         execution_engine,
         validator_selector,
-        checkpoint_state: None,
+        checkpoint_state: Some(checkpoint_state),
+        epochs: EpochTracker::new(),
         admission: None,
         breakers: None,
         reconcile_handle: None,
+        probe_handle: None,
     };
 
     app.run().await
@@ -167,9 +357,12 @@ async fn run() -> Result<()> {
 
 struct App {
     config: Arc<AppConfig>,
+    /// Validated HTTP API listen address
+    api_addr: std::net::SocketAddr,
     grpc: GrpcClients,
     jsonrpc: JsonRpc,
     graphql: Option<GraphQLRpc>,
+    graphql_health: Option<GraphQLHealth>,
     deepbook: Option<DeepBookAdapter>,
     router: Arc<Router>,
     /// Route selector stored separately for direct access (e.g., updating latency estimates)
@@ -180,14 +373,37 @@ struct App {
     execution_engine: Arc<ExecutionEngine>,
     validator_selector: Arc<ValidatorSelector>,
     checkpoint_state: Option<CheckpointState>,
+    epochs: EpochTracker,
     #[allow(dead_code)]
     admission: Option<AdmissionControl>,
     #[allow(dead_code)]
     breakers: Option<CircuitBreakers>,
     reconcile_handle: Option<tokio::task::JoinHandle<()>>,
+    probe_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl App {
+    /// Pull epoch info over gRPC, seeding the adapter's gas price and dropping
+    /// its cached chain parameters when the epoch has turned over
+    async fn refresh_epoch(&self) {
+        let info = match self.grpc.clone().get_epoch_info().await {
+            Ok(info) => info,
+            Err(err) => {
+                warn!(error = %err, "gRPC epoch info unavailable");
+                return;
+            }
+        };
+        let changed = self.epochs.observe(info).await.is_some();
+        if let Some(adapter) = &self.deepbook {
+            if changed {
+                adapter.invalidate_all_caches().await;
+            }
+            if let Some(price) = info.reference_gas_price {
+                adapter.seed_reference_gas_price(price).await;
+            }
+        }
+    }
+
     async fn run(mut self) -> Result<()> {
         self.grpc
             .readiness_probe()
@@ -195,15 +411,36 @@ impl App {
             .context("gRPC readiness probe failed")?;
 
         info!(
-            address = %self.config.address,
+            address = %self.config.sui_address()?,
             grpc = %self.config.grpc_endpoint,
             jsonrpc = %self.jsonrpc.endpoint(),
             graphql = ?self.config.graphql_endpoint,
             "Numi Sui Stack aggregator online"
         );
 
-        if let Some(_graphql_client) = &self.graphql {
-            info!("GraphQL RPC + Indexer client initialized");
+        self.refresh_epoch().await;
+
+        let probe_interval = self.config.grpc_probe_interval()?;
+        let failure_threshold = self.config.grpc_probe_failure_threshold()?;
+        self.probe_handle = Some(spawn_readiness_probe(
+            self.grpc.clone(),
+            self.validator_selector.clone(),
+            self.config.grpc_endpoint.to_string(),
+            probe_interval,
+            failure_threshold,
+        ));
+        info!(
+            interval_secs = probe_interval.as_secs(),
+            failure_threshold, "started gRPC readiness probe loop"
+        );
+
+        if let (Some(graphql), Some(health)) = (&self.graphql, &self.graphql_health) {
+            check_graphql_health(graphql, health).await;
+            spawn_graphql_health_check(
+                graphql.clone(),
+                health.clone(),
+                self.config.graphql_health_interval()?,
+            );
         }
 
         if let Some(adapter) = &self.deepbook {
@@ -284,25 +521,38 @@ impl App {
         // Control plane is now initialized in main() and passed to Router
 
         // Start checkpoint streaming and reconciliation
-        let checkpoint_state = CheckpointState::new(1024);
+        let checkpoint_state = self
+            .checkpoint_state
+            .get_or_insert_with(|| CheckpointState::new(1024))
+            .clone();
+        if let Some(adapter) = &self.deepbook {
+            spawn_book_invalidation(adapter.clone(), &checkpoint_state);
+        }
+        if let Some(path) = &self.config.execution_stats_path {
+            spawn_stats_flush(
+                self.execution_engine.clone(),
+                path.clone(),
+                self.config.execution_stats_flush_interval()?,
+            );
+        }
         let grpc_clone = self.grpc.clone();
-        let _stream_handle =
-            start_checkpoint_streaming(grpc_clone, checkpoint_state.clone()).await?;
-        self.checkpoint_state = Some(checkpoint_state.clone());
+        let _watchdog_handle = start_staleness_watchdog(checkpoint_state.clone());
+        let _stream_handle = start_checkpoint_streaming(grpc_clone, checkpoint_state).await?;
         info!("started checkpoint streaming");
 
         // Start HTTP API server
         let router_clone = self.router.clone();
         let api_router = ultra_aggr::router::router::create_api_router(router_clone);
-        // Default API server address (can be configured via env var in future)
-        let api_addr: std::net::SocketAddr =
-            "0.0.0.0:8080".parse().expect("valid default API address");
+        // Bind before spawning so a bad or busy address stops startup
+        let api_addr = self.api_addr;
+        let listener = tokio::net::TcpListener::bind(api_addr)
+            .await
+            .with_context(|| {
+                format!("bind HTTP API to {api_addr}; is another process using the port?")
+            })?;
 
         info!(address = %api_addr, "HTTP API server starting");
         let _api_handle = tokio::spawn(async move {
-            let listener = tokio::net::TcpListener::bind(&api_addr)
-                .await
-                .expect("bind API server address");
             if let Err(e) = axum::serve(listener, api_router).await {
                 warn!(error = %e, "API server error");
             }
@@ -330,6 +580,8 @@ impl App {
                         );
                     }
 
+                    self.refresh_epoch().await;
+
                     // Report last checkpoint cursor if available
                     if let Some(cs) = &self.checkpoint_state {
                         if let Some(cursor) = cs.last_cursor().await {
@@ -369,16 +621,187 @@ impl App {
         if let Some(handle) = self.reconcile_handle.take() {
             handle.abort();
         }
+        if let Some(handle) = self.probe_handle.take() {
+            handle.abort();
+        }
+        if let Some(path) = &self.config.latency_state_path {
+            if let Err(err) = self.route_selector.save_state(path).await {
+                warn!(error = %err, "failed to persist latency state");
+            }
+        }
+        if let Some(path) = &self.config.execution_stats_path {
+            if let Err(err) = self.execution_engine.save_stats(path).await {
+                warn!(error = %err, "failed to persist execution statistics");
+            }
+        }
         Ok(())
     }
 }
 
+/// Sponsorship manager with abuse limits and the per-user budget from `config`.
+/// Its gas coins are refreshed from chain when a DeepBook adapter is available.
+async fn build_sponsorship_manager(
+    config: &SponsorshipConfig,
+    gas_price: u64,
+    user: SuiAddress,
+    adapter: Option<&Arc<DeepBookAdapter>>,
+) -> Result<Arc<SponsorshipManager>> {
+    let sponsor_address = config
+        .sponsor_address_parsed()
+        .context("parse sponsor address")?;
+
+    let abuse_config = AbuseConfig {
+        max_tx_per_window: config.max_tx_per_window.unwrap_or(1000),
+        max_gas_per_window: config.max_gas_per_window.unwrap_or(1_000_000_000),
+        window_duration: Duration::from_secs(config.abuse_window_seconds.unwrap_or(3600)),
+        max_tracked_users: config.max_tracked_users()?,
+    };
+
+    let sponsorship_manager = Arc::new(
+        SponsorshipManager::new(config.signer()?, sponsor_address, gas_price, abuse_config)
+            .context("initialize sponsorship manager")?
+            .with_debit_ttl(config.debit_idempotency_ttl()?),
+    );
+
+    // Set per-user budget if configured
+    if let Some(per_user_budget) = config.per_user_budget {
+        let window = config.budget_window_seconds.map(Duration::from_secs);
+        sponsorship_manager
+            .set_user_budget(
+                user,
+                per_user_budget,
+                config.per_tx_limit.unwrap_or(10_000_000),
+                window,
+            )
+            .await;
+    }
+
+    let refresh = config.gas_coin_refresh()?;
+    match adapter {
+        Some(adapter) => spawn_gas_coin_refresher(
+            Arc::clone(&sponsorship_manager),
+            Arc::clone(adapter),
+            refresh,
+        ),
+        None => warn!(
+            sponsor = %sponsor_address,
+            "no DeepBook adapter; sponsor gas coins will not be refreshed"
+        ),
+    }
+
+    spawn_abuse_eviction(Arc::clone(&sponsorship_manager));
+    Ok(sponsorship_manager)
+}
+
+/// Once per abuse window, forget users whose window has expired
+fn spawn_abuse_eviction(manager: Arc<SponsorshipManager>) {
+    let every = manager
+        .abuse_config()
+        .window_duration
+        .max(Duration::from_secs(1));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let evicted = manager.evict_idle_abuse_metrics().await;
+            if evicted > 0 {
+                debug!(evicted, "evicted idle sponsorship abuse metrics");
+            }
+        }
+    });
+}
+
+/// Periodically retune the admission rate from the breakers' recent error rate
+fn spawn_rate_adapter(admission: Arc<AdmissionControl>, breakers: Arc<CircuitBreakers>) {
+    let Some(every) = admission.adaptive_rate().map(|params| params.interval) else {
+        return;
+    };
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+            admission.adapt(breakers.recent_error_rate().await).await;
+        }
+    });
+}
+
+/// Cancel the orders of sessions whose heartbeats stopped. Checks several
+/// times per timeout so cancellation follows a dropped connection closely.
+fn spawn_session_watchdog(router: Arc<Router>) {
+    let every = (router.sessions().timeout() / 4).max(Duration::from_millis(100));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+            let cancelled = router.cancel_expired_sessions().await;
+            if cancelled > 0 {
+                info!(cancelled, "cancelled orders of expired sessions");
+            }
+        }
+    });
+}
+
 fn init_tracing() -> Result<()> {
     let env_filter =
         std::env::var("RUST_LOG").unwrap_or_else(|_| "info,hyper=warn,tonic=warn".to_string());
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::new(env_filter))
-        .with_target(false)
+    // Spans go to an OTLP collector only when one is configured
+    let otel = telemetry::otlp_endpoint()
+        .map(|endpoint| telemetry::otlp_layer(&endpoint))
+        .transpose()?;
+    let exporting = otel.is_some();
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(env_filter))
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(otel)
         .try_init()
-        .map_err(|err| anyhow!("tracing subscriber init: {err}"))
+        .map_err(|err| anyhow!("tracing subscriber init: {err}"))?;
+    if exporting {
+        info!("exporting traces over OTLP");
+    }
+    Ok(())
+}
+
+/// Periodically write lifetime execution statistics to `path`
+fn spawn_stats_flush(engine: Arc<ExecutionEngine>, path: PathBuf, every: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        // The first tick fires immediately; nothing new to write yet
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(err) = engine.save_stats(&path).await {
+                warn!(error = %err, "failed to persist execution statistics");
+            }
+        }
+    });
+}
+
+/// Drop cached order book snapshots for pools touched by each streamed checkpoint
+fn spawn_book_invalidation(adapter: DeepBookAdapter, checkpoints: &CheckpointState) {
+    let settings = *adapter.book_cache_settings();
+    if settings.ttl.is_zero() || !settings.invalidate_on_checkpoint {
+        return;
+    }
+    let mut updates = checkpoints.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match updates.recv().await {
+                Ok(update) => {
+                    if let Some(checkpoint) = &update.checkpoint {
+                        let pools = adapter.invalidate_books_for_checkpoint(checkpoint).await;
+                        if !pools.is_empty() {
+                            debug!(cursor = update.cursor, ?pools, "invalidated book snapshots");
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    // Missed checkpoints may have touched any pool
+                    warn!(skipped, "book invalidation lagged; clearing all snapshots");
+                    adapter.invalidate_book_snapshots().await;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
 }
@@ -4,8 +4,13 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::router::execution::ExecutionStats;
 use once_cell::sync::Lazy;
-use prometheus::{register_counter_vec, register_histogram_vec, CounterVec, HistogramVec};
+use prometheus::{
+    register_counter_vec, register_gauge, register_histogram_vec, register_int_counter,
+    register_int_gauge, register_int_gauge_vec, CounterVec, Gauge, HistogramVec, IntCounter,
+    IntGauge, IntGaugeVec,
+};
 
 pub static REQ_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -25,6 +30,24 @@ pub static REQ_ERRORS: Lazy<CounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static SLOW_REQUESTS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "aggr_slow_requests_total",
+        "HTTP requests exceeding the slow-request threshold",
+        &["method", "path"]
+    )
+    .unwrap()
+});
+
+pub static VENUE_PANICS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "aggr_venue_panics_total",
+        "route evaluations that panicked, by venue",
+        &["venue"]
+    )
+    .unwrap()
+});
+
 pub static DEEPBOOK_CACHE_HITS: Lazy<CounterVec> = Lazy::new(|| {
     register_counter_vec!(
         "aggr_deepbook_cache_hits_total",
@@ -69,3 +92,97 @@ pub static DEEPBOOK_EVENT_COUNTER: Lazy<CounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static INFLIGHT_PERMITS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("aggr_inflight_permits", "admission permits currently held").unwrap()
+});
+
+pub static ADMISSION_RATE_PER_SEC: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aggr_admission_rate_per_sec",
+        "admissions per second currently allowed, after any adaptive backoff"
+    )
+    .unwrap()
+});
+
+pub static MAX_INFLIGHT_PERMITS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aggr_max_inflight_permits",
+        "configured admission concurrency limit"
+    )
+    .unwrap()
+});
+
+pub static ORDERS_SHED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aggr_orders_shed_total",
+        "orders refused because the global order ceiling was reached"
+    )
+    .unwrap()
+});
+
+pub static CHECKPOINT_STALE_ALERTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aggr_checkpoint_stale_alerts_total",
+        "times the checkpoint stream went silent past the staleness threshold"
+    )
+    .unwrap()
+});
+
+pub static CHECKPOINT_GAPS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aggr_checkpoint_gaps_total",
+        "reconnects whose first checkpoint did not follow the last processed cursor"
+    )
+    .unwrap()
+});
+
+pub static EXECUTIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aggr_executions",
+        "lifetime route executions by outcome (total, successful, failed)",
+        &["outcome"]
+    )
+    .unwrap()
+});
+
+pub static EXECUTION_SUCCESS_RATE: Lazy<Gauge> = Lazy::new(|| {
+    register_gauge!(
+        "aggr_execution_success_rate",
+        "lifetime share of executions that succeeded"
+    )
+    .unwrap()
+});
+
+pub static LATENCY_ESTIMATE_MS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aggr_latency_estimate_ms",
+        "route latency estimates used for scoring, by object class (owned, shared)",
+        &["objects"]
+    )
+    .unwrap()
+});
+
+/// Publish execution statistics as gauges
+pub fn record_execution_stats(stats: &ExecutionStats) {
+    EXECUTIONS
+        .with_label_values(&["total"])
+        .set(stats.total_executions as i64);
+    EXECUTIONS
+        .with_label_values(&["successful"])
+        .set(stats.successful_executions as i64);
+    EXECUTIONS
+        .with_label_values(&["failed"])
+        .set(stats.failed_executions as i64);
+    EXECUTION_SUCCESS_RATE.set(stats.success_rate);
+}
+
+/// Publish the fast-path and shared-object latency estimates
+pub fn record_latency_estimates(owned_ms: u64, shared_ms: u64) {
+    LATENCY_ESTIMATE_MS
+        .with_label_values(&["owned"])
+        .set(owned_ms as i64);
+    LATENCY_ESTIMATE_MS
+        .with_label_values(&["shared"])
+        .set(shared_ms as i64);
+}
@@ -3,7 +3,9 @@
 //
 // Numan Thabit 2025 Nov
 
-use anyhow::{ensure, Result};
+use crate::errors::AggrError;
+use anyhow::{bail, ensure, Result};
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct PoolParams {
@@ -15,7 +17,90 @@ pub struct PoolParams {
     pub min_size: f64,
 }
 
+impl PoolParams {
+    /// Reject non-positive or non-finite values, naming the first bad field
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("tick size", self.tick_size),
+            ("lot size", self.lot_size),
+            ("min size", self.min_size),
+        ] {
+            ensure!(
+                value.is_finite() && value > 0.0,
+                "{name} {value} is not a positive number"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Params to quantize `pool` with: `fetched` when valid, otherwise the
+/// configured `fallback`. Errors clearly when neither is usable instead of
+/// letting quantization fail on a zero or NaN step.
+pub fn resolve_pool_params(
+    pool: &str,
+    fetched: PoolParams,
+    fallback: Option<&PoolParams>,
+) -> Result<PoolParams> {
+    let Err(err) = fetched.validate() else {
+        return Ok(fetched);
+    };
+    match fallback {
+        Some(fallback) => {
+            fallback.validate().map_err(|fallback_err| {
+                anyhow::anyhow!("pool params override for {pool} is invalid: {fallback_err}")
+            })?;
+            Ok(fallback.clone())
+        }
+        None => bail!(
+            "pool {pool} reported invalid params ({err}); configure a pool params override to trade it"
+        ),
+    }
+}
+
+/// Pool constraint an order price or size fell short of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantConstraint {
+    TickSize,
+    MinSize,
+    LotSize,
+}
+
+impl QuantConstraint {
+    /// API error code for an order that fails this constraint
+    pub fn code(&self) -> &'static str {
+        match self {
+            QuantConstraint::TickSize => "PRICE_BELOW_TICK",
+            QuantConstraint::MinSize => "SIZE_BELOW_MINIMUM",
+            QuantConstraint::LotSize => "SIZE_BELOW_LOT",
+        }
+    }
+}
+
+impl fmt::Display for QuantConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QuantConstraint::TickSize => "tick size",
+            QuantConstraint::MinSize => "minimum size",
+            QuantConstraint::LotSize => "lot size",
+        })
+    }
+}
+
+/// Tolerance, in ticks, for float error when a price sits on a tick boundary,
+/// so 0.3 / 0.1 = 2.9999999999999996 still counts as three ticks
+const TICK_EPSILON: f64 = 1e-9;
+
+/// Round `price` down to a multiple of `tick_size`. A price below one tick is
+/// rejected with [`AggrError::Quantization`].
 pub fn quantize_price(price: f64, tick_size: f64) -> Result<f64> {
+    quantize_price_for_side(price, tick_size, true)
+}
+
+/// Round `price` to a multiple of `tick_size` on the passive side: down for
+/// bids, up for asks, so neither pays more (or sells for less) than asked.
+/// A bid below one tick is rejected with [`AggrError::Quantization`].
+pub fn quantize_price_for_side(price: f64, tick_size: f64, is_bid: bool) -> Result<f64> {
     ensure!(
         tick_size.is_finite() && tick_size > 0.0,
         "tick size must be positive"
@@ -24,14 +109,25 @@ pub fn quantize_price(price: f64, tick_size: f64) -> Result<f64> {
         price.is_finite() && price > 0.0,
         "price must be positive and finite"
     );
-    let steps = (price / tick_size).floor();
+    let ticks = price / tick_size;
+    let steps = if is_bid {
+        (ticks + TICK_EPSILON).floor()
+    } else {
+        (ticks - TICK_EPSILON).ceil().max(1.0)
+    };
     ensure!(
         steps >= 1.0,
-        "price {price} is below minimum tick {tick_size}"
+        AggrError::Quantization {
+            constraint: QuantConstraint::TickSize,
+            value: price,
+            limit: tick_size,
+        }
     );
     Ok(steps * tick_size)
 }
 
+/// Round `quantity` down to a multiple of `lot_size`. A quantity below
+/// `min_size` or one lot is rejected with [`AggrError::Quantization`].
 pub fn quantize_size(quantity: f64, lot_size: f64, min_size: f64) -> Result<f64> {
     ensure!(
         lot_size.is_finite() && lot_size > 0.0,
@@ -41,14 +137,69 @@ pub fn quantize_size(quantity: f64, lot_size: f64, min_size: f64) -> Result<f64>
         min_size.is_finite() && min_size > 0.0,
         "min size must be positive"
     );
+    ensure!(quantity.is_finite(), "quantity must be finite");
     ensure!(
-        quantity.is_finite() && quantity >= min_size,
-        "quantity {quantity} below minimum size {min_size}"
+        quantity >= min_size,
+        AggrError::Quantization {
+            constraint: QuantConstraint::MinSize,
+            value: quantity,
+            limit: min_size,
+        }
     );
     let steps = (quantity / lot_size).floor();
     ensure!(
         steps >= 1.0,
-        "quantity {quantity} insufficient for lot size {lot_size}"
+        AggrError::Quantization {
+            constraint: QuantConstraint::LotSize,
+            value: quantity,
+            limit: lot_size,
+        }
     );
     Ok(steps * lot_size)
 }
+
+/// Most decimal places a tick or lot size is assumed to carry
+const MAX_DECIMALS: u32 = 12;
+
+/// Decimal places used when echoing a pool's prices and sizes, so responses
+/// carry no more precision than the chain accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precision {
+    pub price_decimals: u32,
+    pub size_decimals: u32,
+}
+
+impl Precision {
+    pub fn from_pool_params(params: &PoolParams) -> Self {
+        Self {
+            price_decimals: decimals_for_step(params.tick_size),
+            size_decimals: decimals_for_step(params.lot_size),
+        }
+    }
+
+    pub fn round_price(&self, price: f64) -> f64 {
+        round_to(price, self.price_decimals)
+    }
+
+    pub fn round_size(&self, quantity: f64) -> f64 {
+        round_to(quantity, self.size_decimals)
+    }
+}
+
+/// Decimal places needed to represent multiples of `step` (0.001 -> 3, 5.0 -> 0)
+pub fn decimals_for_step(step: f64) -> u32 {
+    if !(step.is_finite() && step > 0.0) {
+        return MAX_DECIMALS;
+    }
+    (0..MAX_DECIMALS)
+        .find(|decimals| {
+            let scaled = step * 10f64.powi(*decimals as i32);
+            (scaled - scaled.round()).abs() < 1e-9
+        })
+        .unwrap_or(MAX_DECIMALS)
+}
+
+fn round_to(value: f64, decimals: u32) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    (value * scale).round() / scale
+}
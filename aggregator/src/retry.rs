@@ -0,0 +1,49 @@
+// Shared retry policy
+// Exponential backoff parameters used by the venue adapter and the
+// HTTP transports
+//
+// Numan Thabit 2025 Nov
+
+use backoff::ExponentialBackoff;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    initial: Duration,
+    max: Duration,
+    max_elapsed: Duration,
+    multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(200),
+            max: Duration::from_secs(5),
+            max_elapsed: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(initial: Duration, max: Duration, max_elapsed: Duration, multiplier: f64) -> Self {
+        Self {
+            initial,
+            max,
+            max_elapsed,
+            multiplier,
+        }
+    }
+
+    /// Fresh backoff state for one retried operation
+    pub fn to_backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoff {
+            initial_interval: self.initial,
+            max_interval: self.max,
+            max_elapsed_time: Some(self.max_elapsed),
+            multiplier: self.multiplier,
+            ..ExponentialBackoff::default()
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Capabilities document
+// Which optional features this deployment runs with, so clients can adapt
+// instead of probing endpoints
+//
+// Numan Thabit 2025 Nov
+
+use serde::{Deserialize, Serialize};
+
+/// Whether the binary was built with the `grpc-exec` feature
+pub const GRPC_EXEC_COMPILED: bool = cfg!(feature = "grpc-exec");
+
+/// Features enabled in this deployment, from build flags and runtime config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Server version
+    pub version: String,
+    /// Orders can be placed, cancelled and replaced; false in observer mode
+    pub execution: bool,
+    /// Transactions go out through gRPC ExecuteTransaction; needs the
+    /// `grpc-exec` build feature and the runtime switch
+    pub grpc_exec: bool,
+    /// DeepBook venue adapter configured
+    pub deepbook: bool,
+    /// AMM venues (Cetus, Turbos) available for routing
+    pub amm_venues: bool,
+    /// A default gas sponsor is configured
+    pub sponsorship: bool,
+    /// Aliases of sponsors orders may select with `sponsor`
+    pub sponsors: Vec<String>,
+    /// Gas budgets sized from a simulation before submitting
+    pub simulation: bool,
+    /// Orders are simulated only, never submitted
+    pub dry_run: bool,
+    /// Session heartbeats over WebSocket
+    pub websocket_sessions: bool,
+    /// POST /api/v1/build_signed enabled
+    pub build_signed: bool,
+    /// POST /api/v1/sign_message enabled
+    pub sign_message: bool,
+    /// GET /api/v1/history/volume backed by GraphQL
+    pub history: bool,
+    /// POST /api/v1/admin/book_snapshot enabled
+    pub book_export: bool,
+}
+
+/// gRPC execution runs only when compiled in and switched on
+pub fn grpc_exec_enabled(configured: bool) -> bool {
+    GRPC_EXEC_COMPILED && configured
+}
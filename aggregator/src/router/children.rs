@@ -0,0 +1,291 @@
+// Child order submission for slicing strategies
+// Runs a strategy's child orders with a bounded number in flight and a
+// policy for what happens to the rest when one of them fails, and schedules
+// TWAP slices on a fixed interval
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use crate::router::quotes::now_ms;
+use crate::venues::adapter::LimitReq;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+/// Most slices one TWAP may be split into
+pub const MAX_TWAP_SLICES: u32 = 1000;
+/// Longest a TWAP may run, as slices times the interval between them
+pub const MAX_TWAP_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What to do with remaining children after one fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChildFailurePolicy {
+    /// Submit nothing further once a child fails
+    #[default]
+    Abort,
+    /// Keep submitting the remaining children
+    Continue,
+}
+
+/// Limits applied to one strategy's child submissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChildSubmissionPolicy {
+    /// Children in flight at once (minimum 1)
+    pub max_parallel: usize,
+    pub on_failure: ChildFailurePolicy,
+}
+
+impl Default for ChildSubmissionPolicy {
+    fn default() -> Self {
+        Self {
+            max_parallel: 1,
+            on_failure: ChildFailurePolicy::Abort,
+        }
+    }
+}
+
+/// Outcome of a strategy's child submissions
+#[derive(Debug)]
+pub struct ChildReport<T> {
+    /// Result of every child that was submitted, in child order
+    pub submitted: Vec<Result<T>>,
+    /// Children never submitted, because an earlier child failed under `Abort`
+    /// or the strategy was cancelled
+    pub skipped: usize,
+}
+
+impl<T> ChildReport<T> {
+    pub fn failures(&self) -> usize {
+        self.submitted
+            .iter()
+            .filter(|result| result.is_err())
+            .count()
+    }
+}
+
+/// Submit `children` through `submit`, at most `policy.max_parallel` at a time.
+/// Children start in order; under `Abort`, children that have not started when
+/// a failure is seen are skipped.
+pub async fn submit_children<T, F, Fut>(
+    children: Vec<LimitReq>,
+    policy: &ChildSubmissionPolicy,
+    submit: F,
+) -> ChildReport<T>
+where
+    F: Fn(LimitReq) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    submit_children_until(children, policy, || false, submit).await
+}
+
+/// Like `submit_children`, but skips every child not yet started once
+/// `cancelled` returns true
+pub async fn submit_children_until<T, C, F, Fut>(
+    children: Vec<LimitReq>,
+    policy: &ChildSubmissionPolicy,
+    cancelled: C,
+    submit: F,
+) -> ChildReport<T>
+where
+    C: Fn() -> bool,
+    F: Fn(LimitReq) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let aborted = AtomicBool::new(false);
+    let outcomes: Vec<Option<Result<T>>> = stream::iter(children)
+        .map(|child| {
+            let aborted = &aborted;
+            let cancelled = &cancelled;
+            let submit = &submit;
+            async move {
+                if aborted.load(Ordering::SeqCst) || cancelled() {
+                    return None;
+                }
+                let client_order_id = child.client_order_id.clone();
+                let result = submit(child).await;
+                if let Err(err) = &result {
+                    warn!(
+                        client_order_id = %client_order_id,
+                        error = %err,
+                        policy = ?policy.on_failure,
+                        "child order failed"
+                    );
+                    if policy.on_failure == ChildFailurePolicy::Abort {
+                        aborted.store(true, Ordering::SeqCst);
+                    }
+                }
+                Some(result)
+            }
+        })
+        .buffered(policy.max_parallel.max(1))
+        .collect()
+        .await;
+
+    let skipped = outcomes.iter().filter(|outcome| outcome.is_none()).count();
+    ChildReport {
+        submitted: outcomes.into_iter().flatten().collect(),
+        skipped,
+    }
+}
+
+/// Time source for TWAP scheduling, replaceable in tests
+pub trait TwapClock: Send + Sync {
+    fn now_ms(&self) -> u64;
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()>;
+}
+
+/// Wall clock with tokio timers
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TwapClock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        now_ms()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// Split `base` into `slices` children of equal quantity; the last child
+/// absorbs rounding so the slices sum to the parent. Child ids are the
+/// parent's with a `-{index}` suffix.
+pub fn twap_children(base: &LimitReq, slices: u32) -> Vec<LimitReq> {
+    let slices = slices.max(1);
+    let per_slice = base.quantity / slices as f64;
+    (0..slices)
+        .map(|index| {
+            let mut child = base.clone();
+            child.quantity = if index + 1 == slices {
+                base.quantity - per_slice * (slices - 1) as f64
+            } else {
+                per_slice
+            };
+            child.client_order_id = format!("{}-{index}", base.client_order_id);
+            child
+        })
+        .collect()
+}
+
+/// One submitted TWAP slice
+#[derive(Debug, Serialize)]
+pub struct TwapSlice<T> {
+    pub client_order_id: String,
+    pub quantity: f64,
+    /// Clock time the slice was handed to the executor
+    pub submitted_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a TWAP's slices
+#[derive(Debug, Serialize)]
+pub struct TwapResult<T> {
+    /// Registry id of the strategy, when it was registered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// Every slice that was submitted, in order
+    pub slices: Vec<TwapSlice<T>>,
+    /// Slices never submitted, because of cancellation, a failure under
+    /// `ChildFailurePolicy::Abort` or an open breaker
+    pub skipped: usize,
+    /// Why the remaining slices were abandoned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aborted: Option<String>,
+}
+
+impl<T> TwapResult<T> {
+    pub fn failures(&self) -> usize {
+        self.slices
+            .iter()
+            .filter(|slice| slice.error.is_some())
+            .count()
+    }
+}
+
+/// Submit `children` one at a time, `interval` apart on `clock`. Once
+/// `cancelled` returns true the remaining slices are skipped; the wait between
+/// slices ends early when `until_cancelled` resolves, so a cancel takes effect
+/// without waiting out the interval. A failed slice skips the rest under
+/// `ChildFailurePolicy::Abort`, and always when it failed on an open circuit
+/// breaker.
+pub async fn run_twap<T, C, W, WFut, F, Fut>(
+    children: Vec<LimitReq>,
+    interval: Duration,
+    clock: &dyn TwapClock,
+    on_failure: ChildFailurePolicy,
+    cancelled: C,
+    until_cancelled: W,
+    submit: F,
+) -> TwapResult<T>
+where
+    C: Fn() -> bool,
+    W: Fn() -> WFut,
+    WFut: Future<Output = ()>,
+    F: Fn(LimitReq) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let total = children.len();
+    let mut result = TwapResult {
+        strategy: None,
+        slices: Vec::with_capacity(total),
+        skipped: 0,
+        aborted: None,
+    };
+    for (index, child) in children.into_iter().enumerate() {
+        if index > 0 {
+            tokio::select! {
+                _ = clock.sleep(interval) => {}
+                _ = until_cancelled() => {}
+            }
+        }
+        if cancelled() {
+            result.skipped = total - index;
+            result.aborted = Some("strategy cancelled".to_string());
+            break;
+        }
+        let client_order_id = child.client_order_id.clone();
+        let quantity = child.quantity;
+        let submitted_ms = clock.now_ms();
+        let (execution, error) = match submit(child).await {
+            Ok(execution) => (Some(execution), None),
+            Err(err) => {
+                warn!(
+                    client_order_id = %client_order_id,
+                    error = %err,
+                    policy = ?on_failure,
+                    "TWAP slice failed"
+                );
+                let breaker_open = matches!(
+                    err.downcast_ref::<AggrError>(),
+                    Some(AggrError::CircuitOpen(_))
+                );
+                if breaker_open || on_failure == ChildFailurePolicy::Abort {
+                    result.skipped = total - index - 1;
+                    result.aborted = Some(err.to_string());
+                }
+                (None, Some(err.to_string()))
+            }
+        };
+        result.slices.push(TwapSlice {
+            client_order_id,
+            quantity,
+            submitted_ms,
+            execution,
+            error,
+        });
+        if result.aborted.is_some() {
+            break;
+        }
+    }
+    result
+}
@@ -0,0 +1,42 @@
+// Request deadlines
+// Bounds handler work by the client's `timeout_ms` without abandoning a
+// transaction that is already on its way to the network. Work is cancelled
+// only while nothing has been submitted; once the execution engine marks the
+// submission point the operation runs to completion so its digest,
+// idempotency entry and inventory update are all recorded.
+//
+// Numan Thabit 2025 Nov
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+tokio::task_local! {
+    static SUBMITTED: Arc<AtomicBool>;
+}
+
+/// Run `fut` until `deadline`, returning `None` if it expired before submitting.
+/// A future that called [`begin_submission`] is awaited past the deadline.
+pub async fn run_until_submitted<T>(deadline: Duration, fut: impl Future<Output = T>) -> Option<T> {
+    let submitted = Arc::new(AtomicBool::new(false));
+    let fut = SUBMITTED.scope(submitted.clone(), fut);
+    tokio::pin!(fut);
+
+    tokio::select! {
+        out = &mut fut => return Some(out),
+        _ = tokio::time::sleep(deadline) => {}
+    }
+
+    if submitted.load(Ordering::Acquire) {
+        Some(fut.await)
+    } else {
+        None
+    }
+}
+
+/// Mark that the current request is about to submit a transaction.
+/// Outside [`run_until_submitted`] this is a no-op.
+pub fn begin_submission() {
+    let _ = SUBMITTED.try_with(|flag| flag.store(true, Ordering::Release));
+}
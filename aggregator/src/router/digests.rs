@@ -0,0 +1,254 @@
+// Seen transaction digests
+// Remembers the digests the execution engine submitted so a retried plan that
+// compiles to the same transaction is refused. Entries expire after a TTL; the
+// file-backed store keeps them across restarts as append-only JSON lines.
+//
+// Numan Thabit 2025 Nov
+
+use crate::router::quotes::now_ms;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::warn;
+
+/// How long a submitted digest is remembered by default
+pub const DEFAULT_DIGEST_TTL: Duration = Duration::from_secs(3_600);
+/// Appended lines tolerated before the file is rewritten with live entries only
+const MIN_COMPACTION_LINES: usize = 1_024;
+
+/// Wall-clock milliseconds since the Unix epoch, replaceable in tests
+pub type WallClock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// Digests of submitted transactions
+pub trait DigestStore: Send + Sync {
+    /// Whether `digest` was recorded within the TTL
+    fn contains(&self, digest: &str) -> bool;
+    /// Record `digest` as submitted now
+    fn insert(&self, digest: &str) -> Result<()>;
+}
+
+/// Digest -> insertion time, dropping entries older than `ttl`
+struct DigestSet {
+    ttl_ms: u64,
+    entries: HashMap<String, u64>,
+}
+
+impl DigestSet {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl_ms: ttl.as_millis() as u64,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn is_live(&self, at_ms: u64, now_ms: u64) -> bool {
+        now_ms.saturating_sub(at_ms) < self.ttl_ms
+    }
+
+    fn contains(&self, digest: &str, now_ms: u64) -> bool {
+        self.entries
+            .get(digest)
+            .is_some_and(|at_ms| self.is_live(*at_ms, now_ms))
+    }
+
+    fn insert(&mut self, digest: &str, now_ms: u64) {
+        let ttl_ms = self.ttl_ms;
+        self.entries
+            .retain(|_, at_ms| now_ms.saturating_sub(*at_ms) < ttl_ms);
+        self.entries.insert(digest.to_string(), now_ms);
+    }
+}
+
+/// In-process store; forgets everything on restart
+pub struct MemoryDigestStore {
+    set: Mutex<DigestSet>,
+    clock: WallClock,
+}
+
+impl MemoryDigestStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            set: Mutex::new(DigestSet::new(ttl)),
+            clock: Arc::new(now_ms),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn set(&self) -> MutexGuard<'_, DigestSet> {
+        self.set
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn len(&self) -> usize {
+        self.set().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryDigestStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIGEST_TTL)
+    }
+}
+
+impl DigestStore for MemoryDigestStore {
+    fn contains(&self, digest: &str) -> bool {
+        self.set().contains(digest, (self.clock)())
+    }
+
+    fn insert(&self, digest: &str) -> Result<()> {
+        self.set().insert(digest, (self.clock)());
+        Ok(())
+    }
+}
+
+/// One line of the digest file
+#[derive(Debug, Serialize, Deserialize)]
+struct DigestLine {
+    digest: String,
+    at_ms: u64,
+}
+
+struct FileState {
+    set: DigestSet,
+    file: File,
+    /// Lines in the file, live or not
+    lines: usize,
+}
+
+/// Store backed by an append-only JSON lines file. Opening reloads the live
+/// entries and rewrites the file without expired ones.
+pub struct FileDigestStore {
+    path: PathBuf,
+    state: Mutex<FileState>,
+    clock: WallClock,
+}
+
+impl FileDigestStore {
+    pub fn open(path: impl Into<PathBuf>, ttl: Duration) -> Result<Self> {
+        Self::open_with_clock(path, ttl, Arc::new(now_ms))
+    }
+
+    pub fn open_with_clock(
+        path: impl Into<PathBuf>,
+        ttl: Duration,
+        clock: WallClock,
+    ) -> Result<Self> {
+        let path = path.into();
+        let now = clock();
+        let mut set = DigestSet::new(ttl);
+        for line in read_lines(&path)? {
+            if set.is_live(line.at_ms, now) {
+                set.entries.insert(line.digest, line.at_ms);
+            }
+        }
+        let file = rewrite(&path, &set)?;
+        let lines = set.entries.len();
+        Ok(Self {
+            path,
+            state: Mutex::new(FileState { set, file, lines }),
+            clock,
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, FileState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn len(&self) -> usize {
+        self.state().set.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl DigestStore for FileDigestStore {
+    fn contains(&self, digest: &str) -> bool {
+        self.state().set.contains(digest, (self.clock)())
+    }
+
+    fn insert(&self, digest: &str) -> Result<()> {
+        let now = (self.clock)();
+        let mut state = self.state();
+        state.set.insert(digest, now);
+        if state.lines >= MIN_COMPACTION_LINES.max(state.set.entries.len() * 2) {
+            state.file = rewrite(&self.path, &state.set)?;
+            state.lines = state.set.entries.len();
+            return Ok(());
+        }
+        let mut line = serde_json::to_vec(&DigestLine {
+            digest: digest.to_string(),
+            at_ms: now,
+        })?;
+        line.push(b'\n');
+        state
+            .file
+            .write_all(&line)
+            .with_context(|| format!("append digest to {}", self.path.display()))?;
+        state.lines += 1;
+        Ok(())
+    }
+}
+
+/// Entries in `path`; a missing file is empty. Lines that do not parse, such
+/// as one cut short by a crash, are skipped.
+fn read_lines(path: &Path) -> Result<Vec<DigestLine>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("open {}", path.display())),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => {
+                warn!(path = %path.display(), error = %err, "skipping unreadable digest line")
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Replace `path` with the live entries of `set` and reopen it for appending
+fn rewrite(path: &Path, set: &DigestSet) -> Result<File> {
+    let mut contents = Vec::new();
+    for (digest, at_ms) in &set.entries {
+        serde_json::to_writer(
+            &mut contents,
+            &DigestLine {
+                digest: digest.clone(),
+                at_ms: *at_ms,
+            },
+        )?;
+        contents.push(b'\n');
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents).with_context(|| format!("write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("replace {}", path.display()))?;
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .with_context(|| format!("open {} for appending", path.display()))
+}
@@ -4,32 +4,89 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::control::BreakerClass;
 use crate::errors::AggrError;
-use crate::metrics::DEEPBOOK_EVENT_COUNTER;
-use crate::quant::{quantize_price, quantize_size};
-use crate::router::routes::{Route, RoutePlan};
+use crate::metrics::{DEEPBOOK_EVENT_COUNTER, REQ_LATENCY};
+use crate::quant::{quantize_price_for_side, quantize_size};
+use crate::router::capabilities::grpc_exec_enabled;
+use crate::router::deadline;
+use crate::router::digests::{DigestStore, MemoryDigestStore};
+use crate::router::routes::{ReplaceCommand, ReplaceMode, Route, RoutePlan};
 use crate::router::validator::ValidatorSelector;
-use crate::signing::sign_tx_bcs_ed25519_to_serialized_signature;
-use crate::sponsorship::{SponsorshipManager, SponsorshipRequest};
-use crate::transport::grpc::sui::rpc::v2::ExecutedTransaction;
+use crate::signing::Signer;
+use crate::sponsorship::{
+    GaslessFallback, SponsorRegistry, SponsorshipManager, SponsorshipRequest,
+};
+use crate::state::PendingConfirmations;
+use crate::transport::grpc::sui::rpc::v2::{
+    Bcs, ExecutedTransaction, SignatureScheme, UserSignature,
+};
 use crate::transport::grpc::GrpcClients;
-use crate::transport::jsonrpc::JsonRpc;
+use crate::transport::jsonrpc::{DryRunResp, JsonRpc};
 use crate::venues::adapter::{BalanceSnapshot, DeepBookAdapter, LimitReq};
+use crate::venues::amm::{AmmSwapReq, CetusAdapter};
 use anyhow::{Context, Result};
 use backoff::{future::retry, ExponentialBackoff};
 use bcs;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 use sui_sdk::rpc_types::SuiEvent;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_sdk::types::transaction::{InputObjectKind, TransactionData, TransactionKind};
+use sui_sdk::types::transaction::{
+    InputObjectKind, TransactionData, TransactionDataAPI, TransactionKind,
+};
+use tokio::sync::oneshot;
 use tracing::{debug, info, warn};
 
-const MICROS_PER_UNIT: f64 = 1_000_000.0;
 const PRICE_TOLERANCE: f64 = 1e-6;
+/// Default cap on rebuilds after object version conflicts
+pub const DEFAULT_MAX_CONFLICT_REBUILDS: u32 = 3;
+/// Error fragments reported when a transaction references superseded object versions
+const VERSION_CONFLICT_MARKERS: &[&str] = &[
+    "ObjectVersionUnavailableForConsumption",
+    "unavailable for consumption",
+    "needs to be rebuilt",
+];
+/// Execution failures that resubmitting the same signed bytes cannot fix
+const PERMANENT_SUBMIT_MARKERS: &[&str] = &[
+    "MoveAbort",
+    "InsufficientGas",
+    "GasBalanceTooLow",
+    "GasBudgetTooLow",
+    "InsufficientCoinBalance",
+    "Invalid user signature",
+    "signature is not valid",
+];
+/// Default period between writes of persisted execution statistics
+pub const DEFAULT_STATS_FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// Default multiplier applied to simulated gas when sizing the gas budget
+pub const DEFAULT_GAS_SAFETY_FACTOR: f64 = 1.2;
+/// Smallest gas budget (MIST) the network accepts at the default reference price
+pub const MIN_GAS_BUDGET: u64 = 2_000_000;
+/// Largest gas budget (MIST) the network accepts for one transaction
+pub const MAX_GAS_BUDGET: u64 = 50_000_000_000;
+/// DeepBook event names that report a fill
+const FILL_EVENTS: &[&str] = &[
+    "OrderFilled",
+    "OrderFilledV2",
+    "OrderMatch",
+    "OrderMatched",
+    "OrderFill",
+];
+/// Event fields carrying the filled base quantity
+const BASE_FILLED_FIELDS: &[&str] = &[
+    "filledQuantity",
+    "baseFilled",
+    "quantityFilled",
+    "base_filled",
+];
+/// Event fields carrying the filled quote quantity
+const QUOTE_FILLED_FIELDS: &[&str] = &["quoteFilled", "quote_filled"];
+/// How long to wait for a streamed checkpoint to include an executed transaction
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Execution statistics for monitoring
 #[derive(Debug, Clone, serde::Serialize)]
@@ -47,6 +104,149 @@ pub struct ExecutionStats {
     pub total_sponsored_gas: u64,
 }
 
+/// Running execution counters, updated and read under a single lock so that
+/// snapshots are internally consistent. Also the persisted form of lifetime stats.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ExecutionCounters {
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub failed_executions: u64,
+    pub total_effects_time_ms: f64,
+    pub total_checkpoint_time_ms: f64,
+    pub checkpoint_count: u64,
+    pub total_quote_fees: f64,
+    pub total_deep_fees: f64,
+    pub total_quote_rebates: f64,
+    pub total_deep_rebates: f64,
+    pub total_sponsor_gas: u64,
+}
+
+impl ExecutionCounters {
+    /// Sum of two sets of counters, e.g. restored lifetime totals plus this run
+    pub fn merged(&self, other: &Self) -> Self {
+        Self {
+            total_executions: self.total_executions + other.total_executions,
+            successful_executions: self.successful_executions + other.successful_executions,
+            failed_executions: self.failed_executions + other.failed_executions,
+            total_effects_time_ms: self.total_effects_time_ms + other.total_effects_time_ms,
+            total_checkpoint_time_ms: self.total_checkpoint_time_ms
+                + other.total_checkpoint_time_ms,
+            checkpoint_count: self.checkpoint_count + other.checkpoint_count,
+            total_quote_fees: self.total_quote_fees + other.total_quote_fees,
+            total_deep_fees: self.total_deep_fees + other.total_deep_fees,
+            total_quote_rebates: self.total_quote_rebates + other.total_quote_rebates,
+            total_deep_rebates: self.total_deep_rebates + other.total_deep_rebates,
+            total_sponsor_gas: self.total_sponsor_gas + other.total_sponsor_gas,
+        }
+    }
+
+    /// Write the counters to `path` as JSON. The file is replaced atomically
+    /// (temp file + rename) so a crash mid-write leaves the previous snapshot.
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        tokio::fs::write(&tmp, json)
+            .await
+            .with_context(|| format!("write {}", tmp.display()))?;
+        tokio::fs::File::open(&tmp)
+            .await
+            .with_context(|| format!("open {}", tmp.display()))?
+            .sync_all()
+            .await
+            .with_context(|| format!("sync {}", tmp.display()))?;
+        tokio::fs::rename(&tmp, path)
+            .await
+            .with_context(|| format!("replace execution stats at {}", path.display()))
+    }
+
+    /// Load counters written by `save`; a missing file yields `None`
+    pub async fn load(path: &Path) -> Result<Option<Self>> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("read execution stats from {}", path.display()))
+            }
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .with_context(|| format!("parse execution stats from {}", path.display()))
+    }
+
+    pub fn snapshot(&self) -> ExecutionStats {
+        let successful = self.successful_executions;
+        ExecutionStats {
+            total_executions: self.total_executions,
+            successful_executions: successful,
+            failed_executions: self.failed_executions,
+            avg_effects_time_ms: if successful > 0 {
+                Some(self.total_effects_time_ms / successful as f64)
+            } else {
+                None
+            },
+            avg_checkpoint_time_ms: if self.checkpoint_count > 0 {
+                Some(self.total_checkpoint_time_ms / self.checkpoint_count as f64)
+            } else {
+                None
+            },
+            success_rate: if self.total_executions > 0 {
+                successful as f64 / self.total_executions as f64
+            } else {
+                0.0
+            },
+            total_quote_fees: self.total_quote_fees,
+            total_deep_fees: self.total_deep_fees,
+            total_quote_rebates: self.total_quote_rebates,
+            total_deep_rebates: self.total_deep_rebates,
+            total_sponsored_gas: self.total_sponsor_gas,
+        }
+    }
+}
+
+/// Live execution counters shared by concurrent executions. Every update and
+/// snapshot takes the one lock, and an attempt is counted before its outcome,
+/// so a snapshot never shows more outcomes than attempts.
+#[derive(Debug, Default)]
+pub struct ExecutionTally {
+    counters: Mutex<ExecutionCounters>,
+}
+
+impl ExecutionTally {
+    /// Count an execution before its outcome is known
+    pub fn record_attempt(&self) {
+        self.lock().total_executions += 1;
+    }
+
+    pub fn record_failure(&self) {
+        self.lock().failed_executions += 1;
+    }
+
+    pub fn record_success(&self, effects_time_ms: f64, checkpoint_time_ms: Option<f64>) {
+        let mut counters = self.lock();
+        counters.successful_executions += 1;
+        counters.total_effects_time_ms += effects_time_ms;
+        if let Some(checkpoint_ms) = checkpoint_time_ms {
+            counters.total_checkpoint_time_ms += checkpoint_ms;
+            counters.checkpoint_count += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> ExecutionStats {
+        self.lock().snapshot()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ExecutionCounters> {
+        // Counters stay usable even if a holder panicked mid-update
+        self.counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
 #[derive(Debug, Clone, Copy, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum FeeKind {
@@ -89,8 +289,45 @@ pub struct ExecutionAccounting {
     pub gas_used: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sponsor_gas_used: Option<u64>,
+    /// Gas the pre-submission simulation charged, when one ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_gas: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deepbook_events: Option<DeepBookEventStats>,
+    /// Fills attributed to each leg of a multi-leg route
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legs: Vec<LegFill>,
+}
+
+/// Fills of one route leg, decoded from its venue's events
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct LegFill {
+    /// Position of the leg in the route
+    pub leg: usize,
+    pub venue: String,
+    pub pool: String,
+    pub filled_quantity: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_price: Option<f64>,
+    pub fee: f64,
+}
+
+/// Venue event reduced to what fill attribution reads
+#[derive(Debug, Clone, Copy)]
+pub struct VenueEvent<'a> {
+    pub module: &'a str,
+    pub name: &'a str,
+    pub fields: &'a Value,
+}
+
+impl<'a> From<&'a SuiEvent> for VenueEvent<'a> {
+    fn from(event: &'a SuiEvent) -> Self {
+        Self {
+            module: event.type_.module.as_str(),
+            name: event.type_.name.as_str(),
+            fields: &event.parsed_json,
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -130,6 +367,17 @@ impl OrderIndex {
     }
 }
 
+/// A transaction built and submitted by `build_and_submit`
+struct Submission {
+    digest: String,
+    executed: ExecutedTransaction,
+    submit_start: Instant,
+    is_sponsored: bool,
+    estimated_gas: Option<u64>,
+    /// Resolves with the including checkpoint; registered before submission
+    confirmation: Option<oneshot::Receiver<u64>>,
+}
+
 /// Execution result with timing information
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
@@ -141,35 +389,48 @@ pub struct ExecutionResult {
     pub checkpoint_time_ms: Option<f64>,
     pub accounting: ExecutionAccounting,
     pub orders: Vec<OrderHandle>,
+    /// Simulated only; nothing was submitted and `digest` is not on chain
+    pub dry_run: bool,
 }
 
 /// Execution engine that compiles routes to PTBs and executes them
 pub struct ExecutionEngine {
     deepbook: Option<Arc<DeepBookAdapter>>,
+    /// Builds the AMM legs of multi-venue routes
+    cetus: Option<Arc<CetusAdapter>>,
     grpc: Arc<tokio::sync::Mutex<GrpcClients>>,
     jsonrpc: Arc<JsonRpc>,
     validator_selector: Arc<ValidatorSelector>,
-    secret_key_hex: String,
+    /// Signs as the user; a local key or an external signer
+    signer: Arc<dyn Signer>,
     /// User's Sui address (derived from secret key or from config)
     user_address: sui_sdk::types::base_types::SuiAddress,
-    /// Set of transaction digests we've seen (for idempotent retries)
-    seen_digests: Arc<tokio::sync::RwLock<HashSet<String>>>,
+    /// Transaction digests we've submitted (for idempotent retries)
+    seen_digests: Arc<dyn DigestStore>,
     /// Use gRPC execution if available
     use_grpc_execute: bool,
     /// Optional sponsorship manager for sponsored transactions
     sponsorship: Option<Arc<SponsorshipManager>>,
-    /// Execution statistics
-    total_executions: AtomicU64,
-    successful_executions: AtomicU64,
-    failed_executions: AtomicU64,
-    total_effects_time_ms: AtomicU64, // Sum of all effects times in milliseconds (as u64 * 1000 for precision)
-    total_checkpoint_time_ms: AtomicU64, // Sum of all checkpoint times in milliseconds
-    checkpoint_count: AtomicU64,
-    total_quote_fees_micros: AtomicU64,
-    total_deep_fees_micros: AtomicU64,
-    total_quote_rebates_micros: AtomicU64,
-    total_deep_rebates_micros: AtomicU64,
-    total_sponsor_gas: AtomicU64,
+    /// Additional sponsors selectable per request by alias
+    sponsors: SponsorRegistry,
+    /// Checkpoint-driven confirmations for transactions executed before inclusion
+    confirmations: Option<PendingConfirmations>,
+    /// Rebuild attempts allowed after object version conflicts
+    max_conflict_rebuilds: u32,
+    /// Healthy, recently seen validators required before submitting (0 disables)
+    min_healthy_validators: usize,
+    /// Simulate instead of submitting
+    dry_run: bool,
+    /// Size gas budgets from a simulation times this factor (None: static budget)
+    gas_safety_factor: Option<f64>,
+    /// Simulate every compiled transaction and refuse to submit it when it aborts
+    simulate_before_execute: bool,
+    /// What sponsored orders do when the gasless build fails
+    gasless_fallback: GaslessFallback,
+    /// Execution statistics since start or the last window reset
+    counters: ExecutionTally,
+    /// Totals from earlier runs and reset windows; lifetime = this + `counters`
+    lifetime_base: Mutex<ExecutionCounters>,
     order_index: Arc<tokio::sync::RwLock<OrderIndex>>,
 }
 
@@ -179,41 +440,163 @@ impl ExecutionEngine {
         grpc: GrpcClients,
         jsonrpc: JsonRpc,
         validator_selector: Arc<ValidatorSelector>,
-        secret_key_hex: String,
+        signer: Arc<dyn Signer>,
         user_address: sui_sdk::types::base_types::SuiAddress,
         use_grpc_execute: bool,
     ) -> Self {
         Self {
             deepbook,
+            cetus: None,
             grpc: Arc::new(tokio::sync::Mutex::new(grpc)),
             jsonrpc: Arc::new(jsonrpc),
             validator_selector,
-            secret_key_hex,
+            signer,
             user_address,
-            seen_digests: Arc::new(tokio::sync::RwLock::new(HashSet::new())),
+            seen_digests: Arc::new(MemoryDigestStore::default()),
             use_grpc_execute,
             sponsorship: None,
-            total_executions: AtomicU64::new(0),
-            successful_executions: AtomicU64::new(0),
-            failed_executions: AtomicU64::new(0),
-            total_effects_time_ms: AtomicU64::new(0),
-            total_checkpoint_time_ms: AtomicU64::new(0),
-            checkpoint_count: AtomicU64::new(0),
-            total_quote_fees_micros: AtomicU64::new(0),
-            total_deep_fees_micros: AtomicU64::new(0),
-            total_quote_rebates_micros: AtomicU64::new(0),
-            total_deep_rebates_micros: AtomicU64::new(0),
-            total_sponsor_gas: AtomicU64::new(0),
+            sponsors: SponsorRegistry::new(),
+            confirmations: None,
+            max_conflict_rebuilds: DEFAULT_MAX_CONFLICT_REBUILDS,
+            min_healthy_validators: 0,
+            dry_run: false,
+            gas_safety_factor: None,
+            simulate_before_execute: false,
+            gasless_fallback: GaslessFallback::default(),
+            counters: ExecutionTally::default(),
+            lifetime_base: Mutex::new(ExecutionCounters::default()),
             order_index: Arc::new(tokio::sync::RwLock::new(OrderIndex::default())),
         }
     }
 
+    /// Compile Cetus swaps in multi-venue routes with `cetus`
+    pub fn with_cetus(mut self, cetus: Arc<CetusAdapter>) -> Self {
+        self.cetus = Some(cetus);
+        self
+    }
+
     /// Set sponsorship manager for sponsored transactions
     pub fn with_sponsorship(mut self, sponsorship: Arc<SponsorshipManager>) -> Self {
         self.sponsorship = Some(sponsorship);
         self
     }
 
+    /// Sponsors that individual requests may select by alias
+    pub fn with_sponsor_registry(mut self, sponsors: SponsorRegistry) -> Self {
+        self.sponsors = sponsors;
+        self
+    }
+
+    /// Registered per-request sponsors
+    pub fn sponsors(&self) -> &SponsorRegistry {
+        &self.sponsors
+    }
+
+    /// Remember submitted digests in `store`, e.g. one that survives restarts
+    pub fn with_digest_store(mut self, store: Arc<dyn DigestStore>) -> Self {
+        self.seen_digests = store;
+        self
+    }
+
+    /// Cap rebuilds after object version conflicts (0 disables rebuilding)
+    pub fn with_max_conflict_rebuilds(mut self, max: u32) -> Self {
+        self.max_conflict_rebuilds = max;
+        self
+    }
+
+    /// Refuse to submit while fewer than `min` validators are healthy
+    pub fn with_min_healthy_validators(mut self, min: usize) -> Self {
+        self.min_healthy_validators = min;
+        self
+    }
+
+    /// Compile, sign and simulate every plan but never submit it
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// What sponsored orders do when their gasless transaction cannot be built
+    pub fn with_gasless_fallback(mut self, fallback: GaslessFallback) -> Self {
+        self.gasless_fallback = fallback;
+        self
+    }
+
+    /// Whether submissions go through gRPC ExecuteTransaction
+    pub fn uses_grpc_execute(&self) -> bool {
+        grpc_exec_enabled(self.use_grpc_execute)
+    }
+
+    /// Whether a default sponsor pays gas when asked
+    pub fn has_default_sponsor(&self) -> bool {
+        self.sponsorship.is_some()
+    }
+
+    /// Whether gas budgets come from a simulation
+    pub fn simulates_gas_budget(&self) -> bool {
+        self.gas_safety_factor.is_some()
+    }
+
+    /// Simulate each transaction first and budget its simulated gas times `factor`
+    pub fn with_simulated_gas_budget(mut self, factor: f64) -> Self {
+        self.gas_safety_factor = Some(factor);
+        self
+    }
+
+    /// Simulate each transaction, sponsored ones with the sponsor's gas, before
+    /// submitting it; a failed simulation aborts with `AggrError::SimulationRejected`
+    pub fn with_simulation(mut self, enabled: bool) -> Self {
+        self.simulate_before_execute = enabled;
+        self
+    }
+
+    pub fn simulates_before_execute(&self) -> bool {
+        self.simulate_before_execute
+    }
+
+    /// Track checkpoint inclusion of transactions through the checkpoint stream
+    pub fn with_confirmations(mut self, confirmations: PendingConfirmations) -> Self {
+        self.confirmations = Some(confirmations);
+        self
+    }
+
+    /// Report checkpoint inclusion of `digest` once `receiver` resolves,
+    /// giving up after `CONFIRMATION_TIMEOUT`
+    fn watch_confirmation(
+        confirmations: &PendingConfirmations,
+        digest: &str,
+        receiver: oneshot::Receiver<u64>,
+        submitted_at: Instant,
+    ) {
+        let confirmations = confirmations.clone();
+        let digest = digest.to_string();
+        tokio::spawn(async move {
+            match tokio::time::timeout(CONFIRMATION_TIMEOUT, receiver).await {
+                Ok(Ok(checkpoint)) => {
+                    let elapsed = submitted_at.elapsed();
+                    REQ_LATENCY
+                        .with_label_values(&["checkpoint", "confirmation"])
+                        .observe(elapsed.as_secs_f64());
+                    debug!(
+                        digest = %digest,
+                        checkpoint,
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        "transaction included in checkpoint"
+                    );
+                }
+                Ok(Err(_)) => {}
+                Err(_) => {
+                    confirmations.forget(&digest).await;
+                    warn!(digest = %digest, "no checkpoint confirmation within timeout");
+                }
+            }
+        });
+    }
+
     /// Execute a route plan
     pub async fn execute(&self, plan: &RoutePlan) -> Result<ExecutionResult> {
         self.execute_with_sponsorship(plan, false).await
@@ -226,62 +609,109 @@ impl ExecutionEngine {
         warn!("set_sponsorship called but sponsorship is immutable after construction");
     }
 
-    /// Get execution statistics
+    /// Internally consistent snapshot of execution statistics
     pub fn get_stats(&self) -> ExecutionStats {
-        let total = self.total_executions.load(Ordering::Relaxed);
-        let successful = self.successful_executions.load(Ordering::Relaxed);
-        let failed = self.failed_executions.load(Ordering::Relaxed);
-        let total_effects_ms = self.total_effects_time_ms.load(Ordering::Relaxed) as f64 / 1000.0;
-        let total_checkpoint_ms =
-            self.total_checkpoint_time_ms.load(Ordering::Relaxed) as f64 / 1000.0;
-        let checkpoint_count = self.checkpoint_count.load(Ordering::Relaxed);
-        let total_quote_fees =
-            self.total_quote_fees_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
-        let total_deep_fees =
-            self.total_deep_fees_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
-        let total_quote_rebates =
-            self.total_quote_rebates_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
-        let total_deep_rebates =
-            self.total_deep_rebates_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
-        let total_sponsored_gas = self.total_sponsor_gas.load(Ordering::Relaxed);
+        self.counters.snapshot()
+    }
 
-        ExecutionStats {
-            total_executions: total,
-            successful_executions: successful,
-            failed_executions: failed,
-            avg_effects_time_ms: if successful > 0 {
-                Some(total_effects_ms / successful as f64)
-            } else {
-                None
-            },
-            avg_checkpoint_time_ms: if checkpoint_count > 0 {
-                Some(total_checkpoint_ms / checkpoint_count as f64)
-            } else {
-                None
-            },
-            success_rate: if total > 0 {
-                successful as f64 / total as f64
-            } else {
-                0.0
-            },
-            total_quote_fees,
-            total_deep_fees,
-            total_quote_rebates,
-            total_deep_rebates,
-            total_sponsored_gas,
+    fn counters(&self) -> MutexGuard<'_, ExecutionCounters> {
+        self.counters.lock()
+    }
+
+    fn lifetime_base(&self) -> MutexGuard<'_, ExecutionCounters> {
+        self.lifetime_base
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Cumulative counters across restarts and window resets
+    pub fn lifetime_counters(&self) -> ExecutionCounters {
+        // Lock order: base, then window (matches `reset_window`)
+        let base = self.lifetime_base();
+        base.merged(&self.counters())
+    }
+
+    /// Statistics across restarts and window resets
+    pub fn lifetime_snapshot(&self) -> ExecutionStats {
+        self.lifetime_counters().snapshot()
+    }
+
+    /// Start a fresh statistics window; lifetime totals keep everything so far
+    pub fn reset_window(&self) {
+        let mut base = self.lifetime_base();
+        let mut window = self.counters();
+        *base = base.merged(&window);
+        *window = ExecutionCounters::default();
+    }
+
+    /// Seed lifetime totals from an earlier run
+    pub fn restore_lifetime(&self, counters: ExecutionCounters) {
+        *self.lifetime_base() = counters;
+    }
+
+    /// Persist lifetime totals to `path`
+    pub async fn save_stats(&self, path: &Path) -> Result<()> {
+        self.lifetime_counters().save(path).await
+    }
+
+    /// Restore lifetime totals saved by `save_stats`; a missing file is not an error
+    pub async fn load_stats(&self, path: &Path) -> Result<bool> {
+        match ExecutionCounters::load(path).await? {
+            Some(counters) => {
+                self.restore_lifetime(counters);
+                Ok(true)
+            }
+            None => Ok(false),
         }
     }
 
     /// Execute a route plan with optional sponsorship
-    #[tracing::instrument(skip_all, fields(uses_sponsorship = use_sponsorship))]
     pub async fn execute_with_sponsorship(
         &self,
         plan: &RoutePlan,
         use_sponsorship: bool,
     ) -> Result<ExecutionResult> {
-        self.total_executions.fetch_add(1, Ordering::Relaxed);
+        let sponsor = self.sponsorship.clone().filter(|_| use_sponsorship);
+        self.execute_sponsored_by(plan, sponsor, None).await
+    }
+
+    /// Execute a route plan with gas paid by the sponsor registered as `alias`.
+    /// Retries carrying the same `idempotency_key` debit the sponsor once.
+    pub async fn execute_with_sponsor(
+        &self,
+        plan: &RoutePlan,
+        alias: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<ExecutionResult> {
+        let sponsor = self.sponsors.get(alias)?;
+        self.execute_sponsored_by(plan, Some(sponsor), idempotency_key)
+            .await
+    }
+
+    #[tracing::instrument(skip_all, fields(uses_sponsorship = sponsor.is_some()))]
+    async fn execute_sponsored_by(
+        &self,
+        plan: &RoutePlan,
+        sponsor: Option<Arc<SponsorshipManager>>,
+        idempotency_key: Option<&str>,
+    ) -> Result<ExecutionResult> {
+        self.counters.record_attempt();
 
-        let uses_deepbook = !Self::deepbook_requests(plan).is_empty();
+        if self.min_healthy_validators > 0 {
+            if let Err(err) = self
+                .validator_selector
+                .require_healthy(self.min_healthy_validators)
+                .await
+            {
+                self.counters.record_failure();
+                return Err(err.into());
+            }
+        }
+
+        // Market orders have no limit price to account against, but their
+        // fill events still feed inventory
+        let uses_deepbook = matches!(plan.route, Route::MarketOrder(_))
+            || !Self::deepbook_requests(plan).is_empty();
         let pre_balances = if uses_deepbook {
             if let Some(adapter) = &self.deepbook {
                 Self::collect_balance_snapshots(adapter, plan).await
@@ -292,56 +722,34 @@ impl ExecutionEngine {
             HashMap::new()
         };
 
-        // 1. Compile route to PTB (may be gasless if sponsorship is enabled)
-        let (tx_bcs, is_sponsored) = if use_sponsorship && self.sponsorship.is_some() {
-            self.compile_route_sponsored(plan).await?
-        } else {
-            (self.compile_route(plan).await?, false)
-        };
-
-        // 2. Sign transaction(s)
-        let signatures = if is_sponsored {
-            // For sponsored transactions, we need both user and sponsor signatures
-            self.sign_sponsored_transaction(&tx_bcs).await?
-        } else {
-            // Regular transaction: just user signature
-            let (signature_bytes, _pubkey) =
-                sign_tx_bcs_ed25519_to_serialized_signature(&tx_bcs, &self.secret_key_hex)
-                    .map_err(|e| AggrError::Signing(e.to_string()))?;
-            vec![signature_bytes]
-        };
-
-        // 3. Compute transaction digest (for idempotency check)
-        let digest = self.compute_digest(&tx_bcs)?;
-
-        // 4. Check if we've already seen this digest (idempotent retry)
+        // 1-5. Build, sign and submit; version conflicts rebuild from fresh object refs
+        let Submission {
+            digest,
+            executed,
+            submit_start,
+            is_sponsored,
+            estimated_gas,
+            confirmation,
+        } = match retry_on_version_conflict(self.max_conflict_rebuilds, || {
+            self.build_and_submit(plan, sponsor.as_deref())
+        })
+        .await
         {
-            let seen = self.seen_digests.read().await;
-            if seen.contains(&digest) {
-                warn!(
-                    digest = %digest,
-                    "transaction digest already seen, skipping duplicate execution"
-                );
-                self.failed_executions.fetch_add(1, Ordering::Relaxed);
-                anyhow::bail!("transaction already executed: {}", digest);
-            }
-        }
-
-        // 5. Submit and wait for execution
-        let submit_start = Instant::now();
-        let executed = match self.submit_with_retry(tx_bcs, signatures).await {
-            Ok(executed) => executed,
+            Ok(submitted) => submitted,
             Err(e) => {
-                self.failed_executions.fetch_add(1, Ordering::Relaxed);
+                self.counters.record_failure();
                 return Err(e);
             }
         };
         let submit_duration = submit_start.elapsed();
 
+        if self.dry_run {
+            return Ok(self.dry_run_result(digest, executed, submit_duration));
+        }
+
         // 6. Record digest to prevent duplicate execution
-        {
-            let mut seen = self.seen_digests.write().await;
-            seen.insert(digest.clone());
+        if let Err(err) = self.seen_digests.insert(&digest) {
+            warn!(digest = %digest, error = %err, "failed to record submitted digest");
         }
 
         // 7. Extract timing information
@@ -357,6 +765,13 @@ impl ExecutionEngine {
         // 8. Extract checkpoint inclusion time if available
         // Check checkpoint info before moving executed into ExecutionResult
         let checkpoint_time_ms = if executed.checkpoint.is_some() {
+            if let Some(confirmations) = self
+                .confirmations
+                .as_ref()
+                .filter(|_| confirmation.is_some())
+            {
+                confirmations.forget(&digest).await;
+            }
             // ExecutedTransaction includes checkpoint sequence number and timestamp
             // The checkpoint timestamp is absolute, so we approximate checkpoint inclusion time
             // as effects_time_ms (since checkpoint inclusion typically happens shortly after effects)
@@ -371,22 +786,21 @@ impl ExecutionEngine {
                 Some(effects_time_ms)
             }
         } else {
-            // Transaction not yet included in a checkpoint (may be included in future checkpoint)
+            // Transaction not yet included in a checkpoint; watch the stream for it
+            if let (Some(confirmations), Some(receiver)) = (&self.confirmations, confirmation) {
+                Self::watch_confirmation(confirmations, &digest, receiver, submit_start);
+            }
             None
         };
 
         // Update statistics
-        self.successful_executions.fetch_add(1, Ordering::Relaxed);
-        self.total_effects_time_ms
-            .fetch_add((effects_time_ms * 1000.0) as u64, Ordering::Relaxed);
+        self.counters
+            .record_success(effects_time_ms, checkpoint_time_ms);
 
-        if let Some(checkpoint_ms) = checkpoint_time_ms {
-            self.total_checkpoint_time_ms
-                .fetch_add((checkpoint_ms * 1000.0) as u64, Ordering::Relaxed);
-            self.checkpoint_count.fetch_add(1, Ordering::Relaxed);
-        }
-
-        let mut accounting = ExecutionAccounting::default();
+        let mut accounting = ExecutionAccounting {
+            estimated_gas,
+            ..Default::default()
+        };
         let mut orders: Vec<OrderHandle> = Vec::new();
         let gas_used = Self::extract_gas_used(&executed);
         if let Some(gas) = gas_used {
@@ -409,6 +823,12 @@ impl ExecutionEngine {
 
                 if !events.is_empty() {
                     accounting.deepbook_events = Self::summarize_deepbook_events(&events);
+                    if matches!(plan.route, Route::MultiVenueSplit { .. }) {
+                        let decoded: Vec<VenueEvent> =
+                            events.iter().map(VenueEvent::from).collect();
+                        accounting.legs =
+                            attribute_deepbook_fills(&Self::deepbook_requests(plan), &decoded);
+                    }
                 }
 
                 match Self::build_deepbook_accounting(adapter, plan, &pre_balances).await {
@@ -431,13 +851,18 @@ impl ExecutionEngine {
 
         if is_sponsored {
             if let Some(gas) = gas_used {
-                if let Some(manager) = &self.sponsorship {
+                if let Some(manager) = &sponsor {
                     let route_class = Self::route_class(plan);
                     manager
-                        .apply_spending(self.user_address, Some(route_class.as_str()), gas)
+                        .apply_spending_once(
+                            idempotency_key,
+                            self.user_address,
+                            Some(route_class.as_str()),
+                            gas,
+                        )
                         .await;
                     accounting.sponsor_gas_used = Some(gas);
-                    self.total_sponsor_gas.fetch_add(gas, Ordering::Relaxed);
+                    self.counters().total_sponsor_gas += gas;
                 }
             } else {
                 warn!(
@@ -468,9 +893,66 @@ impl ExecutionEngine {
             checkpoint_time_ms,
             accounting,
             orders,
+            dry_run: false,
         })
     }
 
+    /// Synthetic result for a simulated plan. Leaves digests, confirmations,
+    /// sponsor budgets and order tracking untouched since nothing reached the chain.
+    fn dry_run_result(
+        &self,
+        digest: String,
+        executed: ExecutedTransaction,
+        simulate_duration: Duration,
+    ) -> ExecutionResult {
+        let accounting = ExecutionAccounting {
+            gas_used: Self::extract_gas_used(&executed),
+            ..Default::default()
+        };
+        warn!(
+            digest = %digest,
+            gas_used = ?accounting.gas_used,
+            "DRY RUN: transaction simulated, not submitted"
+        );
+        ExecutionResult {
+            digest,
+            executed,
+            effects_time_ms: simulate_duration.as_secs_f64() * 1000.0,
+            checkpoint_time_ms: None,
+            accounting,
+            orders: Vec::new(),
+            dry_run: true,
+        }
+    }
+
+    /// Compile, sign and simulate a route plan without submitting it.
+    /// Returns `None` when simulation is unavailable (no `grpc-exec` feature).
+    pub async fn simulate(&self, plan: &RoutePlan) -> Result<Option<ExecutedTransaction>> {
+        let tx_bcs = self.compile_route(plan).await?;
+        self.signer.sign_intent(&tx_bcs).await?;
+
+        let simulated = self.grpc.lock().await.simulate_ptb(tx_bcs).await?;
+        if let Some(tx) = &simulated {
+            if let Some(reason) = simulation_failure(tx) {
+                anyhow::bail!("simulation aborted: {reason}");
+            }
+        }
+        Ok(simulated)
+    }
+
+    /// Startup self-test: build and simulate a small order on the full
+    /// compile→sign→simulate path, falling back to a JSON-RPC dry run without
+    /// `grpc-exec`. Fails when the order aborts in simulation. Never submits.
+    pub async fn self_test(&self, req: LimitReq) -> Result<()> {
+        let pool = req.pool.clone();
+        let plan = RoutePlan::deepbook_single(req, 0.0, 0.0, 0.0, 0, 0, 0.0);
+        let tx_bcs = self.compile_route(&plan).await?;
+        self.signer.sign_intent(&tx_bcs).await?;
+        self.preflight(&tx_bcs).await?;
+        info!(pool = %pool, "warmup order simulated successfully");
+        Ok(())
+    }
+
     /// Compile a route plan into a PTB (BCS TransactionData bytes)
     async fn compile_route(&self, plan: &RoutePlan) -> Result<Vec<u8>> {
         match &plan.route {
@@ -484,8 +966,19 @@ impl ExecutionEngine {
                     .await
                     .context("build DeepBook limit order PTB")
             }
-            crate::router::routes::Route::MultiVenueSplit { deepbook } => {
-                self.compile_multi_venue_split(deepbook.as_ref()).await
+            crate::router::routes::Route::MarketOrder(req) => {
+                let adapter = self
+                    .deepbook
+                    .as_ref()
+                    .context("DeepBook adapter not available")?;
+                adapter
+                    .build_market_order_ptb_bcs(req)
+                    .await
+                    .context("build DeepBook market order PTB")
+            }
+            crate::router::routes::Route::MultiVenueSplit { deepbook, amm_swap } => {
+                self.compile_multi_venue_split(deepbook.as_ref(), amm_swap.as_ref())
+                    .await
             }
             crate::router::routes::Route::CancelReplace {
                 cancel_digest,
@@ -495,8 +988,26 @@ impl ExecutionEngine {
                 self.compile_cancel_replace(cancel_digest.as_deref(), *existing_order_id, replace)
                     .await
             }
-            crate::router::routes::Route::CancelDeepBook { pool, order_id } => {
-                self.compile_cancel(pool, *order_id).await
+            crate::router::routes::Route::CancelDeepBook {
+                pool,
+                order_id,
+                manager,
+            } => {
+                self.compile_cancel(pool, manager.as_deref(), *order_id)
+                    .await
+            }
+            crate::router::routes::Route::CancelAll { pool, manager } => {
+                let adapter = self
+                    .deepbook
+                    .as_ref()
+                    .context("DeepBook adapter not available")?;
+                adapter
+                    .build_cancel_all_orders_ptb_bcs(pool, manager.as_deref())
+                    .await
+                    .context("build DeepBook cancel-all PTB")
+            }
+            crate::router::routes::Route::Twap { .. } => {
+                anyhow::bail!("TWAP routes are submitted slice by slice through Router::start_twap")
             }
             crate::router::routes::Route::FlashLoanArb { .. } => {
                 // Flash loan routes require flash loan contract integration
@@ -510,6 +1021,7 @@ impl ExecutionEngine {
     async fn compile_multi_venue_split(
         &self,
         deepbook_req: Option<&crate::venues::adapter::LimitReq>,
+        amm_req: Option<&AmmSwapReq>,
     ) -> Result<Vec<u8>> {
         let mut ptb = ProgrammableTransactionBuilder::new();
         let mut has_commands = false;
@@ -522,34 +1034,15 @@ impl ExecutionEngine {
                 .context("DeepBook adapter not available for multi-venue route")?;
 
             // Build DeepBook order command directly into the PTB
-            use crate::quant::{quantize_price, quantize_size};
-            use sui_deepbookv3::utils::config::MAX_TIMESTAMP;
-            use sui_deepbookv3::utils::types::{
-                OrderType, PlaceLimitOrderParams, SelfMatchingOptions,
-            };
+            use crate::quant::{quantize_price_for_side, quantize_size};
 
             // Quantize price and size
             let params = adapter.pool_params(&req.pool).await?;
-            let q_px = quantize_price(req.price, params.tick_size)?;
+            let q_px = quantize_price_for_side(req.price, params.tick_size, req.is_bid)?;
             let q_sz = quantize_size(req.quantity, params.lot_size, params.min_size)?;
 
-            let client_order_id = req
-                .client_order_id
-                .parse::<u64>()
-                .context("client_order_id must parse to u64")?;
-
-            let place_params = PlaceLimitOrderParams {
-                pool_key: req.pool.clone(),
-                balance_manager_key: adapter.manager_key.clone(),
-                client_order_id,
-                price: q_px,
-                quantity: q_sz,
-                is_bid: req.is_bid,
-                expiration: Some(req.expiration_ms.unwrap_or(MAX_TIMESTAMP)),
-                order_type: Some(OrderType::NoRestriction),
-                self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
-                pay_with_deep: Some(req.pay_with_deep),
-            };
+            let manager_key = adapter.manager_key_for(req.manager.as_deref())?;
+            let place_params = req.place_params(manager_key, q_px, q_sz)?;
 
             adapter
                 .db
@@ -561,13 +1054,19 @@ impl ExecutionEngine {
             has_commands = true;
         }
 
-        // Future: Add AMM orders here when AMM adapters are implemented
-        // if let Some(amm_req) = amm_req {
-        //     // Add AMM swap commands to PTB using AMM adapter
-        //     // Example:
-        //     // amm_adapter.build_swap_command(&mut ptb, amm_req).await?;
-        //     has_commands = true;
-        // }
+        // Add the AMM swap if present
+        if let Some(swap) = amm_req {
+            let cetus = self
+                .cetus
+                .as_ref()
+                .context("Cetus adapter not available for multi-venue route")?;
+            cetus
+                .add_swap_commands(&mut ptb, self.user_address, swap)
+                .await
+                .context("build Cetus swap command for multi-venue route")?;
+
+            has_commands = true;
+        }
 
         if !has_commands {
             anyhow::bail!("multi-venue route must have at least one venue order");
@@ -582,20 +1081,17 @@ impl ExecutionEngine {
             .map(|obj| InputObjectKind::object_id(&obj))
             .collect();
 
-        // Get gas price and select gas
-        let adapter = self
-            .deepbook
-            .as_ref()
-            .context("DeepBook adapter needed for gas selection")?;
-        let gas_price = adapter
-            .reference_gas_price()
-            .await
-            .context("fetch reference gas price")?;
+        // Get gas price and select gas, through DeepBook when available for its cached price
+        let (sui, gas_price) = match (&self.deepbook, &self.cetus) {
+            (Some(adapter), _) => (adapter.sui_client(), adapter.reference_gas_price().await),
+            (None, Some(cetus)) => (cetus.sui_client(), cetus.reference_gas_price().await),
+            (None, None) => anyhow::bail!("no venue adapter available for gas selection"),
+        };
+        let gas_price = gas_price.context("fetch reference gas price")?;
 
         use sui_deepbookv3::utils::config::GAS_BUDGET;
 
-        let gas = adapter
-            .sui_client()
+        let gas = sui
             .transaction_builder()
             .select_gas(
                 self.user_address,
@@ -633,9 +1129,8 @@ impl ExecutionEngine {
             .as_ref()
             .context("DeepBook adapter not available")?;
 
-        // Build a PTB that:
-        // 1. Cancels the existing order (by digest)
-        // 2. Places a new order
+        // Build a PTB that cancels the existing order and places the new one,
+        // in the order the atomic replace mode defines
 
         let mut ptb = ProgrammableTransactionBuilder::new();
 
@@ -647,7 +1142,7 @@ impl ExecutionEngine {
                 anyhow::anyhow!("cancel_replace requires either existing_order_id or cancel_digest")
             })?;
             adapter
-                .get_order_id_from_digest(digest, &replace.pool)
+                .get_order_id_from_digest(digest, &replace.pool, None)
                 .await
                 .context("lookup order ID from transaction digest")?
                 .ok_or_else(|| {
@@ -662,46 +1157,41 @@ impl ExecutionEngine {
             "found order ID for cancel-replace"
         );
 
-        // 2. Build cancel order command
-        adapter
-            .build_cancel_order_command(&mut ptb, &replace.pool, order_id)
-            .await
-            .context("build cancel order command")?;
-
-        // 3. Build place order command
-        let client_order_id = replace
-            .client_order_id
-            .parse::<u64>()
-            .context("client_order_id must parse to u64")?;
-
-        use crate::quant::{quantize_price, quantize_size};
-        use sui_deepbookv3::utils::config::MAX_TIMESTAMP;
-        use sui_deepbookv3::utils::types::{OrderType, PlaceLimitOrderParams, SelfMatchingOptions};
-
-        // Quantize price and size
-        let params = adapter.pool_params(&replace.pool).await?;
-        let q_px = quantize_price(replace.price, params.tick_size)?;
-        let q_sz = quantize_size(replace.quantity, params.lot_size, params.min_size)?;
-
-        let place_params = PlaceLimitOrderParams {
-            pool_key: replace.pool.clone(),
-            balance_manager_key: adapter.manager_key.clone(),
-            client_order_id,
-            price: q_px,
-            quantity: q_sz,
-            is_bid: replace.is_bid,
-            expiration: Some(replace.expiration_ms.unwrap_or(MAX_TIMESTAMP)),
-            order_type: Some(OrderType::NoRestriction),
-            self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
-            pay_with_deep: Some(replace.pay_with_deep),
-        };
-
-        adapter
-            .db
-            .deep_book
-            .place_limit_order(&mut ptb, place_params)
-            .await
-            .context("build place order command")?;
+        // 2-3. Cancel and place commands; the replacement trades from the same manager
+        for command in ReplaceMode::Atomic.transactions().concat() {
+            match command {
+                ReplaceCommand::Cancel => {
+                    adapter
+                        .build_cancel_order_command(
+                            &mut ptb,
+                            &replace.pool,
+                            replace.manager.as_deref(),
+                            order_id,
+                        )
+                        .await
+                        .context("build cancel order command")?;
+                }
+                ReplaceCommand::Place => {
+                    use crate::quant::{quantize_price_for_side, quantize_size};
+
+                    // Quantize price and size
+                    let params = adapter.pool_params(&replace.pool).await?;
+                    let q_px =
+                        quantize_price_for_side(replace.price, params.tick_size, replace.is_bid)?;
+                    let q_sz = quantize_size(replace.quantity, params.lot_size, params.min_size)?;
+
+                    let manager_key = adapter.manager_key_for(replace.manager.as_deref())?;
+                    let place_params = replace.place_params(manager_key, q_px, q_sz)?;
+
+                    adapter
+                        .db
+                        .deep_book
+                        .place_limit_order(&mut ptb, place_params)
+                        .await
+                        .context("build place order command")?;
+                }
+            }
+        }
 
         // 4. Finalize PTB and build TransactionData
         let programmable = ptb.finish();
@@ -742,29 +1232,33 @@ impl ExecutionEngine {
         Ok(tx_bcs)
     }
 
-    async fn compile_cancel(&self, pool: &str, order_id: u128) -> Result<Vec<u8>> {
+    async fn compile_cancel(
+        &self,
+        pool: &str,
+        manager: Option<&str>,
+        order_id: u128,
+    ) -> Result<Vec<u8>> {
         let adapter = self
             .deepbook
             .as_ref()
             .context("DeepBook adapter not available")?;
         adapter
-            .build_cancel_order_ptb_bcs(pool, order_id)
+            .build_cancel_order_ptb_bcs(pool, manager, order_id)
             .await
             .context("build DeepBook cancel order PTB")
     }
 
     /// Compile a route plan into a sponsored PTB
     /// Returns (tx_bcs, is_sponsored)
-    async fn compile_route_sponsored(&self, plan: &RoutePlan) -> Result<(Vec<u8>, bool)> {
-        let sponsorship = self
-            .sponsorship
-            .as_ref()
-            .context("sponsorship not available")?;
-
+    async fn compile_route_sponsored(
+        &self,
+        plan: &RoutePlan,
+        sponsorship: &SponsorshipManager,
+    ) -> Result<(Vec<u8>, bool)> {
         // Check if sponsorship is allowed
         let req = SponsorshipRequest {
             user_address: self.user_address,
-            route_plan_id: format!("{:?}", plan.route),
+            route_plan_id: Self::route_class(plan),
             estimated_gas: plan.estimated_gas,
             created_at: Instant::now(),
         };
@@ -774,7 +1268,20 @@ impl ExecutionEngine {
             return Ok((self.compile_route(plan).await?, false));
         }
 
-        // Build gasless transaction
+        sponsored_or_user_paid(
+            self.gasless_fallback,
+            || self.compile_gasless(plan, sponsorship),
+            || self.compile_route(plan),
+        )
+        .await
+    }
+
+    /// Compile a plan into TransactionData paid from the sponsor's gas coins
+    async fn compile_gasless(
+        &self,
+        plan: &RoutePlan,
+        sponsorship: &SponsorshipManager,
+    ) -> Result<Vec<u8>> {
         match &plan.route {
             crate::router::routes::Route::DeepBookSingle(req) => {
                 let adapter = self
@@ -788,13 +1295,13 @@ impl ExecutionEngine {
                     .await
                     .context("build gasless DeepBook limit order PTB")?;
 
-                // Resolve sponsor gas coin ObjectRefs
+                // Resolve sponsor gas coin ObjectRefs and balances
                 let gas_coin_ids = sponsorship.gas_coin_ids().await;
                 if gas_coin_ids.is_empty() {
                     anyhow::bail!("no sponsor gas coins available");
                 }
-                let gas_object_refs = adapter
-                    .object_refs_for_ids(&gas_coin_ids)
+                let gas_coins = adapter
+                    .coin_refs_with_balances(&gas_coin_ids)
                     .await
                     .context("resolve sponsor gas object refs")?;
 
@@ -803,13 +1310,13 @@ impl ExecutionEngine {
                     .build_sponsored_transaction_data(
                         programmable,
                         self.user_address,
-                        gas_object_refs,
+                        gas_coins,
                         plan.estimated_gas.max(10_000_000), // fallback minimum
                     )
                     .await
                     .context("build sponsored transaction data")?;
 
-                Ok((tx_bcs, true))
+                Ok(tx_bcs)
             }
             _ => {
                 anyhow::bail!("sponsored transactions not yet implemented for this route type")
@@ -817,20 +1324,221 @@ impl ExecutionEngine {
         }
     }
 
-    /// Sign a sponsored transaction (user + sponsor signatures)
-    async fn sign_sponsored_transaction(&self, tx_bcs: &[u8]) -> Result<Vec<Vec<u8>>> {
-        let sponsorship = self
-            .sponsorship
-            .as_ref()
-            .context("sponsorship not available")?;
+    /// Compile and sign `plan` without submitting it, for callers that broadcast
+    /// the transaction through their own infrastructure. The actual gas of a
+    /// sponsored transaction is never observed here, so its full gas budget is
+    /// debited from the sponsor's budgets once it is signed.
+    pub async fn build_signed(
+        &self,
+        plan: &RoutePlan,
+        sponsor: Option<&str>,
+    ) -> Result<SignedTransaction> {
+        let sponsor = match sponsor {
+            Some(alias) => Some(self.sponsors.get(alias)?),
+            None => None,
+        };
+        let (tx_bcs, is_sponsored) = self.build_tx(plan, sponsor.as_deref()).await?;
+        let signatures = self
+            .sign_tx(&tx_bcs, sponsor.as_deref(), is_sponsored)
+            .await?;
+        let signed = SignedTransaction::new(&tx_bcs, &signatures, is_sponsored)?;
+        if let Some(manager) = sponsor.filter(|_| is_sponsored) {
+            let tx_data: TransactionData =
+                bcs::from_bytes(&tx_bcs).context("decode TransactionData for gas budget")?;
+            let budget = tx_data.gas_data().budget;
+            let route_class = Self::route_class(plan);
+            manager
+                .apply_spending(self.user_address, Some(route_class.as_str()), budget)
+                .await;
+            debug!(
+                digest = %signed.digest,
+                budget,
+                "sponsor debited the gas budget of an externally broadcast transaction"
+            );
+        }
+        Ok(signed)
+    }
+
+    /// Compile a plan to transaction bytes and report whether gas is sponsored
+    async fn build_tx(
+        &self,
+        plan: &RoutePlan,
+        sponsor: Option<&SponsorshipManager>,
+    ) -> Result<(Vec<u8>, bool)> {
+        // Compile route to PTB (may be gasless if sponsorship is enabled)
+        let (mut tx_bcs, is_sponsored) = match sponsor {
+            Some(sponsorship) => self.compile_route_sponsored(plan, sponsorship).await?,
+            None => (self.compile_route(plan).await?, false),
+        };
+
+        // Sponsored budgets are set by the sponsor's policy; size the rest from a simulation
+        if let Some(factor) = self.gas_safety_factor.filter(|_| !is_sponsored) {
+            tx_bcs = self.apply_simulated_gas_budget(tx_bcs, factor).await?;
+        }
+        Ok((tx_bcs, is_sponsored))
+    }
+
+    /// Serialized signatures for compiled transaction bytes, sender first
+    async fn sign_tx(
+        &self,
+        tx_bcs: &[u8],
+        sponsor: Option<&SponsorshipManager>,
+        is_sponsored: bool,
+    ) -> Result<Vec<Vec<u8>>> {
+        match sponsor.filter(|_| is_sponsored) {
+            // For sponsored transactions, we need both user and sponsor signatures
+            Some(sponsorship) => self.sign_sponsored_transaction(tx_bcs, sponsorship).await,
+            None => {
+                // Regular transaction: just user signature
+                let signature_bytes = self.signer.sign_intent(tx_bcs).await?;
+                Ok(vec![signature_bytes])
+            }
+        }
+    }
+
+    /// Compile, sign and submit a plan once. Rebuilt from scratch on every call so
+    /// object refs are re-read rather than resubmitting stale transaction bytes.
+    async fn build_and_submit(
+        &self,
+        plan: &RoutePlan,
+        sponsor: Option<&SponsorshipManager>,
+    ) -> Result<Submission> {
+        // 1. Compile route to PTB, then check it executes before anything is signed
+        let (tx_bcs, is_sponsored) = self.build_tx(plan, sponsor).await?;
+        let estimated_gas = if self.simulate_before_execute && !self.dry_run {
+            self.preflight(&tx_bcs).await?
+        } else {
+            None
+        };
+
+        // 2. Sign transaction(s)
+        let signatures = self.sign_tx(&tx_bcs, sponsor, is_sponsored).await?;
+
+        // 3. Compute transaction digest (for idempotency check)
+        let digest = self.compute_digest(&tx_bcs)?;
+
+        // 4. Check if we've already seen this digest (idempotent retry)
+        if self.seen_digests.contains(&digest) {
+            warn!(
+                digest = %digest,
+                "transaction digest already seen, skipping duplicate execution"
+            );
+            anyhow::bail!("transaction already executed: {}", digest);
+        }
+
+        // 5. Submit and wait for execution, or only simulate in dry-run mode.
+        // Checkpoint inclusion is watched from before submission, so a
+        // checkpoint that arrives ahead of the execution response is not missed
+        let confirmations = self.confirmations.as_ref().filter(|_| !self.dry_run);
+        let confirmation = match confirmations {
+            Some(confirmations) => Some(confirmations.register(digest.clone()).await),
+            None => None,
+        };
+
+        let submit_start = Instant::now();
+        let executed = match submit_or_simulate(
+            self.dry_run,
+            || self.simulate_signed(tx_bcs.clone()),
+            || self.submit_with_retry(tx_bcs.clone(), signatures),
+        )
+        .await
+        {
+            Ok(executed) => executed,
+            Err(err) => {
+                if let Some(confirmations) = confirmations {
+                    confirmations.forget(&digest).await;
+                }
+                return Err(err);
+            }
+        };
+        Ok(Submission {
+            digest,
+            executed,
+            submit_start,
+            is_sponsored,
+            estimated_gas,
+            confirmation,
+        })
+    }
+
+    /// Simulate compiled transaction bytes through gRPC, or JSON-RPC dry run
+    /// without the `grpc-exec` feature. Returns the simulated gas cost.
+    async fn preflight(&self, tx_bcs: &[u8]) -> Result<Option<u64>> {
+        let gas = simulate_or_dry_run(
+            || async {
+                self.grpc
+                    .lock()
+                    .await
+                    .simulate_ptb(tx_bcs.to_vec())
+                    .await
+                    .context("simulate transaction before submission")
+            },
+            || async {
+                self.jsonrpc
+                    .dry_run_tx_block(tx_bcs)
+                    .await
+                    .context("dry run transaction before submission")
+            },
+        )
+        .await?;
+        debug!(?gas, "transaction simulated before submission");
+        Ok(gas)
+    }
+
+    /// Re-budget compiled transaction bytes from a simulation of them. Keeps the
+    /// static budget when simulation is unavailable or aborts.
+    async fn apply_simulated_gas_budget(&self, tx_bcs: Vec<u8>, factor: f64) -> Result<Vec<u8>> {
+        let simulated_gas = match self.grpc.lock().await.simulate_ptb(tx_bcs.clone()).await {
+            Ok(Some(simulated)) if simulation_failure(&simulated).is_none() => {
+                Self::extract_gas_cost(&simulated)
+            }
+            Ok(_) => None,
+            Err(err) => {
+                debug!(error = %err, "gas simulation failed; keeping static budget");
+                None
+            }
+        };
+        let budget = simulated_gas_budget(simulated_gas, factor);
+        let mut tx_data: TransactionData =
+            bcs::from_bytes(&tx_bcs).context("decode TransactionData for gas budget")?;
+        if tx_data.gas_data().budget == budget {
+            return Ok(tx_bcs);
+        }
+        debug!(?simulated_gas, budget, "gas budget sized from simulation");
+        tx_data.gas_data_mut().budget = budget;
+        bcs::to_bytes(&tx_data).context("serialize TransactionData")
+    }
+
+    /// Simulate already compiled and signed transaction bytes
+    async fn simulate_signed(&self, tx_bcs: Vec<u8>) -> Result<ExecutedTransaction> {
+        let simulated = self
+            .grpc
+            .lock()
+            .await
+            .simulate_ptb(tx_bcs)
+            .await?
+            .context("dry run requires simulation, which needs the grpc-exec feature")?;
+        if let Some(reason) = simulation_failure(&simulated) {
+            return Err(AggrError::SimulationRejected(reason).into());
+        }
+        Ok(simulated)
+    }
 
+    /// Sign a sponsored transaction (user + sponsor signatures)
+    async fn sign_sponsored_transaction(
+        &self,
+        tx_bcs: &[u8],
+        sponsorship: &SponsorshipManager,
+    ) -> Result<Vec<Vec<u8>>> {
         // User signs
-        let (user_sig, _) =
-            sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, &self.secret_key_hex)
-                .map_err(|e| AggrError::Signing(format!("user signing failed: {}", e)))?;
+        let user_sig = self
+            .signer
+            .sign_intent(tx_bcs)
+            .await
+            .map_err(|e| AggrError::Signing(format!("user signing failed: {}", e)))?;
 
         // Sponsor signs
-        let sponsor_sig = sponsorship.sign_sponsored_transaction(tx_bcs)?;
+        let sponsor_sig = sponsorship.sign_sponsored_transaction(tx_bcs).await?;
 
         Ok(vec![user_sig, sponsor_sig])
     }
@@ -841,6 +1549,8 @@ impl ExecutionEngine {
         tx_bcs: Vec<u8>,
         signatures: Vec<Vec<u8>>,
     ) -> Result<ExecutedTransaction> {
+        // From here on a request deadline must not abandon the transaction
+        deadline::begin_submission();
         let backoff = ExponentialBackoff {
             initial_interval: Duration::from_millis(100),
             max_interval: Duration::from_secs(5),
@@ -851,7 +1561,14 @@ impl ExecutionEngine {
 
         let grpc_clone = self.grpc.clone();
         let jsonrpc_clone = self.jsonrpc.clone();
-        let use_grpc = self.use_grpc_execute;
+        // Fall back to JSON-RPC while every gRPC endpoint is out of rotation
+        let use_grpc =
+            if self.use_grpc_execute && self.validator_selector.select_best().await.is_none() {
+                warn!("no healthy gRPC endpoint in rotation; submitting via JSON-RPC");
+                false
+            } else {
+                self.use_grpc_execute
+            };
 
         retry(backoff, || {
             let tx_bcs = tx_bcs.clone();
@@ -865,11 +1582,17 @@ impl ExecutionEngine {
                 } else {
                     Self::submit_jsonrpc_internal(&jsonrpc, &tx_bcs, &signatures).await
                 };
-                result.map_err(backoff::Error::transient)
+                result.map_err(classify_submit_error)
             }
         })
         .await
-        .map_err(|e| anyhow::anyhow!("submission failed after retries: {}", e))
+        .map_err(|e| {
+            if is_permanent_submit_error(&e) {
+                e
+            } else {
+                anyhow::anyhow!("submission failed after retries: {}", e)
+            }
+        })
     }
 
     /// Internal helper for gRPC submission (used by retry logic)
@@ -880,20 +1603,12 @@ impl ExecutionEngine {
     ) -> Result<ExecutedTransaction> {
         #[cfg(feature = "grpc-exec")]
         {
-            use crate::transport::grpc::sui::rpc::v2::{Bcs, SignatureScheme, UserSignature};
             let mut grpc_guard = grpc.lock().await;
 
             // Convert all signatures to UserSignature format
-            let user_signatures: Vec<UserSignature> = signatures
+            let user_signatures = signatures
                 .iter()
-                .map(|sig_bytes| UserSignature {
-                    bcs: Some(Bcs {
-                        name: Some("sui.types.Signature".to_string()),
-                        value: Some(sig_bytes.clone()),
-                    }),
-                    scheme: Some(SignatureScheme::Ed25519 as i32),
-                    ..Default::default()
-                })
+                .map(|sig_bytes| user_signature(sig_bytes))
                 .collect();
 
             grpc_guard
@@ -940,20 +1655,27 @@ impl ExecutionEngine {
         );
     }
 
+    /// Sponsorship budget class: `venue:pool`, or `venue` for routes without a pool
     fn route_class(plan: &RoutePlan) -> String {
-        format!("{:?}", plan.route)
+        BreakerClass::new(plan.route.venue(), plan.route.pool()).to_string()
     }
 
     fn deepbook_requests(plan: &RoutePlan) -> Vec<&LimitReq> {
         match &plan.route {
             Route::DeepBookSingle(req) => vec![req],
-            Route::MultiVenueSplit { deepbook } => deepbook.iter().collect(),
+            Route::MultiVenueSplit { deepbook, .. } => deepbook.iter().collect(),
             Route::CancelReplace { replace, .. } => vec![replace],
-            Route::FlashLoanArb { .. } => Vec::new(),
-            Route::CancelDeepBook { .. } => Vec::new(),
+            Route::MarketOrder(_) | Route::FlashLoanArb { .. } | Route::Twap { .. } => Vec::new(),
+            Route::CancelDeepBook { .. } | Route::CancelAll { .. } => Vec::new(),
         }
     }
 
+    /// Balances of the BalanceManager `req` trades from
+    async fn order_balances(adapter: &DeepBookAdapter, req: &LimitReq) -> Result<BalanceSnapshot> {
+        let manager = adapter.manager_key_for(req.manager.as_deref())?;
+        adapter.manager_balances(&req.pool, &manager).await
+    }
+
     async fn collect_balance_snapshots(
         adapter: &DeepBookAdapter,
         plan: &RoutePlan,
@@ -969,7 +1691,7 @@ impl ExecutionEngine {
 
             adapter.invalidate_pool_balances(&pool).await;
 
-            match adapter.balance_manager_balances(&pool).await {
+            match Self::order_balances(adapter, req).await {
                 Ok(snapshot) => {
                     snapshots.insert(pool, snapshot);
                 }
@@ -1002,7 +1724,7 @@ impl ExecutionEngine {
                 }
             };
 
-            let q_price = match quantize_price(req.price, params.tick_size) {
+            let q_price = match quantize_price_for_side(req.price, params.tick_size, req.is_bid) {
                 Ok(price) => price,
                 Err(err) => {
                     warn!(
@@ -1103,7 +1825,7 @@ impl ExecutionEngine {
                 None
             };
 
-            let post_snapshot = match adapter.balance_manager_balances(&pool).await {
+            let post_snapshot = match Self::order_balances(adapter, req).await {
                 Ok(snapshot) => snapshot,
                 Err(err) => {
                     warn!(pool = %pool, error = %err, "failed to fetch post-trade balances");
@@ -1160,6 +1882,14 @@ impl ExecutionEngine {
         computation.checked_add(storage)?.checked_sub(rebate)
     }
 
+    /// Gas charged before the storage rebate, which is what the budget must cover
+    fn extract_gas_cost(executed: &ExecutedTransaction) -> Option<u64> {
+        let gas_used = executed.effects.as_ref()?.gas_used.as_ref()?;
+        gas_used
+            .computation_cost?
+            .checked_add(gas_used.storage_cost?)
+    }
+
     async fn collect_order_handles(
         adapter: &DeepBookAdapter,
         plan: &RoutePlan,
@@ -1175,11 +1905,27 @@ impl ExecutionEngine {
                 continue;
             }
 
-            let mut order_id_opt =
-                events.and_then(|ev| adapter.order_id_from_events(ev, &pool, digest));
+            let client_order_id = req.client_order_id.parse::<u64>().ok();
+            let mut order_id_opt = events.and_then(|ev| {
+                adapter
+                    .order_id_from_events(ev, &pool, client_order_id, digest)
+                    .inspect_err(|err| {
+                        warn!(
+                            digest = %digest,
+                            pool = %pool,
+                            error = %err,
+                            "ambiguous DeepBook order events"
+                        )
+                    })
+                    .ok()
+                    .flatten()
+            });
 
             if order_id_opt.is_none() && events.is_none() {
-                match adapter.get_order_id_from_digest(digest, &pool).await {
+                match adapter
+                    .get_order_id_from_digest(digest, &pool, client_order_id)
+                    .await
+                {
                     Ok(Some(order_id)) => order_id_opt = Some(order_id),
                     Ok(None) => (),
                     Err(err) => {
@@ -1196,7 +1942,6 @@ impl ExecutionEngine {
 
             match order_id_opt {
                 Some(order_id) => {
-                    let client_order_id = req.client_order_id.parse::<u64>().ok();
                     handles.push(OrderHandle {
                         pool,
                         order_id,
@@ -1238,22 +1983,16 @@ impl ExecutionEngine {
                     stats.placed += 1;
                     DEEPBOOK_EVENT_COUNTER.with_label_values(&["placed"]).inc();
                 }
-                "OrderFilled" | "OrderFilledV2" | "OrderMatch" | "OrderMatched" | "OrderFill" => {
+                name if FILL_EVENTS.contains(&name) => {
                     stats.filled += 1;
                     DEEPBOOK_EVENT_COUNTER.with_label_values(&["filled"]).inc();
-                    if let Some(base) = Self::event_f64_field(
-                        &event.parsed_json,
-                        &[
-                            "filledQuantity",
-                            "baseFilled",
-                            "quantityFilled",
-                            "base_filled",
-                        ],
-                    ) {
+                    if let Some(base) =
+                        Self::event_f64_field(&event.parsed_json, BASE_FILLED_FIELDS)
+                    {
                         total_base += base;
                     }
                     if let Some(quote) =
-                        Self::event_f64_field(&event.parsed_json, &["quoteFilled", "quote_filled"])
+                        Self::event_f64_field(&event.parsed_json, QUOTE_FILLED_FIELDS)
                     {
                         total_quote += quote;
                     }
@@ -1389,34 +2128,374 @@ impl ExecutionEngine {
             .map(|entry| entry.deep_rebate_delta.max(0.0))
             .sum();
 
-        self.total_quote_fees_micros
-            .fetch_add(Self::to_micros(total_quote_fees), Ordering::Relaxed);
-        self.total_deep_fees_micros
-            .fetch_add(Self::to_micros(total_deep_fees), Ordering::Relaxed);
-        self.total_quote_rebates_micros
-            .fetch_add(Self::to_micros(total_quote_rebates), Ordering::Relaxed);
-        self.total_deep_rebates_micros
-            .fetch_add(Self::to_micros(total_deep_rebates), Ordering::Relaxed);
+        let mut counters = self.counters();
+        counters.total_quote_fees += total_quote_fees;
+        counters.total_deep_fees += total_deep_fees;
+        counters.total_quote_rebates += total_quote_rebates;
+        counters.total_deep_rebates += total_deep_rebates;
+    }
+
+    /// Compute transaction digest from BCS bytes
+    fn compute_digest(&self, tx_bcs: &[u8]) -> Result<String> {
+        transaction_digest(tx_bcs)
+    }
+}
+
+/// Sui transaction digest of `tx_bcs` (BCS `TransactionData`) in the Base58
+/// form explorers and checkpoints use
+pub fn transaction_digest(tx_bcs: &[u8]) -> Result<String> {
+    let tx_data: TransactionData =
+        bcs::from_bytes(tx_bcs).context("decode transaction data for its digest")?;
+    Ok(tx_data.digest().to_string())
+}
+
+/// gRPC form of a serialized signature (`flag || signature || public key`).
+/// `SignatureScheme` values match the flag byte, so the scheme is read from
+/// it; an unknown flag leaves the scheme unset for the node to derive.
+pub fn user_signature(serialized: &[u8]) -> UserSignature {
+    UserSignature {
+        bcs: Some(Bcs {
+            name: Some("sui.types.Signature".to_string()),
+            value: Some(serialized.to_vec()),
+        }),
+        scheme: serialized
+            .first()
+            .and_then(|flag| SignatureScheme::try_from(i32::from(*flag)).ok())
+            .map(|scheme| scheme as i32),
+        ..Default::default()
+    }
+}
+
+/// Transaction compiled and signed for the caller to broadcast
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SignedTransaction {
+    /// Base64 BCS-encoded `TransactionData`
+    pub tx_bytes: String,
+    /// Base64 serialized signatures (`flag || signature || public key`), sender first
+    pub signatures: Vec<String>,
+    pub digest: String,
+    /// Gas is paid by a sponsor, whose signature follows the sender's
+    pub sponsored: bool,
+}
+
+impl SignedTransaction {
+    /// Encode transaction bytes and their signatures for broadcast
+    pub fn new(tx_bcs: &[u8], signatures: &[Vec<u8>], sponsored: bool) -> Result<Self> {
+        use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+
+        Ok(Self {
+            digest: transaction_digest(tx_bcs)?,
+            tx_bytes: B64.encode(tx_bcs),
+            signatures: signatures.iter().map(|sig| B64.encode(sig)).collect(),
+            sponsored,
+        })
     }
+}
+
+/// Build and sign a transaction once and return it encoded for broadcast.
+/// Nothing is submitted; the bytes and signatures go back to the caller.
+pub async fn sign_for_broadcast<B, BFut, S>(build: B, sign: S) -> Result<SignedTransaction>
+where
+    B: FnOnce() -> BFut,
+    BFut: std::future::Future<Output = Result<(Vec<u8>, bool)>>,
+    S: FnOnce(&[u8], bool) -> Result<Vec<Vec<u8>>>,
+{
+    let (tx_bcs, sponsored) = build().await?;
+    let signatures = sign(&tx_bcs, sponsored)?;
+    SignedTransaction::new(&tx_bcs, &signatures, sponsored)
+}
 
-    fn to_micros(value: f64) -> u64 {
-        if !value.is_finite() || value <= 0.0 {
-            return 0;
+/// Build a sponsored transaction with `gasless`; if that fails and `fallback`
+/// allows, build one the sender pays for with `user_paid` instead. Returns
+/// (tx_bcs, is_sponsored).
+pub async fn sponsored_or_user_paid<G, GFut, U, UFut>(
+    fallback: GaslessFallback,
+    gasless: G,
+    user_paid: U,
+) -> Result<(Vec<u8>, bool)>
+where
+    G: FnOnce() -> GFut,
+    GFut: std::future::Future<Output = Result<Vec<u8>>>,
+    U: FnOnce() -> UFut,
+    UFut: std::future::Future<Output = Result<Vec<u8>>>,
+{
+    match gasless().await {
+        Ok(tx_bcs) => Ok((tx_bcs, true)),
+        Err(err) if fallback == GaslessFallback::UserPaid => {
+            warn!(error = %err, "gasless build failed, falling back to user-paid transaction");
+            Ok((user_paid().await?, false))
         }
-        let scaled = (value * MICROS_PER_UNIT).round();
-        if scaled >= u64::MAX as f64 {
-            u64::MAX
-        } else {
-            scaled as u64
+        Err(err) => Err(err),
+    }
+}
+
+/// Failure reason reported by a simulated transaction, if it aborted
+pub fn simulation_failure(tx: &ExecutedTransaction) -> Option<String> {
+    let status = tx.effects.as_ref()?.status.as_ref()?;
+    if status.success.unwrap_or(true) {
+        return None;
+    }
+    Some(
+        status
+            .error
+            .as_ref()
+            .and_then(|err| err.description.clone())
+            .unwrap_or_else(|| "unknown execution error".to_string()),
+    )
+}
+
+/// Gas budget for a transaction whose simulation charged `simulated_gas`: that
+/// amount times `safety_factor`, clamped to the network's budget limits. Falls
+/// back to the static DeepBook budget when nothing was simulated.
+pub fn simulated_gas_budget(simulated_gas: Option<u64>, safety_factor: f64) -> u64 {
+    use sui_deepbookv3::utils::config::GAS_BUDGET;
+
+    match simulated_gas {
+        Some(gas) => {
+            let padded = (gas as f64 * safety_factor).ceil();
+            if padded >= MAX_GAS_BUDGET as f64 {
+                MAX_GAS_BUDGET
+            } else {
+                (padded as u64).max(MIN_GAS_BUDGET)
+            }
         }
+        None => GAS_BUDGET,
     }
+}
 
-    /// Compute transaction digest from BCS bytes
-    fn compute_digest(&self, tx_bcs: &[u8]) -> Result<String> {
-        use blake2::{Blake2b512, Digest};
-        let mut hasher = Blake2b512::new();
-        hasher.update(tx_bcs);
-        let hash = hasher.finalize();
-        Ok(hex::encode(&hash[..32]))
+/// A DeepBook fill event decoded to quantities
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedFill {
+    /// Filled base quantity
+    pub base: f64,
+    /// Filled quote quantity, from the event or its price
+    pub quote: Option<f64>,
+    pub fee: f64,
+    /// Pool the event names, if any
+    pub pool: Option<String>,
+    /// Client order id the event names, if any
+    pub client_order_id: Option<String>,
+}
+
+/// Decode a DeepBook fill event; `None` for other events and fills without a
+/// base quantity
+pub fn decode_fill(event: &VenueEvent) -> Option<DecodedFill> {
+    if !matches!(event.module, "clob" | "clob_v2") || !FILL_EVENTS.contains(&event.name) {
+        return None;
+    }
+    let base = ExecutionEngine::event_f64_field(event.fields, BASE_FILLED_FIELDS)?;
+    let quote = ExecutionEngine::event_f64_field(event.fields, QUOTE_FILLED_FIELDS)
+        .or_else(|| ExecutionEngine::event_f64_field(event.fields, &["price"]).map(|p| p * base));
+    let fee = ExecutionEngine::event_f64_field(event.fields, &["takerFee", "taker_fee", "fee"])
+        .unwrap_or(0.0);
+    Some(DecodedFill {
+        base,
+        quote,
+        fee,
+        pool: event_string_field(event.fields, &["poolKey", "pool", "pool_id"]),
+        client_order_id: event_string_field(
+            event.fields,
+            &[
+                "clientOrderId",
+                "client_order_id",
+                "takerClientOrderId",
+                "taker_client_order_id",
+            ],
+        ),
+    })
+}
+
+/// First of `keys` present as a string or number
+fn event_string_field(fields: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match fields.get(*key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// Attribute DeepBook fill events to the legs that caused them: by client order
+/// id when the event carries one, else by pool, else to a lone leg. Every leg
+/// gets an entry, unfilled legs included.
+pub fn attribute_deepbook_fills(legs: &[&LimitReq], events: &[VenueEvent]) -> Vec<LegFill> {
+    let mut totals = vec![(0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64); legs.len()];
+    for fill in events.iter().filter_map(decode_fill) {
+        let Some(leg) = fill_leg(legs, &fill) else {
+            continue;
+        };
+        let (filled, notional, priced, fees) = &mut totals[leg];
+        *filled += fill.base;
+        if let Some(quote) = fill.quote {
+            *notional += quote;
+            *priced += fill.base;
+        }
+        *fees += fill.fee;
+    }
+
+    legs.iter()
+        .zip(totals)
+        .enumerate()
+        .map(|(leg, (req, (filled, notional, priced, fee)))| LegFill {
+            leg,
+            venue: "deepbook".to_string(),
+            pool: req.pool.clone(),
+            filled_quantity: filled,
+            avg_price: (priced > 0.0).then_some(notional / priced),
+            fee,
+        })
+        .collect()
+}
+
+/// Index of the leg a fill event belongs to
+fn fill_leg(legs: &[&LimitReq], fill: &DecodedFill) -> Option<usize> {
+    if let Some(client_id) = &fill.client_order_id {
+        if let Some(leg) = legs
+            .iter()
+            .position(|req| &req.client_order_id == client_id)
+        {
+            return Some(leg);
+        }
+    }
+    if let Some(pool) = &fill.pool {
+        if let Some(leg) = legs
+            .iter()
+            .position(|req| req.pool.eq_ignore_ascii_case(pool))
+        {
+            return Some(leg);
+        }
+    }
+    (legs.len() == 1).then_some(0)
+}
+
+/// Hand signed transaction bytes to `submit`, or in dry-run mode to `simulate`
+/// only. `submit` is never called while `dry_run` is set.
+pub async fn submit_or_simulate<T, S, SFut, X, XFut>(
+    dry_run: bool,
+    simulate: S,
+    submit: X,
+) -> Result<T>
+where
+    S: FnOnce() -> SFut,
+    SFut: std::future::Future<Output = Result<T>>,
+    X: FnOnce() -> XFut,
+    XFut: std::future::Future<Output = Result<T>>,
+{
+    if dry_run {
+        simulate().await
+    } else {
+        submit().await
+    }
+}
+
+/// Simulate compiled bytes through `simulate` (gRPC), or through `dry_run`
+/// (JSON-RPC) when gRPC simulation is unavailable. Returns the simulated gas
+/// cost, or `SimulationRejected` when the transaction aborts.
+pub async fn simulate_or_dry_run<S, SFut, D, DFut>(simulate: S, dry_run: D) -> Result<Option<u64>>
+where
+    S: FnOnce() -> SFut,
+    SFut: std::future::Future<Output = Result<Option<ExecutedTransaction>>>,
+    D: FnOnce() -> DFut,
+    DFut: std::future::Future<Output = Result<DryRunResp>>,
+{
+    let (failure, gas) = match simulate().await? {
+        Some(simulated) => (
+            simulation_failure(&simulated),
+            ExecutionEngine::extract_gas_cost(&simulated),
+        ),
+        None => {
+            let dry_run = dry_run().await?;
+            (dry_run.failure(), dry_run.gas_cost())
+        }
+    };
+    if let Some(reason) = failure {
+        warn!(reason = %reason, "simulation aborted; transaction not submitted");
+        return Err(AggrError::SimulationRejected(reason).into());
+    }
+    Ok(gas)
+}
+
+/// Whether an execution error means the transaction used stale object versions
+pub fn is_version_conflict(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        let message = cause.to_string();
+        VERSION_CONFLICT_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker))
+    })
+}
+
+/// Whether resubmitting the same signed transaction after `err` cannot succeed:
+/// version conflicts, rejected arguments or preconditions, bad signatures,
+/// gas shortfalls, Move aborts and local validation or signing failures
+pub fn is_permanent_submit_error(err: &anyhow::Error) -> bool {
+    if is_version_conflict(err) {
+        return true;
+    }
+    if let Some(status) = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<tonic::Status>())
+    {
+        if matches!(
+            status.code(),
+            tonic::Code::InvalidArgument
+                | tonic::Code::FailedPrecondition
+                | tonic::Code::PermissionDenied
+                | tonic::Code::Unauthenticated
+                | tonic::Code::Unimplemented
+        ) {
+            return true;
+        }
+    }
+    if let Some(
+        AggrError::Signing(_)
+        | AggrError::BuildTx(_)
+        | AggrError::RiskCheck(_)
+        | AggrError::SimulationRejected(_)
+        | AggrError::Quantization { .. },
+    ) = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<AggrError>())
+    {
+        return true;
+    }
+    err.chain().any(|cause| {
+        let message = cause.to_string();
+        PERMANENT_SUBMIT_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker))
+    })
+}
+
+/// Retry class of a submission failure: permanent errors stop the backoff
+/// loop at once, everything else (network, unavailable, timeouts) is retried
+pub fn classify_submit_error(err: anyhow::Error) -> backoff::Error<anyhow::Error> {
+    if is_permanent_submit_error(&err) {
+        backoff::Error::permanent(err)
+    } else {
+        backoff::Error::transient(err)
+    }
+}
+
+/// Run `attempt`, calling it again (up to `max_rebuilds` times) whenever it fails
+/// with an object version conflict. `attempt` must rebuild the transaction itself.
+pub async fn retry_on_version_conflict<T, F, Fut>(max_rebuilds: u32, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut rebuilds = 0;
+    loop {
+        match attempt().await {
+            Err(e) if rebuilds < max_rebuilds && is_version_conflict(&e) => {
+                rebuilds += 1;
+                warn!(
+                    error = %e,
+                    rebuild = rebuilds,
+                    max_rebuilds,
+                    "object version conflict; rebuilding transaction"
+                );
+            }
+            result => return result,
+        }
     }
 }
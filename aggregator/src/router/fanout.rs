@@ -0,0 +1,53 @@
+// Bounded fan-out
+// Runs per-pool work for bulk requests with a cap on calls in flight and a
+// timeout per item, so one slow pool cannot stall the rest
+//
+// Numan Thabit 2025 Nov
+
+use futures::stream::{self, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+
+/// Default number of pools a bulk quote evaluates at once
+pub const DEFAULT_BULK_QUOTE_WORKERS: usize = 8;
+/// Default time one pool in a bulk quote may take
+pub const DEFAULT_BULK_QUOTE_POOL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Concurrency and per-pool time limits for bulk quotes
+#[derive(Debug, Clone, Copy)]
+pub struct FanOutLimits {
+    /// Items evaluated concurrently (at least 1)
+    pub workers: usize,
+    /// Time each item may take before it is abandoned
+    pub item_timeout: Duration,
+}
+
+impl Default for FanOutLimits {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_BULK_QUOTE_WORKERS,
+            item_timeout: DEFAULT_BULK_QUOTE_POOL_TIMEOUT,
+        }
+    }
+}
+
+/// Run `task` over `items` with at most `limits.workers` in flight. Results keep
+/// the input order; `None` marks an item that ran past `limits.item_timeout`.
+pub async fn bounded_fan_out<T, R, F, Fut>(
+    items: Vec<T>,
+    limits: FanOutLimits,
+    task: F,
+) -> Vec<Option<R>>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R>,
+{
+    stream::iter(items)
+        .map(|item| {
+            let work = task(item);
+            async move { tokio::time::timeout(limits.item_timeout, work).await.ok() }
+        })
+        .buffered(limits.workers.max(1))
+        .collect()
+        .await
+}
@@ -0,0 +1,176 @@
+// Trade flow tracking and fill probability estimation
+// Keeps a rolling window of observed fill volume per pool and combines it
+// with queue position to estimate how likely and how soon a resting order fills
+//
+// Numan Thabit 2025 Nov
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// One observed fill: when, base quantity, and average price when known
+type FillEntry = (Instant, f64, Option<f64>);
+
+/// Rolling record of traded base volume per pool
+#[derive(Debug)]
+pub struct TradeFlow {
+    window: Duration,
+    fills: RwLock<HashMap<String, VecDeque<FillEntry>>>,
+}
+
+impl TradeFlow {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            fills: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a fill of `base_quantity` on a pool
+    pub async fn record(&self, pool: &str, base_quantity: f64) {
+        self.record_entry(pool, base_quantity, None).await;
+    }
+
+    /// Record a fill of `base_quantity` that traded at an average `price`
+    pub async fn record_at_price(&self, pool: &str, base_quantity: f64, price: f64) {
+        let price = Some(price).filter(|p| p.is_finite() && *p > 0.0);
+        self.record_entry(pool, base_quantity, price).await;
+    }
+
+    async fn record_entry(&self, pool: &str, base_quantity: f64, price: Option<f64>) {
+        if !(base_quantity.is_finite() && base_quantity > 0.0) {
+            return;
+        }
+        let now = Instant::now();
+        let mut fills = self.fills.write().await;
+        let entries = fills.entry(pool.to_string()).or_default();
+        entries.push_back((now, base_quantity, price));
+        Self::prune(entries, now, self.window);
+    }
+
+    /// Observed base volume per millisecond over the window, if any flow was seen
+    pub async fn rate_per_ms(&self, pool: &str) -> Option<f64> {
+        let now = Instant::now();
+        let fills = self.fills.read().await;
+        let entries = fills.get(pool)?;
+        let volume: f64 = entries
+            .iter()
+            .filter(|(at, _, _)| now.duration_since(*at) <= self.window)
+            .map(|(_, qty, _)| qty)
+            .sum();
+        if volume <= 0.0 {
+            return None;
+        }
+        Some(volume / self.window.as_millis().max(1) as f64)
+    }
+
+    /// Base volume per millisecond that traded at or through `price` from the
+    /// point of view of a resting order on the given side: at or below it for
+    /// a bid, at or above it for an ask. Falls back to the pool-wide rate when
+    /// no priced fills were seen; `Some(0.0)` means priced flow exists but none
+    /// reached `price`.
+    pub async fn rate_through_price_per_ms(
+        &self,
+        pool: &str,
+        price: f64,
+        is_bid: bool,
+    ) -> Option<f64> {
+        let now = Instant::now();
+        let priced: Vec<(f64, f64)> = {
+            let fills = self.fills.read().await;
+            fills
+                .get(pool)?
+                .iter()
+                .filter(|(at, _, _)| now.duration_since(*at) <= self.window)
+                .filter_map(|(_, qty, traded)| traded.map(|traded| (*qty, traded)))
+                .collect()
+        };
+        if priced.is_empty() {
+            return self.rate_per_ms(pool).await;
+        }
+        let volume: f64 = priced
+            .iter()
+            .filter(|(_, traded)| {
+                if is_bid {
+                    *traded <= price
+                } else {
+                    *traded >= price
+                }
+            })
+            .map(|(qty, _)| qty)
+            .sum();
+        Some(volume / self.window.as_millis().max(1) as f64)
+    }
+
+    fn prune(entries: &mut VecDeque<FillEntry>, now: Instant, window: Duration) {
+        while let Some((at, _, _)) = entries.front() {
+            if now.duration_since(*at) > window {
+                entries.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+impl Default for TradeFlow {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+/// Rough probability that a resting order fully fills within `horizon_ms`.
+///
+/// Treats traded volume as a Poisson-like arrival process at `flow_rate_per_ms`
+/// that must consume the `queue_ahead` plus the order's own `quantity`.
+pub fn fill_probability(
+    queue_ahead: f64,
+    quantity: f64,
+    flow_rate_per_ms: f64,
+    horizon_ms: u64,
+) -> f64 {
+    let expected_volume = flow_rate_per_ms.max(0.0) * horizon_ms as f64;
+    let needed = queue_ahead.max(0.0) + quantity.max(0.0);
+    if needed <= 0.0 {
+        return 1.0;
+    }
+    (1.0 - (-expected_volume / needed).exp()).clamp(0.0, 1.0)
+}
+
+/// Expected wait before a resting order is filled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TimeToFill {
+    /// Expected to fill in about `estimated_fill_ms`
+    Expected { estimated_fill_ms: u64 },
+    /// Not expected to fill within the `horizon_ms` asked about
+    UnlikelyWithinHorizon { horizon_ms: u64 },
+}
+
+/// Heuristic time for flow at `flow_rate_per_ms` to consume the `queue_ahead`
+/// and then the order's own `quantity`, capped at `horizon_ms`.
+pub fn time_to_fill(
+    queue_ahead: f64,
+    quantity: f64,
+    flow_rate_per_ms: f64,
+    horizon_ms: u64,
+) -> TimeToFill {
+    let needed = queue_ahead.max(0.0) + quantity.max(0.0);
+    if needed <= 0.0 {
+        return TimeToFill::Expected {
+            estimated_fill_ms: 0,
+        };
+    }
+    if !(flow_rate_per_ms.is_finite() && flow_rate_per_ms > 0.0) {
+        return TimeToFill::UnlikelyWithinHorizon { horizon_ms };
+    }
+    let expected_ms = (needed / flow_rate_per_ms).ceil();
+    if expected_ms > horizon_ms as f64 {
+        TimeToFill::UnlikelyWithinHorizon { horizon_ms }
+    } else {
+        TimeToFill::Expected {
+            estimated_fill_ms: expected_ms as u64,
+        }
+    }
+}
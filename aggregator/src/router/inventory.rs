@@ -0,0 +1,95 @@
+// Inventory tracking for risk controls
+// Tracks the net base-asset position per pool from executed fills and
+// enforces reduce-only order constraints against it
+//
+// Numan Thabit 2025 Nov
+
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Quantities below this are treated as a flat position
+const POSITION_EPSILON: f64 = 1e-9;
+
+/// Outcome of applying a reduce-only constraint to an order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReduceOnlyDecision {
+    /// Order fits within the current position and is unchanged
+    Accept(f64),
+    /// Order would open, increase or flip exposure
+    Reject,
+}
+
+/// Maximum quantity an order on `is_bid` side can have without increasing or
+/// flipping a position of `position` base units (positive = long)
+pub fn max_reduce_quantity(position: f64, is_bid: bool) -> f64 {
+    let reducible = if is_bid { -position } else { position };
+    if reducible > POSITION_EPSILON {
+        reducible
+    } else {
+        0.0
+    }
+}
+
+/// Apply a reduce-only constraint to an order quantity. An order larger than
+/// the position would flip it, so it is rejected rather than resized.
+pub fn apply_reduce_only(position: f64, is_bid: bool, quantity: f64) -> ReduceOnlyDecision {
+    let max = max_reduce_quantity(position, is_bid);
+    if max <= 0.0 || quantity > max + POSITION_EPSILON {
+        ReduceOnlyDecision::Reject
+    } else {
+        ReduceOnlyDecision::Accept(quantity)
+    }
+}
+
+/// Net base-asset position per pool, built from executed fills
+#[derive(Debug, Default)]
+pub struct InventoryTracker {
+    positions: RwLock<HashMap<String, f64>>,
+}
+
+impl InventoryTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current net position for a pool (positive = long base)
+    pub async fn position(&self, pool: &str) -> f64 {
+        self.positions
+            .read()
+            .await
+            .get(pool)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Overwrite the position for a pool (e.g. seeded from an external book)
+    pub async fn set_position(&self, pool: &str, position: f64) {
+        self.positions
+            .write()
+            .await
+            .insert(pool.to_string(), position);
+    }
+
+    /// Apply a fill of `base_quantity` on the given side to the pool position
+    pub async fn record_fill(&self, pool: &str, is_bid: bool, base_quantity: f64) {
+        if !(base_quantity.is_finite() && base_quantity > 0.0) {
+            return;
+        }
+        let delta = if is_bid {
+            base_quantity
+        } else {
+            -base_quantity
+        };
+        let mut positions = self.positions.write().await;
+        let entry = positions.entry(pool.to_string()).or_insert(0.0);
+        *entry += delta;
+        if entry.abs() < POSITION_EPSILON {
+            *entry = 0.0;
+        }
+    }
+
+    /// Snapshot of all tracked positions
+    pub async fn positions(&self) -> HashMap<String, f64> {
+        self.positions.read().await.clone()
+    }
+}
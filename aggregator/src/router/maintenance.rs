@@ -0,0 +1,214 @@
+// Maintenance windows
+// Scheduled periods during which new orders are refused while reads stay live
+//
+// Numan Thabit 2025 Nov
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+
+use crate::errors::AggrError;
+
+const MINUTE_MS: u64 = 60_000;
+const DAY_MS: u64 = 24 * 60 * MINUTE_MS;
+const WEEK_MS: u64 = 7 * DAY_MS;
+/// The Unix epoch fell on a Thursday; weeks here start on Monday
+const EPOCH_WEEKDAY_OFFSET_MS: u64 = 3 * DAY_MS;
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// How a window repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    /// A single absolute period; bounds are Unix milliseconds
+    Once,
+    /// Every day; bounds are milliseconds since 00:00 UTC
+    Daily,
+    /// Every week; bounds are milliseconds since Monday 00:00 UTC
+    Weekly,
+}
+
+/// One maintenance window. Recurring windows may wrap past the end of their
+/// period, e.g. `daily 23:30-00:30`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    pub recurrence: Recurrence,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl MaintenanceWindow {
+    /// If `now_ms` falls inside this window, the Unix time at which it ends
+    pub fn active_until(&self, now_ms: u64) -> Option<u64> {
+        let period = match self.recurrence {
+            Recurrence::Once => {
+                return (self.start_ms..self.end_ms)
+                    .contains(&now_ms)
+                    .then_some(self.end_ms)
+            }
+            Recurrence::Daily => DAY_MS,
+            Recurrence::Weekly => WEEK_MS,
+        };
+        let shifted = match self.recurrence {
+            Recurrence::Weekly => now_ms + EPOCH_WEEKDAY_OFFSET_MS,
+            _ => now_ms,
+        };
+        let offset = shifted % period;
+        let remaining = if self.start_ms <= self.end_ms {
+            (self.start_ms..self.end_ms)
+                .contains(&offset)
+                .then(|| self.end_ms - offset)
+        } else if offset >= self.start_ms {
+            Some(period - offset + self.end_ms)
+        } else if offset < self.end_ms {
+            Some(self.end_ms - offset)
+        } else {
+            None
+        };
+        remaining.map(|ms| now_ms + ms)
+    }
+}
+
+/// Parses `<start_ms>-<end_ms>` (one-off, Unix ms), `daily HH:MM-HH:MM` or
+/// `weekly <mon..sun> HH:MM-HH:MM`; times are UTC.
+impl FromStr for MaintenanceWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let window = match parts.as_slice() {
+            [range] => {
+                let (start, end) = split_range(range)?;
+                MaintenanceWindow {
+                    recurrence: Recurrence::Once,
+                    start_ms: start.parse().context("window start must be Unix ms")?,
+                    end_ms: end.parse().context("window end must be Unix ms")?,
+                }
+            }
+            [kind, range] if kind.eq_ignore_ascii_case("daily") => {
+                let (start, end) = split_range(range)?;
+                MaintenanceWindow {
+                    recurrence: Recurrence::Daily,
+                    start_ms: parse_time_of_day(start)?,
+                    end_ms: parse_time_of_day(end)?,
+                }
+            }
+            [kind, day, range] if kind.eq_ignore_ascii_case("weekly") => {
+                let day = day.to_ascii_lowercase();
+                let Some(index) = WEEKDAYS.iter().position(|d| day.starts_with(d)) else {
+                    bail!("unknown weekday {day:?} in maintenance window {s:?}");
+                };
+                let (start, end) = split_range(range)?;
+                let day_start = index as u64 * DAY_MS;
+                let start_ms = day_start + parse_time_of_day(start)?;
+                let mut end_ms = day_start + parse_time_of_day(end)?;
+                if end_ms <= start_ms {
+                    // Ends the following day, wrapping Sunday into Monday
+                    end_ms = (end_ms + DAY_MS) % WEEK_MS;
+                }
+                MaintenanceWindow {
+                    recurrence: Recurrence::Weekly,
+                    start_ms,
+                    end_ms,
+                }
+            }
+            _ => bail!("unrecognised maintenance window {s:?}"),
+        };
+        if window.start_ms == window.end_ms
+            || (window.recurrence == Recurrence::Once && window.start_ms > window.end_ms)
+        {
+            bail!("maintenance window {s:?} is empty");
+        }
+        Ok(window)
+    }
+}
+
+impl fmt::Display for MaintenanceWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clock = |ms: u64| {
+            let minutes = ms % DAY_MS / MINUTE_MS;
+            format!("{:02}:{:02}", minutes / 60, minutes % 60)
+        };
+        match self.recurrence {
+            Recurrence::Once => write!(f, "{}-{}", self.start_ms, self.end_ms),
+            Recurrence::Daily => write!(f, "daily {}-{}", clock(self.start_ms), clock(self.end_ms)),
+            Recurrence::Weekly => write!(
+                f,
+                "weekly {} {}-{}",
+                WEEKDAYS[(self.start_ms / DAY_MS) as usize],
+                clock(self.start_ms),
+                clock(self.end_ms)
+            ),
+        }
+    }
+}
+
+fn split_range(range: &str) -> Result<(&str, &str)> {
+    range
+        .split_once('-')
+        .with_context(|| format!("maintenance window range {range:?} must be <start>-<end>"))
+}
+
+fn parse_time_of_day(time: &str) -> Result<u64> {
+    let (hours, minutes) = time
+        .split_once(':')
+        .with_context(|| format!("time {time:?} must be HH:MM"))?;
+    let hours: u64 = hours
+        .parse()
+        .with_context(|| format!("bad hour in {time:?}"))?;
+    let minutes: u64 = minutes
+        .parse()
+        .with_context(|| format!("bad minute in {time:?}"))?;
+    // 24:00 is accepted as the end of the day
+    if hours > 24 || minutes > 59 || (hours == 24 && minutes > 0) {
+        bail!("time {time:?} is out of range");
+    }
+    Ok((hours * 60 + minutes) * MINUTE_MS)
+}
+
+/// Configured maintenance windows; empty means always open
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceSchedule {
+    windows: Vec<MaintenanceWindow>,
+}
+
+impl MaintenanceSchedule {
+    pub fn new(windows: Vec<MaintenanceWindow>) -> Self {
+        Self { windows }
+    }
+
+    pub fn windows(&self) -> &[MaintenanceWindow] {
+        &self.windows
+    }
+
+    /// Latest end among the windows covering `now_ms`, if any
+    pub fn active_until(&self, now_ms: u64) -> Option<u64> {
+        self.windows
+            .iter()
+            .filter_map(|window| window.active_until(now_ms))
+            .max()
+    }
+
+    /// Refuse new orders while a window is in effect
+    pub fn ensure_open(&self, now_ms: u64) -> Result<(), AggrError> {
+        match self.active_until(now_ms) {
+            Some(until_ms) => Err(AggrError::Maintenance { until_ms }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses windows separated by `;`
+impl FromStr for MaintenanceSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let windows = s
+            .split(';')
+            .map(str::trim)
+            .filter(|window| !window.is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        Ok(Self { windows })
+    }
+}
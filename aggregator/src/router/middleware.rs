@@ -0,0 +1,228 @@
+// HTTP middleware for the router API
+// Cross-cutting request instrumentation and limits applied around handlers
+//
+// Numan Thabit 2025 Nov
+
+use crate::metrics::SLOW_REQUESTS;
+use axum::{
+    body::{self, Body},
+    extract::{DefaultBodyLimit, MatchedPath, Request, State},
+    http::{
+        header::{ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderMap, HeaderValue,
+    },
+    middleware::{self, Next},
+    response::Response,
+    routing::MethodRouter,
+    Router as AxumRouter,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Fallback request ids for callers that do not send `x-request-id`
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Default cap on request bodies across the API
+pub const DEFAULT_MAX_BODY_BYTES: usize = 256 * 1024;
+/// Default cap on single-order request bodies
+pub const DEFAULT_MAX_ORDER_BODY_BYTES: usize = 16 * 1024;
+/// Media type a client can `Accept` to receive enveloped responses
+pub const ENVELOPE_MEDIA_TYPE: &str = "application/vnd.ultra-aggr.envelope+json";
+
+/// When a request arrived and the id it is logged under. Stamped on the
+/// request by the first layer that needs it so later layers measure the same span.
+#[derive(Debug, Clone)]
+pub struct RequestTiming {
+    pub request_id: String,
+    pub started: Instant,
+}
+
+impl RequestTiming {
+    /// The timing an outer layer stamped on `request`, or a new one starting now
+    pub fn of(request: &mut Request) -> Self {
+        if let Some(timing) = request.extensions().get::<RequestTiming>() {
+            return timing.clone();
+        }
+        let request_id = request
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)
+            .unwrap_or_else(|| NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed).to_string());
+        let timing = RequestTiming {
+            request_id,
+            started: Instant::now(),
+        };
+        request.extensions_mut().insert(timing.clone());
+        timing
+    }
+}
+
+/// JSON payload wrapped with the request id and server processing time
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseEnvelope {
+    pub request_id: String,
+    pub processing_time_ms: u64,
+    pub data: serde_json::Value,
+}
+
+/// Reject request bodies larger than `limit` bytes with 413 on every route
+pub fn with_body_limit<S>(router: AxumRouter<S>, limit: usize) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(DefaultBodyLimit::max(limit))
+}
+
+/// Per-route body cap; overrides the router-wide limit for this route
+pub fn limit_body<S>(route: MethodRouter<S>, limit: usize) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    route.layer(DefaultBodyLimit::max(limit))
+}
+
+/// Warn about and count requests that take longer than `threshold`
+pub fn with_slow_request_logging<S>(router: AxumRouter<S>, threshold: Duration) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(middleware::from_fn_with_state(threshold, log_slow_requests))
+}
+
+async fn log_slow_requests(
+    State(threshold): State<Duration>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    // Prefer the route template so metric labels stay bounded
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let timing = RequestTiming::of(&mut request);
+
+    let response = next.run(request).await;
+    let elapsed = timing.started.elapsed();
+
+    if elapsed > threshold {
+        SLOW_REQUESTS.with_label_values(&[&method, &path]).inc();
+        warn!(
+            method = %method,
+            path = %path,
+            request_id = %timing.request_id,
+            duration_ms = elapsed.as_millis() as u64,
+            threshold_ms = threshold.as_millis() as u64,
+            status = response.status().as_u16(),
+            "slow request"
+        );
+    }
+    response
+}
+
+/// Wrap JSON responses in a [`ResponseEnvelope`] for requests that ask for one
+/// with `?envelope=true` or `Accept: application/vnd.ultra-aggr.envelope+json`.
+/// Other requests get the bare payload.
+pub fn with_response_envelope<S>(router: AxumRouter<S>) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(middleware::from_fn(wrap_in_envelope))
+}
+
+/// Whether the client asked for an enveloped response
+pub fn wants_envelope(request: &Request) -> bool {
+    let by_query = request.uri().query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| matches!(pair, "envelope=true" | "envelope=1"))
+    });
+    by_query || accepts_envelope(request)
+}
+
+fn accepts_envelope(request: &Request) -> bool {
+    request
+        .headers()
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|media| {
+            media
+                .split(';')
+                .next()
+                .is_some_and(|t| t.trim().eq_ignore_ascii_case(ENVELOPE_MEDIA_TYPE))
+        })
+}
+
+async fn wrap_in_envelope(mut request: Request, next: Next) -> Response {
+    if !wants_envelope(&request) {
+        return next.run(request).await;
+    }
+    let negotiated = accepts_envelope(&request);
+    let timing = RequestTiming::of(&mut request);
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(request_id = %timing.request_id, error = %e, "failed to buffer response for envelope");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let Ok(data) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let envelope = ResponseEnvelope {
+        request_id: timing.request_id,
+        processing_time_ms: timing.started.elapsed().as_millis() as u64,
+        data,
+    };
+    let Ok(enveloped) = serde_json::to_vec(&envelope) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    parts.headers.remove(CONTENT_LENGTH);
+    let content_type = if negotiated {
+        ENVELOPE_MEDIA_TYPE
+    } else {
+        "application/json"
+    };
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    Response::from_parts(parts, Body::from(enveloped))
+}
+
+/// Whether `headers` carry `Authorization: Bearer <expected>`. Compares in
+/// constant time so response timing does not leak the token.
+pub fn bearer_token_matches(headers: &HeaderMap, expected: &str) -> bool {
+    let Some(presented) = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    let (presented, expected) = (presented.trim().as_bytes(), expected.as_bytes());
+    if expected.is_empty() || presented.len() != expected.len() {
+        return false;
+    }
+    presented
+        .iter()
+        .zip(expected)
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
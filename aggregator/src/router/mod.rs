@@ -4,9 +4,23 @@
 //
 // Numan Thabit 2025 Nov
 
+pub mod capabilities;
+pub mod children;
+pub mod deadline;
+pub mod digests;
 pub mod execution;
+pub mod fanout;
+pub mod flow;
+pub mod inventory;
+pub mod maintenance;
+pub mod middleware;
+pub mod quotes;
 pub mod routes;
 pub mod selector;
+pub mod sessions;
+pub mod strategies;
+pub mod strategy_registry;
+pub mod tags;
 pub mod validation;
 pub mod validator;
 
@@ -14,7 +28,8 @@ pub mod validator;
 pub mod router;
 
 pub use execution::ExecutionEngine;
-pub use router::Router;
+pub use inventory::InventoryTracker;
+pub use router::{ApiSettings, Router};
 pub use routes::{Route, RoutePlan, RouteScore};
 pub use selector::RouteSelector;
 pub use validator::ValidatorSelector;
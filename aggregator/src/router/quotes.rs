@@ -0,0 +1,108 @@
+// Firm quote registry
+// Remembers quoted orders until their validity window closes so a client can
+// commit to exactly what it was quoted, and refuses commits after expiry
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use crate::venues::adapter::LimitReq;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// Default validity of a quote
+pub const DEFAULT_QUOTE_TTL: Duration = Duration::from_secs(5);
+
+/// Milliseconds since the Unix epoch
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Handle returned to the client for a firm quote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteTicket {
+    pub quote_id: String,
+    pub valid_until_ms: u64,
+}
+
+struct IssuedQuote {
+    request: LimitReq,
+    valid_until_ms: u64,
+}
+
+/// Quoted orders awaiting commit
+pub struct QuoteRegistry {
+    ttl: Duration,
+    quotes: Arc<RwLock<HashMap<String, IssuedQuote>>>,
+    next_id: AtomicU64,
+}
+
+impl Default for QuoteRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUOTE_TTL)
+    }
+}
+
+impl QuoteRegistry {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            quotes: Arc::new(RwLock::new(HashMap::new())),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Remember `request` as quoted at `now_ms`; expired quotes are dropped on the way
+    pub async fn issue(&self, request: LimitReq, now_ms: u64) -> QuoteTicket {
+        let valid_until_ms = now_ms + self.ttl.as_millis() as u64;
+        let seq = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let quote_id = format!("q{now_ms:x}-{seq:x}");
+        let mut quotes = self.quotes.write().await;
+        quotes.retain(|_, quote| quote.valid_until_ms >= now_ms);
+        quotes.insert(
+            quote_id.clone(),
+            IssuedQuote {
+                request,
+                valid_until_ms,
+            },
+        );
+        QuoteTicket {
+            quote_id,
+            valid_until_ms,
+        }
+    }
+
+    /// Claim a quote for execution. Each quote can be committed once, and only
+    /// until its `valid_until_ms`.
+    pub async fn commit(&self, quote_id: &str, now_ms: u64) -> Result<LimitReq, AggrError> {
+        let quote = self
+            .quotes
+            .write()
+            .await
+            .remove(quote_id)
+            .ok_or_else(|| AggrError::UnknownQuote(quote_id.to_string()))?;
+        if now_ms > quote.valid_until_ms {
+            return Err(AggrError::QuoteExpired {
+                valid_until_ms: quote.valid_until_ms,
+            });
+        }
+        Ok(quote.request)
+    }
+
+    pub async fn len(&self) -> usize {
+        self.quotes.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.quotes.read().await.is_empty()
+    }
+}
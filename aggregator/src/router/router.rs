@@ -3,10 +3,22 @@
 //
 // Numan Thabit 2025 Nov
 
-use crate::venues::adapter::LimitReq;
+use crate::venues::adapter::{LimitOrderType, LimitReq, MarketReq, VWAP_BOOK_TICKS};
+use crate::venues::book::{
+    apply_post_only, best_price, crosses, depth_curve, mid_price, opposite_side, own_side,
+    queue_ahead, BookSweep, DepthCurve, PostOnlyDecision, PostOnlyFallback,
+};
+use crate::venues::book_export::{
+    count_snapshots, write_snapshots, BookExportSettings, BookLevel, BookSnapshot, ExportThrottle,
+    SnapshotFormat, MAX_EXPORT_POOLS, MAX_EXPORT_TICKS,
+};
+use crate::venues::pools::PoolNormalizer;
 use axum::{
     body::Body,
-    extract::State,
+    extract::{
+        ws::{WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
     http::{HeaderMap, StatusCode},
     response::{Html, Json, Response},
     routing::{get, post},
@@ -15,23 +27,58 @@ use axum::{
 use prometheus::{Encoder, TextEncoder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use sui_deepbookv3::client::Level2TicksFromMid;
 use tokio::sync::RwLock;
-use tracing::{field, info_span};
+use tracing::{field, info, info_span, warn};
 
 use super::{ExecutionEngine, RoutePlan, RouteSelector};
-use crate::control::{AdmissionControl, CircuitBreakers};
-use crate::metrics::{REQ_ERRORS, REQ_LATENCY};
+use crate::amounts::OrderAmounts;
+use crate::control::{AdmissionControl, BreakerClass, CircuitBreakers, OrderCeiling};
+use crate::errors::AggrError;
+use crate::metrics::{record_execution_stats, record_latency_estimates, REQ_ERRORS, REQ_LATENCY};
+use crate::quant::Precision;
+use crate::router::capabilities::Capabilities;
+use crate::router::children::{
+    run_twap, submit_children, submit_children_until, twap_children, ChildReport,
+    ChildSubmissionPolicy, SystemClock, TwapResult, MAX_TWAP_DURATION, MAX_TWAP_SLICES,
+};
+use crate::router::deadline::run_until_submitted;
 use crate::router::execution::ExecutionAccounting;
-use crate::router::execution::{ExecutionResult, ExecutionStats, OrderHandle};
-use crate::router::routes::RouteSelection;
+use crate::router::execution::{ExecutionResult, ExecutionStats, OrderHandle, SignedTransaction};
+use crate::router::fanout::{bounded_fan_out, FanOutLimits};
+use crate::router::flow::{fill_probability, time_to_fill, TimeToFill, TradeFlow};
+use crate::router::inventory::{apply_reduce_only, InventoryTracker, ReduceOnlyDecision};
+use crate::router::maintenance::MaintenanceSchedule;
+use crate::router::middleware::{
+    bearer_token_matches, limit_body, with_body_limit, with_response_envelope,
+    with_slow_request_logging, DEFAULT_MAX_BODY_BYTES, DEFAULT_MAX_ORDER_BODY_BYTES,
+};
+use crate::router::quotes::{now_ms, QuoteRegistry};
+use crate::router::routes::{ReplaceCommand, ReplaceMode, Route, RouteSelection, ScoreBreakdown};
 use crate::router::selector::LatencyStats;
-use crate::router::validation::validate_limit_order;
+use crate::router::sessions::{
+    cancel_expired_sessions, track_or_cancel, SessionOrder, SessionRegistry,
+};
+use crate::router::strategies::{estimate_strategies, StrategyEstimate, StrategyParams};
+use crate::router::strategy_registry::{
+    StrategyHandle, StrategyProgress, StrategyRegistry, StrategyType,
+};
+use crate::router::tags::{format_tags, validate_tags, OrderTags};
+use crate::router::validation::{check_gas_price, validate_limit_order};
+use crate::signing::{address_from_public_key, signature_public_key, Signer};
+use crate::state::CheckpointState;
+use crate::transport::graphql::{
+    graphql_status, GraphQLHealth, GraphQLRpc, GraphQLStatus, PoolVolume,
+};
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
 
 const CANCEL_GAS_ESTIMATE: u64 = 5_000_000;
 const CANCEL_REPLACE_GAS_ESTIMATE: u64 = 15_000_000;
+const TWAP_SLICE_GAS_ESTIMATE: u64 = 10_000_000;
 
 #[derive(Debug, Deserialize)]
 pub struct CancelOrderRequest {
@@ -40,6 +87,30 @@ pub struct CancelOrderRequest {
     pub order_id: Option<String>,
     #[serde(default)]
     pub digest: Option<String>,
+    /// Picks the order out of a `digest` that placed several in the pool
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    /// BalanceManager holding the order; the configured default when omitted
+    #[serde(default)]
+    pub manager: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelByIdRequest {
+    pub pool: String,
+    /// On-chain DeepBook order id, decimal-encoded u128
+    pub order_id: String,
+    /// BalanceManager holding the order; the configured default when omitted
+    #[serde(default)]
+    pub manager: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelAllRequest {
+    pub pool: String,
+    /// BalanceManager holding the orders; the configured default when omitted
+    #[serde(default)]
+    pub manager: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -50,6 +121,56 @@ pub struct ReplaceOrderRequest {
     pub cancel_order_id: Option<String>,
     #[serde(default)]
     pub cancel_digest: Option<String>,
+    /// Picks the order to cancel out of a `cancel_digest` that placed several
+    #[serde(default)]
+    pub cancel_client_order_id: Option<String>,
+    /// Sequencing of cancel and place; the configured mode when omitted
+    #[serde(default)]
+    pub mode: Option<ReplaceMode>,
+}
+
+/// HTTP-layer limits applied by the API handlers
+#[derive(Debug, Clone)]
+pub struct ApiSettings {
+    /// Upper bound on a client-supplied `timeout_ms`
+    pub max_request_timeout: Duration,
+    /// Read-only deployment: quotes and data only, execution endpoints return 501
+    pub observer_mode: bool,
+    /// Log and count handlers slower than this (disabled when `None`)
+    pub slow_request_threshold: Option<Duration>,
+    /// Largest request body accepted on any endpoint; larger bodies get 413
+    pub max_body_bytes: usize,
+    /// Tighter body cap for single-order endpoints
+    pub max_order_body_bytes: usize,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            max_request_timeout: Duration::from_secs(30),
+            observer_mode: false,
+            slow_request_threshold: None,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_order_body_bytes: DEFAULT_MAX_ORDER_BODY_BYTES,
+        }
+    }
+}
+
+impl ApiSettings {
+    /// Refuse order placement, cancellation and replacement in observer mode
+    pub fn ensure_execution_enabled(&self) -> Result<(), (StatusCode, Json<ApiError>)> {
+        if self.observer_mode {
+            return Err((
+                StatusCode::NOT_IMPLEMENTED,
+                Json(ApiError {
+                    code: "OBSERVER_MODE".to_string(),
+                    message: "execution is disabled on this read-only deployment".to_string(),
+                    details: None,
+                }),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// High-level Router that ties selection and execution together
@@ -58,8 +179,29 @@ pub struct Router {
     executor: Arc<ExecutionEngine>,
     admission: Option<Arc<AdmissionControl>>,
     breakers: Option<Arc<CircuitBreakers>>,
+    order_ceiling: Option<OrderCeiling>,
     idempotency: Arc<RwLock<HashMap<String, IdemEntry>>>,
     idem_ttl: Duration,
+    inventory: Arc<InventoryTracker>,
+    flow: Arc<TradeFlow>,
+    api: ApiSettings,
+    pools: Option<PoolNormalizer>,
+    checkpoints: Option<CheckpointState>,
+    max_gas_price: Option<u64>,
+    verify_trade_authority: bool,
+    bulk_quotes: FanOutLimits,
+    message_signing: Option<MessageSigning>,
+    build_signed_token: Option<String>,
+    book_export: Option<BookExport>,
+    replace_mode: ReplaceMode,
+    sessions: SessionRegistry,
+    history: Option<GraphQLRpc>,
+    graphql_health: Option<GraphQLHealth>,
+    graphql_required: bool,
+    maintenance: MaintenanceSchedule,
+    quotes: QuoteRegistry,
+    child_policy: ChildSubmissionPolicy,
+    strategies: StrategyRegistry,
 }
 
 impl Router {
@@ -69,11 +211,199 @@ impl Router {
             executor,
             admission: None,
             breakers: None,
+            order_ceiling: None,
             idempotency: Arc::new(RwLock::new(HashMap::new())),
             idem_ttl: Duration::from_secs(300),
+            inventory: Arc::new(InventoryTracker::new()),
+            flow: Arc::new(TradeFlow::default()),
+            api: ApiSettings::default(),
+            pools: None,
+            checkpoints: None,
+            max_gas_price: None,
+            verify_trade_authority: false,
+            bulk_quotes: FanOutLimits::default(),
+            message_signing: None,
+            build_signed_token: None,
+            book_export: None,
+            replace_mode: ReplaceMode::default(),
+            sessions: SessionRegistry::default(),
+            history: None,
+            graphql_health: None,
+            graphql_required: false,
+            maintenance: MaintenanceSchedule::default(),
+            quotes: QuoteRegistry::default(),
+            child_policy: ChildSubmissionPolicy::default(),
+            strategies: StrategyRegistry::new(),
         }
     }
 
+    /// Canonicalize pool identifiers at the API boundary
+    pub fn with_pool_normalizer(mut self, pools: PoolNormalizer) -> Self {
+        self.pools = Some(pools);
+        self
+    }
+
+    /// Report checkpoint stream staleness on the health endpoint
+    pub fn with_checkpoint_health(mut self, checkpoints: CheckpointState) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// How long a quote may be committed after it was priced
+    pub fn with_quote_ttl(mut self, ttl: Duration) -> Self {
+        self.quotes = QuoteRegistry::new(ttl);
+        self
+    }
+
+    /// Firm quotes awaiting commit
+    pub fn quotes(&self) -> &QuoteRegistry {
+        &self.quotes
+    }
+
+    /// Default limits for strategies submitting child orders
+    pub fn with_child_policy(mut self, policy: ChildSubmissionPolicy) -> Self {
+        self.child_policy = policy;
+        self
+    }
+
+    pub fn child_policy(&self) -> &ChildSubmissionPolicy {
+        &self.child_policy
+    }
+
+    /// Slicing strategies currently submitting children
+    pub fn strategies(&self) -> &StrategyRegistry {
+        &self.strategies
+    }
+
+    /// Refuse orders while the reference gas price exceeds `max_gas_price`
+    pub fn with_max_gas_price(mut self, max_gas_price: u64) -> Self {
+        self.max_gas_price = Some(max_gas_price);
+        self
+    }
+
+    /// Cap pools evaluated at once by a bulk quote, and the time each may take
+    pub fn with_bulk_quote_limits(mut self, limits: FanOutLimits) -> Self {
+        self.bulk_quotes = limits;
+        self
+    }
+
+    /// How long a session may go without a heartbeat before its orders are cancelled
+    pub fn with_session_timeout(mut self, timeout: Duration) -> Self {
+        self.sessions = SessionRegistry::new(timeout);
+        self
+    }
+
+    /// Client sessions whose orders are cancelled when heartbeats stop
+    pub fn sessions(&self) -> &SessionRegistry {
+        &self.sessions
+    }
+
+    /// Cancel the orders of sessions whose heartbeats stopped
+    pub async fn cancel_expired_sessions(&self) -> usize {
+        cancel_expired_sessions(&self.sessions, Instant::now(), |order| {
+            self.cancel_session_order(order)
+        })
+        .await
+    }
+
+    /// Cancel one order placed under a session
+    async fn cancel_session_order(&self, order: SessionOrder) -> Result<()> {
+        let plan = RoutePlan::cancel_deepbook(
+            order.pool,
+            order.order_id,
+            order.manager,
+            CANCEL_GAS_ESTIMATE,
+        );
+        self.execute_cancel(&plan).await.map(|_| ())
+    }
+
+    /// Submit a cancel plan. Cancels are deliberately exempt from admission
+    /// control and the order ceiling: they only take orders off the book, and
+    /// a saturated admission queue is exactly when a session, strategy or
+    /// client needs its cancels to go out ahead of the new orders waiting there.
+    async fn execute_cancel(&self, plan: &RoutePlan) -> Result<ExecutionResult> {
+        self.executor.execute(plan).await
+    }
+
+    /// Answer historical queries such as pool volume from the GraphQL indexer
+    pub fn with_history(mut self, graphql: GraphQLRpc) -> Self {
+        self.history = Some(graphql);
+        self
+    }
+
+    /// Refuse new orders during these maintenance windows
+    pub fn with_maintenance_schedule(mut self, schedule: MaintenanceSchedule) -> Self {
+        self.maintenance = schedule;
+        self
+    }
+
+    /// Serve /api/v1/sign_message through the trading-key `signer`, local or
+    /// remote, to callers presenting `token` as a bearer token
+    pub fn with_message_signing(mut self, signer: Arc<dyn Signer>, token: String) -> Self {
+        self.message_signing = Some(MessageSigning { signer, token });
+        self
+    }
+
+    /// Serve /api/v1/build_signed, returning signed orders for the caller to
+    /// broadcast, to callers presenting `token` as a bearer token
+    pub fn with_build_signed(mut self, token: String) -> Self {
+        self.build_signed_token = Some(token);
+        self
+    }
+
+    /// Serve /api/v1/admin/book_snapshot, writing level2 snapshots under
+    /// `settings.dir`, to callers presenting `settings.token` as a bearer token
+    pub fn with_book_export(mut self, settings: BookExportSettings) -> Self {
+        self.book_export = Some(BookExport {
+            throttle: ExportThrottle::new(settings.min_interval),
+            settings,
+        });
+        self
+    }
+
+    /// Report GraphQL reachability on /health; when `required`, an unreachable
+    /// endpoint makes the service unhealthy
+    pub fn with_graphql_health(mut self, health: GraphQLHealth, required: bool) -> Self {
+        self.graphql_health = Some(health);
+        self.graphql_required = required;
+        self
+    }
+
+    /// Default sequencing of cancel-replace requests
+    pub fn with_replace_mode(mut self, mode: ReplaceMode) -> Self {
+        self.replace_mode = mode;
+        self
+    }
+
+    /// Check before each order that the sender may trade from its BalanceManager
+    pub fn with_trade_authority_check(mut self, enabled: bool) -> Self {
+        self.verify_trade_authority = enabled;
+        self
+    }
+
+    /// Enforce the gas price ceiling, if configured, against the cached reference price
+    pub async fn check_gas_ceiling(&self, allow_override: bool) -> Result<()> {
+        let (Some(ceiling), Some(adapter)) = (self.max_gas_price, self.selector.deepbook_adapter())
+        else {
+            return Ok(());
+        };
+        let price = adapter.reference_gas_price().await?;
+        check_gas_price(price, ceiling, allow_override)?;
+        Ok(())
+    }
+
+    /// Override HTTP-layer limits
+    pub fn with_api_settings(mut self, api: ApiSettings) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Share an inventory tracker (e.g. one seeded from an external position source)
+    pub fn with_inventory(mut self, inventory: Arc<InventoryTracker>) -> Self {
+        self.inventory = inventory;
+        self
+    }
+
     /// Set admission control and circuit breakers
     pub fn with_control(
         mut self,
@@ -85,6 +415,12 @@ impl Router {
         self
     }
 
+    /// Shed orders beyond `ceiling` with 429 instead of queuing them
+    pub fn with_order_ceiling(mut self, ceiling: OrderCeiling) -> Self {
+        self.order_ceiling = Some(ceiling);
+        self
+    }
+
     /// Get access to the route selector (for operations like updating latency estimates)
     pub fn selector(&self) -> &Arc<RouteSelector> {
         &self.selector
@@ -95,6 +431,164 @@ impl Router {
         &self.executor
     }
 
+    /// Canonical key for a client-supplied pool identifier (unchanged without a normalizer)
+    pub fn canonical_pool(&self, pool: &str) -> Result<String, AggrError> {
+        match &self.pools {
+            Some(pools) => pools.resolve(pool),
+            None => Ok(pool.to_string()),
+        }
+    }
+
+    /// Get access to the per-pool inventory tracker
+    pub fn inventory(&self) -> &Arc<InventoryTracker> {
+        &self.inventory
+    }
+
+    /// Rough probability that `req` fully fills within `horizon_ms` if left resting.
+    /// Returns `None` when no recent trade flow has been observed for the pool.
+    pub async fn estimate_fill_probability(
+        &self,
+        req: &LimitReq,
+        horizon_ms: u64,
+    ) -> Result<Option<f64>> {
+        let adapter = self
+            .selector
+            .deepbook_adapter()
+            .context("DeepBook adapter not configured")?;
+        let level2 = adapter.level2_ticks_from_mid(&req.pool, 20).await?;
+        if crosses(&level2, req.price, req.is_bid) {
+            return Ok(Some(1.0));
+        }
+
+        let Some(rate) = self.flow.rate_per_ms(&req.pool).await else {
+            return Ok(None);
+        };
+        let (prices, quantities) = own_side(&level2, req.is_bid);
+        let ahead = queue_ahead(prices, quantities, req.price, req.is_bid);
+        Ok(Some(fill_probability(
+            ahead,
+            req.quantity,
+            rate,
+            horizon_ms,
+        )))
+    }
+
+    /// Heuristic time until `req` fills if left resting, from the recent flow
+    /// that traded at or through its price and its queue position. Returns
+    /// `None` when no recent trade flow has been observed for the pool.
+    pub async fn estimate_time_to_fill(
+        &self,
+        req: &LimitReq,
+        horizon_ms: u64,
+    ) -> Result<Option<TimeToFill>> {
+        let adapter = self
+            .selector
+            .deepbook_adapter()
+            .context("DeepBook adapter not configured")?;
+        let level2 = adapter.level2_ticks_from_mid(&req.pool, 20).await?;
+        if crosses(&level2, req.price, req.is_bid) {
+            return Ok(Some(TimeToFill::Expected {
+                estimated_fill_ms: 0,
+            }));
+        }
+
+        let Some(rate) = self
+            .flow
+            .rate_through_price_per_ms(&req.pool, req.price, req.is_bid)
+            .await
+        else {
+            return Ok(None);
+        };
+        let (prices, quantities) = own_side(&level2, req.is_bid);
+        let ahead = queue_ahead(prices, quantities, req.price, req.is_bid);
+        Ok(Some(time_to_fill(ahead, req.quantity, rate, horizon_ms)))
+    }
+
+    /// Immediate, TWAP and passive estimates for working `req`
+    pub async fn quote_strategies(
+        &self,
+        req: &LimitReq,
+        execution_latency_ms: u64,
+    ) -> Result<Option<Vec<StrategyEstimate>>> {
+        let Some(adapter) = self.selector.deepbook_adapter() else {
+            return Ok(None);
+        };
+        let level2 = adapter
+            .level2_ticks_from_mid(&req.pool, VWAP_BOOK_TICKS)
+            .await?;
+        let rate = self.flow.rate_per_ms(&req.pool).await;
+        let params = StrategyParams {
+            execution_latency_ms,
+            ..StrategyParams::default()
+        };
+        Ok(Some(estimate_strategies(
+            &level2,
+            req.quantity,
+            req.is_bid,
+            rate,
+            params,
+        )))
+    }
+
+    /// Refuse a reduce-only order that would increase or flip the current net
+    /// position for its pool
+    async fn enforce_reduce_only(&self, req: &LimitReq) -> Result<()> {
+        let position = self.inventory.position(&req.pool).await;
+        match apply_reduce_only(position, req.is_bid, req.quantity) {
+            ReduceOnlyDecision::Accept(_) => Ok(()),
+            ReduceOnlyDecision::Reject => Err(AggrError::RiskCheck(format!(
+                "reduce-only {} of {} would increase or flip exposure (position {position})",
+                if req.is_bid { "bid" } else { "ask" },
+                req.quantity
+            ))
+            .into()),
+        }
+    }
+
+    async fn enforce_post_only(
+        &self,
+        req: &LimitReq,
+        fallback: PostOnlyFallback,
+    ) -> Result<LimitReq> {
+        let Some(adapter) = self.selector.deepbook_adapter() else {
+            return Ok(req.clone());
+        };
+        let level2 = adapter.level2_ticks_from_mid(&req.pool, 20).await?;
+        let (prices, _) = opposite_side(&level2, req.is_bid);
+        let best = best_price(prices);
+        let tick_size = adapter.pool_params(&req.pool).await?.tick_size;
+        match apply_post_only(fallback, req.price, req.is_bid, best, tick_size) {
+            PostOnlyDecision::Rest => Ok(req.clone()),
+            PostOnlyDecision::Reprice(price) => {
+                info!(
+                    pool = %req.pool,
+                    requested = req.price,
+                    repriced = price,
+                    "post-only order repriced to best maker price"
+                );
+                let mut repriced = req.clone();
+                repriced.price = price;
+                Ok(repriced)
+            }
+            PostOnlyDecision::Downgrade => {
+                info!(
+                    pool = %req.pool,
+                    price = req.price,
+                    "post-only order would cross; submitting as a plain limit order"
+                );
+                let mut downgraded = req.clone();
+                downgraded.post_only = None;
+                downgraded.order_type = LimitOrderType::Gtc;
+                Ok(downgraded)
+            }
+            PostOnlyDecision::Reject { best } => Err(AggrError::PostOnlyWouldCross {
+                price: req.price,
+                best,
+            }
+            .into()),
+        }
+    }
+
     async fn idem_get(&self, key: &str) -> Option<OrderActionResponse> {
         let guard = self.idempotency.read().await;
         if let Some(entry) = guard.get(key) {
@@ -118,64 +612,412 @@ impl Router {
 
     /// Route a single DeepBook limit order request and execute it
     pub async fn execute_limit_order(&self, req: &LimitReq) -> Result<ExecutionResult> {
-        // 1. Acquire admission control permit
-        let _permit = if let Some(admission) = &self.admission {
-            Some(admission.acquire().await)
-        } else {
-            None
+        self.execute_limit_order_with_sponsor(req, None, None).await
+    }
+
+    /// Like `execute_limit_order`, with gas paid by the sponsor registered as
+    /// `sponsor`. Retries of one logical order share an `idempotency_key` so
+    /// the sponsor's budget is debited once.
+    pub async fn execute_limit_order_with_sponsor(
+        &self,
+        req: &LimitReq,
+        sponsor: Option<&str>,
+        idempotency_key: Option<&str>,
+    ) -> Result<ExecutionResult> {
+        // 0. Nothing new goes out during planned downtime
+        self.maintenance.ensure_open(now_ms())?;
+        if let Some(ceiling) = &self.order_ceiling {
+            ceiling.try_admit().await?;
+        }
+
+        // 1. Everything from planning to the result holds an admission permit
+        let work = async {
+            // 2-5. Apply order policies, validate and select a route
+            let best = self.plan_limit_order(req).await?;
+
+            self.execute_plan(&best, &req.pool, req.is_bid, sponsor, idempotency_key)
+                .await
         };
+        match &self.admission {
+            Some(admission) => admission.admit(work).await,
+            None => work.await,
+        }
+    }
 
-        // 2. Pre-trade validation
-        if let Some(adapter) = self.selector.deepbook_adapter() {
-            let validation = validate_limit_order(adapter, req).await?;
-            validation
-                .into_result()
-                .context("pre-trade validation failed")?;
+    /// Sweep the book with a DeepBook market order, rejecting it up front when
+    /// the expected fill moves further from mid than `req.max_slippage_bps`
+    pub async fn execute_market_order(&self, req: &MarketReq) -> Result<ExecutionResult> {
+        self.maintenance.ensure_open(now_ms())?;
+        if let Some(ceiling) = &self.order_ceiling {
+            ceiling.try_admit().await?;
         }
+        let work = async {
+            let best = self.selector.select_market_route(req).await?;
 
-        // 3. Select route
-        let sel = self.selector.select_route(req).await?;
-        let best = sel.best_plan().clone();
+            self.execute_plan(&best, &req.pool, req.is_bid, None, None)
+                .await
+        };
+        match &self.admission {
+            Some(admission) => admission.admit(work).await,
+            None => work.await,
+        }
+    }
+
+    /// Execute a selected plan behind its route class's circuit breaker,
+    /// folding any fills into inventory and trade flow
+    async fn execute_plan(
+        &self,
+        best: &RoutePlan,
+        pool: &str,
+        is_bid: bool,
+        sponsor: Option<&str>,
+        idempotency_key: Option<&str>,
+    ) -> Result<ExecutionResult> {
         let uses_shared = best.uses_shared_objects;
 
-        // 4. Check circuit breaker for route class
-        let route_class = format!("{:?}", best.route);
-        if let Some(breakers) = &self.breakers {
-            if breakers.is_open(&route_class).await {
-                anyhow::bail!("circuit breaker open for route class: {}", route_class);
+        // 6-7. Execute the route behind its class's circuit breaker
+        let route_class = BreakerClass::new(best.route.venue(), best.route.pool());
+        let execution = async {
+            match sponsor {
+                Some(alias) => {
+                    self.executor
+                        .execute_with_sponsor(best, alias, idempotency_key)
+                        .await
+                }
+                None => self.executor.execute(best).await,
             }
-        }
+        };
+        let result = match &self.breakers {
+            Some(breakers) => breakers.guard(&route_class, execution).await?,
+            None => execution.await?,
+        };
 
-        // 5. Execute route
-        let result = match self.executor.execute(&best).await {
-            Ok(result) => {
-                // Record success in circuit breaker
-                if let Some(breakers) = &self.breakers {
-                    breakers.record_success(&route_class).await;
+        // Fold any fills into the tracked inventory
+        let events = result.accounting.deepbook_events.as_ref();
+        if let Some(filled) = events.and_then(|events| events.total_base_filled) {
+            self.inventory.record_fill(pool, is_bid, filled).await;
+            match events.and_then(|events| events.total_quote_filled) {
+                Some(quote) => {
+                    self.flow
+                        .record_at_price(pool, filled, quote / filled)
+                        .await
                 }
-                // Record latency observation for adaptive updates
-                self.selector
-                    .record_latency(result.effects_time_ms, uses_shared)
-                    .await;
-                Ok(result)
+                None => self.flow.record(pool, filled).await,
             }
-            Err(e) => {
-                // Record failure in circuit breaker
-                if let Some(breakers) = &self.breakers {
-                    breakers.record_failure(&route_class).await;
+        }
+        // Record latency observation for adaptive updates; simulations
+        // say nothing about submission latency
+        if !result.dry_run {
+            self.selector
+                .record_latency(result.effects_time_ms, uses_shared)
+                .await;
+        }
+        Ok(result)
+    }
+
+    /// Optional features this deployment runs with
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            execution: !self.api.observer_mode,
+            grpc_exec: self.executor.uses_grpc_execute(),
+            deepbook: self.selector.deepbook_adapter().is_some(),
+            amm_venues: self.selector.cetus_adapter().is_some(),
+            sponsorship: self.executor.has_default_sponsor(),
+            sponsors: self.executor.sponsors().aliases(),
+            simulation: self.executor.simulates_gas_budget(),
+            dry_run: self.executor.is_dry_run(),
+            websocket_sessions: true,
+            build_signed: self.build_signed_token.is_some(),
+            sign_message: self.message_signing.is_some(),
+            history: self.history.is_some(),
+            book_export: self.book_export.is_some(),
+        }
+    }
+
+    /// Replace resting order `order_id` with `replace`, submitting the
+    /// transactions `mode` defines one after another. The replacement passes
+    /// the same reduce-only, post-only and pre-trade checks as a new order,
+    /// and the whole replace holds a single admission permit. Each
+    /// transaction runs behind the route's circuit breaker like any order.
+    /// Returns the execution that placed the replacement and, when the cancel
+    /// ran separately, the cancel's digest.
+    pub async fn replace_order(
+        &self,
+        order_id: u128,
+        cancel_digest: Option<String>,
+        replace: LimitReq,
+        mode: ReplaceMode,
+    ) -> Result<(ExecutionResult, Option<String>)> {
+        self.maintenance.ensure_open(now_ms())?;
+        if let Some(ceiling) = &self.order_ceiling {
+            ceiling.try_admit().await?;
+        }
+        let work = async {
+            let replace = self.resolve_limit_order(&replace).await?;
+            submit_replace(order_id, mode, |commands| {
+                let replace = &replace;
+                let cancel_digest = cancel_digest.clone();
+                async move {
+                    let plan = replace_plan(&commands, order_id, cancel_digest, replace)?;
+                    self.execute_plan(&plan, &replace.pool, replace.is_bid, None, None)
+                        .await
                 }
-                // Execution failed - this is already tracked in ExecutionEngine stats
-                Err(e)
+            })
+            .await
+        };
+        match &self.admission {
+            Some(admission) => admission.admit(work).await,
+            None => work.await,
+        }
+    }
+
+    /// Compile and sign `req` for the caller to broadcast; nothing is submitted.
+    /// A signed order counts against the order ceiling and holds an admission
+    /// permit like one submitted here, since the caller can broadcast it.
+    pub async fn build_signed_order(
+        &self,
+        req: &LimitReq,
+        sponsor: Option<&str>,
+    ) -> Result<SignedTransaction> {
+        self.maintenance.ensure_open(now_ms())?;
+        if let Some(ceiling) = &self.order_ceiling {
+            ceiling.try_admit().await?;
+        }
+        let work = async {
+            let best = self.plan_limit_order(req).await?;
+            self.executor.build_signed(&best, sponsor).await
+        };
+        match &self.admission {
+            Some(admission) => admission.admit(work).await,
+            None => work.await,
+        }
+    }
+
+    /// Apply reduce-only and post-only policies to `req`, run pre-trade
+    /// validation and select the best route plan for it
+    async fn plan_limit_order(&self, req: &LimitReq) -> Result<RoutePlan> {
+        let req = self.resolve_limit_order(req).await?;
+
+        // 5. Select route
+        let sel = self.selector.select_route(&req).await?;
+        Ok(sel.best_plan().clone())
+    }
+
+    /// Apply reduce-only and post-only policies to `req` and run pre-trade
+    /// validation, returning the order as it will be placed
+    async fn resolve_limit_order(&self, req: &LimitReq) -> Result<LimitReq> {
+        // 2. Reduce-only orders may only shrink the current position
+        if req.reduce_only {
+            self.enforce_reduce_only(req).await?;
+        }
+
+        // 3. Post-only orders that would take follow their fallback policy
+        let req = match req.post_only {
+            Some(fallback) => self.enforce_post_only(req, fallback).await?,
+            None => req.clone(),
+        };
+
+        // 4. Pre-trade validation
+        if let Some(adapter) = self.selector.deepbook_adapter() {
+            if self.verify_trade_authority {
+                adapter
+                    .verify_trade_authority(req.manager.as_deref())
+                    .await?;
             }
+            let validation = validate_limit_order(adapter, &req).await?;
+            validation
+                .into_result()
+                .context("pre-trade validation failed")?;
+        }
+        Ok(req)
+    }
+
+    /// Execute a strategy's child orders under `policy`. Each child goes through
+    /// `execute_limit_order`, so admission control and circuit breakers apply
+    /// to every child individually.
+    pub async fn execute_children(
+        &self,
+        children: Vec<LimitReq>,
+        policy: &ChildSubmissionPolicy,
+    ) -> ChildReport<ExecutionResult> {
+        submit_children(children, policy, |child| async move {
+            self.execute_limit_order(&child).await
+        })
+        .await
+    }
+
+    /// Run a registered strategy's children until done or cancelled, recording
+    /// progress on `handle`. The strategy leaves the registry when this returns.
+    pub async fn run_strategy(
+        &self,
+        handle: &StrategyHandle,
+        children: Vec<LimitReq>,
+        policy: &ChildSubmissionPolicy,
+    ) -> ChildReport<ExecutionResult> {
+        let report = submit_children_until(
+            children,
+            policy,
+            || handle.is_cancelled(),
+            |child| async move {
+                let result = self.execute_limit_order(&child).await;
+                match &result {
+                    Ok(execution) => {
+                        handle.record_submitted(&execution.orders, child.manager.as_deref())
+                    }
+                    Err(_) => handle.record_failed(),
+                }
+                result
+            },
+        )
+        .await;
+        self.strategies.finish(handle.id()).await;
+        report
+    }
+
+    /// Start a `Route::Twap` plan in the background: its slices go out
+    /// `interval_ms` apart, each through `execute_limit_order` so it waits for
+    /// admission and is checked against its circuit breaker, after the gas
+    /// price is re-checked against the ceiling. A failed slice skips the rest
+    /// under the configured child failure policy's `Abort`, as does a slice
+    /// finding the breaker open. The TWAP is registered as a
+    /// strategy until its last slice, so its progress can be listed and it can
+    /// be cancelled; the returned progress carries its id.
+    pub async fn start_twap(
+        self: &Arc<Self>,
+        plan: &RoutePlan,
+        allow_high_gas: bool,
+    ) -> Result<StrategyProgress> {
+        let Route::Twap {
+            base,
+            slices,
+            interval_ms,
+        } = &plan.route
+        else {
+            anyhow::bail!("start_twap requires a TWAP route");
         };
+        self.maintenance.ensure_open(now_ms())?;
+        let children = twap_children(base, *slices);
+        let interval = Duration::from_millis(*interval_ms);
+        let handle = self
+            .strategies
+            .start(StrategyType::Twap, children.len())
+            .await;
+        let finished = self.strategies.finish_on_drop(handle.id());
+        let started = handle.progress();
+        let router = Arc::clone(self);
+        tokio::spawn(async move {
+            let _finished = finished;
+            let result = router
+                .run_twap_slices(&handle, children, interval, allow_high_gas)
+                .await;
+            info!(
+                strategy = %handle.id(),
+                submitted = result.slices.len(),
+                failed = result.failures(),
+                skipped = result.skipped,
+                aborted = ?result.aborted,
+                "TWAP finished"
+            );
+        });
+        Ok(started)
+    }
 
-        result
+    /// Submit a TWAP's slices, recording progress on `handle`
+    async fn run_twap_slices(
+        &self,
+        handle: &StrategyHandle,
+        children: Vec<LimitReq>,
+        interval: Duration,
+        allow_high_gas: bool,
+    ) -> TwapResult<ExecutionResult> {
+        run_twap(
+            children,
+            interval,
+            &SystemClock,
+            self.child_policy.on_failure,
+            || handle.is_cancelled(),
+            || handle.cancelled(),
+            |child| async move {
+                // The gas price may have risen since the TWAP started
+                let result = match self.check_gas_ceiling(allow_high_gas).await {
+                    Ok(()) => self.execute_limit_order(&child).await,
+                    Err(err) => Err(err),
+                };
+                match &result {
+                    Ok(execution) => {
+                        handle.record_submitted(&execution.orders, child.manager.as_deref())
+                    }
+                    Err(_) => handle.record_failed(),
+                }
+                result
+            },
+        )
+        .await
+    }
+
+    /// Stop a running strategy. With `cancel_resting`, also cancel the orders its
+    /// children left on the book.
+    pub async fn cancel_strategy(
+        &self,
+        id: &str,
+        cancel_resting: bool,
+    ) -> Result<StrategyCancellation> {
+        let handle = self.strategies.cancel(id).await?;
+        let mut cancelled_orders = Vec::new();
+        let mut failed_cancels = Vec::new();
+        if cancel_resting {
+            for order in handle.take_resting_orders() {
+                let plan = RoutePlan::cancel_deepbook(
+                    order.pool.clone(),
+                    order.order_id,
+                    order.manager.clone(),
+                    CANCEL_GAS_ESTIMATE,
+                );
+                match self.execute_cancel(&plan).await {
+                    Ok(execution) => cancelled_orders.push(execution.digest),
+                    Err(err) => {
+                        warn!(
+                            strategy = %id,
+                            pool = %order.pool,
+                            order_id = order.order_id,
+                            error = %err,
+                            "failed to cancel resting strategy child"
+                        );
+                        failed_cancels.push(format!("{}:{}: {err}", order.pool, order.order_id));
+                    }
+                }
+            }
+        }
+        Ok(StrategyCancellation {
+            strategy: handle.progress(),
+            cancelled_orders,
+            failed_cancels,
+        })
     }
 
     /// Select route without executing (for quote/preview)
     pub async fn select_route(&self, req: &LimitReq) -> Result<RouteSelection> {
         self.selector.select_route(req).await
     }
+
+    /// Select a route after evaluating every venue
+    pub async fn select_route_exhaustive(&self, req: &LimitReq) -> Result<RouteSelection> {
+        self.selector.select_route_exhaustive(req).await
+    }
+}
+
+/// Trading-key signer and bearer token for the message-signing endpoint
+#[derive(Clone)]
+struct MessageSigning {
+    signer: Arc<dyn Signer>,
+    token: String,
+}
+
+/// Settings and write throttle for the book export endpoint
+struct BookExport {
+    settings: BookExportSettings,
+    throttle: ExportThrottle,
 }
 
 #[derive(Clone)]
@@ -184,15 +1026,109 @@ struct IdemEntry {
     response: OrderActionResponse,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MarketOrderRequest {
+    pub pool: String,
+    pub quantity: f64,
+    pub is_bid: bool,
+    pub client_order_id: String,
+    pub pay_with_deep: Option<bool>,
+    /// Reject the order when its expected average fill is further than this
+    /// many basis points from mid; unbounded when omitted
+    #[serde(default)]
+    pub max_slippage_bps: Option<f64>,
+    /// Execute even when the reference gas price is above the configured ceiling
+    #[serde(default)]
+    pub allow_high_gas: Option<bool>,
+    /// BalanceManager key to trade from; the configured default when omitted
+    #[serde(default)]
+    pub manager: Option<String>,
+}
+
+impl From<MarketOrderRequest> for MarketReq {
+    fn from(req: MarketOrderRequest) -> Self {
+        MarketReq {
+            pool: req.pool,
+            quantity: req.quantity,
+            is_bid: req.is_bid,
+            client_order_id: req.client_order_id,
+            pay_with_deep: req.pay_with_deep.unwrap_or(false),
+            max_slippage_bps: req.max_slippage_bps,
+            manager: req.manager,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LimitOrderRequest {
     pub pool: String,
+    /// Price in quote coins; may be omitted when `price_human` is given
+    #[serde(default)]
     pub price: f64,
+    /// Quantity in base coins; may be omitted when `quantity_human` is given
+    #[serde(default)]
     pub quantity: f64,
+    /// Exact decimal price in quote coins, e.g. "2.25" or "2.25 USDC"
+    #[serde(default)]
+    pub price_human: Option<String>,
+    /// Exact decimal quantity in base coins, e.g. "100.5" or "100.5 SUI"
+    #[serde(default)]
+    pub quantity_human: Option<String>,
     pub is_bid: bool,
     pub client_order_id: String,
     pub pay_with_deep: Option<bool>,
     pub expiration_ms: Option<u64>,
+    #[serde(default)]
+    pub reduce_only: Option<bool>,
+    /// Client metadata echoed in the response and logs; not sent on-chain
+    #[serde(default)]
+    pub tags: Option<OrderTags>,
+    /// Alias of a configured gas sponsor to pay for this order
+    #[serde(default)]
+    pub sponsor: Option<String>,
+    /// Execute even when the reference gas price is above the configured ceiling
+    #[serde(default)]
+    pub allow_high_gas: Option<bool>,
+    /// Only rest on the book; never take liquidity
+    #[serde(default)]
+    pub post_only: Option<bool>,
+    /// What a post-only order does if it would cross (default reject)
+    #[serde(default)]
+    pub post_only_fallback: Option<PostOnlyFallback>,
+    /// `GTC` (default), `IOC`, `FOK` or `PostOnly`
+    #[serde(default)]
+    pub order_type: Option<String>,
+    /// BalanceManager key to trade from; the configured default when omitted
+    #[serde(default)]
+    pub manager: Option<String>,
+    /// Open session to tie the order to; it is cancelled if the session's heartbeats stop
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+impl From<LimitOrderRequest> for LimitReq {
+    /// `order_type` is checked by `validate_limit_order_req` beforehand
+    fn from(req: LimitOrderRequest) -> Self {
+        let order_type = req
+            .order_type
+            .as_deref()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default();
+        LimitReq {
+            pool: req.pool,
+            price: req.price,
+            quantity: req.quantity,
+            is_bid: req.is_bid,
+            client_order_id: req.client_order_id,
+            pay_with_deep: req.pay_with_deep.unwrap_or(false),
+            expiration_ms: req.expiration_ms,
+            reduce_only: req.reduce_only.unwrap_or(false),
+            post_only: (req.post_only.unwrap_or(false) || order_type == LimitOrderType::PostOnly)
+                .then(|| req.post_only_fallback.unwrap_or_default()),
+            order_type,
+            manager: req.manager,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -204,12 +1140,116 @@ pub struct OrderActionResponse {
     pub accounting: Option<ExecutionAccounting>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub orders: Vec<OrderHandle>,
+    /// Base64 BCS-encoded transaction effects (only when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_effects: Option<String>,
+    /// Tags supplied with the order request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<OrderTags>,
+    /// Simulated only (dry-run mode); the digest was never submitted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// Separate cancel transaction of a place-then-cancel replacement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_digest: Option<String>,
+    /// Human and raw price and quantity (when given as human amounts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amounts: Option<OrderAmounts>,
+}
+
+/// Query options shared by the order endpoints
+#[derive(Debug, Default, Deserialize)]
+pub struct OrderQuery {
+    /// Include base64 raw BCS effects in the response
+    #[serde(default)]
+    pub include_raw_effects: Option<bool>,
+    /// Client deadline for the whole operation, capped server-side
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Quote only: estimate the passive fill probability over this horizon
+    #[serde(default)]
+    pub horizon_ms: Option<u64>,
+    /// Quote only: include the VWAP for taking the order size from the book
+    #[serde(default)]
+    pub vwap: Option<bool>,
+    /// Quote only: compare immediate, TWAP and passive execution
+    #[serde(default)]
+    pub strategies: Option<bool>,
+    /// Quote only: evaluate every venue instead of stopping at a good-enough route
+    #[serde(default)]
+    pub full_evaluation: Option<bool>,
+}
+
+impl OrderQuery {
+    fn include_raw_effects(&self) -> bool {
+        self.include_raw_effects.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteQuoteResponse {
+    /// Pass to /api/v1/quote/commit to execute this quote
+    pub quote_id: String,
+    /// Commits after this time (ms since epoch) are rejected
+    pub valid_until_ms: u64,
+    /// When the book data behind this quote was read (ms since epoch)
+    pub source_timestamp_ms: u64,
+    pub plan: RoutePlanResponse,
+    pub alternatives: Vec<RoutePlanResponse>,
+    /// Probability of a full passive fill within `horizon_ms` (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_probability: Option<f64>,
+    /// Heuristic time until a passive fill, or unlikely within `horizon_ms`
+    /// (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_to_fill: Option<TimeToFill>,
+    /// Taker VWAP and worst price for the order size (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vwap: Option<BookSweep>,
+    /// Alternative ways to work the order (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategies: Option<Vec<StrategyEstimate>>,
+    /// Human and raw price and quantity (when given as human amounts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amounts: Option<OrderAmounts>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignMessageRequest {
+    /// Base64 bytes to sign, e.g. an onboarding challenge
+    pub message: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct RouteQuoteResponse {
-    pub plan: RoutePlanResponse,
-    pub alternatives: Vec<RoutePlanResponse>,
+pub struct SignMessageResponse {
+    /// Base64 `flag || signature || public key`, as Sui wallets serialize it
+    pub signature: String,
+    /// Base64 public key: 32-byte Ed25519 or 33-byte compressed secp256k1
+    pub public_key: String,
+    /// Address the signature proves control of
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkQuoteRequest {
+    pub orders: Vec<LimitOrderRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkQuoteResponse {
+    /// One entry per requested order, in request order
+    pub quotes: Vec<BulkQuoteResult>,
+}
+
+/// A quote for one order of a bulk request, or the error that replaced it
+#[derive(Debug, Serialize)]
+pub struct BulkQuoteResult {
+    pub pool: String,
+    pub client_order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<RouteQuoteResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ApiError>,
 }
 
 #[derive(Debug, Serialize)]
@@ -224,6 +1264,76 @@ pub struct RoutePlanResponse {
     pub expected_latency_ms: u64,
     pub uses_shared_objects: bool,
     pub estimated_gas: u64,
+    pub score_breakdown: ScoreBreakdown,
+    /// Average fill price from walking the opposite side of the book
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_fill_price: Option<f64>,
+    /// Expected fill at each visible level the order takes, best first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fills: Vec<BookLevel>,
+    /// Whether the circuit breaker for this route's venue/pool is open
+    pub breaker_open: bool,
+    pub availability: Availability,
+}
+
+/// Whether a quoted route can currently be executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Available,
+    /// The route's circuit breaker is open; execution will be rejected
+    Unavailable,
+}
+
+impl RoutePlanResponse {
+    /// Describe `plan`, annotated with the state of its route class breaker
+    pub async fn from_plan(plan: &RoutePlan, breakers: Option<&CircuitBreakers>) -> Self {
+        let breaker_open = match breakers {
+            Some(breakers) => {
+                let class = BreakerClass::new(plan.route.venue(), plan.route.pool());
+                breakers.is_open_for(&class).await
+            }
+            None => false,
+        };
+        Self {
+            route_type: format!("{:?}", plan.route),
+            total_cost: plan.score.total_cost,
+            l2_price: plan.score.l2_price,
+            slippage: plan.score.slippage,
+            gas_cost: plan.score.gas_cost,
+            latency_penalty: plan.score.latency_penalty,
+            risk_factor: plan.score.risk_factor,
+            expected_latency_ms: plan.expected_latency_ms,
+            uses_shared_objects: plan.uses_shared_objects,
+            estimated_gas: plan.estimated_gas,
+            score_breakdown: plan.score.breakdown(),
+            expected_fill_price: plan.expected_fill_price,
+            fills: plan
+                .fill_levels
+                .iter()
+                .map(|&(price, quantity)| BookLevel { price, quantity })
+                .collect(),
+            breaker_open,
+            availability: if breaker_open {
+                Availability::Unavailable
+            } else {
+                Availability::Available
+            },
+        }
+    }
+
+    /// Round the quoted price to the pool's tick precision
+    pub fn rounded(mut self, precision: &Precision) -> Self {
+        self.l2_price = precision.round_price(self.l2_price);
+        self.expected_fill_price = self
+            .expected_fill_price
+            .map(|price| precision.round_price(price));
+        for fill in &mut self.fills {
+            fill.price = precision.round_price(fill.price);
+            fill.quantity = precision.round_size(fill.quantity);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -236,24 +1346,106 @@ pub struct ApiError {
 
 /// Create the HTTP router with API endpoints
 pub fn create_api_router(router: Arc<Router>) -> AxumRouter {
-    AxumRouter::new()
+    let slow_request_threshold = router.api.slow_request_threshold;
+    let body_limit = router.api.max_body_bytes;
+    let order_limit = router.api.max_order_body_bytes;
+    let api = AxumRouter::new()
         .route("/health", get(health_check))
         .route("/openapi.json", get(openapi_json))
         .route("/docs", get(swagger_ui))
-        .route("/metrics", get(metrics_endpoint))
-        .route("/api/v1/quote", post(quote_route))
-        .route("/api/v1/order", post(execute_order))
-        .route("/api/v1/order/cancel", post(cancel_order))
-        .route("/api/v1/order/replace", post(replace_order))
+        .route("/metrics", get(refresh_and_export_metrics))
+        .route("/api/v1/capabilities", get(capabilities))
+        .route("/api/v1/quote", limit_body(post(quote_route), order_limit))
+        .route("/api/v1/quote/bulk", post(bulk_quote))
+        .route("/api/v1/quote/depth", get(quote_depth))
+        .route("/api/v1/orderbook", get(orderbook))
+        .route("/api/v1/history/volume", get(history_volume))
+        .route(
+            "/api/v1/quote/commit",
+            limit_body(post(commit_quote), order_limit),
+        )
+        .route(
+            "/api/v1/order",
+            limit_body(post(execute_order), order_limit),
+        )
+        .route(
+            "/api/v1/order/cancel",
+            limit_body(post(cancel_order), order_limit),
+        )
+        .route(
+            "/api/v1/order/cancel_by_id",
+            limit_body(post(cancel_order_by_id), order_limit),
+        )
+        .route(
+            "/api/v1/cancel_all",
+            limit_body(post(cancel_all), order_limit),
+        )
+        .route(
+            "/api/v1/order/replace",
+            limit_body(post(replace_order), order_limit),
+        )
+        .route(
+            "/api/v1/build_signed",
+            limit_body(post(build_signed), order_limit),
+        )
+        .route(
+            "/api/v1/market",
+            limit_body(post(execute_market), order_limit),
+        )
+        .route("/api/v1/twap", limit_body(post(execute_twap), order_limit))
+        .route("/api/v1/sign_message", post(sign_message))
+        .route("/api/v1/session/:id/heartbeat", get(session_heartbeat))
+        .route("/api/v1/strategy", get(list_strategies))
+        .route("/api/v1/strategy/:id/cancel", post(cancel_strategy))
         .route("/api/v1/stats", get(get_stats))
+        .route("/api/v1/stats/reset", post(reset_stats_window))
+        .route("/api/v1/admin/book_snapshot", post(export_book_snapshot))
         .route("/api/v1/latency", get(get_latency_stats))
         .route("/api/v1/latency", post(update_latency))
-        .with_state(router)
+        .with_state(router);
+    let api = with_response_envelope(with_body_limit(api, body_limit));
+    // Slow-request logging wraps the envelope so both measure the same span
+    match slow_request_threshold {
+        Some(threshold) => with_slow_request_logging(api, threshold),
+        None => api,
+    }
+}
+
+/// Body of GET /health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// False while the checkpoint stream is stalled; absent without a stream
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_stream_healthy: Option<bool>,
+    pub graphql: GraphQLStatus,
+}
+
+impl HealthReport {
+    /// 503 while the checkpoint stream is stalled, or while a required
+    /// GraphQL endpoint is unreachable
+    pub fn status_code(&self, graphql_required: bool) -> StatusCode {
+        let stalled = self.checkpoint_stream_healthy == Some(false);
+        let graphql_down = graphql_required && self.graphql == GraphQLStatus::Unreachable;
+        if stalled || graphql_down {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::OK
+        }
+    }
 }
 
 /// Health check endpoint
-async fn health_check() -> StatusCode {
-    StatusCode::OK
+async fn health_check(State(router): State<Arc<Router>>) -> (StatusCode, Json<HealthReport>) {
+    let report = HealthReport {
+        checkpoint_stream_healthy: router.checkpoints.as_ref().map(CheckpointState::is_healthy),
+        graphql: graphql_status(router.graphql_health.as_ref()),
+    };
+    (report.status_code(router.graphql_required), Json(report))
+}
+
+/// Features enabled in this deployment
+async fn capabilities(State(router): State<Arc<Router>>) -> Json<Capabilities> {
+    Json(router.capabilities())
 }
 
 /// Serve OpenAPI spec from a bundled JSON file
@@ -298,8 +1490,17 @@ async fn swagger_ui() -> Html<&'static str> {
     Html(HTML)
 }
 
-/// Prometheus metrics endpoint
-async fn metrics_endpoint() -> Response {
+/// Prometheus metrics endpoint; refreshes the execution and latency gauges
+/// before exporting
+async fn refresh_and_export_metrics(State(router): State<Arc<Router>>) -> Response {
+    record_execution_stats(&router.executor.lifetime_snapshot());
+    let (owned_ms, shared_ms) = router.selector.get_latency_estimates();
+    record_latency_estimates(owned_ms, shared_ms);
+    metrics_endpoint().await
+}
+
+/// Default Prometheus registry in the text exposition format
+pub async fn metrics_endpoint() -> Response {
     let encoder = TextEncoder::new();
     let metric_families = prometheus::gather();
     let mut buffer = Vec::new();
@@ -353,12 +1554,63 @@ fn validate_limit_order_req(req: &LimitOrderRequest) -> Result<(), ApiError> {
             details: None,
         });
     }
+    if let Some(value) = &req.order_type {
+        let order_type = value
+            .parse::<LimitOrderType>()
+            .map_err(|message| ApiError {
+                code: "VALIDATION".to_string(),
+                message,
+                details: None,
+            })?;
+        if req.post_only.unwrap_or(false)
+            && matches!(order_type, LimitOrderType::Ioc | LimitOrderType::Fok)
+        {
+            return Err(ApiError {
+                code: "VALIDATION".to_string(),
+                message: format!("post_only cannot be combined with order_type {value}"),
+                details: None,
+            });
+        }
+    }
+    if let Some(tags) = &req.tags {
+        validate_tags(tags).map_err(|message| ApiError {
+            code: "VALIDATION".to_string(),
+            message,
+            details: None,
+        })?;
+    }
+    Ok(())
+}
+
+fn validate_market_order_req(req: &MarketOrderRequest) -> Result<(), ApiError> {
+    let invalid = |message: &str| ApiError {
+        code: "VALIDATION".to_string(),
+        message: message.to_string(),
+        details: None,
+    };
+    if req.pool.trim().is_empty() {
+        return Err(invalid("pool must not be empty"));
+    }
+    if !(req.quantity.is_finite() && req.quantity > 0.0) {
+        return Err(invalid("quantity must be a positive finite number"));
+    }
+    if req.client_order_id.trim().is_empty() || req.client_order_id.parse::<u64>().is_err() {
+        return Err(invalid("client_order_id must be a non-empty u64 string"));
+    }
+    if let Some(bps) = req.max_slippage_bps {
+        if !(bps.is_finite() && bps >= 0.0) {
+            return Err(invalid(
+                "max_slippage_bps must be a non-negative finite number",
+            ));
+        }
+    }
     Ok(())
 }
 
 /// Quote route endpoint - returns route selection without executing
 async fn quote_route(
     State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
     Json(req): Json<LimitOrderRequest>,
 ) -> Result<Json<RouteQuoteResponse>, (StatusCode, Json<ApiError>)> {
     let span = info_span!(
@@ -371,22 +1623,286 @@ async fn quote_route(
     let _timer = REQ_LATENCY
         .with_label_values(&["http", "quote"])
         .start_timer();
-    if let Err(e) = validate_limit_order_req(&req) {
-        REQ_ERRORS.with_label_values(&["http", "quote"]).inc();
-        return Err((StatusCode::BAD_REQUEST, Json(e)));
+    let (limit_req, amounts) = quote_request(&router, req)
+        .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "quote"]).inc())?;
+    with_deadline(&router, &query, quote_order(&router, limit_req, &query))
+        .await
+        .map(|quote| Json(RouteQuoteResponse { amounts, ..quote }))
+        .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "quote"]).inc())
+}
+
+/// Quote many orders at once, a bounded number of pools at a time. Each order
+/// gets its quote or its own error; a slow pool times out alone.
+async fn bulk_quote(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    Json(req): Json<BulkQuoteRequest>,
+) -> Result<Json<BulkQuoteResponse>, (StatusCode, Json<ApiError>)> {
+    let span = info_span!("http.bulk_quote", orders = req.orders.len());
+    let _enter = span.enter();
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "bulk_quote"])
+        .start_timer();
+    if req.orders.is_empty() {
+        REQ_ERRORS.with_label_values(&["http", "bulk_quote"]).inc();
+        return Err(bad_request("VALIDATION", "orders must not be empty"));
+    }
+
+    let limits = router.bulk_quotes;
+    let keys: Vec<(String, String)> = req
+        .orders
+        .iter()
+        .map(|order| (order.pool.clone(), order.client_order_id.clone()))
+        .collect();
+    let (router_ref, query_ref) = (router.as_ref(), &query);
+    let outcomes = with_deadline(&router, &query, async {
+        Ok(bounded_fan_out(req.orders, limits, |order| async move {
+            let (limit_req, amounts) = quote_request(router_ref, order)?;
+            let quote = quote_order(router_ref, limit_req, query_ref).await?;
+            Ok(RouteQuoteResponse { amounts, ..quote })
+        })
+        .await)
+    })
+    .await
+    .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "bulk_quote"]).inc())?;
+
+    let quotes = keys
+        .into_iter()
+        .zip(outcomes)
+        .map(|((pool, client_order_id), outcome)| {
+            let (quote, error) = match outcome {
+                Some(Ok(quote)) => (Some(quote), None),
+                Some(Err((_, Json(error)))) => (None, Some(error)),
+                None => (
+                    None,
+                    Some(ApiError {
+                        code: "TIMEOUT".to_string(),
+                        message: format!(
+                            "pool quote exceeded {} ms",
+                            limits.item_timeout.as_millis()
+                        ),
+                        details: None,
+                    }),
+                ),
+            };
+            BulkQuoteResult {
+                pool,
+                client_order_id,
+                quote,
+                error,
+            }
+        })
+        .collect();
+    Ok(Json(BulkQuoteResponse { quotes }))
+}
+
+/// Session heartbeat over a WebSocket. Connecting opens the session and every
+/// frame received keeps it alive; once frames stop for the session timeout,
+/// its orders are cancelled.
+async fn session_heartbeat(
+    State(router): State<Arc<Router>>,
+    Path(session_id): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let sessions = router.sessions.clone();
+    sessions.heartbeat(&session_id, Instant::now()).await;
+    ws.on_upgrade(move |socket| heartbeat_loop(sessions, session_id, socket))
+}
+
+async fn heartbeat_loop(sessions: SessionRegistry, session_id: String, mut socket: WebSocket) {
+    while let Some(Ok(message)) = socket.recv().await {
+        if matches!(message, axum::extract::ws::Message::Close(_)) {
+            break;
+        }
+        sessions.heartbeat(&session_id, Instant::now()).await;
+    }
+    info!(session = %session_id, "session heartbeat connection closed");
+}
+
+/// Sign an arbitrary message with the trading key under the personal-message
+/// intent, proving control of the trading address. Disabled unless configured,
+/// and only for callers presenting the configured bearer token.
+async fn sign_message(
+    State(router): State<Arc<Router>>,
+    headers: HeaderMap,
+    Json(req): Json<SignMessageRequest>,
+) -> Result<Json<SignMessageResponse>, (StatusCode, Json<ApiError>)> {
+    let Some(signing) = &router.message_signing else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "SIGN_MESSAGE_DISABLED".to_string(),
+                message: "message signing is not enabled".to_string(),
+                details: None,
+            }),
+        ));
+    };
+    if !bearer_token_matches(&headers, &signing.token) {
+        REQ_ERRORS
+            .with_label_values(&["http", "sign_message"])
+            .inc();
+        warn!("rejected message signing request without a valid token");
+        return Err(unauthorized());
     }
-    let limit_req = LimitReq {
-        pool: req.pool,
-        price: req.price,
-        quantity: req.quantity,
-        is_bid: req.is_bid,
-        client_order_id: req.client_order_id,
-        pay_with_deep: req.pay_with_deep.unwrap_or(false),
-        expiration_ms: req.expiration_ms,
+    let message = B64
+        .decode(req.message.trim())
+        .map_err(|e| bad_request("INVALID_MESSAGE", format!("message is not base64: {e}")))?;
+
+    let signature = signing
+        .signer
+        .sign_personal_message(&message)
+        .await
+        .map_err(|e| internal_error("SIGN_ERROR", e))?;
+    // The scheme and key come from the signature, so a remote signer needs no local key
+    let (scheme, public_key) =
+        signature_public_key(&signature).map_err(|e| internal_error("SIGN_ERROR", e))?;
+    let address =
+        address_from_public_key(scheme, public_key).map_err(|e| internal_error("SIGN_ERROR", e))?;
+    info!(address = %address, bytes = message.len(), "signed personal message");
+    Ok(Json(SignMessageResponse {
+        signature: B64.encode(signature),
+        public_key: B64.encode(public_key),
+        address: address.to_string(),
+    }))
+}
+
+/// Compile and sign an order without submitting it; the caller broadcasts the
+/// returned transaction through its own infrastructure
+async fn build_signed(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    headers: HeaderMap,
+    Json(mut req): Json<LimitOrderRequest>,
+) -> Result<Json<SignedTransaction>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "build_signed"])
+        .start_timer();
+    router.api.ensure_execution_enabled()?;
+    let Some(token) = &router.build_signed_token else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "BUILD_SIGNED_DISABLED".to_string(),
+                message: "signed transaction building is not enabled".to_string(),
+                details: None,
+            }),
+        ));
     };
+    if !bearer_token_matches(&headers, token) {
+        REQ_ERRORS
+            .with_label_values(&["http", "build_signed"])
+            .inc();
+        warn!("rejected build_signed request without a valid token");
+        return Err(unauthorized());
+    }
+    if req.session_id.is_some() {
+        return Err(bad_request(
+            "VALIDATION",
+            "session_id is not supported for orders the caller broadcasts",
+        ));
+    }
+    let sponsor = req.sponsor.take();
+    let allow_high_gas = req.allow_high_gas.unwrap_or(false);
+    let (limit_req, _) = quote_request(&router, req)?;
+    if let Some(alias) = &sponsor {
+        ensure_sponsor(&router, alias)?;
+    }
+
+    let signed = with_deadline(&router, &query, async {
+        router
+            .check_gas_ceiling(allow_high_gas)
+            .await
+            .map_err(|e| order_error("BUILD_SIGNED_ERROR", e))?;
+        router
+            .build_signed_order(&limit_req, sponsor.as_deref())
+            .await
+            .map_err(|e| order_error("BUILD_SIGNED_ERROR", e))
+    })
+    .await
+    .inspect_err(|_| {
+        REQ_ERRORS
+            .with_label_values(&["http", "build_signed"])
+            .inc()
+    })?;
+    info!(
+        digest = %signed.digest,
+        client_order_id = %limit_req.client_order_id,
+        sponsored = signed.sponsored,
+        "signed order returned for external broadcast"
+    );
+    Ok(Json(signed))
+}
+
+/// Validate a quote request and resolve its pool and manager to canonical keys
+fn quote_request(
+    router: &Router,
+    mut req: LimitOrderRequest,
+) -> Result<(LimitReq, Option<OrderAmounts>), (StatusCode, Json<ApiError>)> {
+    let amounts = resolve_human_amounts(router, &mut req)?;
+    validate_limit_order_req(&req).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    req.pool = canonical_pool(router, &req.pool)?;
+    req.manager = canonical_manager(router, req.manager.as_deref())?;
+    Ok((LimitReq::from(req), amounts))
+}
+
+/// Convert `price_human`/`quantity_human` into `price`/`quantity` using the
+/// pool's coin decimals. A side given only as a number is kept as is.
+fn resolve_human_amounts(
+    router: &Router,
+    req: &mut LimitOrderRequest,
+) -> Result<Option<OrderAmounts>, (StatusCode, Json<ApiError>)> {
+    if req.price_human.is_none() && req.quantity_human.is_none() {
+        return Ok(None);
+    }
+    if req.price_human.is_some() && req.price != 0.0 {
+        return Err(bad_request(
+            "VALIDATION",
+            "give price or price_human, not both",
+        ));
+    }
+    if req.quantity_human.is_some() && req.quantity != 0.0 {
+        return Err(bad_request(
+            "VALIDATION",
+            "give quantity or quantity_human, not both",
+        ));
+    }
+    req.pool = canonical_pool(router, &req.pool)?;
+    let adapter = router.selector().deepbook_adapter().ok_or_else(|| {
+        bad_request(
+            "INVALID_AMOUNT",
+            "human-readable amounts need the DeepBook adapter",
+        )
+    })?;
+    let price = req
+        .price_human
+        .take()
+        .unwrap_or_else(|| req.price.to_string());
+    let quantity = req
+        .quantity_human
+        .take()
+        .unwrap_or_else(|| req.quantity.to_string());
+    let amounts = adapter
+        .pool_units(&req.pool)
+        .and_then(|units| units.order_amounts(&price, &quantity))
+        .map_err(|e| bad_request("INVALID_AMOUNT", e.to_string()))?;
+    req.price = amounts.price.value();
+    req.quantity = amounts.quantity.value();
+    Ok(Some(amounts))
+}
 
-    let selection = router.select_route(&limit_req).await.map_err(|e| {
-        REQ_ERRORS.with_label_values(&["http", "quote"]).inc();
+/// Select a route for `limit_req`, issue a firm quote for it and attach the
+/// estimates `query` asks for
+async fn quote_order(
+    router: &Router,
+    limit_req: LimitReq,
+    query: &OrderQuery,
+) -> Result<RouteQuoteResponse, (StatusCode, Json<ApiError>)> {
+    let selection = if query.full_evaluation.unwrap_or(false) {
+        router.select_route_exhaustive(&limit_req).await
+    } else {
+        router.select_route(&limit_req).await
+    };
+    let selection = selection.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -396,64 +1912,418 @@ async fn quote_route(
             }),
         )
     })?;
+    let source_timestamp_ms = now_ms();
+    let ticket = router
+        .quotes
+        .issue(limit_req.clone(), source_timestamp_ms)
+        .await;
+
+    let breakers = router.breakers.as_deref();
+    let plan_response = RoutePlanResponse::from_plan(&selection.plan, breakers).await;
+    let mut alternatives = Vec::with_capacity(selection.alternatives.len());
+    for plan in &selection.alternatives {
+        alternatives.push(RoutePlanResponse::from_plan(plan, breakers).await);
+    }
 
-    let plan_response = RoutePlanResponse {
-        route_type: format!("{:?}", selection.plan.route),
-        total_cost: selection.plan.score.total_cost,
-        l2_price: selection.plan.score.l2_price,
-        slippage: selection.plan.score.slippage,
-        gas_cost: selection.plan.score.gas_cost,
-        latency_penalty: selection.plan.score.latency_penalty,
-        risk_factor: selection.plan.score.risk_factor,
-        expected_latency_ms: selection.plan.expected_latency_ms,
-        uses_shared_objects: selection.plan.uses_shared_objects,
-        estimated_gas: selection.plan.estimated_gas,
+    let fill_probability = match query.horizon_ms {
+        Some(horizon_ms) => match router
+            .estimate_fill_probability(&limit_req, horizon_ms)
+            .await
+        {
+            Ok(probability) => probability,
+            Err(err) => {
+                warn!(error = %err, "fill probability estimate failed");
+                None
+            }
+        },
+        None => None,
     };
 
-    let alternatives: Vec<RoutePlanResponse> = selection
-        .alternatives
-        .into_iter()
-        .map(|plan| RoutePlanResponse {
-            route_type: format!("{:?}", plan.route),
-            total_cost: plan.score.total_cost,
-            l2_price: plan.score.l2_price,
-            slippage: plan.score.slippage,
-            gas_cost: plan.score.gas_cost,
-            latency_penalty: plan.score.latency_penalty,
-            risk_factor: plan.score.risk_factor,
-            expected_latency_ms: plan.expected_latency_ms,
-            uses_shared_objects: plan.uses_shared_objects,
-            estimated_gas: plan.estimated_gas,
+    let time_to_fill = match query.horizon_ms {
+        Some(horizon_ms) => router
+            .estimate_time_to_fill(&limit_req, horizon_ms)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(error = %err, "time-to-fill estimate failed");
+                None
+            }),
+        None => None,
+    };
+
+    let vwap = match (
+        query.vwap.unwrap_or(false),
+        router.selector().deepbook_adapter(),
+    ) {
+        (true, Some(adapter)) => adapter
+            .vwap_for_size(&limit_req.pool, limit_req.quantity, limit_req.is_bid)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(error = %err, "VWAP estimate failed");
+                None
+            }),
+        _ => None,
+    };
+
+    let strategies = if query.strategies.unwrap_or(false) {
+        router
+            .quote_strategies(&limit_req, plan_response.expected_latency_ms)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(error = %err, "strategy estimates failed");
+                None
+            })
+    } else {
+        None
+    };
+
+    let (plan_response, alternatives, vwap) =
+        match response_precision(router, &limit_req.pool).await {
+            Some(precision) => (
+                plan_response.rounded(&precision),
+                alternatives
+                    .into_iter()
+                    .map(|plan| plan.rounded(&precision))
+                    .collect(),
+                vwap.map(|sweep| sweep.rounded(&precision)),
+            ),
+            None => (plan_response, alternatives, vwap),
+        };
+
+    Ok(RouteQuoteResponse {
+        quote_id: ticket.quote_id,
+        valid_until_ms: ticket.valid_until_ms,
+        source_timestamp_ms,
+        plan: plan_response,
+        alternatives,
+        fill_probability,
+        time_to_fill,
+        vwap,
+        strategies,
+        amounts: None,
+    })
+}
+
+/// Size points (shares of visible depth) priced when the caller gives none
+const DEFAULT_DEPTH_POINTS: [f64; 4] = [0.1, 0.25, 0.5, 1.0];
+const MAX_DEPTH_POINTS: usize = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct DepthQuery {
+    pub pool: String,
+    pub is_bid: bool,
+    /// Comma-separated shares of visible depth in (0, 1], e.g. "0.1,0.5,1"
+    #[serde(default)]
+    pub points: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepthResponse {
+    pub pool: String,
+    pub is_bid: bool,
+    #[serde(flatten)]
+    pub curve: DepthCurve,
+}
+
+/// Ticks from mid returned by the order book endpoint when none are requested
+pub const DEFAULT_ORDERBOOK_TICKS: u64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct OrderBookQuery {
+    pub pool: String,
+    #[serde(default)]
+    pub ticks: Option<u64>,
+}
+
+/// Level2 depth the selector prices against. Bids are best (highest) first,
+/// asks best (lowest) first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookResponse {
+    pub pool: String,
+    /// Ticks from mid requested on each side
+    pub ticks: u64,
+    /// Midpoint of the best bid and ask; absent when either side is empty
+    pub mid_price: Option<f64>,
+    pub bid_prices: Vec<f64>,
+    pub bid_quantities: Vec<f64>,
+    pub ask_prices: Vec<f64>,
+    pub ask_quantities: Vec<f64>,
+}
+
+impl OrderBookResponse {
+    pub fn from_level2(pool: &str, ticks: u64, level2: &Level2TicksFromMid) -> Self {
+        Self {
+            pool: pool.to_string(),
+            ticks,
+            mid_price: mid_price(level2),
+            bid_prices: level2.bid_prices.clone(),
+            bid_quantities: level2.bid_quantities.clone(),
+            ask_prices: level2.ask_prices.clone(),
+            ask_quantities: level2.ask_quantities.clone(),
+        }
+    }
+
+    /// Round prices and quantities to the pool's tick and lot precision
+    pub fn rounded(mut self, precision: &Precision) -> Self {
+        let prices = |values: &mut Vec<f64>| {
+            values
+                .iter_mut()
+                .for_each(|value| *value = precision.round_price(*value))
+        };
+        let sizes = |values: &mut Vec<f64>| {
+            values
+                .iter_mut()
+                .for_each(|value| *value = precision.round_size(*value))
+        };
+        self.mid_price = self.mid_price.map(|mid| precision.round_price(mid));
+        prices(&mut self.bid_prices);
+        prices(&mut self.ask_prices);
+        sizes(&mut self.bid_quantities);
+        sizes(&mut self.ask_quantities);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VolumeQuery {
+    pub pool: String,
+    /// Window start, inclusive (ms since epoch)
+    pub start_ms: u64,
+    /// Window end, exclusive (ms since epoch)
+    pub end_ms: u64,
+}
+
+/// Historical fill volume and VWAP for a pool over a time window
+async fn history_volume(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<VolumeQuery>,
+) -> Result<Json<PoolVolume>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "history_volume"])
+        .start_timer();
+    if query.start_ms >= query.end_ms {
+        return Err(bad_request("VALIDATION", "start_ms must be before end_ms"));
+    }
+    let pool = canonical_pool(&router, &query.pool)?;
+    let history = router.history.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "HISTORY_UNAVAILABLE".to_string(),
+                message: "no GraphQL endpoint configured".to_string(),
+                details: None,
+            }),
+        )
+    })?;
+    // Events may name the pool by key or object id
+    let same_pool = |event_pool: &str| {
+        router
+            .canonical_pool(event_pool)
+            .is_ok_and(|key| key.eq_ignore_ascii_case(&pool))
+    };
+    let volume = history
+        .get_pool_volume_with(&pool, query.start_ms, query.end_ms, same_pool)
+        .await
+        .map_err(|e| {
+            REQ_ERRORS
+                .with_label_values(&["http", "history_volume"])
+                .inc();
+            internal_error("HISTORY_ERROR", e)
+        })?;
+    Ok(Json(volume))
+}
+
+/// Order book endpoint - level2 depth around the mid price
+async fn orderbook(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderBookQuery>,
+) -> Result<Json<OrderBookResponse>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "orderbook"])
+        .start_timer();
+    let pool = canonical_pool(&router, &query.pool)?;
+    let ticks = query.ticks.unwrap_or(DEFAULT_ORDERBOOK_TICKS);
+    if ticks == 0 || ticks > MAX_EXPORT_TICKS {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("ticks must be between 1 and {MAX_EXPORT_TICKS}"),
+        ));
+    }
+    let adapter = router.selector().deepbook_adapter().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "ORDERBOOK_UNAVAILABLE".to_string(),
+                message: "DeepBook adapter not configured".to_string(),
+                details: None,
+            }),
+        )
+    })?;
+    let level2 = adapter
+        .level2_ticks_from_mid(&pool, ticks)
+        .await
+        .map_err(|e| internal_error("ORDERBOOK_ERROR", e))?;
+    let book = OrderBookResponse::from_level2(&pool, ticks, &level2);
+    Ok(Json(match response_precision(&router, &pool).await {
+        Some(precision) => book.rounded(&precision),
+        None => book,
+    }))
+}
+
+/// Depth endpoint - cost and slippage of taking several shares of the book
+async fn quote_depth(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<DepthQuery>,
+) -> Result<Json<DepthResponse>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "quote_depth"])
+        .start_timer();
+    let pool = canonical_pool(&router, &query.pool)?;
+    let points = match &query.points {
+        Some(raw) => parse_depth_points(raw)?,
+        None => DEFAULT_DEPTH_POINTS.to_vec(),
+    };
+    let adapter = router
+        .selector()
+        .deepbook_adapter()
+        .ok_or_else(|| internal_error("DEPTH_ERROR", "DeepBook adapter not configured"))?;
+    let level2 = adapter
+        .level2_ticks_from_mid(&pool, VWAP_BOOK_TICKS)
+        .await
+        .map_err(|e| internal_error("DEPTH_ERROR", e))?;
+    let curve = depth_curve(&level2, query.is_bid, &points)
+        .ok_or_else(|| bad_request("EMPTY_BOOK", format!("no two-sided book for {pool}")))?;
+    let curve = match response_precision(&router, &pool).await {
+        Some(precision) => curve.rounded(&precision),
+        None => curve,
+    };
+    Ok(Json(DepthResponse {
+        pool,
+        is_bid: query.is_bid,
+        curve,
+    }))
+}
+
+fn parse_depth_points(raw: &str) -> Result<Vec<f64>, (StatusCode, Json<ApiError>)> {
+    let points = raw
+        .split(',')
+        .map(|point| match point.trim().parse::<f64>() {
+            Ok(value) if value > 0.0 && value <= 1.0 => Ok(value),
+            _ => Err(bad_request(
+                "VALIDATION",
+                format!("depth point {point:?} must be a number in (0, 1]"),
+            )),
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
+    if points.len() > MAX_DEPTH_POINTS {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("at most {MAX_DEPTH_POINTS} depth points may be requested"),
+        ));
+    }
+    Ok(points)
+}
 
-    Ok(Json(RouteQuoteResponse {
-        plan: plan_response,
-        alternatives,
-    }))
+#[derive(Debug, Deserialize)]
+pub struct CommitQuoteRequest {
+    pub quote_id: String,
+    /// Execute even when the reference gas price is above the configured ceiling
+    #[serde(default)]
+    pub allow_high_gas: Option<bool>,
+}
+
+/// Commit endpoint - executes a previously quoted order while the quote is valid
+async fn commit_quote(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    Json(req): Json<CommitQuoteRequest>,
+) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "quote_commit"])
+        .start_timer();
+    router.api.ensure_execution_enabled()?;
+    let limit_req = router
+        .quotes
+        .commit(&req.quote_id, now_ms())
+        .await
+        .map_err(|e| order_error("COMMIT_ERROR", e.into()))
+        .inspect_err(|_| {
+            REQ_ERRORS
+                .with_label_values(&["http", "quote_commit"])
+                .inc()
+        })?;
+
+    let execution = with_deadline(&router, &query, async {
+        router
+            .check_gas_ceiling(req.allow_high_gas.unwrap_or(false))
+            .await
+            .map_err(|e| order_error("COMMIT_ERROR", e))?;
+        router
+            .execute_limit_order(&limit_req)
+            .await
+            .map_err(|e| order_error("COMMIT_ERROR", e))
+    })
+    .await
+    .inspect_err(|_| {
+        REQ_ERRORS
+            .with_label_values(&["http", "quote_commit"])
+            .inc()
+    })?;
+
+    let response = order_response(&router, execution, &query).await;
+    info!(
+        digest = %response.digest,
+        quote_id = %req.quote_id,
+        "quote committed"
+    );
+    Ok(Json(response))
 }
 
 /// Execute order endpoint - routes and executes the order
 async fn execute_order(
     State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
     headers: HeaderMap,
-    Json(req): Json<LimitOrderRequest>,
+    Json(mut req): Json<LimitOrderRequest>,
 ) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
     let span = info_span!(
         "http.execute_order",
         pool = %req.pool,
         is_bid = req.is_bid,
         client_order_id = %req.client_order_id,
-        idempotency_key = field::Empty
+        idempotency_key = field::Empty,
+        tags = field::Empty
     );
     let _enter = span.enter();
     let _timer = REQ_LATENCY
         .with_label_values(&["http", "order"])
         .start_timer();
+    router.api.ensure_execution_enabled()?;
+    let amounts = resolve_human_amounts(&router, &mut req)
+        .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "order"]).inc())?;
     if let Err(e) = validate_limit_order_req(&req) {
         REQ_ERRORS.with_label_values(&["http", "order"]).inc();
         return Err((StatusCode::BAD_REQUEST, Json(e)));
     }
+    req.pool = canonical_pool(&router, &req.pool)
+        .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "order"]).inc())?;
+    req.manager = canonical_manager(&router, req.manager.as_deref())
+        .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "order"]).inc())?;
+    let sponsor = req.sponsor.take();
+    let allow_high_gas = req.allow_high_gas.unwrap_or(false);
+    if let Some(alias) = &sponsor {
+        ensure_sponsor(&router, alias)
+            .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "order"]).inc())?;
+    }
+    let session_id = req.session_id.take();
+    if let Some(session) = &session_id {
+        if !router.sessions.is_live(session, Instant::now()).await {
+            REQ_ERRORS.with_label_values(&["http", "order"]).inc();
+            return Err(bad_request(
+                "UNKNOWN_SESSION",
+                format!("session {session} is not open; connect its heartbeat first"),
+            ));
+        }
+    }
     let idem_key = headers
         .get("idempotency-key")
         .and_then(|v| v.to_str().ok())
@@ -461,34 +2331,56 @@ async fn execute_order(
     if let Some(idem) = &idem_key {
         span.record("idempotency_key", idem.as_str());
     }
+    let tags = req.tags.clone();
+    if let Some(tags) = &tags {
+        span.record("tags", format_tags(tags).as_str());
+    }
     if let Some(ref key) = idem_key {
         if let Some(resp) = router.idem_get(key).await {
             return Ok(Json(resp));
         }
     }
-    let limit_req = LimitReq {
-        pool: req.pool,
-        price: req.price,
-        quantity: req.quantity,
-        is_bid: req.is_bid,
-        client_order_id: req.client_order_id,
-        pay_with_deep: req.pay_with_deep.unwrap_or(false),
-        expiration_ms: req.expiration_ms,
-    };
-
-    let execution = router.execute_limit_order(&limit_req).await.map_err(|e| {
-        REQ_ERRORS.with_label_values(&["http", "order"]).inc();
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiError {
-                code: "ORDER_ERROR".to_string(),
-                message: e.to_string(),
-                details: None,
-            }),
-        )
-    })?;
-
-    let response = into_order_response(execution);
+    let limit_req = LimitReq::from(req);
+
+    let execution = with_deadline(&router, &query, async {
+        router
+            .check_gas_ceiling(allow_high_gas)
+            .await
+            .map_err(|e| order_error("ORDER_ERROR", e))?;
+        router
+            .execute_limit_order_with_sponsor(&limit_req, sponsor.as_deref(), idem_key.as_deref())
+            .await
+            .map_err(|e| order_error("ORDER_ERROR", e))
+    })
+    .await
+    .inspect_err(|_| REQ_ERRORS.with_label_values(&["http", "order"]).inc())?;
+
+    let mut response = order_response(&router, execution, &query).await;
+    info!(
+        digest = %response.digest,
+        client_order_id = %limit_req.client_order_id,
+        tags = %tags.as_ref().map(format_tags).unwrap_or_default(),
+        "order executed"
+    );
+    if let Some(session) = &session_id {
+        let orders = response
+            .orders
+            .iter()
+            .map(|handle| SessionOrder {
+                pool: handle.pool.clone(),
+                order_id: handle.order_id,
+                manager: limit_req.manager.clone(),
+            })
+            .collect();
+        // A session that expired while the order was in flight no longer
+        // covers it, so its orders are cancelled now rather than left resting
+        track_or_cancel(&router.sessions, session, orders, |order| {
+            router.cancel_session_order(order)
+        })
+        .await;
+    }
+    response.tags = tags;
+    response.amounts = amounts;
     if let Some(key) = idem_key {
         router.idem_put(key, response.clone()).await;
     }
@@ -497,63 +2389,397 @@ async fn execute_order(
 
 async fn cancel_order(
     State(router): State<Arc<Router>>,
-    Json(req): Json<CancelOrderRequest>,
+    Query(query): Query<OrderQuery>,
+    Json(mut req): Json<CancelOrderRequest>,
+) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
+    if req.pool.trim().is_empty() {
+        return Err(bad_request("VALIDATION", "pool must not be empty"));
+    }
+    req.pool = canonical_pool(&router, &req.pool)?;
+    req.manager = canonical_manager(&router, req.manager.as_deref())?;
+
+    let order_id = resolve_order_id(
+        &router,
+        &req.pool,
+        &req.order_id,
+        &req.digest,
+        &req.client_order_id,
+    )
+    .await?;
+
+    let plan = RoutePlan::cancel_deepbook(
+        req.pool.clone(),
+        order_id,
+        req.manager.clone(),
+        CANCEL_GAS_ESTIMATE,
+    );
+    let execution = with_deadline(&router, &query, async {
+        router
+            .execute_cancel(&plan)
+            .await
+            .map_err(|e| order_error("CANCEL_ERROR", e))
+    })
+    .await?;
+
+    Ok(Json(order_response(&router, execution, &query).await))
+}
+
+/// Cancel a resting order by its raw on-chain order id
+async fn cancel_order_by_id(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    Json(mut req): Json<CancelByIdRequest>,
 ) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
     if req.pool.trim().is_empty() {
         return Err(bad_request("VALIDATION", "pool must not be empty"));
     }
+    req.pool = canonical_pool(&router, &req.pool)?;
+    req.manager = canonical_manager(&router, req.manager.as_deref())?;
+    let order_id = parse_order_id(&req.order_id, "order_id")?;
+
+    // Ownership is only checkable when the open-order set can be loaded
+    if let Some(adapter) = router.selector().deepbook_adapter() {
+        let manager = req
+            .manager
+            .as_deref()
+            .unwrap_or(adapter.balance_managers().default_key());
+        match adapter.open_order_ids_for(&req.pool, manager).await {
+            Ok(open) if !open.contains(&order_id) => {
+                return Err(bad_request(
+                    "ORDER_NOT_OWNED",
+                    format!("order {order_id} is not open on our balance manager"),
+                ));
+            }
+            Ok(_) => {}
+            Err(err) => warn!(
+                pool = %req.pool,
+                error = %err,
+                "could not load open orders; skipping ownership check"
+            ),
+        }
+    }
 
-    let order_id = resolve_order_id(&router, &req.pool, &req.order_id, &req.digest).await?;
+    let plan = RoutePlan::cancel_deepbook(
+        req.pool.clone(),
+        order_id,
+        req.manager.clone(),
+        CANCEL_GAS_ESTIMATE,
+    );
+    let execution = with_deadline(&router, &query, async {
+        router
+            .execute_cancel(&plan)
+            .await
+            .map_err(|e| order_error("CANCEL_ERROR", e))
+    })
+    .await?;
+
+    Ok(Json(order_response(&router, execution, &query).await))
+}
 
-    let plan = RoutePlan::cancel_deepbook(req.pool.clone(), order_id, CANCEL_GAS_ESTIMATE);
-    let execution = router
-        .executor()
-        .execute(&plan)
-        .await
-        .map_err(|e| internal_error("CANCEL_ERROR", e))?;
+/// Sweep the book with a market order, bounded by the caller's slippage limit
+async fn execute_market(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    Json(mut req): Json<MarketOrderRequest>,
+) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
+    validate_market_order_req(&req).map_err(|err| (StatusCode::BAD_REQUEST, Json(err)))?;
+    req.pool = canonical_pool(&router, &req.pool)?;
+    req.manager = canonical_manager(&router, req.manager.as_deref())?;
+    let allow_high_gas = req.allow_high_gas.unwrap_or(false);
+    let market_req = MarketReq::from(req);
+
+    let execution = with_deadline(&router, &query, async {
+        router
+            .check_gas_ceiling(allow_high_gas)
+            .await
+            .map_err(|e| order_error("MARKET_ORDER_ERROR", e))?;
+        router
+            .execute_market_order(&market_req)
+            .await
+            .map_err(|e| order_error("MARKET_ORDER_ERROR", e))
+    })
+    .await?;
+
+    Ok(Json(order_response(&router, execution, &query).await))
+}
 
-    Ok(Json(into_order_response(execution)))
+/// Cancel every open order in a pool with a single transaction
+async fn cancel_all(
+    State(router): State<Arc<Router>>,
+    Query(query): Query<OrderQuery>,
+    Json(mut req): Json<CancelAllRequest>,
+) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
+    if req.pool.trim().is_empty() {
+        return Err(bad_request("VALIDATION", "pool must not be empty"));
+    }
+    req.pool = canonical_pool(&router, &req.pool)?;
+    req.manager = canonical_manager(&router, req.manager.as_deref())?;
+
+    let plan = RoutePlan::cancel_all(req.pool.clone(), req.manager.clone(), CANCEL_GAS_ESTIMATE);
+    let execution = with_deadline(&router, &query, async {
+        router
+            .execute_cancel(&plan)
+            .await
+            .map_err(|e| order_error("CANCEL_ERROR", e))
+    })
+    .await?;
+
+    Ok(Json(order_response(&router, execution, &query).await))
 }
 
 async fn replace_order(
     State(router): State<Arc<Router>>,
-    Json(req): Json<ReplaceOrderRequest>,
+    Query(query): Query<OrderQuery>,
+    Json(mut req): Json<ReplaceOrderRequest>,
 ) -> Result<Json<OrderActionResponse>, (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
+    let amounts = resolve_human_amounts(&router, &mut req.order)?;
     validate_limit_order_req(&req.order).map_err(|err| (StatusCode::BAD_REQUEST, Json(err)))?;
+    req.order.pool = canonical_pool(&router, &req.order.pool)?;
+    req.order.manager = canonical_manager(&router, req.order.manager.as_deref())?;
+    if req.order.sponsor.is_some() {
+        return Err(bad_request(
+            "VALIDATION",
+            "sponsor selection is not supported for replacements",
+        ));
+    }
+    if req.order.session_id.is_some() {
+        return Err(bad_request(
+            "VALIDATION",
+            "session_id is not supported for replacements",
+        ));
+    }
 
     let pool = req.order.pool.clone();
-    let order_id =
-        resolve_order_id(&router, &pool, &req.cancel_order_id, &req.cancel_digest).await?;
+    let order_id = resolve_order_id(
+        &router,
+        &pool,
+        &req.cancel_order_id,
+        &req.cancel_digest,
+        &req.cancel_client_order_id,
+    )
+    .await?;
+
+    let tags = req.order.tags.clone();
+    let allow_high_gas = req.order.allow_high_gas.unwrap_or(false);
+    let mode = req.mode.unwrap_or(router.replace_mode);
+    let limit_req = LimitReq::from(req.order);
+
+    let (execution, cancel_digest) = with_deadline(&router, &query, async {
+        router
+            .check_gas_ceiling(allow_high_gas)
+            .await
+            .map_err(|e| order_error("REPLACE_ERROR", e))?;
+        router
+            .replace_order(order_id, req.cancel_digest.clone(), limit_req, mode)
+            .await
+            .map_err(|e| order_error("REPLACE_ERROR", e))
+    })
+    .await?;
+
+    let mut response = order_response(&router, execution, &query).await;
+    info!(
+        digest = %response.digest,
+        cancel_digest = ?cancel_digest,
+        mode = ?mode,
+        tags = %tags.as_ref().map(format_tags).unwrap_or_default(),
+        "order replaced"
+    );
+    response.tags = tags;
+    response.amounts = amounts;
+    response.cancel_digest = cancel_digest;
+    Ok(Json(response))
+}
 
-    let limit_req = LimitReq {
-        pool,
-        price: req.order.price,
-        quantity: req.order.quantity,
-        is_bid: req.order.is_bid,
-        client_order_id: req.order.client_order_id,
-        pay_with_deep: req.order.pay_with_deep.unwrap_or(false),
-        expiration_ms: req.order.expiration_ms,
-    };
+/// Submit the transactions of a `mode` cancel-replace one after another
+/// through `submit`, returning the execution that placed the replacement and,
+/// when the cancel ran separately, the cancel's digest. A cancel of `order_id`
+/// failing after the replacement was placed is reported with both resting.
+pub async fn submit_replace<F, Fut>(
+    order_id: u128,
+    mode: ReplaceMode,
+    mut submit: F,
+) -> Result<(ExecutionResult, Option<String>)>
+where
+    F: FnMut(Vec<ReplaceCommand>) -> Fut,
+    Fut: Future<Output = Result<ExecutionResult>>,
+{
+    let mut placed: Option<ExecutionResult> = None;
+    let mut separate_cancel = None;
+    for commands in mode.transactions() {
+        let places = commands.contains(&ReplaceCommand::Place);
+        let result = match (submit(commands).await, &placed) {
+            (Err(err), Some(placed)) => {
+                return Err(err.context(format!(
+                    "replacement placed in {} but cancelling order {order_id} failed; both orders are resting",
+                    placed.digest
+                )))
+            }
+            (result, _) => result?,
+        };
+        if places {
+            placed = Some(result);
+        } else {
+            separate_cancel = Some(result.digest);
+        }
+    }
+    let placed = placed.context("replace mode never placed the replacement")?;
+    Ok((placed, separate_cancel))
+}
 
-    let plan = RoutePlan::cancel_replace(
-        req.cancel_digest.clone(),
-        Some(order_id),
-        limit_req,
-        CANCEL_REPLACE_GAS_ESTIMATE,
-    );
+/// Route plan for one transaction of a cancel-replace
+fn replace_plan(
+    commands: &[ReplaceCommand],
+    order_id: u128,
+    cancel_digest: Option<String>,
+    replace: &LimitReq,
+) -> Result<RoutePlan> {
+    Ok(match commands {
+        [ReplaceCommand::Cancel, ReplaceCommand::Place] => RoutePlan::cancel_replace(
+            cancel_digest,
+            Some(order_id),
+            replace.clone(),
+            CANCEL_REPLACE_GAS_ESTIMATE,
+        ),
+        [ReplaceCommand::Place] => {
+            RoutePlan::deepbook_single(replace.clone(), replace.price, 0.0, 0.0, 0, 0, 0.0)
+        }
+        [ReplaceCommand::Cancel] => RoutePlan::cancel_deepbook(
+            replace.pool.clone(),
+            order_id,
+            replace.manager.clone(),
+            CANCEL_GAS_ESTIMATE,
+        ),
+        other => anyhow::bail!("unsupported cancel-replace transaction {other:?}"),
+    })
+}
 
-    let execution = router
-        .executor()
-        .execute(&plan)
+#[derive(Debug, Deserialize)]
+pub struct TwapRequest {
+    /// Parent order; its quantity is split evenly across the slices
+    #[serde(flatten)]
+    pub order: LimitOrderRequest,
+    pub slices: u32,
+    /// Gap between consecutive slice submissions
+    pub interval_ms: u64,
+}
+
+/// Start submitting a parent order as `slices` child orders spaced
+/// `interval_ms` apart. Responds as soon as the TWAP is registered; its
+/// progress is listed under the strategy endpoints until the last slice.
+async fn execute_twap(
+    State(router): State<Arc<Router>>,
+    Json(mut req): Json<TwapRequest>,
+) -> Result<(StatusCode, Json<StrategyProgress>), (StatusCode, Json<ApiError>)> {
+    router.api.ensure_execution_enabled()?;
+    validate_limit_order_req(&req.order).map_err(|err| (StatusCode::BAD_REQUEST, Json(err)))?;
+    if req.slices == 0 || req.slices > MAX_TWAP_SLICES {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("slices must be between 1 and {MAX_TWAP_SLICES}"),
+        ));
+    }
+    let max_duration_ms = MAX_TWAP_DURATION.as_millis() as u64;
+    if u64::from(req.slices).saturating_mul(req.interval_ms) > max_duration_ms {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("slices * interval_ms must not exceed {max_duration_ms}"),
+        ));
+    }
+    if req.order.sponsor.is_some() || req.order.session_id.is_some() {
+        return Err(bad_request(
+            "VALIDATION",
+            "sponsor and session binding are not supported for TWAP orders",
+        ));
+    }
+    req.order.pool = canonical_pool(&router, &req.order.pool)?;
+    req.order.manager = canonical_manager(&router, req.order.manager.as_deref())?;
+    let allow_high_gas = req.order.allow_high_gas.unwrap_or(false);
+    router
+        .check_gas_ceiling(allow_high_gas)
         .await
-        .map_err(|e| internal_error("REPLACE_ERROR", e))?;
+        .map_err(|e| order_error("TWAP_ERROR", e))?;
+
+    let plan = RoutePlan::twap(
+        LimitReq::from(req.order),
+        req.slices,
+        req.interval_ms,
+        TWAP_SLICE_GAS_ESTIMATE,
+    );
+    let started = router
+        .start_twap(&plan, allow_high_gas)
+        .await
+        .map_err(|e| order_error("TWAP_ERROR", e))?;
+    info!(
+        strategy = %started.id,
+        slices = started.total_children,
+        interval_ms = req.interval_ms,
+        "TWAP started"
+    );
+    Ok((StatusCode::ACCEPTED, Json(started)))
+}
+
+/// Outcome of cancelling a strategy
+#[derive(Debug, Serialize)]
+pub struct StrategyCancellation {
+    pub strategy: StrategyProgress,
+    /// Digests of cancels for orders the strategy left on the book
+    pub cancelled_orders: Vec<String>,
+    /// Resting orders that could not be cancelled, as `pool:order_id: error`
+    pub failed_cancels: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrategyListResponse {
+    pub strategies: Vec<StrategyProgress>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelStrategyQuery {
+    /// Also cancel orders the strategy's children left resting on the book
+    #[serde(default)]
+    pub cancel_resting: bool,
+}
+
+/// Active slicing strategies and their progress
+async fn list_strategies(State(router): State<Arc<Router>>) -> Json<StrategyListResponse> {
+    Json(StrategyListResponse {
+        strategies: router.strategies.list().await,
+    })
+}
 
-    Ok(Json(into_order_response(execution)))
+/// Stop a strategy from submitting further children
+async fn cancel_strategy(
+    State(router): State<Arc<Router>>,
+    Path(id): Path<String>,
+    Query(query): Query<CancelStrategyQuery>,
+) -> Result<Json<StrategyCancellation>, (StatusCode, Json<ApiError>)> {
+    if query.cancel_resting {
+        router.api.ensure_execution_enabled()?;
+    }
+    let cancellation = router
+        .cancel_strategy(&id, query.cancel_resting)
+        .await
+        .map_err(|e| order_error("STRATEGY_CANCEL_ERROR", e))?;
+    info!(
+        strategy = %id,
+        submitted = cancellation.strategy.submitted,
+        cancelled_orders = cancellation.cancelled_orders.len(),
+        "strategy cancelled"
+    );
+    Ok(Json(cancellation))
 }
 
 #[derive(Debug, Serialize)]
 pub struct StatsResponse {
+    /// Since process start or the last window reset
     pub execution: ExecutionStats,
+    /// Across restarts (when persisted) and window resets
+    pub lifetime: ExecutionStats,
     pub latency: LatencyStats,
 }
 
@@ -562,14 +2788,165 @@ async fn get_stats(
     State(router): State<Arc<Router>>,
 ) -> Result<Json<StatsResponse>, (StatusCode, Json<ApiError>)> {
     let execution_stats = router.executor().get_stats();
+    let lifetime_stats = router.executor().lifetime_snapshot();
     let latency_stats = router.selector().get_latency_stats().await;
 
     Ok(Json(StatsResponse {
         execution: execution_stats,
+        lifetime: lifetime_stats,
         latency: latency_stats,
     }))
 }
 
+/// Start a new execution statistics window; lifetime totals are kept
+async fn reset_stats_window(
+    State(router): State<Arc<Router>>,
+) -> Result<Json<StatsResponse>, (StatusCode, Json<ApiError>)> {
+    router.executor().reset_window();
+    info!("execution statistics window reset");
+    get_stats(State(router)).await
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookExportRequest {
+    pub pools: Vec<String>,
+    /// Ticks from mid per side (defaults to the configured depth)
+    #[serde(default)]
+    pub ticks: Option<u64>,
+    /// json or csv (defaults to the configured format)
+    #[serde(default)]
+    pub format: Option<SnapshotFormat>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookExportResponse {
+    pub path: String,
+    pub format: SnapshotFormat,
+    pub timestamp_ms: u64,
+    pub pools: Vec<String>,
+}
+
+/// Dump the current level2 book of each requested pool, with pool params and
+/// mid price, to a timestamped file for offline analysis. Disabled unless
+/// configured, only for callers presenting the configured bearer token, and
+/// throttled in both frequency and number of files kept.
+async fn export_book_snapshot(
+    State(router): State<Arc<Router>>,
+    headers: HeaderMap,
+    Json(req): Json<BookExportRequest>,
+) -> Result<Json<BookExportResponse>, (StatusCode, Json<ApiError>)> {
+    let _timer = REQ_LATENCY
+        .with_label_values(&["http", "book_snapshot"])
+        .start_timer();
+    let Some(export) = &router.book_export else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "BOOK_EXPORT_DISABLED".to_string(),
+                message: "book snapshot export is not enabled".to_string(),
+                details: None,
+            }),
+        ));
+    };
+    let settings = &export.settings;
+    if !bearer_token_matches(&headers, &settings.token) {
+        REQ_ERRORS
+            .with_label_values(&["http", "book_snapshot"])
+            .inc();
+        warn!("rejected book snapshot request without a valid token");
+        return Err(unauthorized());
+    }
+    if req.pools.is_empty() || req.pools.len() > MAX_EXPORT_POOLS {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("pools must list between 1 and {MAX_EXPORT_POOLS} pools"),
+        ));
+    }
+    let ticks = req.ticks.unwrap_or(settings.ticks);
+    if ticks == 0 || ticks > MAX_EXPORT_TICKS {
+        return Err(bad_request(
+            "VALIDATION",
+            format!("ticks must be between 1 and {MAX_EXPORT_TICKS}"),
+        ));
+    }
+    let format = req.format.unwrap_or(settings.format);
+    let pools = req
+        .pools
+        .iter()
+        .map(|pool| canonical_pool(&router, pool))
+        .collect::<Result<Vec<_>, _>>()?;
+    let adapter = router
+        .selector()
+        .deepbook_adapter()
+        .ok_or_else(|| internal_error("BOOK_EXPORT_ERROR", "DeepBook adapter not configured"))?;
+
+    if let Err(retry_after) = export.throttle.try_acquire(Instant::now()) {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ApiError {
+                code: "BOOK_EXPORT_THROTTLED".to_string(),
+                message: format!(
+                    "book snapshots are limited to one every {} s",
+                    settings.min_interval.as_secs()
+                ),
+                details: Some(serde_json::json!({
+                    "retry_after_ms": retry_after.as_millis() as u64,
+                })),
+            }),
+        ));
+    }
+    let existing = count_snapshots(&settings.dir)
+        .await
+        .map_err(|e| internal_error("BOOK_EXPORT_ERROR", format!("{e:#}")))?;
+    if existing >= settings.max_files {
+        warn!(
+            dir = %settings.dir.display(),
+            files = existing,
+            "book snapshot directory is full"
+        );
+        return Err((
+            StatusCode::INSUFFICIENT_STORAGE,
+            Json(ApiError {
+                code: "BOOK_EXPORT_FULL".to_string(),
+                message: format!(
+                    "export directory already holds {existing} snapshots (limit {})",
+                    settings.max_files
+                ),
+                details: None,
+            }),
+        ));
+    }
+
+    let timestamp_ms = now_ms();
+    let mut snapshots = Vec::with_capacity(pools.len());
+    for pool in &pools {
+        let level2 = adapter
+            .level2_ticks_from_mid(pool, ticks)
+            .await
+            .map_err(|e| internal_error("BOOK_EXPORT_ERROR", e))?;
+        let mid = adapter.mid_price(pool).await.ok();
+        let params = adapter.pool_params(pool).await.ok();
+        snapshots.push(BookSnapshot::from_level2(
+            pool,
+            timestamp_ms,
+            ticks,
+            mid,
+            params.as_ref(),
+            &level2,
+        ));
+    }
+    let path = write_snapshots(&settings.dir, &snapshots, format, timestamp_ms)
+        .await
+        .map_err(|e| internal_error("BOOK_EXPORT_ERROR", format!("{e:#}")))?;
+    info!(path = %path.display(), pools = pools.len(), ticks, "exported book snapshot");
+    Ok(Json(BookExportResponse {
+        path: path.display().to_string(),
+        format,
+        timestamp_ms,
+        pools,
+    }))
+}
+
 /// Get latency statistics
 async fn get_latency_stats(
     State(router): State<Arc<Router>>,
@@ -605,6 +2982,96 @@ async fn update_latency(
     })))
 }
 
+/// Run a handler body under the client's `timeout_ms`, capped to the server maximum.
+/// Work that expires before submitting a transaction is dropped, releasing any
+/// admission permit it held. Once submitted it runs to completion instead, so
+/// the digest, idempotency entry and inventory update are never lost to a 504.
+async fn with_deadline<T>(
+    router: &Router,
+    query: &OrderQuery,
+    fut: impl Future<Output = Result<T, (StatusCode, Json<ApiError>)>>,
+) -> Result<T, (StatusCode, Json<ApiError>)> {
+    let Some(requested_ms) = query.timeout_ms else {
+        return fut.await;
+    };
+    let deadline = Duration::from_millis(requested_ms).min(router.api.max_request_timeout);
+    match run_until_submitted(deadline, fut).await {
+        Some(result) => result,
+        None => Err((
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(ApiError {
+                code: "TIMEOUT".to_string(),
+                message: format!("operation exceeded {} ms deadline", deadline.as_millis()),
+                details: None,
+            }),
+        )),
+    }
+}
+
+/// Build the order response, attaching raw effects when the caller asked for them
+async fn order_response(
+    router: &Router,
+    execution: ExecutionResult,
+    query: &OrderQuery,
+) -> OrderActionResponse {
+    respond_with_raw_effects(execution, query.include_raw_effects(), |digest| {
+        fetch_raw_effects(router, digest)
+    })
+    .await
+}
+
+/// Order response for `execution`, carrying base64 raw effects only when
+/// `include_raw_effects` is set. Effects embedded in the executed transaction
+/// are used as is; otherwise `fetch` looks them up by digest, except for dry
+/// runs, which never reached the chain.
+pub async fn respond_with_raw_effects<F, Fut>(
+    execution: ExecutionResult,
+    include_raw_effects: bool,
+    fetch: F,
+) -> OrderActionResponse
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Option<Vec<u8>>>,
+{
+    let raw_effects = if !include_raw_effects {
+        None
+    } else if let Some(bytes) = embedded_raw_effects(&execution) {
+        Some(B64.encode(bytes))
+    } else if execution.dry_run {
+        None
+    } else {
+        fetch(execution.digest.clone())
+            .await
+            .map(|bytes| B64.encode(bytes))
+    };
+    let mut response = into_order_response(execution);
+    response.raw_effects = raw_effects;
+    response
+}
+
+/// Raw effects bytes carried by the executed transaction itself
+fn embedded_raw_effects(execution: &ExecutionResult) -> Option<&Vec<u8>> {
+    execution
+        .executed
+        .effects
+        .as_ref()
+        .and_then(|effects| effects.bcs.as_ref())
+        .and_then(|bcs| bcs.value.as_ref())
+        .filter(|bytes| !bytes.is_empty())
+}
+
+/// Raw effects for `digest` from the read API
+async fn fetch_raw_effects(router: &Router, digest: String) -> Option<Vec<u8>> {
+    let adapter = router.selector().deepbook_adapter()?;
+    match adapter.raw_effects_for_digest(&digest).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            warn!(digest = %digest, error = %err, "failed to fetch raw effects");
+            None
+        }
+    }
+}
+
 fn into_order_response(execution: ExecutionResult) -> OrderActionResponse {
     let ExecutionResult {
         digest,
@@ -613,11 +3080,13 @@ fn into_order_response(execution: ExecutionResult) -> OrderActionResponse {
         checkpoint_time_ms,
         accounting,
         orders,
+        dry_run,
     } = execution;
 
     let accounting = if accounting.deepbook.is_empty()
         && accounting.gas_used.is_none()
         && accounting.sponsor_gas_used.is_none()
+        && accounting.legs.is_empty()
     {
         None
     } else {
@@ -630,6 +3099,11 @@ fn into_order_response(execution: ExecutionResult) -> OrderActionResponse {
         checkpoint_time_ms,
         accounting,
         orders,
+        raw_effects: None,
+        tags: None,
+        dry_run,
+        cancel_digest: None,
+        amounts: None,
     }
 }
 
@@ -638,10 +3112,22 @@ async fn resolve_order_id(
     pool: &str,
     order_id_field: &Option<String>,
     digest_field: &Option<String>,
+    client_order_id_field: &Option<String>,
 ) -> Result<u128, (StatusCode, Json<ApiError>)> {
     if let Some(order_id) = parse_order_id_field(order_id_field, "order_id")? {
         return Ok(order_id);
     }
+    let client_order_id = client_order_id_field
+        .as_deref()
+        .map(|value| {
+            value.trim().parse::<u64>().map_err(|_| {
+                bad_request(
+                    "VALIDATION",
+                    "client_order_id must be a decimal-encoded u64 string",
+                )
+            })
+        })
+        .transpose()?;
 
     let digest = match digest_field.as_ref() {
         Some(d) if !d.trim().is_empty() => d.trim(),
@@ -657,6 +3143,7 @@ async fn resolve_order_id(
         .executor()
         .order_handle_from_digest(digest, pool)
         .await
+        .filter(|handle| client_order_id.is_none() || handle.client_order_id == client_order_id)
     {
         return Ok(handle.order_id);
     }
@@ -667,9 +3154,14 @@ async fn resolve_order_id(
         .ok_or_else(|| internal_error("NOT_AVAILABLE", "DeepBook adapter not configured"))?;
 
     let order_id = adapter
-        .get_order_id_from_digest(digest, pool)
+        .get_order_id_from_digest(digest, pool, client_order_id)
         .await
-        .map_err(|e| internal_error("ORDER_LOOKUP", e))?
+        .map_err(|e| match e.downcast_ref::<AggrError>() {
+            Some(err @ AggrError::AmbiguousOrder(_)) => {
+                bad_request("AMBIGUOUS_ORDER", err.to_string())
+            }
+            _ => internal_error("ORDER_LOOKUP", e),
+        })?
         .ok_or_else(|| {
             bad_request(
                 "ORDER_LOOKUP",
@@ -684,7 +3176,7 @@ async fn resolve_order_id(
             OrderHandle {
                 pool: pool.to_string(),
                 order_id,
-                client_order_id: None,
+                client_order_id,
             },
         )
         .await;
@@ -696,19 +3188,63 @@ fn parse_order_id_field(
     field: &Option<String>,
     name: &str,
 ) -> Result<Option<u128>, (StatusCode, Json<ApiError>)> {
-    if let Some(value) = field {
-        let parsed = value.trim().parse::<u128>().map_err(|_| {
-            bad_request(
-                "VALIDATION",
-                format!("{name} must be a decimal-encoded u128 string"),
-            )
-        })?;
-        Ok(Some(parsed))
-    } else {
-        Ok(None)
+    field
+        .as_deref()
+        .map(|value| parse_order_id(value, name))
+        .transpose()
+}
+
+fn parse_order_id(value: &str, name: &str) -> Result<u128, (StatusCode, Json<ApiError>)> {
+    value.trim().parse::<u128>().map_err(|_| {
+        bad_request(
+            "VALIDATION",
+            format!("{name} must be a decimal-encoded u128 string"),
+        )
+    })
+}
+
+/// Display precision for `pool`, when its tick and lot sizes are known
+async fn response_precision(router: &Router, pool: &str) -> Option<Precision> {
+    let adapter = router.selector().deepbook_adapter()?;
+    match adapter.pool_params(pool).await {
+        Ok(params) => Some(Precision::from_pool_params(&params)),
+        Err(err) => {
+            warn!(pool, error = %err, "pool params unavailable; returning full precision");
+            None
+        }
     }
 }
 
+fn canonical_pool(router: &Router, pool: &str) -> Result<String, (StatusCode, Json<ApiError>)> {
+    router
+        .canonical_pool(pool)
+        .map_err(|e| bad_request("UNKNOWN_POOL", e.to_string()))
+}
+
+/// Resolve an optional BalanceManager key; unset stays unset (the default manager)
+fn canonical_manager(
+    router: &Router,
+    manager: Option<&str>,
+) -> Result<Option<String>, (StatusCode, Json<ApiError>)> {
+    let (Some(manager), Some(adapter)) = (manager, router.selector().deepbook_adapter()) else {
+        return Ok(manager.map(str::to_owned));
+    };
+    adapter
+        .balance_managers()
+        .resolve(Some(manager))
+        .map(Some)
+        .map_err(|e| bad_request("UNKNOWN_MANAGER", e.to_string()))
+}
+
+fn ensure_sponsor(router: &Router, alias: &str) -> Result<(), (StatusCode, Json<ApiError>)> {
+    router
+        .executor()
+        .sponsors()
+        .get(alias)
+        .map(|_| ())
+        .map_err(|e| bad_request("UNKNOWN_SPONSOR", e.to_string()))
+}
+
 fn bad_request(code: &str, message: impl Into<String>) -> (StatusCode, Json<ApiError>) {
     (
         StatusCode::BAD_REQUEST,
@@ -720,6 +3256,164 @@ fn bad_request(code: &str, message: impl Into<String>) -> (StatusCode, Json<ApiE
     )
 }
 
+/// Map an execution error to an API error, surfacing rejections as client errors
+pub fn order_error(code: &str, err: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    match err.downcast_ref::<AggrError>() {
+        Some(AggrError::RiskCheck(reason)) => bad_request("RISK_REJECTED", reason.clone()),
+        Some(err @ AggrError::UnknownSponsor(_)) => bad_request("UNKNOWN_SPONSOR", err.to_string()),
+        Some(err @ AggrError::UnknownManager(_)) => bad_request("UNKNOWN_MANAGER", err.to_string()),
+        Some(err @ AggrError::NoOpenOrders(_)) => bad_request("NO_OPEN_ORDERS", err.to_string()),
+        Some(err @ AggrError::InvalidAmount(_)) => bad_request("INVALID_AMOUNT", err.to_string()),
+        Some(err @ AggrError::MissingTradeCap { manager, .. }) => (
+            StatusCode::FORBIDDEN,
+            Json(ApiError {
+                code: "MISSING_TRADE_CAP".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "manager": manager })),
+            }),
+        ),
+        Some(
+            err @ AggrError::Quantization {
+                constraint,
+                value,
+                limit,
+            },
+        ) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: constraint.code().to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({
+                    "value": value,
+                    "constraint": constraint.to_string(),
+                    "limit": limit,
+                })),
+            }),
+        ),
+        Some(err @ AggrError::PostOnlyWouldCross { best, .. }) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "POST_ONLY_WOULD_CROSS".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "best_opposite_price": best })),
+            }),
+        ),
+        Some(err @ AggrError::UnknownQuote(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "UNKNOWN_QUOTE".to_string(),
+                message: err.to_string(),
+                details: None,
+            }),
+        ),
+        Some(err @ AggrError::UnknownStrategy(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "UNKNOWN_STRATEGY".to_string(),
+                message: err.to_string(),
+                details: None,
+            }),
+        ),
+        Some(err @ AggrError::QuoteExpired { valid_until_ms }) => (
+            StatusCode::GONE,
+            Json(ApiError {
+                code: "QUOTE_EXPIRED".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "valid_until_ms": valid_until_ms })),
+            }),
+        ),
+        Some(err @ AggrError::GasPriceTooHigh { price, ceiling }) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "GAS_PRICE_TOO_HIGH".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({
+                    "gas_price": price,
+                    "max_gas_price": ceiling,
+                })),
+            }),
+        ),
+        Some(err @ AggrError::Maintenance { until_ms }) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "MAINTENANCE".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "until_ms": until_ms })),
+            }),
+        ),
+        Some(
+            err @ AggrError::Overloaded {
+                limit,
+                window_ms,
+                retry_after_ms,
+            },
+        ) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ApiError {
+                code: "OVERLOADED".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({
+                    "limit": limit,
+                    "window_ms": window_ms,
+                    "retry_after_ms": retry_after_ms,
+                })),
+            }),
+        ),
+        Some(err @ AggrError::PoolNotTradable { pool, status }) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "POOL_NOT_TRADABLE".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({ "pool": pool, "status": status })),
+            }),
+        ),
+        Some(
+            err @ AggrError::SlippageExceeded {
+                expected_bps,
+                limit_bps,
+            },
+        ) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                code: "SLIPPAGE_EXCEEDED".to_string(),
+                message: err.to_string(),
+                details: Some(serde_json::json!({
+                    "expected_bps": expected_bps,
+                    "max_slippage_bps": limit_bps,
+                })),
+            }),
+        ),
+        Some(err @ AggrError::SimulationRejected(_)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                code: "SIMULATION_REJECTED".to_string(),
+                message: err.to_string(),
+                details: None,
+            }),
+        ),
+        Some(err @ AggrError::NoHealthyValidators { .. }) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                code: "NO_HEALTHY_VALIDATORS".to_string(),
+                message: err.to_string(),
+                details: None,
+            }),
+        ),
+        _ => internal_error(code, err),
+    }
+}
+
+fn unauthorized() -> (StatusCode, Json<ApiError>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ApiError {
+            code: "UNAUTHORIZED".to_string(),
+            message: "a valid bearer token is required".to_string(),
+            details: None,
+        }),
+    )
+}
+
 fn internal_error(code: &str, err: impl std::fmt::Display) -> (StatusCode, Json<ApiError>) {
     (
         StatusCode::INTERNAL_SERVER_ERROR,
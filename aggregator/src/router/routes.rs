@@ -4,18 +4,21 @@
 //
 // Numan Thabit 2025 Nov
 
-use crate::venues::adapter::LimitReq;
+use crate::venues::adapter::{LimitReq, MarketReq};
+use crate::venues::amm::AmmSwapReq;
+use serde::{Deserialize, Serialize};
 
 /// Represents a route strategy that can be compiled into a PTB
 #[derive(Debug, Clone)]
 pub enum Route {
     /// Single-leg order on DeepBook
     DeepBookSingle(LimitReq),
+    /// Market order sweeping the DeepBook book
+    MarketOrder(MarketReq),
     /// Multi-venue split route (e.g., DeepBook + AMM)
     MultiVenueSplit {
         deepbook: Option<LimitReq>,
-        // Future: AMM routes will be added here when AMM adapters are implemented
-        // amm_swap: Option<AmmSwapReq>,
+        amm_swap: Option<AmmSwapReq>,
     },
     /// Cancel and replace chain
     CancelReplace {
@@ -24,13 +27,107 @@ pub enum Route {
         replace: LimitReq,
     },
     /// Cancel an existing DeepBook order without placing a replacement
-    CancelDeepBook { pool: String, order_id: u128 },
+    CancelDeepBook {
+        pool: String,
+        order_id: u128,
+        /// BalanceManager holding the order; the default when unset
+        manager: Option<String>,
+    },
+    /// Cancel every open order the BalanceManager has in a pool in one PTB
+    CancelAll {
+        pool: String,
+        /// BalanceManager holding the orders; the default when unset
+        manager: Option<String>,
+    },
+    /// Parent order split into `slices` children submitted `interval_ms` apart.
+    /// Scheduled by `Router::start_twap`; never compiled into a single PTB.
+    Twap {
+        base: LimitReq,
+        slices: u32,
+        interval_ms: u64,
+    },
     /// Flash-loan backed arbitrage (future)
     FlashLoanArb {
         // TODO: Define flash loan route structure
     },
 }
 
+impl Route {
+    /// Venue the route executes on, as used for circuit breaker classes
+    pub fn venue(&self) -> &'static str {
+        match self {
+            Route::DeepBookSingle(_)
+            | Route::MarketOrder(_)
+            | Route::CancelReplace { .. }
+            | Route::CancelDeepBook { .. }
+            | Route::CancelAll { .. }
+            | Route::Twap { .. } => "deepbook",
+            Route::MultiVenueSplit {
+                deepbook: None,
+                amm_swap: Some(_),
+            } => "cetus",
+            Route::MultiVenueSplit { .. } => "multi_venue",
+            Route::FlashLoanArb { .. } => "flash_loan",
+        }
+    }
+
+    /// Pool the route trades in, if it is tied to one
+    pub fn pool(&self) -> Option<&str> {
+        match self {
+            Route::DeepBookSingle(req) => Some(&req.pool),
+            Route::MarketOrder(req) => Some(&req.pool),
+            Route::MultiVenueSplit { deepbook, amm_swap } => deepbook
+                .as_ref()
+                .map(|req| req.pool.as_str())
+                .or_else(|| amm_swap.as_ref().map(|swap| swap.pool.as_str())),
+            Route::CancelReplace { replace, .. } => Some(&replace.pool),
+            Route::Twap { base, .. } => Some(&base.pool),
+            Route::CancelDeepBook { pool, .. } | Route::CancelAll { pool, .. } => Some(pool),
+            Route::FlashLoanArb { .. } => None,
+        }
+    }
+}
+
+/// How a replacement is sequenced against the cancel of the order it replaces.
+///
+/// `Atomic` compiles cancel and place into one PTB: there is never a moment
+/// with no order or with both orders resting, but a failing placement also
+/// rolls back the cancel. `PlaceThenCancel` submits the replacement on its own
+/// and builds the cancel only once the placement has executed, so the book is
+/// never left without an order; in exchange both orders rest briefly (double
+/// exposure that must be funded), it costs two transactions, and if the cancel
+/// fails the old order stays live alongside the new one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceMode {
+    #[default]
+    Atomic,
+    PlaceThenCancel,
+}
+
+/// One command of a cancel-replace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceCommand {
+    /// Cancel the order being replaced
+    Cancel,
+    /// Place the replacement
+    Place,
+}
+
+impl ReplaceMode {
+    /// Commands of each transaction a replacement compiles to, in submission
+    /// order. A transaction is only built after the previous one executed.
+    pub fn transactions(&self) -> Vec<Vec<ReplaceCommand>> {
+        match self {
+            ReplaceMode::Atomic => vec![vec![ReplaceCommand::Cancel, ReplaceCommand::Place]],
+            ReplaceMode::PlaceThenCancel => {
+                vec![vec![ReplaceCommand::Place], vec![ReplaceCommand::Cancel]]
+            }
+        }
+    }
+}
+
 /// Route plan with execution metadata
 #[derive(Debug, Clone)]
 pub struct RoutePlan {
@@ -42,6 +139,11 @@ pub struct RoutePlan {
     pub uses_shared_objects: bool,
     /// Estimated gas cost
     pub estimated_gas: u64,
+    /// Average price the order is expected to fill at, when the book was walked
+    pub expected_fill_price: Option<f64>,
+    /// `(price, quantity)` expected from each visible level, best first; empty
+    /// when the book was not walked
+    pub fill_levels: Vec<(f64, f64)>,
 }
 
 /// Route scoring based on price-of-execution
@@ -81,6 +183,25 @@ impl RouteScore {
         }
     }
 
+    /// Each component with its share of `total_cost`, for API explanations
+    pub fn breakdown(&self) -> ScoreBreakdown {
+        let component = |value: f64| ScoreComponent {
+            value,
+            share_pct: if self.total_cost == 0.0 {
+                0.0
+            } else {
+                value / self.total_cost * 100.0
+            },
+        };
+        ScoreBreakdown {
+            l2_price: component(self.l2_price),
+            slippage: component(self.slippage),
+            gas_cost: component(self.gas_cost),
+            latency_penalty: component(self.latency_penalty),
+            risk_factor: component(self.risk_factor),
+        }
+    }
+
     /// Calculate latency penalty based on route type and expected latency
     pub fn latency_penalty_for_route(
         uses_shared_objects: bool,
@@ -100,6 +221,35 @@ impl RouteScore {
     }
 }
 
+/// A score component in quote units and as a percentage of total cost
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScoreComponent {
+    pub value: f64,
+    pub share_pct: f64,
+}
+
+/// `RouteScore` split into its additive components
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    pub l2_price: ScoreComponent,
+    pub slippage: ScoreComponent,
+    pub gas_cost: ScoreComponent,
+    pub latency_penalty: ScoreComponent,
+    pub risk_factor: ScoreComponent,
+}
+
+impl ScoreBreakdown {
+    pub fn components(&self) -> [(&'static str, ScoreComponent); 5] {
+        [
+            ("l2_price", self.l2_price),
+            ("slippage", self.slippage),
+            ("gas_cost", self.gas_cost),
+            ("latency_penalty", self.latency_penalty),
+            ("risk_factor", self.risk_factor),
+        ]
+    }
+}
+
 impl RoutePlan {
     /// Create a route plan for a DeepBook single-leg order
     pub fn deepbook_single(
@@ -127,9 +277,88 @@ impl RoutePlan {
             expected_latency_ms,
             uses_shared_objects,
             estimated_gas: 10_000_000, // Default estimate, should be refined
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
         }
     }
 
+    /// Create a route plan for a DeepBook market order. `slippage` is the
+    /// expected cost of the sweep versus mid.
+    pub fn market_order(
+        req: MarketReq,
+        l2_price: f64,
+        slippage: f64,
+        gas_cost: f64,
+        expected_latency_ms: u64,
+        base_latency_ms: u64,
+        risk_factor: f64,
+    ) -> Self {
+        let uses_shared_objects = true;
+        let latency_penalty = RouteScore::latency_penalty_for_route(
+            uses_shared_objects,
+            expected_latency_ms,
+            base_latency_ms,
+        );
+
+        let score = RouteScore::new(l2_price, slippage, gas_cost, latency_penalty, risk_factor);
+
+        Self {
+            route: Route::MarketOrder(req),
+            score,
+            expected_latency_ms,
+            uses_shared_objects,
+            estimated_gas: 10_000_000,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
+        }
+    }
+
+    /// Create a route plan for a swap on a Cetus pool. Swaps touch the
+    /// shared pool object, so they go through consensus.
+    pub fn cetus_swap(
+        swap: AmmSwapReq,
+        l2_price: f64,
+        slippage: f64,
+        gas_cost: f64,
+        expected_latency_ms: u64,
+        base_latency_ms: u64,
+        risk_factor: f64,
+    ) -> Self {
+        let uses_shared_objects = true;
+        let latency_penalty = RouteScore::latency_penalty_for_route(
+            uses_shared_objects,
+            expected_latency_ms,
+            base_latency_ms,
+        );
+
+        let score = RouteScore::new(l2_price, slippage, gas_cost, latency_penalty, risk_factor);
+
+        Self {
+            route: Route::MultiVenueSplit {
+                deepbook: None,
+                amm_swap: Some(swap),
+            },
+            score,
+            expected_latency_ms,
+            uses_shared_objects,
+            estimated_gas: 5_000_000,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
+        }
+    }
+
+    /// Record the average fill price the book walk expects
+    pub fn with_expected_fill_price(mut self, price: f64) -> Self {
+        self.expected_fill_price = Some(price);
+        self
+    }
+
+    /// Per-level fills from walking the book
+    pub fn with_fill_levels(mut self, fills: Vec<(f64, f64)>) -> Self {
+        self.fill_levels = fills;
+        self
+    }
+
     /// Compare route plans - lower total_cost is better
     pub fn compare(&self, other: &Self) -> std::cmp::Ordering {
         self.score
@@ -138,13 +367,53 @@ impl RoutePlan {
             .unwrap_or(std::cmp::Ordering::Equal)
     }
 
-    pub fn cancel_deepbook(pool: String, order_id: u128, estimated_gas: u64) -> Self {
+    pub fn cancel_deepbook(
+        pool: String,
+        order_id: u128,
+        manager: Option<String>,
+        estimated_gas: u64,
+    ) -> Self {
+        Self {
+            route: Route::CancelDeepBook {
+                pool,
+                order_id,
+                manager,
+            },
+            score: RouteScore::new(0.0, 0.0, 0.0, 0.0, 0.0),
+            expected_latency_ms: 2_000,
+            uses_shared_objects: true,
+            estimated_gas,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
+        }
+    }
+
+    pub fn cancel_all(pool: String, manager: Option<String>, estimated_gas: u64) -> Self {
         Self {
-            route: Route::CancelDeepBook { pool, order_id },
+            route: Route::CancelAll { pool, manager },
             score: RouteScore::new(0.0, 0.0, 0.0, 0.0, 0.0),
             expected_latency_ms: 2_000,
             uses_shared_objects: true,
             estimated_gas,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
+        }
+    }
+
+    /// TWAP of `base`; gas is estimated per slice
+    pub fn twap(base: LimitReq, slices: u32, interval_ms: u64, estimated_gas: u64) -> Self {
+        Self {
+            route: Route::Twap {
+                base,
+                slices,
+                interval_ms,
+            },
+            score: RouteScore::new(0.0, 0.0, 0.0, 0.0, 0.0),
+            expected_latency_ms: interval_ms.saturating_mul(slices.saturating_sub(1) as u64),
+            uses_shared_objects: true,
+            estimated_gas,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
         }
     }
 
@@ -164,6 +433,8 @@ impl RoutePlan {
             expected_latency_ms: 3_000,
             uses_shared_objects: true,
             estimated_gas,
+            expected_fill_price: None,
+            fill_levels: Vec::new(),
         }
     }
 }
@@ -4,20 +4,181 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::metrics::VENUE_PANICS;
 use crate::router::routes::{RoutePlan, RouteSelection};
-use crate::venues::adapter::{DeepBookAdapter, LimitReq};
-use anyhow::{Context, Result};
-use std::collections::VecDeque;
+use crate::venues::adapter::{DeepBookAdapter, LimitReq, MarketReq, VWAP_BOOK_TICKS};
+use crate::venues::amm::{AmmAdapter, AmmSwapReq, CetusAdapter};
+use crate::venues::book::{
+    estimate_slippage, expected_fill_fraction, partial_fill_cost, slippage_bps,
+    DEFAULT_IMPACT_COEFFICIENT, DEFAULT_PARTIAL_FILL_PENALTY,
+};
+use anyhow::{anyhow, Context, Result};
+use futures::future::{join_all, BoxFuture};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
 };
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
+
+/// A named venue's pending route evaluation
+pub type VenueEvaluation<'a> = (&'static str, BoxFuture<'a, Result<RoutePlan>>);
+
+/// Run one venue's evaluation, turning a panic inside it into an error for
+/// that venue instead of unwinding through the request
+pub async fn evaluate_venue<F>(venue: &str, evaluation: F) -> Result<RoutePlan>
+where
+    F: Future<Output = Result<RoutePlan>>,
+{
+    match AssertUnwindSafe(evaluation).catch_unwind().await {
+        Ok(result) => result,
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|msg| msg.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "non-string panic payload".to_string());
+            VENUE_PANICS.with_label_values(&[venue]).inc();
+            error!(venue, panic = %message, "venue route evaluation panicked");
+            Err(anyhow!("{venue} route evaluation panicked: {message}"))
+        }
+    }
+}
+
+/// Evaluate venues concurrently, keeping every plan that succeeded. Errors
+/// and panics only drop the affected venue.
+pub async fn gather_alternatives(evaluations: Vec<VenueEvaluation<'_>>) -> Vec<RoutePlan> {
+    let results =
+        join_all(
+            evaluations
+                .into_iter()
+                .map(|(venue, evaluation)| async move {
+                    (venue, evaluate_venue(venue, evaluation).await)
+                }),
+        )
+        .await;
+    results
+        .into_iter()
+        .filter_map(|(venue, result)| match result {
+            Ok(plan) => {
+                debug!(
+                    venue,
+                    total_cost = plan.score.total_cost,
+                    latency_ms = plan.expected_latency_ms,
+                    "evaluated route"
+                );
+                Some(plan)
+            }
+            Err(e) => {
+                debug!(venue, error = %e, "failed to evaluate route");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Which venues a selection evaluates, in what order, and when it may stop
+/// early. The default evaluates every venue concurrently.
+#[derive(Debug, Clone, Default)]
+pub struct EvaluationPolicy {
+    /// Venues to evaluate per pool, in order; unlisted pools evaluate every venue
+    pub venue_order: HashMap<String, Vec<String>>,
+    /// Stop once this many viable routes have been found
+    pub max_alternatives: Option<usize>,
+    /// Stop at the first route whose costs beyond the price are within this
+    /// many basis points of the order notional
+    pub good_enough_bps: Option<f64>,
+}
+
+impl EvaluationPolicy {
+    /// Whether every venue is evaluated, concurrently, with no early exit
+    pub fn is_exhaustive(&self) -> bool {
+        self.venue_order.is_empty()
+            && self.max_alternatives.is_none()
+            && self.good_enough_bps.is_none()
+    }
+
+    /// Keep only the venues configured for `pool`, in configured order. Pool
+    /// keys match case-insensitively, as environment config lowercases them.
+    pub fn order_for<'a>(
+        &self,
+        pool: &str,
+        mut evaluations: Vec<VenueEvaluation<'a>>,
+    ) -> Vec<VenueEvaluation<'a>> {
+        let order = self
+            .venue_order
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(pool))
+            .map(|(_, order)| order);
+        let Some(order) = order else {
+            return evaluations;
+        };
+        let mut ordered = Vec::with_capacity(order.len());
+        for venue in order {
+            if let Some(i) = evaluations.iter().position(|(name, _)| name == venue) {
+                ordered.push(evaluations.swap_remove(i));
+            }
+        }
+        ordered
+    }
+
+    /// Whether `plan` is cheap enough to stop looking for a better route
+    pub fn is_good_enough(&self, plan: &RoutePlan, notional: f64) -> bool {
+        let Some(threshold) = self.good_enough_bps else {
+            return false;
+        };
+        if !(notional.is_finite() && notional > 0.0) {
+            return false;
+        }
+        let overhead = plan.score.total_cost - plan.score.l2_price;
+        overhead / notional * 10_000.0 <= threshold
+    }
+}
+
+/// Evaluate venues one at a time in the given order, stopping at the first
+/// good-enough route or once `policy.max_alternatives` routes are viable.
+/// Venues after the stop are never polled.
+pub async fn gather_until_good_enough(
+    evaluations: Vec<VenueEvaluation<'_>>,
+    policy: &EvaluationPolicy,
+    notional: f64,
+) -> Vec<RoutePlan> {
+    let mut plans = Vec::new();
+    for (venue, evaluation) in evaluations {
+        match evaluate_venue(venue, evaluation).await {
+            Ok(plan) => {
+                let good_enough = policy.is_good_enough(&plan, notional);
+                plans.push(plan);
+                if good_enough {
+                    debug!(
+                        venue,
+                        "route within good-enough threshold; skipping remaining venues"
+                    );
+                    break;
+                }
+                if policy
+                    .max_alternatives
+                    .is_some_and(|max| plans.len() >= max)
+                {
+                    break;
+                }
+            }
+            Err(e) => debug!(venue, error = %e, "failed to evaluate route"),
+        }
+    }
+    plans
+}
 
 /// Route selector that evaluates and selects optimal execution paths
 pub struct RouteSelector {
     deepbook: Option<Arc<DeepBookAdapter>>,
+    cetus: Option<Arc<CetusAdapter>>,
     /// Base latency for fast-path routes (owned objects) in milliseconds
     base_latency_ms: AtomicU64,
     /// Current expected latency for shared-object routes
@@ -30,6 +191,13 @@ pub struct RouteSelector {
     max_samples: usize,
     /// EWMA alpha for latency updates (0.0-1.0, higher = more weight to recent observations)
     latency_alpha: f64,
+    /// Venue order and short-circuit rules for hot-path selections
+    evaluation: EvaluationPolicy,
+    /// Price impact per multiple of visible depth for orders larger than the book
+    impact_coefficient: f64,
+    /// Share of notional charged for the part of a marketable order the book
+    /// is not expected to fill
+    partial_fill_penalty: f64,
 }
 
 impl RouteSelector {
@@ -40,15 +208,48 @@ impl RouteSelector {
     ) -> Self {
         Self {
             deepbook,
+            cetus: None,
             base_latency_ms: AtomicU64::new(base_latency_ms),
             shared_object_latency_ms: AtomicU64::new(shared_object_latency_ms),
             owned_latency_samples: Arc::new(RwLock::new(VecDeque::new())),
             shared_latency_samples: Arc::new(RwLock::new(VecDeque::new())),
             max_samples: 100,
             latency_alpha: 0.1, // 10% weight to new observations
+            evaluation: EvaluationPolicy::default(),
+            impact_coefficient: DEFAULT_IMPACT_COEFFICIENT,
+            partial_fill_penalty: DEFAULT_PARTIAL_FILL_PENALTY,
         }
     }
 
+    /// Limit and order the venues evaluated per selection
+    pub fn with_evaluation_policy(mut self, policy: EvaluationPolicy) -> Self {
+        self.evaluation = policy;
+        self
+    }
+
+    /// Extrapolate slippage beyond visible depth with `coefficient`
+    pub fn with_impact_coefficient(mut self, coefficient: f64) -> Self {
+        self.impact_coefficient = coefficient;
+        self
+    }
+
+    /// Charge `penalty` of notional for the unfilled remainder of marketable orders
+    pub fn with_partial_fill_penalty(mut self, penalty: f64) -> Self {
+        self.partial_fill_penalty = penalty;
+        self
+    }
+
+    /// Also quote Cetus for pools it has configured
+    pub fn with_cetus(mut self, cetus: Arc<CetusAdapter>) -> Self {
+        self.cetus = Some(cetus);
+        self
+    }
+
+    /// Get the Cetus adapter if available
+    pub fn cetus_adapter(&self) -> Option<&Arc<CetusAdapter>> {
+        self.cetus.as_ref()
+    }
+
     /// Get the DeepBook adapter if available
     pub fn deepbook_adapter(&self) -> Option<&Arc<DeepBookAdapter>> {
         self.deepbook.as_ref()
@@ -135,36 +336,42 @@ impl RouteSelector {
         }
     }
 
-    /// Select optimal route for a limit order request
-    #[tracing::instrument(skip_all, fields(pool = %req.pool, side = if req.is_bid { "bid" } else { "ask" }))]
+    /// Select optimal route for a limit order request, following the
+    /// configured evaluation policy
     pub async fn select_route(&self, req: &LimitReq) -> Result<RouteSelection> {
-        let mut alternatives = Vec::new();
+        self.select_route_with(req, false).await
+    }
+
+    /// Select a route after evaluating every venue, ignoring the evaluation policy
+    pub async fn select_route_exhaustive(&self, req: &LimitReq) -> Result<RouteSelection> {
+        self.select_route_with(req, true).await
+    }
+
+    #[tracing::instrument(skip_all, fields(pool = %req.pool, side = if req.is_bid { "bid" } else { "ask" }))]
+    async fn select_route_with(&self, req: &LimitReq, exhaustive: bool) -> Result<RouteSelection> {
+        let mut evaluations: Vec<VenueEvaluation<'_>> = Vec::new();
 
         // Evaluate DeepBook route if adapter is available
         if let Some(adapter) = &self.deepbook {
-            match self.evaluate_deepbook_route(adapter, req).await {
-                Ok(plan) => {
-                    debug!(
-                        pool = %req.pool,
-                        side = if req.is_bid { "bid" } else { "ask" },
-                        total_cost = plan.score.total_cost,
-                        latency_ms = plan.expected_latency_ms,
-                        "evaluated DeepBook route"
-                    );
-                    alternatives.push(plan);
-                }
-                Err(e) => {
-                    debug!(
-                        error = %e,
-                        pool = %req.pool,
-                        "failed to evaluate DeepBook route"
-                    );
-                }
+            evaluations.push((
+                "deepbook",
+                Box::pin(self.evaluate_deepbook_route(adapter, req)),
+            ));
+        }
+
+        // Swaps always take, so post-only orders stay on DeepBook
+        if let Some(adapter) = &self.cetus {
+            if req.post_only.is_none() && adapter.pool(&req.pool).is_some() {
+                evaluations.push(("cetus", Box::pin(self.evaluate_cetus_route(adapter, req))));
             }
         }
 
-        // Future: Evaluate other venues (AMMs, etc.)
-        // For now, we only have DeepBook
+        let mut alternatives = if exhaustive || self.evaluation.is_exhaustive() {
+            gather_alternatives(evaluations).await
+        } else {
+            let evaluations = self.evaluation.order_for(&req.pool, evaluations);
+            gather_until_good_enough(evaluations, &self.evaluation, req.price * req.quantity).await
+        };
 
         if alternatives.is_empty() {
             anyhow::bail!("no viable routes found for order");
@@ -188,6 +395,78 @@ impl RouteSelector {
         })
     }
 
+    /// Price a market order against the DeepBook book. The expected average
+    /// fill comes from walking the opposite side from mid; the order is
+    /// rejected when that fill is further from mid than the caller's bound.
+    #[tracing::instrument(skip_all, fields(pool = %req.pool, side = if req.is_bid { "bid" } else { "ask" }))]
+    pub async fn select_market_route(&self, req: &MarketReq) -> Result<RoutePlan> {
+        let adapter = self
+            .deepbook
+            .as_ref()
+            .context("market orders require the DeepBook adapter")?;
+
+        let pool_params = adapter
+            .pool_params(&req.pool)
+            .await
+            .context("fetch pool parameters")?;
+        let mid_price = adapter
+            .mid_price(&req.pool)
+            .await
+            .context("fetch mid price")?;
+        let level2 = adapter
+            .level2_ticks_from_mid(&req.pool, VWAP_BOOK_TICKS)
+            .await
+            .context("fetch level2 order book")?;
+
+        // Measured against mid, the estimate's slippage is the whole sweep cost
+        let estimate = estimate_slippage(
+            &level2,
+            mid_price,
+            req.quantity,
+            req.is_bid,
+            pool_params.tick_size,
+            self.impact_coefficient,
+        );
+        let expected_bps = slippage_bps(mid_price, estimate.avg_fill_price, req.is_bid);
+        debug!(
+            pool = %req.pool,
+            mid_price,
+            avg_fill_price = estimate.avg_fill_price,
+            expected_bps,
+            visible_filled = estimate.visible_filled,
+            "estimated market order fill"
+        );
+        req.check_slippage(expected_bps)?;
+
+        let trade_params = adapter
+            .trade_params(&req.pool)
+            .await
+            .context("fetch trade parameters")?;
+        let gas_price_per_unit = adapter
+            .reference_gas_price()
+            .await
+            .context("fetch reference gas price")?;
+
+        let gas_units = 10_000_000u64;
+        let gas_cost = (gas_units as f64 * gas_price_per_unit as f64) / 1e9 * mid_price;
+        // Market orders always take
+        let notional = estimate.avg_fill_price * req.quantity;
+        let fee_cost = notional * trade_params.taker_fee;
+        let risk_factor = notional * 0.00001;
+
+        Ok(RoutePlan::market_order(
+            req.clone(),
+            mid_price,
+            estimate.slippage + fee_cost,
+            gas_cost,
+            self.shared_object_latency_ms.load(Ordering::Relaxed),
+            self.base_latency_ms.load(Ordering::Relaxed),
+            risk_factor,
+        )
+        .with_expected_fill_price(estimate.avg_fill_price)
+        .with_fill_levels(estimate.fills))
+    }
+
     /// Evaluate a DeepBook route with real order book data
     async fn evaluate_deepbook_route(
         &self,
@@ -222,9 +501,33 @@ impl RouteSelector {
             .await
             .context("fetch level2 order book")?;
 
-        // Calculate expected slippage based on order book depth
-        let slippage =
-            self.calculate_slippage(req.price, req.quantity, req.is_bid, &level2, &pool_params)?;
+        // Walk the opposite side of the book for expected slippage
+        let estimate = estimate_slippage(
+            &level2,
+            req.price,
+            req.quantity,
+            req.is_bid,
+            pool_params.tick_size,
+            self.impact_coefficient,
+        );
+        // A thin book may fill only part of a marketable order; the rest
+        // rests or is reworked later, so charge for it
+        let fill_fraction = expected_fill_fraction(&level2, req.price, req.quantity, req.is_bid);
+        let unfilled_cost = partial_fill_cost(
+            req.price,
+            req.quantity,
+            fill_fraction,
+            self.partial_fill_penalty,
+        );
+        debug!(
+            pool = %req.pool,
+            slippage = estimate.slippage,
+            avg_fill_price = estimate.avg_fill_price,
+            visible_filled = estimate.visible_filled,
+            fill_fraction,
+            unfilled_cost,
+            "estimated slippage from order book"
+        );
 
         // Fetch trade parameters for fee estimation
         let trade_params = adapter
@@ -270,72 +573,153 @@ impl RouteSelector {
         Ok(RoutePlan::deepbook_single(
             req.clone(),
             l2_price,
-            slippage + fee_cost,
+            estimate.slippage + fee_cost + unfilled_cost,
             gas_cost,
             expected_latency_ms,
             self.base_latency_ms.load(Ordering::Relaxed),
             risk_factor,
-        ))
+        )
+        .with_expected_fill_price(estimate.avg_fill_price)
+        .with_fill_levels(estimate.fills))
     }
 
-    /// Calculate expected slippage based on order book depth
-    fn calculate_slippage(
+    /// Evaluate swapping the order's full size on its Cetus pool. The swap's
+    /// minimum output enforces the limit price, so the route is only viable
+    /// when the pool is expected to fill the whole order at or inside it.
+    async fn evaluate_cetus_route(
         &self,
-        price: f64,
-        quantity: f64,
-        is_bid: bool,
-        level2: &sui_deepbookv3::client::Level2TicksFromMid,
-        pool_params: &crate::quant::PoolParams,
-    ) -> Result<f64> {
-        let (prices, quantities) = if is_bid {
-            (&level2.bid_prices, &level2.bid_quantities)
+        adapter: &CetusAdapter,
+        req: &LimitReq,
+    ) -> Result<RoutePlan> {
+        let mid_price = adapter
+            .get_price(&req.pool)
+            .await
+            .context("fetch Cetus price")?;
+
+        // Buys receive exactly `quantity` base for at most the limit notional;
+        // sells spend exactly `quantity` base for at least that notional
+        let swap = AmmSwapReq::limit(&req.pool, req.price, req.quantity, req.is_bid);
+        let quote_amount = if req.is_bid {
+            adapter
+                .get_amount_in(&req.pool, req.quantity, true)
+                .await
+                .context("estimate Cetus swap input")?
+        } else {
+            adapter
+                .get_liquidity(&req.pool, req.quantity, false)
+                .await
+                .context("estimate Cetus swap output")?
+        };
+        let within_limit = if req.is_bid {
+            quote_amount <= swap.amount_limit
         } else {
-            (&level2.ask_prices, &level2.ask_quantities)
+            quote_amount >= swap.amount_limit
         };
+        if !(within_limit && quote_amount.is_finite() && quote_amount > 0.0) {
+            anyhow::bail!(
+                "Cetus pool cannot fill {} at {} (expected quote {quote_amount})",
+                req.quantity,
+                req.price
+            );
+        }
+
+        // Average price over the requested size, fee included; it already
+        // carries the swap's price impact, so there is no separate slippage
+        let avg_fill_price = quote_amount / req.quantity;
+        let l2_price = avg_fill_price;
+        let slippage = 0.0;
+        debug!(
+            pool = %req.pool,
+            mid_price,
+            avg_fill_price,
+            quote_amount,
+            "estimated Cetus swap"
+        );
 
-        if prices.is_empty() || quantities.is_empty() {
-            // No liquidity, assume high slippage
-            return Ok(price * quantity * 0.01); // 1% slippage
+        // DeepBook's adapter caches the gas price; only ask the node without it
+        let gas_price_per_unit = match &self.deepbook {
+            Some(deepbook) => deepbook.reference_gas_price().await,
+            None => adapter.reference_gas_price().await,
         }
+        .context("fetch reference gas price")?;
 
-        // Find the price level that would fill our order
-        let mut remaining_qty = quantity;
-        let mut total_cost = 0.0;
+        // A single swap call, roughly half a limit order's gas
+        let gas_units = 5_000_000u64;
+        let gas_cost = (gas_units as f64 * gas_price_per_unit as f64) / 1e9 * l2_price;
 
-        for (p, q) in prices.iter().zip(quantities.iter()) {
-            if remaining_qty <= 0.0 {
-                break;
-            }
+        let expected_latency_ms = self.shared_object_latency_ms.load(Ordering::Relaxed);
+
+        // Third-party contract, so twice DeepBook's risk
+        let risk_factor = quote_amount * 0.00002;
+
+        Ok(RoutePlan::cetus_swap(
+            swap,
+            l2_price,
+            slippage,
+            gas_cost,
+            expected_latency_ms,
+            self.base_latency_ms.load(Ordering::Relaxed),
+            risk_factor,
+        )
+        .with_expected_fill_price(avg_fill_price))
+    }
 
-            let fill_qty = remaining_qty.min(*q);
-            let cost = fill_qty * *p;
-            total_cost += cost;
-            remaining_qty -= fill_qty;
+    /// Capture learned latency estimates and recent samples
+    pub async fn export_state(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            base_latency_ms: self.base_latency_ms.load(Ordering::Relaxed),
+            shared_latency_ms: self.shared_object_latency_ms.load(Ordering::Relaxed),
+            owned_samples: self
+                .owned_latency_samples
+                .read()
+                .await
+                .iter()
+                .copied()
+                .collect(),
+            shared_samples: self
+                .shared_latency_samples
+                .read()
+                .await
+                .iter()
+                .copied()
+                .collect(),
         }
+    }
 
-        // If order can't be fully filled at current levels, estimate slippage
-        if remaining_qty > 0.0 {
-            // Use worst-case: assume remaining fills at last price + tick_size
-            let last_price = prices.last().copied().unwrap_or(price);
-            let tick_size = pool_params.tick_size;
-            let worst_price = if is_bid {
-                last_price - tick_size // Bids go down
-            } else {
-                last_price + tick_size // Asks go up
-            };
-            total_cost += remaining_qty * worst_price;
+    /// Restore estimates and samples from a previous `export_state`
+    pub async fn import_state(&self, snapshot: LatencySnapshot) {
+        self.update_latency_estimates(snapshot.base_latency_ms, snapshot.shared_latency_ms);
+        for (target, samples) in [
+            (&self.owned_latency_samples, snapshot.owned_samples),
+            (&self.shared_latency_samples, snapshot.shared_samples),
+        ] {
+            let skip = samples.len().saturating_sub(self.max_samples);
+            *target.write().await = samples.into_iter().skip(skip).collect();
         }
+    }
 
-        let avg_fill_price = total_cost / quantity;
-        let slippage = if is_bid {
-            // For bids, slippage is when we pay more than requested
-            (avg_fill_price - price).max(0.0) * quantity
-        } else {
-            // For asks, slippage is when we receive less than requested
-            (price - avg_fill_price).max(0.0) * quantity
-        };
+    /// Write learned latencies to `path` as JSON
+    pub async fn save_state(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(&self.export_state().await)?;
+        tokio::fs::write(path, json)
+            .await
+            .with_context(|| format!("write latency state to {}", path.display()))
+    }
 
-        Ok(slippage)
+    /// Load latencies saved by `save_state`; a missing file is not an error
+    pub async fn load_state(&self, path: &Path) -> Result<bool> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("read latency state from {}", path.display()))
+            }
+        };
+        let snapshot: LatencySnapshot = serde_json::from_slice(&bytes)
+            .with_context(|| format!("parse latency state from {}", path.display()))?;
+        self.import_state(snapshot).await;
+        Ok(true)
     }
 
     /// Update latency estimates based on recent observations
@@ -352,6 +736,17 @@ impl RouteSelector {
     }
 }
 
+/// Persistable view of the learned latency model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencySnapshot {
+    pub base_latency_ms: u64,
+    pub shared_latency_ms: u64,
+    #[serde(default)]
+    pub owned_samples: Vec<f64>,
+    #[serde(default)]
+    pub shared_samples: Vec<f64>,
+}
+
 /// Latency statistics for monitoring
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LatencyStats {
@@ -0,0 +1,197 @@
+// Client sessions with cancel-on-disconnect
+// A client keeps a session alive with heartbeats over a WebSocket; orders
+// tagged with the session are cancelled once its heartbeats stop
+//
+// Numan Thabit 2025 Nov
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+/// Default time without a heartbeat before a session's orders are cancelled
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An order placed under a session, enough to cancel it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionOrder {
+    pub pool: String,
+    pub order_id: u128,
+    pub manager: Option<String>,
+}
+
+struct Session {
+    last_heartbeat: Instant,
+    orders: Vec<SessionOrder>,
+}
+
+/// Live sessions and the orders placed under each
+#[derive(Clone)]
+pub struct SessionRegistry {
+    timeout: Duration,
+    sessions: Arc<RwLock<HashMap<String, Session>>>,
+}
+
+impl Default for SessionRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_SESSION_TIMEOUT)
+    }
+}
+
+impl SessionRegistry {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Open `session` or keep it alive
+    pub async fn heartbeat(&self, session: &str, now: Instant) {
+        let mut sessions = self.sessions.write().await;
+        match sessions.get_mut(session) {
+            Some(live) => live.last_heartbeat = now,
+            None => {
+                info!(session, "session opened");
+                sessions.insert(
+                    session.to_string(),
+                    Session {
+                        last_heartbeat: now,
+                        orders: Vec::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Whether `session` is open and has heartbeated within the timeout
+    pub async fn is_live(&self, session: &str, now: Instant) -> bool {
+        self.sessions
+            .read()
+            .await
+            .get(session)
+            .is_some_and(|live| now.saturating_duration_since(live.last_heartbeat) <= self.timeout)
+    }
+
+    /// Remember `orders` for cancellation with `session`; false when the
+    /// session is not open
+    pub async fn track(
+        &self,
+        session: &str,
+        orders: impl IntoIterator<Item = SessionOrder>,
+    ) -> bool {
+        match self.sessions.write().await.get_mut(session) {
+            Some(live) => {
+                live.orders.extend(orders);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Orders currently tracked for `session`
+    pub async fn orders(&self, session: &str) -> Vec<SessionOrder> {
+        self.sessions
+            .read()
+            .await
+            .get(session)
+            .map(|live| live.orders.clone())
+            .unwrap_or_default()
+    }
+
+    /// Close every session silent for longer than the timeout, returning the
+    /// orders each had open
+    pub async fn expire(&self, now: Instant) -> Vec<(String, Vec<SessionOrder>)> {
+        let mut sessions = self.sessions.write().await;
+        let expired: Vec<String> = sessions
+            .iter()
+            .filter(|(_, live)| now.saturating_duration_since(live.last_heartbeat) > self.timeout)
+            .map(|(id, _)| id.clone())
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|id| sessions.remove(&id).map(|live| (id, live.orders)))
+            .collect()
+    }
+}
+
+/// Cancel the orders of every session whose heartbeats stopped, returning the
+/// number cancelled. A failed cancel is logged; the session is closed either way.
+pub async fn cancel_expired_sessions<F, Fut>(
+    registry: &SessionRegistry,
+    now: Instant,
+    cancel: F,
+) -> usize
+where
+    F: Fn(SessionOrder) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut cancelled = 0;
+    for (session, orders) in registry.expire(now).await {
+        warn!(
+            session = %session,
+            orders = orders.len(),
+            "session heartbeat lost; cancelling its orders"
+        );
+        cancelled += cancel_session_orders(&session, orders, &cancel).await;
+    }
+    cancelled
+}
+
+/// Track `orders` under `session`, or cancel them through `cancel` right away
+/// when the session closed while they were in flight. Returns the number
+/// cancelled.
+pub async fn track_or_cancel<F, Fut>(
+    registry: &SessionRegistry,
+    session: &str,
+    orders: Vec<SessionOrder>,
+    cancel: F,
+) -> usize
+where
+    F: Fn(SessionOrder) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    if registry.track(session, orders.iter().cloned()).await {
+        return 0;
+    }
+    warn!(
+        session = %session,
+        orders = orders.len(),
+        "orders placed after their session closed; cancelling them"
+    );
+    cancel_session_orders(session, orders, &cancel).await
+}
+
+/// Cancel `orders` of `session` one by one, logging failures
+async fn cancel_session_orders<F, Fut>(
+    session: &str,
+    orders: Vec<SessionOrder>,
+    cancel: &F,
+) -> usize
+where
+    F: Fn(SessionOrder) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut cancelled = 0;
+    for order in orders {
+        let (pool, order_id) = (order.pool.clone(), order.order_id);
+        match cancel(order).await {
+            Ok(()) => cancelled += 1,
+            Err(err) => error!(
+                session = %session,
+                pool = %pool,
+                order_id = %order_id,
+                error = %err,
+                "failed to cancel session order"
+            ),
+        }
+    }
+    cancelled
+}
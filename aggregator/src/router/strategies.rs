@@ -0,0 +1,118 @@
+// Execution strategy estimates for quotes
+// Compares taking the full size now, slicing it over time, and resting it
+// passively, using the visible book and recent trade flow
+//
+// Numan Thabit 2025 Nov
+
+use crate::router::flow::fill_probability;
+use crate::venues::book::{best_price, mid_price, own_side, queue_ahead, sweep_for_taker};
+use serde::Serialize;
+use sui_deepbookv3::client::Level2TicksFromMid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    /// Take the whole size from the book now
+    Immediate,
+    /// Take equal slices spaced out in time
+    Twap,
+    /// Rest at the best price on our own side
+    Passive,
+}
+
+/// Estimated outcome of one way to work an order
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyEstimate {
+    pub kind: StrategyKind,
+    /// Cost versus mid in quote units; negative means price improvement
+    pub impact: f64,
+    /// Expected time to complete, when it can be estimated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_time_ms: Option<u64>,
+    /// Probability of completing the full size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_probability: Option<f64>,
+    /// Child orders used (1 for immediate and passive)
+    pub slices: u32,
+}
+
+/// Tunables for strategy estimates
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyParams {
+    /// Number of TWAP slices
+    pub twap_slices: u32,
+    /// Gap between TWAP slices
+    pub slice_interval_ms: u64,
+    /// Horizon used for the passive fill probability
+    pub passive_horizon_ms: u64,
+    /// Expected latency of a single execution
+    pub execution_latency_ms: u64,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            twap_slices: 5,
+            slice_interval_ms: 30_000,
+            passive_horizon_ms: 300_000,
+            execution_latency_ms: 400,
+        }
+    }
+}
+
+/// Estimate immediate, TWAP and passive execution of `quantity`.
+/// TWAP assumes the book refills between slices. Returns an empty list when
+/// the book has no two-sided market to price against.
+pub fn estimate_strategies(
+    level2: &Level2TicksFromMid,
+    quantity: f64,
+    is_bid: bool,
+    flow_rate_per_ms: Option<f64>,
+    params: StrategyParams,
+) -> Vec<StrategyEstimate> {
+    let Some(mid) = mid_price(level2) else {
+        return Vec::new();
+    };
+    // Paying above mid on a buy or receiving below mid on a sell is a cost
+    let cost_per_unit = |price: f64| if is_bid { price - mid } else { mid - price };
+    let mut strategies = Vec::with_capacity(3);
+
+    if let Some(fill) = sweep_for_taker(level2, quantity, is_bid) {
+        strategies.push(StrategyEstimate {
+            kind: StrategyKind::Immediate,
+            impact: cost_per_unit(fill.vwap) * fill.filled,
+            expected_time_ms: Some(params.execution_latency_ms),
+            fill_probability: Some(fill.filled / quantity),
+            slices: 1,
+        });
+    }
+
+    let slices = params.twap_slices.max(1);
+    let slice_quantity = quantity / slices as f64;
+    if let Some(fill) = sweep_for_taker(level2, slice_quantity, is_bid) {
+        let duration = params.slice_interval_ms * (slices as u64 - 1);
+        strategies.push(StrategyEstimate {
+            kind: StrategyKind::Twap,
+            impact: cost_per_unit(fill.vwap) * fill.filled * slices as f64,
+            expected_time_ms: Some(duration + params.execution_latency_ms),
+            fill_probability: Some(fill.filled / slice_quantity),
+            slices,
+        });
+    }
+
+    let (prices, quantities) = own_side(level2, is_bid);
+    if let Some(price) = best_price(prices) {
+        let ahead = queue_ahead(prices, quantities, price, is_bid);
+        let rate = flow_rate_per_ms.filter(|rate| *rate > 0.0);
+        strategies.push(StrategyEstimate {
+            kind: StrategyKind::Passive,
+            impact: cost_per_unit(price) * quantity,
+            expected_time_ms: rate.map(|rate| ((ahead + quantity) / rate).ceil() as u64),
+            fill_probability: rate
+                .map(|rate| fill_probability(ahead, quantity, rate, params.passive_horizon_ms)),
+            slices: 1,
+        });
+    }
+
+    strategies
+}
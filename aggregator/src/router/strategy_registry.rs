@@ -0,0 +1,237 @@
+// Running strategy registry
+// Tracks slicing strategies (TWAP, iceberg, peg) by id so operators can list
+// their progress and stop them before every child has been submitted
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use crate::router::execution::OrderHandle;
+use crate::router::quotes::now_ms;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, RwLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyType {
+    Twap,
+    Iceberg,
+    Peg,
+}
+
+impl StrategyType {
+    fn id_prefix(self) -> &'static str {
+        match self {
+            StrategyType::Twap => "twap",
+            StrategyType::Iceberg => "ice",
+            StrategyType::Peg => "peg",
+        }
+    }
+}
+
+/// Point-in-time view of a running strategy
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyProgress {
+    pub id: String,
+    pub kind: StrategyType,
+    pub started_ms: u64,
+    pub total_children: usize,
+    pub submitted: usize,
+    pub failed: usize,
+    pub cancelled: bool,
+    /// Orders placed by submitted children that may still rest on the book
+    pub resting_orders: usize,
+}
+
+/// An order a child placed, with the balance manager it was placed through so
+/// it can be cancelled from the same manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestingOrder {
+    pub pool: String,
+    pub order_id: u128,
+    pub manager: Option<String>,
+}
+
+#[derive(Default)]
+struct Progress {
+    cancelled: AtomicBool,
+    cancel_signal: Notify,
+    submitted: AtomicUsize,
+    failed: AtomicUsize,
+    resting: Mutex<Vec<RestingOrder>>,
+}
+
+/// Shared handle to one running strategy
+#[derive(Clone)]
+pub struct StrategyHandle {
+    id: String,
+    kind: StrategyType,
+    started_ms: u64,
+    total_children: usize,
+    progress: Arc<Progress>,
+}
+
+impl StrategyHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn kind(&self) -> StrategyType {
+        self.kind
+    }
+
+    /// Stop submitting further children; children already in flight complete
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::SeqCst);
+        self.progress.cancel_signal.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.progress.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the strategy is cancelled
+    pub async fn cancelled(&self) {
+        let mut signal = std::pin::pin!(self.progress.cancel_signal.notified());
+        // Register before checking the flag so a cancel in between is not missed
+        signal.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        signal.await;
+    }
+
+    /// Count a child that reached the chain, remembering the orders it placed
+    /// through `manager` (`None` for the default balance manager)
+    pub fn record_submitted(&self, orders: &[OrderHandle], manager: Option<&str>) {
+        self.progress.submitted.fetch_add(1, Ordering::SeqCst);
+        if !orders.is_empty() {
+            let mut resting = self.progress.resting.lock().unwrap();
+            resting.extend(orders.iter().map(|order| RestingOrder {
+                pool: order.pool.clone(),
+                order_id: order.order_id,
+                manager: manager.map(str::to_string),
+            }));
+        }
+    }
+
+    pub fn record_failed(&self) {
+        self.progress.failed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Orders placed by this strategy's children, removed from the handle
+    pub fn take_resting_orders(&self) -> Vec<RestingOrder> {
+        std::mem::take(&mut *self.progress.resting.lock().unwrap())
+    }
+
+    pub fn progress(&self) -> StrategyProgress {
+        StrategyProgress {
+            id: self.id.clone(),
+            kind: self.kind,
+            started_ms: self.started_ms,
+            total_children: self.total_children,
+            submitted: self.progress.submitted.load(Ordering::SeqCst),
+            failed: self.progress.failed.load(Ordering::SeqCst),
+            cancelled: self.is_cancelled(),
+            resting_orders: self.progress.resting.lock().unwrap().len(),
+        }
+    }
+}
+
+/// Strategies that have started and not yet finished
+#[derive(Clone, Default)]
+pub struct StrategyRegistry {
+    running: Arc<RwLock<HashMap<String, StrategyHandle>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a strategy that will submit `total_children` child orders
+    pub async fn start(&self, kind: StrategyType, total_children: usize) -> StrategyHandle {
+        let started_ms = now_ms();
+        let seq = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let handle = StrategyHandle {
+            id: format!("{}-{started_ms:x}-{seq:x}", kind.id_prefix()),
+            kind,
+            started_ms,
+            total_children,
+            progress: Arc::new(Progress::default()),
+        };
+        self.running
+            .write()
+            .await
+            .insert(handle.id.clone(), handle.clone());
+        handle
+    }
+
+    pub async fn get(&self, id: &str) -> Option<StrategyHandle> {
+        self.running.read().await.get(id).cloned()
+    }
+
+    /// Flag a running strategy as cancelled and return its handle
+    pub async fn cancel(&self, id: &str) -> Result<StrategyHandle, AggrError> {
+        let handle = self
+            .get(id)
+            .await
+            .ok_or_else(|| AggrError::UnknownStrategy(id.to_string()))?;
+        handle.cancel();
+        Ok(handle)
+    }
+
+    /// Drop a strategy once it has no more children to submit
+    pub async fn finish(&self, id: &str) -> Option<StrategyHandle> {
+        self.running.write().await.remove(id)
+    }
+
+    /// Guard that drops strategy `id` from the registry when it goes out of
+    /// scope, so a strategy run in a background task leaves the registry even
+    /// if that task panics or is aborted
+    pub fn finish_on_drop(&self, id: &str) -> FinishGuard {
+        FinishGuard {
+            registry: self.clone(),
+            id: id.to_string(),
+        }
+    }
+
+    /// Progress of every running strategy, oldest first
+    pub async fn list(&self) -> Vec<StrategyProgress> {
+        let mut progress: Vec<StrategyProgress> = self
+            .running
+            .read()
+            .await
+            .values()
+            .map(StrategyHandle::progress)
+            .collect();
+        progress.sort_by(|a, b| a.started_ms.cmp(&b.started_ms).then(a.id.cmp(&b.id)));
+        progress
+    }
+}
+
+/// Removes a strategy from its registry when dropped
+pub struct FinishGuard {
+    registry: StrategyRegistry,
+    id: String,
+}
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.registry.running.try_write() {
+            running.remove(&self.id);
+            return;
+        }
+        // The registry lock is async and busy, so release it from a task
+        let registry = self.registry.clone();
+        let id = std::mem::take(&mut self.id);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                registry.finish(&id).await;
+            });
+        }
+    }
+}
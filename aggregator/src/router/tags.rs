@@ -0,0 +1,43 @@
+// Client order tags
+// Free-form metadata clients attach to orders for correlation; echoed back
+// and logged, never written on-chain
+//
+// Numan Thabit 2025 Nov
+
+use std::collections::BTreeMap;
+
+/// Client-supplied tags, ordered so log output is stable
+pub type OrderTags = BTreeMap<String, String>;
+
+pub const MAX_TAGS: usize = 16;
+pub const MAX_TAG_KEY_LEN: usize = 64;
+pub const MAX_TAG_VALUE_LEN: usize = 256;
+
+/// Check tag count and sizes against the per-order bounds
+pub fn validate_tags(tags: &OrderTags) -> Result<(), String> {
+    if tags.len() > MAX_TAGS {
+        return Err(format!("at most {MAX_TAGS} tags are allowed"));
+    }
+    for (key, value) in tags {
+        if key.trim().is_empty() {
+            return Err("tag keys must not be empty".to_string());
+        }
+        if key.len() > MAX_TAG_KEY_LEN {
+            return Err(format!("tag key {key:?} exceeds {MAX_TAG_KEY_LEN} bytes"));
+        }
+        if value.len() > MAX_TAG_VALUE_LEN {
+            return Err(format!(
+                "tag {key:?} value exceeds {MAX_TAG_VALUE_LEN} bytes"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compact `key=value,...` rendering for log records
+pub fn format_tags(tags: &OrderTags) -> String {
+    tags.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -3,7 +3,9 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::errors::AggrError;
 use crate::venues::adapter::{DeepBookAdapter, LimitReq};
+use crate::venues::pools::PoolStatus;
 use anyhow::Result;
 use tracing::warn;
 
@@ -12,6 +14,9 @@ use tracing::warn;
 pub struct ValidationResult {
     pub is_valid: bool,
     pub errors: Vec<String>,
+    /// First failure caused by the order itself rather than the server, kept
+    /// typed so the API can answer it with a client error
+    pub rejection: Option<AggrError>,
 }
 
 impl ValidationResult {
@@ -19,6 +24,7 @@ impl ValidationResult {
         Self {
             is_valid: true,
             errors: Vec::new(),
+            rejection: None,
         }
     }
 
@@ -27,11 +33,24 @@ impl ValidationResult {
         self.errors.push(error);
     }
 
+    /// Record `error`, keeping it typed when it is a quantization failure
+    pub fn add_quantization_error(&mut self, label: &str, error: anyhow::Error) {
+        self.add_error(format!("{label} quantization failed: {error}"));
+        if self.rejection.is_none() {
+            if let Ok(rejection @ AggrError::Quantization { .. }) = error.downcast::<AggrError>() {
+                self.rejection = Some(rejection);
+            }
+        }
+    }
+
     pub fn into_result(self) -> Result<()> {
         if self.is_valid {
-            Ok(())
-        } else {
-            anyhow::bail!("validation failed: {}", self.errors.join("; "))
+            return Ok(());
+        }
+        let message = format!("validation failed: {}", self.errors.join("; "));
+        match self.rejection {
+            Some(rejection) => Err(anyhow::Error::new(rejection).context(message)),
+            None => Err(anyhow::anyhow!(message)),
         }
     }
 }
@@ -42,6 +61,34 @@ impl Default for ValidationResult {
     }
 }
 
+/// Reject execution while the reference gas price is above `ceiling`, unless
+/// the caller explicitly accepts the higher cost
+pub fn check_gas_price(price: u64, ceiling: u64, allow_override: bool) -> Result<(), AggrError> {
+    if price <= ceiling {
+        return Ok(());
+    }
+    if allow_override {
+        warn!(
+            price,
+            ceiling, "gas price above ceiling; proceeding on override"
+        );
+        return Ok(());
+    }
+    Err(AggrError::GasPriceTooHigh { price, ceiling })
+}
+
+/// Reject orders to pools that are paused or not yet launched; they would
+/// fail on chain after spending gas
+pub fn check_pool_status(pool: &str, status: PoolStatus) -> Result<(), AggrError> {
+    if status.is_tradable() {
+        return Ok(());
+    }
+    Err(AggrError::PoolNotTradable {
+        pool: pool.to_string(),
+        status: status.to_string(),
+    })
+}
+
 /// Validate a limit order request before routing/execution
 pub async fn validate_limit_order(
     adapter: &DeepBookAdapter,
@@ -60,8 +107,11 @@ pub async fn validate_limit_order(
         }
     };
 
-    // 2. Validate quantization (price and size meet tick/lot/min constraints)
-    match crate::quant::quantize_price(req.price, pool_params.tick_size) {
+    // 2. Refuse pools that are not trading; surfaced as an error rather than a finding
+    check_pool_status(&req.pool, adapter.pool_status(&req.pool).await)?;
+
+    // 3. Validate quantization (price and size meet tick/lot/min constraints)
+    match crate::quant::quantize_price_for_side(req.price, pool_params.tick_size, req.is_bid) {
         Ok(price) => {
             quantized_price = Some(price);
             if (price - req.price).abs() / req.price > 0.001 {
@@ -72,9 +122,7 @@ pub async fn validate_limit_order(
                 );
             }
         }
-        Err(e) => {
-            result.add_error(format!("price quantization failed: {}", e));
-        }
+        Err(e) => result.add_quantization_error("price", e),
     }
 
     match crate::quant::quantize_size(req.quantity, pool_params.lot_size, pool_params.min_size) {
@@ -94,12 +142,10 @@ pub async fn validate_limit_order(
                 );
             }
         }
-        Err(e) => {
-            result.add_error(format!("size quantization failed: {}", e));
-        }
+        Err(e) => result.add_quantization_error("size", e),
     }
 
-    // 3. Validate BalanceManager balance (if adapter supports it)
+    // 4. Validate BalanceManager balance (if adapter supports it)
     // For bids: need quote coin balance
     // For asks: need base coin balance
     // Note: This requires knowing the pool's base/quote coins
@@ -123,7 +169,11 @@ pub async fn validate_balance_manager_funding(
     quantized_price: f64,
     quantized_size: f64,
 ) -> Result<Option<String>> {
-    let snapshot = match adapter.balance_manager_balances(&req.pool).await {
+    let balances = match adapter.manager_key_for(req.manager.as_deref()) {
+        Ok(manager) => adapter.manager_balances(&req.pool, &manager).await,
+        Err(e) => Err(e),
+    };
+    let snapshot = match balances {
         Ok(snapshot) => snapshot,
         Err(e) => {
             return Ok(Some(format!(
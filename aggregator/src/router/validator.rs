@@ -6,9 +6,13 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::{debug, warn};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::errors::AggrError;
+use crate::transport::grpc::GrpcClients;
 
 /// Validator endpoint identifier
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -25,8 +29,12 @@ struct ValidatorStats {
     pub observations: u64,
     /// Last update timestamp
     pub last_update: Instant,
+    /// Last time the validator answered (effects observed or probe passed)
+    pub last_seen: Instant,
     /// Whether validator is considered healthy
     pub healthy: bool,
+    /// Readiness probes failed in a row since the last success
+    pub consecutive_probe_failures: u32,
 }
 
 impl ValidatorStats {
@@ -35,7 +43,9 @@ impl ValidatorStats {
             effects_ewma_ms: 500.0, // Initial estimate: 500ms
             observations: 0,
             last_update: Instant::now(),
+            last_seen: Instant::now(),
             healthy: true,
+            consecutive_probe_failures: 0,
         }
     }
 
@@ -49,6 +59,7 @@ impl ValidatorStats {
         }
         self.observations += 1;
         self.last_update = Instant::now();
+        self.last_seen = self.last_update;
     }
 }
 
@@ -126,6 +137,84 @@ impl ValidatorSelector {
         }
     }
 
+    /// Record a readiness probe outcome; the validator is taken out of rotation after
+    /// `failure_threshold` consecutive failures and restored on the next success.
+    /// Returns whether the validator is healthy afterwards.
+    pub async fn record_probe_result(
+        &self,
+        endpoint: &str,
+        success: bool,
+        failure_threshold: u32,
+    ) -> bool {
+        let id = ValidatorId {
+            endpoint: endpoint.to_string(),
+        };
+        let mut validators = self.validators.write().await;
+        let Some(stats) = validators.get_mut(&id) else {
+            warn!(endpoint = %endpoint, "probe result for unregistered validator");
+            return false;
+        };
+
+        if success {
+            if !stats.healthy {
+                info!(endpoint = %endpoint, "validator passed readiness probe; restored to rotation");
+            }
+            stats.consecutive_probe_failures = 0;
+            stats.healthy = true;
+            stats.last_seen = Instant::now();
+        } else {
+            stats.consecutive_probe_failures = stats.consecutive_probe_failures.saturating_add(1);
+            if stats.healthy && stats.consecutive_probe_failures >= failure_threshold {
+                stats.healthy = false;
+                warn!(
+                    endpoint = %endpoint,
+                    failures = stats.consecutive_probe_failures,
+                    "validator failed readiness probes; removed from rotation"
+                );
+            }
+        }
+        stats.healthy
+    }
+
+    /// Whether a registered validator is currently in rotation
+    pub async fn is_healthy(&self, endpoint: &str) -> bool {
+        let id = ValidatorId {
+            endpoint: endpoint.to_string(),
+        };
+        self.validators
+            .read()
+            .await
+            .get(&id)
+            .map(|stats| stats.healthy)
+            .unwrap_or(false)
+    }
+
+    /// Healthy validators seen within the staleness window
+    pub async fn healthy_count(&self) -> usize {
+        let now = Instant::now();
+        self.validators
+            .read()
+            .await
+            .values()
+            .filter(|stats| {
+                stats.healthy
+                    && now.duration_since(stats.last_seen).as_secs() < self.max_staleness_secs
+            })
+            .count()
+    }
+
+    /// Fail fast unless at least `min_healthy` validators are healthy and recently seen
+    pub async fn require_healthy(&self, min_healthy: usize) -> Result<usize, AggrError> {
+        let healthy = self.healthy_count().await;
+        if healthy < min_healthy {
+            return Err(AggrError::NoHealthyValidators {
+                healthy,
+                required: min_healthy,
+            });
+        }
+        Ok(healthy)
+    }
+
     /// Select the best validator based on EWMA latency
     pub async fn select_best(&self) -> Option<String> {
         let validators = self.validators.read().await;
@@ -185,6 +274,33 @@ impl ValidatorSelector {
     }
 }
 
+/// Periodically probe a gRPC endpoint and move it in or out of validator rotation
+pub fn spawn_readiness_probe(
+    mut grpc: GrpcClients,
+    selector: Arc<ValidatorSelector>,
+    endpoint: String,
+    interval: Duration,
+    failure_threshold: u32,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let success = match grpc.readiness_probe().await {
+                Ok(()) => true,
+                Err(err) => {
+                    debug!(endpoint = %endpoint, error = %err, "gRPC readiness probe failed");
+                    false
+                }
+            };
+            selector
+                .record_probe_result(&endpoint, success, failure_threshold)
+                .await;
+        }
+    })
+}
+
 impl Default for ValidatorSelector {
     fn default() -> Self {
         Self::new(0.2, 300, 5) // alpha=0.2, 5min staleness, 5 min observations
@@ -5,21 +5,92 @@
 // Numan Thabit 2025 Nov
 
 use crate::errors::AggrError;
-use base64::{engine::general_purpose::STANDARD_NO_PAD as B64, Engine as _};
-use blake2::{Blake2b512, Digest};
-use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use crate::transport::http::{http_client, read_capped_body, HttpClientConfig};
+use base64::{
+    engine::general_purpose::STANDARD, engine::general_purpose::STANDARD_NO_PAD as B64, Engine as _,
+};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::{Signer as _, SigningKey, VerifyingKey};
+use futures::future::BoxFuture;
 use hex::FromHex;
+use k256::ecdsa::{Signature as Secp256k1Signature, SigningKey as Secp256k1SigningKey};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_sdk::types::base_types::SuiAddress;
+use url::Url;
 
-const INTENT_SCOPE_TRANSACTION_DATA: u8 = 0x00;
 const INTENT_VERSION: u8 = 0x00;
 const INTENT_APP_ID_SUI: u8 = 0x00;
 
-/// Construct the Sui "intent message" = 3-byte intent header || BCS TransactionData bytes.
-/// Hash to 32 bytes with Blake2b, then sign with Ed25519. Output serialized signature
+/// What a signature authorizes. The scope is the first intent byte, so a
+/// signature over a personal message can never be replayed as a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentScope {
+    TransactionData,
+    PersonalMessage,
+}
+
+impl IntentScope {
+    pub fn byte(self) -> u8 {
+        match self {
+            IntentScope::TransactionData => 0x00,
+            IntentScope::PersonalMessage => 0x03,
+        }
+    }
+}
+
+/// Signature scheme of a signing key; selects the flag byte and key format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyScheme {
+    #[default]
+    Ed25519,
+    Secp256k1,
+}
+
+impl KeyScheme {
+    /// Leading byte of serialized signatures and address preimages
+    pub fn flag(self) -> u8 {
+        match self {
+            KeyScheme::Ed25519 => 0x00,
+            KeyScheme::Secp256k1 => 0x01,
+        }
+    }
+}
+
+/// Blake2b-256 of `scope || version || app_id || payload`
+fn intent_digest(scope: IntentScope, payload: &[u8]) -> [u8; 32] {
+    let mut intent = Vec::with_capacity(3 + payload.len());
+    intent.push(scope.byte());
+    intent.push(INTENT_VERSION);
+    intent.push(INTENT_APP_ID_SUI);
+    intent.extend_from_slice(payload);
+    Blake2b::<U32>::digest(&intent).into()
+}
+
+fn secp256k1_signing_key(secret_hex: &str) -> Result<Secp256k1SigningKey, AggrError> {
+    let sk_bytes = <[u8; 32]>::from_hex(secret_hex)
+        .map_err(|e| AggrError::Signing(format!("bad hex key: {e}")))?;
+    Secp256k1SigningKey::from_slice(&sk_bytes)
+        .map_err(|e| AggrError::Signing(format!("bad secp256k1 key: {e}")))
+}
+
+/// Compressed SEC1 public key of a secp256k1 signing key
+fn secp256k1_public_key(signing_key: &Secp256k1SigningKey) -> [u8; 33] {
+    let point = signing_key.verifying_key().to_encoded_point(true);
+    let mut public_key = [0u8; 33];
+    public_key.copy_from_slice(point.as_bytes());
+    public_key
+}
+
+/// Construct the Sui "intent message" = 3-byte intent header || BCS payload bytes.
+/// Hash to 32 bytes with Blake2b-256, then sign with Ed25519. Output serialized signature
 /// format: `flag || signature || pubkey` where flag=0x00 for Ed25519.
-/// See Sui signatures spec.  [oai_citation:0‡Sui Documentation](https://docs.sui.io/concepts/cryptography/transaction-auth/signatures?utm_source=chatgpt.com)
-pub fn sign_tx_bcs_ed25519_to_serialized_signature(
-    tx_bcs: &[u8],
+/// See Sui signatures spec: https://docs.sui.io/concepts/cryptography/transaction-auth/signatures
+pub fn sign_intent_ed25519(
+    scope: IntentScope,
+    payload: &[u8],
     secret_hex: &str,
 ) -> Result<(Vec<u8>, [u8; 32]), AggrError> {
     let sk_bytes = <[u8; 32]>::from_hex(secret_hex)
@@ -27,20 +98,9 @@ pub fn sign_tx_bcs_ed25519_to_serialized_signature(
     let signing_key = SigningKey::from_bytes(&sk_bytes);
     let vk: VerifyingKey = signing_key.verifying_key();
 
-    // Compose intent message bytes.
-    let mut intent = Vec::with_capacity(3 + tx_bcs.len());
-    intent.push(INTENT_SCOPE_TRANSACTION_DATA);
-    intent.push(INTENT_VERSION);
-    intent.push(INTENT_APP_ID_SUI);
-    intent.extend_from_slice(tx_bcs);
-
-    // Blake2b-256 hash of intent message.
-    let mut hasher = Blake2b512::new();
-    hasher.update(&intent);
-    let hash_result = hasher.finalize();
-    // Take first 32 bytes for 256-bit hash
-    let mut digest = [0u8; 32];
-    digest.copy_from_slice(&hash_result[..32]);
+    // Blake2b-256 of the intent message. Truncating Blake2b-512 is not the
+    // same function: the output length is part of Blake2b's parameters.
+    let digest = intent_digest(scope, payload);
 
     // Sign the digest.
     let sig = signing_key.sign(&digest);
@@ -50,13 +110,352 @@ pub fn sign_tx_bcs_ed25519_to_serialized_signature(
 
     // Serialized signature per Sui spec: flag || signature || pubkey
     let mut serialized = Vec::with_capacity(1 + 64 + 32);
-    serialized.push(0x00); // Ed25519 flag
+    serialized.push(KeyScheme::Ed25519.flag());
     serialized.extend_from_slice(&sig_bytes);
     serialized.extend_from_slice(&pk_bytes);
 
     Ok((serialized, pk_bytes))
 }
 
+/// Sign an intent message with secp256k1: ECDSA over SHA-256 of the Blake2b-256
+/// intent digest, as Sui verifies it. Output format: `flag || signature || pubkey`
+/// with flag=0x01, the 64-byte `r || s` signature normalized to low-s, and the
+/// 33-byte compressed public key. Sui's format carries no recovery id; verifiers
+/// use the attached public key.
+pub fn sign_intent_secp256k1(
+    scope: IntentScope,
+    payload: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, [u8; 33]), AggrError> {
+    let signing_key = secp256k1_signing_key(secret_hex)?;
+    let digest = intent_digest(scope, payload);
+
+    let sig: Secp256k1Signature = k256::ecdsa::signature::Signer::sign(&signing_key, &digest);
+    let sig = sig.normalize_s().unwrap_or(sig);
+    let pk_bytes = secp256k1_public_key(&signing_key);
+
+    let mut serialized = Vec::with_capacity(1 + 64 + 33);
+    serialized.push(KeyScheme::Secp256k1.flag());
+    serialized.extend_from_slice(&sig.to_bytes());
+    serialized.extend_from_slice(&pk_bytes);
+
+    Ok((serialized, pk_bytes))
+}
+
+/// Sign BCS TransactionData bytes under the transaction intent with secp256k1
+pub fn sign_tx_bcs_secp256k1_to_serialized_signature(
+    tx_bcs: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, [u8; 33]), AggrError> {
+    sign_intent_secp256k1(IntentScope::TransactionData, tx_bcs, secret_hex)
+}
+
+/// Sign BCS TransactionData bytes with a key of either scheme, returning the
+/// serialized signature
+pub fn sign_tx_bcs_to_serialized_signature(
+    scheme: KeyScheme,
+    tx_bcs: &[u8],
+    secret_hex: &str,
+) -> Result<Vec<u8>, AggrError> {
+    match scheme {
+        KeyScheme::Ed25519 => {
+            sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, secret_hex).map(|(sig, _)| sig)
+        }
+        KeyScheme::Secp256k1 => {
+            sign_tx_bcs_secp256k1_to_serialized_signature(tx_bcs, secret_hex).map(|(sig, _)| sig)
+        }
+    }
+}
+
+/// Sign BCS TransactionData bytes under the transaction intent
+pub fn sign_tx_bcs_ed25519_to_serialized_signature(
+    tx_bcs: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, [u8; 32]), AggrError> {
+    sign_intent_ed25519(IntentScope::TransactionData, tx_bcs, secret_hex)
+}
+
+/// BCS encoding of a personal message: the bytes as a length-prefixed vector
+fn personal_message_payload(message: &[u8]) -> Result<Vec<u8>, AggrError> {
+    bcs::to_bytes(message).map_err(|e| AggrError::Signing(format!("encode personal message: {e}")))
+}
+
+/// Sign an arbitrary message under the personal-message intent, as wallets do
+/// for off-chain authentication. The message is BCS-encoded as a byte vector.
+pub fn sign_personal_message_ed25519(
+    message: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, [u8; 32]), AggrError> {
+    let payload = personal_message_payload(message)?;
+    sign_intent_ed25519(IntentScope::PersonalMessage, &payload, secret_hex)
+}
+
+/// Sign an arbitrary message under the personal-message intent with secp256k1
+pub fn sign_personal_message_secp256k1(
+    message: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, [u8; 33]), AggrError> {
+    let payload = personal_message_payload(message)?;
+    sign_intent_secp256k1(IntentScope::PersonalMessage, &payload, secret_hex)
+}
+
+/// Sign a personal message with a key of either scheme, returning the
+/// serialized signature and the public key
+pub fn sign_personal_message(
+    scheme: KeyScheme,
+    message: &[u8],
+    secret_hex: &str,
+) -> Result<(Vec<u8>, Vec<u8>), AggrError> {
+    match scheme {
+        KeyScheme::Ed25519 => sign_personal_message_ed25519(message, secret_hex)
+            .map(|(sig, public_key)| (sig, public_key.to_vec())),
+        KeyScheme::Secp256k1 => sign_personal_message_secp256k1(message, secret_hex)
+            .map(|(sig, public_key)| (sig, public_key.to_vec())),
+    }
+}
+
+/// Sui address controlled by a public key: Blake2b-256 of `flag || pubkey`
+pub fn address_from_public_key(
+    scheme: KeyScheme,
+    public_key: &[u8],
+) -> Result<SuiAddress, AggrError> {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([scheme.flag()]);
+    hasher.update(public_key);
+    SuiAddress::from_bytes(hasher.finalize())
+        .map_err(|e| AggrError::Signing(format!("derive address: {e}")))
+}
+
+/// Sui address controlled by an Ed25519 key: Blake2b-256 of `0x00 || pubkey`
+pub fn ed25519_address(secret_hex: &str) -> Result<SuiAddress, AggrError> {
+    let sk_bytes = <[u8; 32]>::from_hex(secret_hex)
+        .map_err(|e| AggrError::Signing(format!("bad hex key: {e}")))?;
+    let public_key = SigningKey::from_bytes(&sk_bytes).verifying_key().to_bytes();
+    address_from_public_key(KeyScheme::Ed25519, &public_key)
+}
+
+/// Sui address controlled by a secp256k1 key: Blake2b-256 of `0x01 || compressed pubkey`
+pub fn secp256k1_address(secret_hex: &str) -> Result<SuiAddress, AggrError> {
+    let public_key = secp256k1_public_key(&secp256k1_signing_key(secret_hex)?);
+    address_from_public_key(KeyScheme::Secp256k1, &public_key)
+}
+
+/// Sui address controlled by a key of the given scheme
+pub fn address_for(scheme: KeyScheme, secret_hex: &str) -> Result<SuiAddress, AggrError> {
+    match scheme {
+        KeyScheme::Ed25519 => ed25519_address(secret_hex),
+        KeyScheme::Secp256k1 => secp256k1_address(secret_hex),
+    }
+}
+
+/// Scheme and public key carried by a serialized signature (`flag || signature || pubkey`)
+pub fn signature_public_key(serialized: &[u8]) -> Result<(KeyScheme, &[u8]), AggrError> {
+    let (scheme, key_len) = match serialized.first() {
+        Some(&flag) if flag == KeyScheme::Ed25519.flag() => (KeyScheme::Ed25519, 32),
+        Some(&flag) if flag == KeyScheme::Secp256k1.flag() => (KeyScheme::Secp256k1, 33),
+        Some(flag) => {
+            return Err(AggrError::Signing(format!(
+                "unsupported signature flag {flag:#04x}"
+            )))
+        }
+        None => return Err(AggrError::Signing("empty signature".to_string())),
+    };
+    if serialized.len() != 1 + 64 + key_len {
+        return Err(AggrError::Signing(format!(
+            "{scheme:?} signature is {} bytes, expected {}",
+            serialized.len(),
+            1 + 64 + key_len
+        )));
+    }
+    Ok((scheme, &serialized[65..]))
+}
+
+/// Source of signatures. Implementations may hold a key in process or
+/// delegate to an HSM or signing service, so callers never see key material.
+pub trait Signer: Send + Sync {
+    /// Serialized signature (`flag || signature || pubkey`) over BCS
+    /// TransactionData bytes under the transaction intent
+    fn sign_intent<'a>(&'a self, tx_bcs: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, AggrError>>;
+
+    /// Serialized signature over an arbitrary message under the
+    /// personal-message intent
+    fn sign_personal_message<'a>(
+        &'a self,
+        message: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, AggrError>>;
+}
+
+/// Signs in process with a hex-encoded Ed25519 key
+pub struct LocalEd25519Signer {
+    secret_hex: String,
+}
+
+impl LocalEd25519Signer {
+    pub fn new(secret_hex: impl Into<String>) -> Self {
+        Self {
+            secret_hex: secret_hex.into(),
+        }
+    }
+}
+
+impl Signer for LocalEd25519Signer {
+    fn sign_intent<'a>(&'a self, tx_bcs: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        let signature = sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, &self.secret_hex)
+            .map(|(signature, _)| signature);
+        Box::pin(async move { signature })
+    }
+
+    fn sign_personal_message<'a>(
+        &'a self,
+        message: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        let signature = sign_personal_message_ed25519(message, &self.secret_hex)
+            .map(|(signature, _)| signature);
+        Box::pin(async move { signature })
+    }
+}
+
+/// Signs in process with a hex-encoded secp256k1 key
+pub struct LocalSecp256k1Signer {
+    secret_hex: String,
+}
+
+impl LocalSecp256k1Signer {
+    pub fn new(secret_hex: impl Into<String>) -> Self {
+        Self {
+            secret_hex: secret_hex.into(),
+        }
+    }
+}
+
+impl Signer for LocalSecp256k1Signer {
+    fn sign_intent<'a>(&'a self, tx_bcs: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        let signature = sign_tx_bcs_secp256k1_to_serialized_signature(tx_bcs, &self.secret_hex)
+            .map(|(signature, _)| signature);
+        Box::pin(async move { signature })
+    }
+
+    fn sign_personal_message<'a>(
+        &'a self,
+        message: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        let signature = sign_personal_message_secp256k1(message, &self.secret_hex)
+            .map(|(signature, _)| signature);
+        Box::pin(async move { signature })
+    }
+}
+
+/// In-process signer for a hex key of the given scheme
+pub fn local_signer(scheme: KeyScheme, secret_hex: impl Into<String>) -> Arc<dyn Signer> {
+    match scheme {
+        KeyScheme::Ed25519 => Arc::new(LocalEd25519Signer::new(secret_hex)),
+        KeyScheme::Secp256k1 => Arc::new(LocalSecp256k1Signer::new(secret_hex)),
+    }
+}
+
+/// Default deadline for one remote signing request
+pub const DEFAULT_REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Largest remote signer reply read; a signature reply is well under this
+const MAX_REMOTE_SIGNER_REPLY_BYTES: usize = 16 * 1024;
+
+/// Body POSTed to a remote signer
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignRequest {
+    /// `transaction_data` or `personal_message`; the signer must apply that intent
+    pub intent: String,
+    /// Base64 BCS TransactionData bytes, for the transaction intent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_bytes: Option<String>,
+    /// Base64 raw message bytes, for the personal-message intent; the signer
+    /// BCS-encodes them as a byte vector before signing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Reply expected from a remote signer
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignResponse {
+    /// Base64 serialized signature (`flag || signature || pubkey`)
+    pub signature: String,
+}
+
+/// Delegates signing to an external service (e.g. an HSM front end) by POSTing
+/// the transaction bytes as JSON to `url`. Signatures are only accepted when
+/// their public key belongs to `address`, the account the service signs for.
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: Url,
+    address: SuiAddress,
+}
+
+impl RemoteSigner {
+    pub fn new(url: Url, address: SuiAddress) -> Result<Self, AggrError> {
+        Self::with_timeout(url, address, DEFAULT_REMOTE_SIGNER_TIMEOUT)
+    }
+
+    pub fn with_timeout(
+        url: Url,
+        address: SuiAddress,
+        timeout: Duration,
+    ) -> Result<Self, AggrError> {
+        let client = http_client(&HttpClientConfig::default().with_timeout(timeout))?;
+        Ok(Self {
+            client,
+            url,
+            address,
+        })
+    }
+
+    async fn request_signature(&self, body: RemoteSignRequest) -> Result<Vec<u8>, AggrError> {
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AggrError::Signing(format!("remote signer request failed: {e}")))?;
+        let body = read_capped_body(response, MAX_REMOTE_SIGNER_REPLY_BYTES)
+            .await
+            .map_err(|e| AggrError::Signing(format!("remote signer reply: {e}")))?;
+        let reply: RemoteSignResponse = serde_json::from_slice(&body)
+            .map_err(|e| AggrError::Signing(format!("remote signer reply: {e}")))?;
+        let signature = STANDARD.decode(reply.signature.trim()).map_err(|e| {
+            AggrError::Signing(format!("remote signer signature is not base64: {e}"))
+        })?;
+        let (scheme, public_key) = signature_public_key(&signature)?;
+        let signer = address_from_public_key(scheme, public_key)?;
+        if signer != self.address {
+            return Err(AggrError::Signing(format!(
+                "remote signer key belongs to {signer}, not the configured address {}",
+                self.address
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn sign_intent<'a>(&'a self, tx_bcs: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        Box::pin(self.request_signature(RemoteSignRequest {
+            intent: "transaction_data".to_string(),
+            tx_bytes: Some(STANDARD.encode(tx_bcs)),
+            message: None,
+        }))
+    }
+
+    fn sign_personal_message<'a>(
+        &'a self,
+        message: &'a [u8],
+    ) -> BoxFuture<'a, Result<Vec<u8>, AggrError>> {
+        Box::pin(self.request_signature(RemoteSignRequest {
+            intent: "personal_message".to_string(),
+            tx_bytes: None,
+            message: Some(STANDARD.encode(message)),
+        }))
+    }
+}
+
 /// Base64 for JSON-RPC submit.
 pub fn serialize_signature_b64(sig_ser: &[u8]) -> String {
     B64.encode(sig_ser)
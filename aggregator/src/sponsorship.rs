@@ -5,15 +5,97 @@
 // Numan Thabit 2025 Nov
 
 use crate::errors::AggrError;
-use crate::signing::sign_tx_bcs_ed25519_to_serialized_signature;
+use crate::signing::Signer;
+use crate::venues::adapter::DeepBookAdapter;
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use sui_sdk::types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_sdk::types::transaction::TransactionData;
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
+
+/// Default cap on users with tracked abuse metrics per sponsor
+pub const DEFAULT_MAX_TRACKED_USERS: usize = 100_000;
+/// Default time an idempotency key is remembered as already debited
+pub const DEFAULT_DEBIT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(600);
+/// Default interval between sponsor gas coin refreshes
+pub const DEFAULT_GAS_COIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Default smallest coin balance, in MIST, usable as sponsor gas (0.05 SUI)
+pub const DEFAULT_MIN_GAS_COIN_BALANCE: u64 = 50_000_000;
+/// Default number of usable gas coins below which a refresh warns
+pub const DEFAULT_GAS_COIN_LOW_WATER: usize = 2;
+
+/// How the sponsor's gas coin pool is kept current
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasCoinRefresh {
+    pub interval: Duration,
+    /// Coins holding less than this many MIST are not used for gas
+    pub min_balance: u64,
+    /// Warn when fewer usable coins than this remain
+    pub low_water: usize,
+}
+
+impl Default for GasCoinRefresh {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_GAS_COIN_REFRESH_INTERVAL,
+            min_balance: DEFAULT_MIN_GAS_COIN_BALANCE,
+            low_water: DEFAULT_GAS_COIN_LOW_WATER,
+        }
+    }
+}
+
+/// Route budgets a route class draws on: its own and, for a `venue:pool`
+/// class, the venue's
+pub fn route_budget_keys(route_class: &str) -> Vec<&str> {
+    match route_class.split_once(':') {
+        Some((venue, _)) => vec![route_class, venue],
+        None => vec![route_class],
+    }
+}
+
+/// Check that gas coins (ref and balance in MIST) together cover `gas_budget`.
+/// Sui gas budgets are denominated in MIST, so the reference price is already
+/// folded in; all coins are smashed into one when the transaction runs.
+pub fn ensure_gas_coverage(
+    gas_coins: &[(ObjectRef, u64)],
+    gas_budget: u64,
+) -> Result<(), AggrError> {
+    let available = gas_coins
+        .iter()
+        .fold(0u64, |sum, (_, balance)| sum.saturating_add(*balance));
+    if available < gas_budget {
+        return Err(AggrError::InsufficientSponsorGas {
+            available,
+            required: gas_budget,
+        });
+    }
+    Ok(())
+}
+
+/// Coins of at least `min_balance` MIST, largest first
+pub fn usable_gas_coins(coins: &[(ObjectID, u64)], min_balance: u64) -> Vec<ObjectID> {
+    let mut usable: Vec<_> = coins
+        .iter()
+        .filter(|(_, balance)| *balance >= min_balance)
+        .collect();
+    usable.sort_by(|a, b| b.1.cmp(&a.1));
+    usable.into_iter().map(|(id, _)| *id).collect()
+}
+
+/// What a sponsored order does when its gasless transaction cannot be built
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GaslessFallback {
+    /// Fail the order
+    #[default]
+    Fail,
+    /// Log and build a transaction the sender pays for, as when the sponsor declines
+    UserPaid,
+}
 
 /// Sponsored transaction request metadata
 #[derive(Debug, Clone)]
@@ -95,6 +177,8 @@ struct AbuseMetrics {
     window_start: Instant,
     /// Window duration
     window_duration: Duration,
+    /// Last time this user requested or spent sponsorship
+    last_seen: Instant,
 }
 
 impl AbuseMetrics {
@@ -104,9 +188,15 @@ impl AbuseMetrics {
             gas_spent: 0,
             window_start: Instant::now(),
             window_duration,
+            last_seen: Instant::now(),
         }
     }
 
+    /// Window over, so the counts no longer constrain anything
+    fn is_idle(&self) -> bool {
+        self.window_start.elapsed() >= self.window_duration
+    }
+
     fn record_tx(&mut self, gas: u64) {
         // Reset if window expired
         if self.window_start.elapsed() >= self.window_duration {
@@ -131,8 +221,8 @@ impl AbuseMetrics {
 
 /// Sponsored transaction manager
 pub struct SponsorshipManager {
-    /// Sponsor's private key (hex-encoded Ed25519)
-    sponsor_key_hex: String,
+    /// Signs as the sponsor; a local key or an external signer
+    signer: Arc<dyn Signer>,
     /// Sponsor's address
     sponsor_address: SuiAddress,
     /// Sponsor's gas coins (object IDs)
@@ -147,6 +237,10 @@ pub struct SponsorshipManager {
     abuse_config: AbuseConfig,
     /// Reference gas price (updated periodically)
     gas_price: Arc<RwLock<u64>>,
+    /// Idempotency keys whose execution was already debited, and when
+    debited: Arc<RwLock<HashMap<String, Instant>>>,
+    /// How long a debited idempotency key suppresses further debits
+    debit_ttl: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -157,6 +251,8 @@ pub struct AbuseConfig {
     pub max_gas_per_window: u64,
     /// Abuse detection window duration
     pub window_duration: Duration,
+    /// Most users tracked at once; least recently seen are evicted beyond this
+    pub max_tracked_users: usize,
 }
 
 impl Default for AbuseConfig {
@@ -165,6 +261,7 @@ impl Default for AbuseConfig {
             max_tx_per_window: 1000,
             max_gas_per_window: 100_000_000_000, // 100B gas units
             window_duration: Duration::from_secs(3600), // 1 hour
+            max_tracked_users: DEFAULT_MAX_TRACKED_USERS,
         }
     }
 }
@@ -172,13 +269,13 @@ impl Default for AbuseConfig {
 impl SponsorshipManager {
     /// Create a new sponsorship manager
     pub fn new(
-        sponsor_key_hex: String,
+        signer: Arc<dyn Signer>,
         sponsor_address: SuiAddress,
         gas_price: u64,
         abuse_config: AbuseConfig,
     ) -> Result<Self> {
         Ok(Self {
-            sponsor_key_hex,
+            signer,
             sponsor_address,
             gas_coins: Arc::new(RwLock::new(Vec::new())),
             user_budgets: Arc::new(RwLock::new(HashMap::new())),
@@ -186,9 +283,17 @@ impl SponsorshipManager {
             abuse_metrics: Arc::new(RwLock::new(HashMap::new())),
             abuse_config,
             gas_price: Arc::new(RwLock::new(gas_price)),
+            debited: Arc::new(RwLock::new(HashMap::new())),
+            debit_ttl: DEFAULT_DEBIT_IDEMPOTENCY_TTL,
         })
     }
 
+    /// Remember debited idempotency keys for `ttl`
+    pub fn with_debit_ttl(mut self, ttl: Duration) -> Self {
+        self.debit_ttl = ttl;
+        self
+    }
+
     /// Update sponsor's gas coins
     pub async fn update_gas_coins(&self, coins: Vec<ObjectID>) {
         let mut gas_coins = self.gas_coins.write().await;
@@ -196,6 +301,29 @@ impl SponsorshipManager {
         info!(count = gas_coins.len(), "updated sponsor gas coins");
     }
 
+    /// Replace the gas coin pool with the usable coins among `coins` (object
+    /// ID and balance). Returns how many are usable.
+    pub async fn refresh_gas_coins(
+        &self,
+        coins: &[(ObjectID, u64)],
+        refresh: &GasCoinRefresh,
+    ) -> usize {
+        let usable = usable_gas_coins(coins, refresh.min_balance);
+        let count = usable.len();
+        if count < refresh.low_water {
+            warn!(
+                sponsor = %self.sponsor_address,
+                usable = count,
+                owned = coins.len(),
+                low_water = refresh.low_water,
+                min_balance = refresh.min_balance,
+                "sponsor gas coin pool below low-water mark"
+            );
+        }
+        self.update_gas_coins(usable).await;
+        count
+    }
+
     /// Get current sponsor gas coin IDs
     pub async fn gas_coin_ids(&self) -> Vec<ObjectID> {
         self.gas_coins.read().await.clone()
@@ -263,9 +391,7 @@ impl SponsorshipManager {
         // Check abuse metrics
         {
             let mut metrics = self.abuse_metrics.write().await;
-            let user_metrics = metrics
-                .entry(req.user_address)
-                .or_insert_with(|| AbuseMetrics::new(self.abuse_config.window_duration));
+            let user_metrics = self.track_user(&mut metrics, req.user_address);
 
             if !user_metrics.check_limits(
                 self.abuse_config.max_tx_per_window,
@@ -281,18 +407,21 @@ impl SponsorshipManager {
             }
         }
 
-        // Check route budget
+        // Check route class budgets
         {
             let mut budgets = self.route_budgets.write().await;
-            if let Some(budget) = budgets.get_mut(&req.route_plan_id) {
-                if !budget.can_spend(req.estimated_gas) {
-                    warn!(
-                        route = %req.route_plan_id,
-                        estimated_gas = req.estimated_gas,
-                        remaining = budget.remaining(),
-                        "route budget exceeded"
-                    );
-                    return Ok(false);
+            for key in route_budget_keys(&req.route_plan_id) {
+                if let Some(budget) = budgets.get_mut(key) {
+                    if !budget.can_spend(req.estimated_gas) {
+                        warn!(
+                            route = %req.route_plan_id,
+                            budget = key,
+                            estimated_gas = req.estimated_gas,
+                            remaining = budget.remaining(),
+                            "route budget exceeded"
+                        );
+                        return Ok(false);
+                    }
                 }
             }
         }
@@ -312,30 +441,37 @@ impl SponsorshipManager {
     /// Build sponsored TransactionData with sponsor's gas objects
     /// Returns the BCS bytes of the TransactionData ready for signing
     /// Both user and sponsor sign the same TransactionData bytes
+    ///
+    /// Every coin in `gas_coins` (ref and balance in MIST) is part of the gas
+    /// payment, so several small coins can fund one transaction together.
     pub async fn build_sponsored_transaction_data(
         &self,
         programmable: sui_sdk::types::transaction::TransactionKind,
         sender: SuiAddress,
-        gas_object_refs: Vec<ObjectRef>,
+        gas_coins: Vec<(ObjectRef, u64)>,
         gas_budget: u64,
     ) -> Result<Vec<u8>> {
-        if gas_object_refs.is_empty() {
+        if gas_coins.is_empty() {
             anyhow::bail!("no sponsor gas object refs provided");
         }
+        ensure_gas_coverage(&gas_coins, gas_budget)?;
 
         // Get current gas price
         let gas_price = *self.gas_price.read().await;
 
-        // Build sponsored TransactionData with sponsor's gas
-        // TransactionData::new expects a single gas object ref, not a Vec
-        // For sponsored transactions, we use the first gas object
-        let gas_object_ref = gas_object_refs
-            .first()
-            .copied()
-            .ok_or_else(|| anyhow::anyhow!("no gas object refs provided"))?;
-
-        let sponsored_tx_data =
-            TransactionData::new(programmable, sender, gas_object_ref, gas_budget, gas_price);
+        // Build sponsored TransactionData paying gas from all of the sponsor's coins
+        let gas_payment = gas_coins
+            .into_iter()
+            .map(|(object_ref, _)| object_ref)
+            .collect();
+        let sponsored_tx_data = TransactionData::new_with_gas_coins_allow_sponsor(
+            programmable,
+            sender,
+            gas_payment,
+            gas_budget,
+            gas_price,
+            self.sponsor_address,
+        );
 
         // Serialize sponsored transaction
         let sponsored_tx_bcs =
@@ -346,35 +482,32 @@ impl SponsorshipManager {
 
     /// Sign a sponsored transaction with the sponsor's key
     /// The transaction bytes should be from build_sponsored_transaction_data
-    pub fn sign_sponsored_transaction(&self, tx_bcs: &[u8]) -> Result<Vec<u8>, AggrError> {
-        let (sponsor_sig_bytes, _) =
-            sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, &self.sponsor_key_hex)?;
-        Ok(sponsor_sig_bytes)
+    pub async fn sign_sponsored_transaction(&self, tx_bcs: &[u8]) -> Result<Vec<u8>, AggrError> {
+        self.signer.sign_intent(tx_bcs).await
     }
 
     /// Complete sponsored transaction flow:
     /// 1. Build TransactionData with sponsor's gas
     /// 2. Sign with sponsor's key
-    /// 3. Record spending
+    ///
+    /// Nothing is debited here; a built transaction may never execute. Spending
+    /// is applied once execution reports the gas used.
     ///
     /// Returns (tx_bcs, sponsor_signature_bytes)
     pub async fn build_and_sign_sponsored_transaction(
         &self,
         programmable: sui_sdk::types::transaction::TransactionKind,
         sender: SuiAddress,
-        gas_object_refs: Vec<ObjectRef>,
+        gas_coins: Vec<(ObjectRef, u64)>,
         gas_budget: u64,
     ) -> Result<(Vec<u8>, Vec<u8>)> {
         // Build TransactionData
         let tx_bcs = self
-            .build_sponsored_transaction_data(programmable, sender, gas_object_refs, gas_budget)
+            .build_sponsored_transaction_data(programmable, sender, gas_coins, gas_budget)
             .await?;
 
         // Sign with sponsor's key
-        let sponsor_sig_bytes = self.sign_sponsored_transaction(&tx_bcs)?;
-
-        // Record spending
-        self.apply_spending(sender, None, gas_budget).await;
+        let sponsor_sig_bytes = self.sign_sponsored_transaction(&tx_bcs).await?;
 
         info!(
             user = %sender,
@@ -385,36 +518,111 @@ impl SponsorshipManager {
         Ok((tx_bcs, sponsor_sig_bytes))
     }
 
-    /// Record spending for a user
+    /// Record spending against the user's budget and the route class budgets
+    /// together, so no check sees one debited without the other
     pub async fn apply_spending(&self, user: SuiAddress, route_class: Option<&str>, gas: u64) {
-        self.record_spending_internal(user, gas).await;
-        if let Some(route) = route_class {
-            let mut budgets = self.route_budgets.write().await;
-            if let Some(budget) = budgets.get_mut(route) {
-                budget.spend(gas);
-            }
-        }
-    }
-
-    async fn record_spending_internal(&self, user: SuiAddress, gas: u64) {
-        // Update user budget
         {
-            let mut budgets = self.user_budgets.write().await;
-            if let Some(budget) = budgets.get_mut(&user) {
+            let mut user_budgets = self.user_budgets.write().await;
+            let mut route_budgets = self.route_budgets.write().await;
+            if let Some(budget) = user_budgets.get_mut(&user) {
                 budget.spend(gas);
             }
+            for key in route_class.map(route_budget_keys).unwrap_or_default() {
+                if let Some(budget) = route_budgets.get_mut(key) {
+                    budget.spend(gas);
+                }
+            }
         }
 
         // Update abuse metrics
         {
             let mut metrics = self.abuse_metrics.write().await;
-            let user_metrics = metrics
-                .entry(user)
-                .or_insert_with(|| AbuseMetrics::new(self.abuse_config.window_duration));
-            user_metrics.record_tx(gas);
+            self.track_user(&mut metrics, user).record_tx(gas);
         }
     }
 
+    /// Record spending for an executed transaction, once per idempotency key:
+    /// a retry of the same logical request is not debited again while the key
+    /// is remembered. Returns whether the budget was debited.
+    pub async fn apply_spending_once(
+        &self,
+        idempotency_key: Option<&str>,
+        user: SuiAddress,
+        route_class: Option<&str>,
+        gas: u64,
+    ) -> bool {
+        if let Some(key) = idempotency_key {
+            // Held across the debit so concurrent retries cannot both pass
+            let mut debited = self.debited.write().await;
+            debited.retain(|_, at| at.elapsed() < self.debit_ttl);
+            if debited.contains_key(key) {
+                debug!(user = %user, key, gas, "sponsor already debited for idempotency key");
+                return false;
+            }
+            debited.insert(key.to_string(), Instant::now());
+            self.apply_spending(user, route_class, gas).await;
+            return true;
+        }
+        self.apply_spending(user, route_class, gas).await;
+        true
+    }
+
+    /// Metrics for `user`, making room under `max_tracked_users` for a new entry
+    fn track_user<'a>(
+        &self,
+        metrics: &'a mut HashMap<SuiAddress, AbuseMetrics>,
+        user: SuiAddress,
+    ) -> &'a mut AbuseMetrics {
+        if !metrics.contains_key(&user) && metrics.len() >= self.abuse_config.max_tracked_users {
+            metrics.retain(|_, m| !m.is_idle());
+            if metrics.len() >= self.abuse_config.max_tracked_users {
+                self.evict_least_recent(metrics);
+            }
+        }
+        let user_metrics = metrics
+            .entry(user)
+            .or_insert_with(|| AbuseMetrics::new(self.abuse_config.window_duration));
+        user_metrics.last_seen = Instant::now();
+        user_metrics
+    }
+
+    /// Drop the least recently seen user, sparing users over their limits while
+    /// anyone else can go
+    fn evict_least_recent(&self, metrics: &mut HashMap<SuiAddress, AbuseMetrics>) {
+        let within_limits = |m: &AbuseMetrics| {
+            m.check_limits(
+                self.abuse_config.max_tx_per_window,
+                self.abuse_config.max_gas_per_window,
+            )
+        };
+        let victim = metrics
+            .iter()
+            .min_by_key(|(_, m)| (!within_limits(m), m.last_seen))
+            .map(|(user, _)| *user);
+        if let Some(user) = victim {
+            metrics.remove(&user);
+            debug!(user = %user, "abuse metrics full; evicted least recently seen user");
+        }
+    }
+
+    /// Forget users whose abuse window has expired; returns how many were dropped
+    pub async fn evict_idle_abuse_metrics(&self) -> usize {
+        let mut metrics = self.abuse_metrics.write().await;
+        let before = metrics.len();
+        metrics.retain(|_, m| !m.is_idle());
+        before - metrics.len()
+    }
+
+    /// Users whose abuse metrics are currently tracked
+    pub async fn tracked_users(&self) -> usize {
+        self.abuse_metrics.read().await.len()
+    }
+
+    /// Abuse detection configuration
+    pub fn abuse_config(&self) -> &AbuseConfig {
+        &self.abuse_config
+    }
+
     /// Get sponsor address
     pub fn sponsor_address(&self) -> SuiAddress {
         self.sponsor_address
@@ -425,4 +633,107 @@ impl SponsorshipManager {
         let budgets = self.user_budgets.read().await;
         budgets.get(&user).map(|b| b.remaining())
     }
+
+    /// Get remaining budget for a route class
+    pub async fn get_route_budget_remaining(&self, route_class: &str) -> Option<u64> {
+        let budgets = self.route_budgets.read().await;
+        budgets.get(route_class).map(|b| b.remaining())
+    }
+}
+
+/// Sponsorship managers keyed by alias, for per-request sponsor selection
+#[derive(Clone, Default)]
+pub struct SponsorRegistry {
+    sponsors: HashMap<String, Arc<SponsorshipManager>>,
+}
+
+impl SponsorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `manager` under `alias`, replacing any previous sponsor with that alias
+    pub fn with_sponsor(
+        mut self,
+        alias: impl Into<String>,
+        manager: Arc<SponsorshipManager>,
+    ) -> Self {
+        self.sponsors.insert(alias.into(), manager);
+        self
+    }
+
+    /// Sponsor registered under `alias`
+    pub fn get(&self, alias: &str) -> Result<Arc<SponsorshipManager>, AggrError> {
+        self.sponsors
+            .get(alias)
+            .cloned()
+            .ok_or_else(|| AggrError::UnknownSponsor(alias.to_string()))
+    }
+
+    pub fn contains(&self, alias: &str) -> bool {
+        self.sponsors.contains_key(alias)
+    }
+
+    /// Registered aliases in sorted order
+    pub fn aliases(&self) -> Vec<String> {
+        let mut aliases: Vec<String> = self.sponsors.keys().cloned().collect();
+        aliases.sort();
+        aliases
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sponsors.is_empty()
+    }
+}
+
+/// Every SUI coin `owner` holds, as object ID and balance
+pub async fn fetch_sui_coins(
+    adapter: &DeepBookAdapter,
+    owner: SuiAddress,
+) -> Result<Vec<(ObjectID, u64)>> {
+    let mut coins = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = adapter
+            .sui_client()
+            .coin_read_api()
+            .get_coins(owner, None, cursor, None)
+            .await
+            .with_context(|| format!("list SUI coins of {owner}"))?;
+        coins.extend(
+            page.data
+                .into_iter()
+                .map(|coin| (coin.coin_object_id, coin.balance)),
+        );
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(coins)
+}
+
+/// Keep `manager`'s gas coins current by listing the sponsor's SUI coins
+/// every `refresh.interval`. A failed listing keeps the previous pool.
+pub fn spawn_gas_coin_refresher(
+    manager: Arc<SponsorshipManager>,
+    adapter: Arc<DeepBookAdapter>,
+    refresh: GasCoinRefresh,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(refresh.interval);
+        loop {
+            ticker.tick().await;
+            match fetch_sui_coins(&adapter, manager.sponsor_address()).await {
+                Ok(coins) => {
+                    manager.refresh_gas_coins(&coins, &refresh).await;
+                }
+                Err(err) => warn!(
+                    sponsor = %manager.sponsor_address(),
+                    error = %err,
+                    "failed to refresh sponsor gas coins"
+                ),
+            }
+        }
+    });
 }
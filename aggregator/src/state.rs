@@ -2,26 +2,147 @@
 //
 // Consumes gRPC SubscriptionService checkpoint stream and maintains a simple
 // in-memory reconciliation cursor. Broadcasts new checkpoints to subscribers.
+// The subscription cannot start from a cursor, so on reconnect any checkpoints
+// missed while disconnected are replayed from the ledger service.
 //
 // Numan Thabit 2025 Nov
 
-use crate::transport::grpc::{sui, GrpcClients};
+use crate::metrics::{CHECKPOINT_GAPS, CHECKPOINT_STALE_ALERTS};
+use crate::transport::grpc::{sui, EpochInfo, GrpcClients};
 use anyhow::Result;
 use futures::StreamExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, oneshot, Mutex, RwLock};
 use tracing::{debug, info, warn};
 
+/// Most checkpoints replayed after a reconnect; larger gaps resume from the live stream
+pub const MAX_CHECKPOINT_BACKFILL: u64 = 1_000;
+
+/// Cursors skipped between the last processed checkpoint and the first one seen
+/// after a reconnect. `None` when the stream resumed contiguously (or nothing was
+/// processed yet).
+pub fn missing_cursors(last: Option<u64>, first: u64) -> Option<RangeInclusive<u64>> {
+    let next = last?.checked_add(1)?;
+    (first > next).then(|| next..=first - 1)
+}
+
 #[derive(Clone)]
 pub struct CheckpointUpdate {
     pub cursor: u64,
     pub checkpoint: Option<sui::rpc::v2::Checkpoint>,
+    /// Inclusive cursor range skipped right before this checkpoint, if any
+    pub gap: Option<(u64, u64)>,
+}
+
+/// Transaction digests awaiting checkpoint inclusion
+#[derive(Clone, Default)]
+pub struct PendingConfirmations {
+    inner: Arc<Mutex<HashMap<String, oneshot::Sender<u64>>>>,
+}
+
+impl PendingConfirmations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a digest; the receiver yields the checkpoint that included it.
+    /// Register before submitting the transaction, since only checkpoints
+    /// resolved after registration are delivered.
+    pub async fn register(&self, digest: impl Into<String>) -> oneshot::Receiver<u64> {
+        let (tx, rx) = oneshot::channel();
+        self.inner.lock().await.insert(digest.into(), tx);
+        rx
+    }
+
+    /// Stop waiting on a digest
+    pub async fn forget(&self, digest: &str) {
+        self.inner.lock().await.remove(digest);
+    }
+
+    /// Resolve every pending digest present in `included` under a single lock.
+    /// Returns the number of confirmations delivered.
+    pub async fn resolve_batch<'a, I>(&self, included: I) -> usize
+    where
+        I: IntoIterator<Item = (&'a str, u64)>,
+    {
+        let mut pending = self.inner.lock().await;
+        if pending.is_empty() {
+            return 0;
+        }
+        let mut resolved = 0;
+        for (digest, checkpoint) in included {
+            if let Some(waiter) = pending.remove(digest) {
+                // Receiver may have given up; the entry is cleared either way
+                let _ = waiter.send(checkpoint);
+                resolved += 1;
+            }
+        }
+        resolved
+    }
+
+    pub async fn len(&self) -> usize {
+        self.inner.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.inner.lock().await.is_empty()
+    }
+}
+
+/// Most recent epoch seen from the ledger service; detects epoch boundaries
+#[derive(Clone, Default)]
+pub struct EpochTracker {
+    current: Arc<RwLock<Option<EpochInfo>>>,
+}
+
+impl EpochTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn current(&self) -> Option<EpochInfo> {
+        *self.current.read().await
+    }
+
+    /// Record `info`; returns the previous epoch number when this is a new epoch
+    pub async fn observe(&self, info: EpochInfo) -> Option<u64> {
+        let mut current = self.current.write().await;
+        let previous = current.replace(info).map(|seen| seen.epoch);
+        match previous {
+            Some(epoch) if epoch != info.epoch => {
+                info!(
+                    previous = epoch,
+                    epoch = info.epoch,
+                    reference_gas_price = ?info.reference_gas_price,
+                    protocol_version = ?info.protocol_version,
+                    "epoch changed"
+                );
+                Some(epoch)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct CheckpointState {
     last_cursor: Arc<RwLock<Option<u64>>>,
+    /// Inclusive cursor ranges skipped by the stream and not yet delivered
+    missed_ranges: Arc<RwLock<Vec<(u64, u64)>>>,
     tx: broadcast::Sender<CheckpointUpdate>,
+    pending: PendingConfirmations,
+    /// How long streamed checkpoint digests are buffered before matching pending confirmations
+    confirmation_window: Duration,
+    /// Wall-clock time the last checkpoint arrived (or the state was created)
+    last_update: Arc<RwLock<Instant>>,
+    /// Silence longer than this marks the stream unhealthy
+    stale_after: Option<Duration>,
+    healthy: Arc<AtomicBool>,
 }
 
 impl CheckpointState {
@@ -29,10 +150,33 @@ impl CheckpointState {
         let (tx, _) = broadcast::channel(buffer);
         Self {
             last_cursor: Arc::new(RwLock::new(None)),
+            missed_ranges: Arc::new(RwLock::new(Vec::new())),
             tx,
+            pending: PendingConfirmations::new(),
+            confirmation_window: Duration::ZERO,
+            last_update: Arc::new(RwLock::new(Instant::now())),
+            stale_after: None,
+            healthy: Arc::new(AtomicBool::new(true)),
         }
     }
 
+    /// Raise the staleness alarm when no checkpoint arrives for `interval`
+    pub fn with_stale_after(mut self, interval: Duration) -> Self {
+        self.stale_after = Some(interval);
+        self
+    }
+
+    /// Buffer checkpoint digests for `window` and resolve confirmations in one pass
+    pub fn with_confirmation_window(mut self, window: Duration) -> Self {
+        self.confirmation_window = window;
+        self
+    }
+
+    /// Pending confirmation registry fed by the checkpoint stream
+    pub fn pending(&self) -> &PendingConfirmations {
+        &self.pending
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<CheckpointUpdate> {
         self.tx.subscribe()
     }
@@ -40,6 +184,168 @@ impl CheckpointState {
     pub async fn last_cursor(&self) -> Option<u64> {
         *self.last_cursor.read().await
     }
+
+    /// False while the checkpoint stream is considered stalled
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Cursor ranges (inclusive) the stream skipped, oldest first, for reconciliation
+    /// to backfill from the indexer
+    pub async fn missed_ranges(&self) -> Vec<(u64, u64)> {
+        self.missed_ranges.read().await.clone()
+    }
+
+    /// Record `cursor` as processed and broadcast it to subscribers.
+    /// A cursor past `last + 1` records the skipped range and carries it as the
+    /// update's `gap`; a late cursor inside a missed range fills it without moving
+    /// the cursor back. Returns false for a duplicate, which is dropped.
+    pub async fn advance(&self, cursor: u64, checkpoint: Option<sui::rpc::v2::Checkpoint>) -> bool {
+        let gap = {
+            let mut last = self.last_cursor.write().await;
+            if *last >= Some(cursor) {
+                if !self.fill_missed(cursor).await {
+                    debug!(cursor, "skipping already processed checkpoint");
+                    return false;
+                }
+                debug!(cursor, "late checkpoint filled a missed range");
+                None
+            } else {
+                let gap =
+                    missing_cursors(*last, cursor).map(|range| (*range.start(), *range.end()));
+                *last = Some(cursor);
+                gap
+            }
+        };
+        if let Some((from, to)) = gap {
+            warn!(from, to, cursor, "checkpoint stream skipped cursors");
+            self.missed_ranges.write().await.push((from, to));
+        }
+        self.record_update().await;
+        let _ = self.tx.send(CheckpointUpdate {
+            cursor,
+            checkpoint,
+            gap,
+        });
+        debug!(cursor = cursor, "checkpoint advanced");
+        true
+    }
+
+    /// Remove `cursor` from the missed range containing it, splitting the range
+    async fn fill_missed(&self, cursor: u64) -> bool {
+        let mut ranges = self.missed_ranges.write().await;
+        let Some(idx) = ranges
+            .iter()
+            .position(|&(from, to)| (from..=to).contains(&cursor))
+        else {
+            return false;
+        };
+        let (from, to) = ranges.remove(idx);
+        if cursor < to {
+            ranges.insert(idx, (cursor + 1, to));
+        }
+        if cursor > from {
+            ranges.insert(idx, (from, cursor - 1));
+        }
+        true
+    }
+
+    /// Replay checkpoints missed while the stream was down, given the first cursor
+    /// seen after reconnecting. Warns when that cursor isn't `last + 1`, then fetches
+    /// each missing checkpoint in order. A failed or empty fetch, or a gap larger than
+    /// [`MAX_CHECKPOINT_BACKFILL`], stops the replay and resumes from the live stream.
+    pub async fn backfill<F, Fut>(
+        &self,
+        first_cursor: u64,
+        mut fetch: F,
+    ) -> Vec<(u64, Option<sui::rpc::v2::Checkpoint>)>
+    where
+        F: FnMut(u64) -> Fut,
+        Fut: Future<Output = Result<Option<sui::rpc::v2::Checkpoint>>>,
+    {
+        let last = self.last_cursor().await;
+        let Some(missing) = missing_cursors(last, first_cursor) else {
+            return Vec::new();
+        };
+        CHECKPOINT_GAPS.inc();
+        let count = missing.end() - missing.start() + 1;
+        warn!(
+            last_cursor = last,
+            first_cursor,
+            missing = count,
+            "checkpoint gap after reconnect"
+        );
+        if count > MAX_CHECKPOINT_BACKFILL {
+            warn!(
+                missing = count,
+                max = MAX_CHECKPOINT_BACKFILL,
+                "checkpoint gap too large to replay; resuming from live stream"
+            );
+            return Vec::new();
+        }
+        let mut replayed = Vec::with_capacity(count as usize);
+        for cursor in missing {
+            match fetch(cursor).await {
+                Ok(Some(checkpoint)) => replayed.push((cursor, Some(checkpoint))),
+                Ok(None) => {
+                    warn!(
+                        cursor,
+                        "checkpoint unavailable for replay; resuming from live stream"
+                    );
+                    break;
+                }
+                Err(err) => {
+                    warn!(cursor, error = %err, "checkpoint replay rejected; resuming from live stream");
+                    break;
+                }
+            }
+        }
+        replayed
+    }
+
+    /// Note that a checkpoint arrived, clearing any staleness alarm
+    pub async fn record_update(&self) {
+        *self.last_update.write().await = Instant::now();
+        if !self.healthy.swap(true, Ordering::Relaxed) {
+            info!("checkpoint stream recovered");
+        }
+    }
+
+    /// Compare the time since the last checkpoint against the staleness interval,
+    /// flipping the health flag and alerting on the healthy -> stale transition.
+    /// Returns the resulting health.
+    pub async fn check_staleness(&self) -> bool {
+        let Some(stale_after) = self.stale_after else {
+            return true;
+        };
+        let silence = self.last_update.read().await.elapsed();
+        if silence <= stale_after {
+            return self.is_healthy();
+        }
+        if self.healthy.swap(false, Ordering::Relaxed) {
+            CHECKPOINT_STALE_ALERTS.inc();
+            warn!(
+                silence_ms = silence.as_millis() as u64,
+                threshold_ms = stale_after.as_millis() as u64,
+                "checkpoint stream stalled"
+            );
+        }
+        false
+    }
+}
+
+/// Periodically evaluate the staleness alarm.
+/// Returns `None` when no staleness interval is configured.
+pub fn start_staleness_watchdog(state: CheckpointState) -> Option<tokio::task::JoinHandle<()>> {
+    let stale_after = state.stale_after?;
+    let period = (stale_after / 4).max(Duration::from_millis(10));
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        loop {
+            ticker.tick().await;
+            state.check_staleness().await;
+        }
+    }))
 }
 
 /// Start the checkpoint streaming task.
@@ -49,24 +355,46 @@ pub async fn start_checkpoint_streaming(
     state: CheckpointState,
 ) -> Result<tokio::task::JoinHandle<()>> {
     let handle = tokio::spawn(async move {
+        let mut included: Vec<(String, u64)> = Vec::new();
+        let mut last_flush = Instant::now();
         loop {
             match grpc.subscribe_checkpoints().await {
                 Ok(mut stream) => {
                     info!("checkpoint stream connected");
+                    let mut resumed = false;
                     while let Some(msg) = stream.next().await {
                         match msg {
                             Ok(resp) => {
                                 let cursor = resp.cursor.unwrap_or_default();
-                                {
-                                    let mut guard = state.last_cursor.write().await;
-                                    *guard = Some(cursor);
+                                let mut batch = Vec::new();
+                                if !resumed && state.last_cursor().await < Some(cursor) {
+                                    resumed = true;
+                                    let ledger = grpc.clone();
+                                    batch = state
+                                        .backfill(cursor, move |seq| {
+                                            let mut ledger = ledger.clone();
+                                            async move { ledger.get_checkpoint(seq).await }
+                                        })
+                                        .await;
+                                }
+                                batch.push((cursor, resp.checkpoint));
+                                for (cursor, checkpoint) in batch {
+                                    let digests: Vec<(String, u64)> = checkpoint
+                                        .iter()
+                                        .flat_map(|checkpoint| checkpoint.transactions.iter())
+                                        .filter_map(|tx| {
+                                            tx.digest.clone().map(|digest| (digest, cursor))
+                                        })
+                                        .collect();
+                                    if !state.advance(cursor, checkpoint).await {
+                                        continue;
+                                    }
+                                    included.extend(digests);
+                                    if last_flush.elapsed() >= state.confirmation_window {
+                                        flush_confirmations(&state.pending, &mut included).await;
+                                        last_flush = Instant::now();
+                                    }
                                 }
-                                let update = CheckpointUpdate {
-                                    cursor,
-                                    checkpoint: resp.checkpoint,
-                                };
-                                let _ = state.tx.send(update);
-                                debug!(cursor = cursor, "checkpoint advanced");
                             }
                             Err(err) => {
                                 warn!(error = %err, "checkpoint stream item error; reconnecting");
@@ -85,3 +413,20 @@ pub async fn start_checkpoint_streaming(
     });
     Ok(handle)
 }
+
+async fn flush_confirmations(pending: &PendingConfirmations, included: &mut Vec<(String, u64)>) {
+    if included.is_empty() {
+        return;
+    }
+    let resolved = pending
+        .resolve_batch(included.iter().map(|(digest, seq)| (digest.as_str(), *seq)))
+        .await;
+    if resolved > 0 {
+        debug!(
+            resolved,
+            scanned = included.len(),
+            "confirmed pending transactions from checkpoints"
+        );
+    }
+    included.clear();
+}
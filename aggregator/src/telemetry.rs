@@ -0,0 +1,56 @@
+// OpenTelemetry export
+// Optional OTLP layer for the tracing subscriber so existing spans reach a
+// collector; runs without it stay log-only
+//
+// Numan Thabit 2025 Nov
+
+use anyhow::{Context, Result};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Config, Tracer};
+use opentelemetry_sdk::{runtime, Resource};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Environment variable naming the OTLP collector, e.g. http://localhost:4317
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+/// `service.name` reported on exported spans
+pub const SERVICE_NAME: &str = "ultra-aggr";
+
+/// Collector endpoint from `OTEL_EXPORTER_OTLP_ENDPOINT`, `None` when unset or blank
+pub fn otlp_endpoint() -> Option<String> {
+    std::env::var(OTLP_ENDPOINT_ENV)
+        .ok()
+        .map(|endpoint| endpoint.trim().to_string())
+        .filter(|endpoint| !endpoint.is_empty())
+}
+
+/// Layer exporting spans in batches over gRPC to the collector at `endpoint`.
+/// Installs the global tracer provider; must be called inside a Tokio runtime.
+pub fn otlp_layer<S>(endpoint: &str) -> Result<OpenTelemetryLayer<S, Tracer>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            Config::default().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                SERVICE_NAME,
+            )])),
+        )
+        .install_batch(runtime::Tokio)
+        .with_context(|| format!("install OTLP trace pipeline for {endpoint}"))?;
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flush spans still queued for export; call once before exiting
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
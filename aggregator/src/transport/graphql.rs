@@ -4,33 +4,110 @@
 //
 // Numan Thabit 2025 Nov
 
+use crate::errors::AggrError;
 use crate::metrics::{REQ_ERRORS, REQ_LATENCY};
+use crate::retry::RetryPolicy;
+use crate::router::execution::{decode_fill, VenueEvent};
+use crate::transport::http::{
+    http_client, read_capped_body, HttpClientConfig, DEFAULT_MAX_RESPONSE_BYTES,
+};
 use anyhow::{Context, Result};
+use backoff::future::retry_notify;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::warn;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 use url::Url;
 
+/// Events requested per page when scanning history
+const HISTORY_PAGE_SIZE: u64 = 50;
+/// Most pages one history scan reads before returning what it has
+const MAX_HISTORY_PAGES: usize = 200;
+
+/// Earliest checkpoint up to `latest` whose timestamp is at least
+/// `timestamp_ms`, found by binary search over `timestamp_of`. Checkpoints
+/// without a timestamp count as earlier. `None` when every checkpoint is older.
+pub async fn first_checkpoint_at_or_after<F, Fut>(
+    latest: u64,
+    timestamp_ms: u64,
+    timestamp_of: F,
+) -> Result<Option<u64>>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<Option<u64>>>,
+{
+    let (mut low, mut high) = (0, latest + 1);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if timestamp_of(mid)
+            .await?
+            .is_some_and(|ts| ts >= timestamp_ms)
+        {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok((low <= latest).then_some(low))
+}
+
+/// Nodes requested per page when collecting up to `limit` items
+fn page_size(limit: Option<u64>) -> u64 {
+    limit.map_or(HISTORY_PAGE_SIZE, |limit| limit.clamp(1, HISTORY_PAGE_SIZE))
+}
+
 /// GraphQL RPC client for querying the General-Purpose Indexer
 #[derive(Clone)]
 pub struct GraphQLRpc {
     endpoint: Url,
     client: reqwest::Client,
+    max_response_bytes: usize,
+    retry: Option<RetryPolicy>,
 }
 
 impl GraphQLRpc {
     pub fn new(endpoint: Url) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .gzip(true)
-            .brotli(true)
-            .build()
+        let client = http_client(&HttpClientConfig::default())
             .context("build HTTP client for GraphQL RPC")?;
 
-        Ok(Self { endpoint, client })
+        Ok(Self {
+            endpoint,
+            client,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            retry: Some(RetryPolicy::default()),
+        })
+    }
+
+    /// Rebuild the HTTP client with shared transport settings
+    pub fn with_http_config(mut self, config: &HttpClientConfig) -> Result<Self> {
+        self.client = http_client(config).context("build HTTP client for GraphQL RPC")?;
+        Ok(self)
     }
 
-    /// Execute a GraphQL query
+    /// Cap the size of response bodies accepted from the indexer
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Retry connection failures, timeouts and 5xx responses with `policy`
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Never retry; every failure is returned immediately
+    pub fn without_retry(mut self) -> Self {
+        self.retry = None;
+        self
+    }
+
+    /// Execute a GraphQL query, retrying transient network failures.
+    /// GraphQL error payloads are deterministic and returned without retry.
     async fn execute_query<T: for<'de> Deserialize<'de>>(
         &self,
         query: &str,
@@ -47,50 +124,92 @@ impl GraphQLRpc {
             "operationName": operation_name,
         });
 
+        let attempt = || async {
+            self.query_once(&request_body, operation_name)
+                .await
+                .inspect_err(|_| {
+                    REQ_ERRORS
+                        .with_label_values(&["graphql", operation_name])
+                        .inc();
+                })
+        };
+        match &self.retry {
+            Some(policy) => {
+                retry_notify(policy.to_backoff(), attempt, |err, wait: Duration| {
+                    warn!(
+                        operation = operation_name,
+                        error = %err,
+                        retry_in_ms = wait.as_millis() as u64,
+                        "transient GraphQL failure; retrying"
+                    );
+                })
+                .await
+            }
+            None => attempt().await.map_err(|err| match err {
+                backoff::Error::Permanent(e) | backoff::Error::Transient { err: e, .. } => e,
+            }),
+        }
+    }
+
+    /// Single request, with failures classified for retry
+    async fn query_once<T: for<'de> Deserialize<'de>>(
+        &self,
+        request_body: &serde_json::Value,
+        operation_name: &str,
+    ) -> Result<T, backoff::Error<anyhow::Error>> {
         let response = self
             .client
             .post(self.endpoint.clone())
-            .json(&request_body)
+            .json(request_body)
             .send()
             .await
-            .context("send GraphQL request")?;
+            .context("send GraphQL request")
+            .map_err(backoff::Error::transient)?;
 
         let status = response.status();
         if !status.is_success() {
-            REQ_ERRORS
-                .with_label_values(&["graphql", operation_name])
-                .inc();
-            return Err(anyhow::anyhow!(
-                "GraphQL request failed with status: {}",
-                status
-            ));
+            let err = anyhow::anyhow!("GraphQL request failed with status: {}", status);
+            return Err(if is_transient_status(status) {
+                backoff::Error::transient(err)
+            } else {
+                backoff::Error::permanent(err)
+            });
         }
 
-        let response_body: GraphQLResponse<T> = response
-            .json()
+        let bytes = read_capped_body(response, self.max_response_bytes)
             .await
-            .context("parse GraphQL response JSON")?;
+            .map_err(|err| match err {
+                AggrError::Transport(_) => backoff::Error::transient(
+                    anyhow::Error::new(err).context("read GraphQL response body"),
+                ),
+                other => backoff::Error::permanent(
+                    anyhow::Error::new(other).context("read GraphQL response body"),
+                ),
+            })?;
+        let response_body: GraphQLResponse<T> = serde_json::from_slice(&bytes)
+            .context("parse GraphQL response JSON")
+            .map_err(backoff::Error::permanent)?;
 
         if let Some(errors) = &response_body.errors {
-            REQ_ERRORS
-                .with_label_values(&["graphql", operation_name])
-                .inc();
             warn!(
                 operation = operation_name,
                 errors = ?errors,
                 "GraphQL query returned errors"
             );
-            return Err(anyhow::anyhow!(
+            return Err(backoff::Error::permanent(anyhow::anyhow!(
                 "GraphQL errors: {}",
                 errors
                     .iter()
                     .map(|e| e.message.clone())
                     .collect::<Vec<_>>()
                     .join(", ")
-            ));
+            )));
         }
 
-        response_body.data.context("missing GraphQL response data")
+        response_body
+            .data
+            .context("missing GraphQL response data")
+            .map_err(backoff::Error::permanent)
     }
 
     /// Query checkpoints with optional filters
@@ -190,14 +309,8 @@ impl GraphQLRpc {
         "#;
 
         let mut variables = serde_json::json!({});
-        if let Some(f) = filter {
-            if let Some(digest) = f.transaction_digest {
-                variables["filter"] = serde_json::json!({
-                    "transactionDigest": {
-                        "eq": digest
-                    }
-                });
-            }
+        if let Some(f) = filter.as_ref().and_then(TransactionFilter::to_variables) {
+            variables["filter"] = f;
         }
         if let Some(f) = first {
             variables["first"] = serde_json::json!(f);
@@ -318,6 +431,10 @@ impl GraphQLRpc {
                             address
                         }
                         timestampMs
+                        type {
+                            repr
+                        }
+                        json
                         bcs
                     }
                     pageInfo {
@@ -372,6 +489,7 @@ impl GraphQLRpc {
             .query_transactions(
                 Some(TransactionFilter {
                     transaction_digest: Some(digest),
+                    ..TransactionFilter::default()
                 }),
                 Some(1),
                 None,
@@ -397,78 +515,116 @@ impl GraphQLRpc {
         Ok(connection.nodes.into_iter().next())
     }
 
-    /// Historical join: Get all transactions in a checkpoint range
-    /// This is useful for compliance queries and historical analysis
-    pub async fn get_transactions_in_checkpoint_range(
+    /// Follow `pageInfo.endCursor` from the first page until `hasNextPage` is
+    /// false, `max_items` nodes are collected or the page limit is reached.
+    /// `fetch` receives the cursor to resume after (`None` for the first page).
+    pub async fn paginate_all<C, F, Fut>(
         &self,
-        start_sequence: u64,
-        end_sequence: u64,
-        limit: Option<u64>,
-    ) -> Result<Vec<Transaction>> {
-        // Note: This is a simplified implementation. A full implementation would
-        // need to paginate through checkpoints and join with transactions.
-        // The GraphQL indexer should support this via nested queries.
-        let mut all_transactions = Vec::new();
-        let mut current_seq = start_sequence;
-
-        while current_seq <= end_sequence {
-            if self.get_checkpoint(current_seq).await?.is_none() {
-                current_seq += 1;
-                continue;
+        max_items: Option<usize>,
+        mut fetch: F,
+    ) -> Result<Vec<C::Node>>
+    where
+        C: Connection,
+        F: FnMut(Option<String>) -> Fut,
+        Fut: Future<Output = Result<C>>,
+    {
+        let mut items = Vec::new();
+        let mut cursor = None;
+        for page in 0.. {
+            if page == MAX_HISTORY_PAGES {
+                warn!(
+                    endpoint = %self.endpoint,
+                    items = items.len(),
+                    "pagination hit the page limit; result is partial"
+                );
+                break;
             }
-
-            // Query transactions for this checkpoint
-            // In practice, the GraphQL schema should support nested queries
-            // like checkpoint { transactions { ... } }
-            let transactions = self
-                .query_transactions(None, limit, None)
-                .await
-                .context("query transactions for checkpoint range")?;
-
-            all_transactions.extend(transactions.nodes);
-
-            if let Some(limit) = limit {
-                if all_transactions.len() >= limit as usize {
+            let (nodes, page_info) = fetch(cursor.take()).await?.into_page();
+            items.extend(nodes);
+            if let Some(max) = max_items {
+                if items.len() >= max {
+                    items.truncate(max);
                     break;
                 }
             }
-
-            current_seq += 1;
+            if !page_info.has_next_page {
+                break;
+            }
+            match page_info.end_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
         }
+        Ok(items)
+    }
 
-        Ok(all_transactions)
+    /// Historical join: Get all transactions in a checkpoint range (inclusive)
+    /// This is useful for compliance queries and historical analysis
+    pub async fn get_transactions_in_checkpoint_range(
+        &self,
+        start_sequence: u64,
+        end_sequence: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<Transaction>> {
+        anyhow::ensure!(
+            start_sequence <= end_sequence,
+            "start checkpoint must not be after the end checkpoint"
+        );
+        let filter = TransactionFilter {
+            after_checkpoint: start_sequence.checked_sub(1),
+            before_checkpoint: end_sequence.checked_add(1),
+            ..TransactionFilter::default()
+        };
+        self.paginate_all(limit.map(|l| l as usize), move |cursor| {
+            self.query_transactions(Some(filter.clone()), Some(page_size(limit)), cursor)
+        })
+        .await
+        .context("query transactions for checkpoint range")
     }
 
     /// Compliance query: Get all transactions for a specific address within a time range
     /// This is useful for audit trails and compliance reporting
+    ///
+    /// The sender and the time range, resolved to checkpoint bounds, are
+    /// filtered by the indexer. `end_timestamp_ms` is exclusive.
     pub async fn get_address_transactions(
         &self,
         address: &str,
-        _start_timestamp_ms: Option<u64>,
-        _end_timestamp_ms: Option<u64>,
+        start_timestamp_ms: Option<u64>,
+        end_timestamp_ms: Option<u64>,
         limit: Option<u64>,
     ) -> Result<Vec<Transaction>> {
-        // Query transactions with sender filter
-        // Note: The actual GraphQL schema may need to support timestamp filtering
-        // This is a placeholder that demonstrates the pattern
-        let connection = self
-            .query_transactions(None, limit, None)
-            .await
-            .context("query transactions for address")?;
-
-        // Filter by sender address (in production, this should be done in GraphQL query)
-        let filtered: Vec<Transaction> = connection
-            .nodes
-            .into_iter()
-            .filter(|tx| {
-                tx.sender
-                    .as_ref()
-                    .map(|s| s.address == address)
-                    .unwrap_or(false)
-            })
-            .collect();
-
-        Ok(filtered)
+        let mut filter = TransactionFilter {
+            sender: Some(address.to_string()),
+            ..TransactionFilter::default()
+        };
+        if start_timestamp_ms.is_some() || end_timestamp_ms.is_some() {
+            let latest = self
+                .get_latest_checkpoint()
+                .await?
+                .context("indexer reports no checkpoints")?
+                .sequence_number;
+            let timestamp_of = |seq| async move {
+                let checkpoint = self.get_checkpoint(seq).await?;
+                Ok::<_, anyhow::Error>(checkpoint.and_then(|c| c.timestamp_ms))
+            };
+            if let Some(start) = start_timestamp_ms {
+                match first_checkpoint_at_or_after(latest, start, timestamp_of).await? {
+                    Some(first) => filter.after_checkpoint = first.checked_sub(1),
+                    // Nothing has happened since the window opened
+                    None => return Ok(Vec::new()),
+                }
+            }
+            if let Some(end) = end_timestamp_ms {
+                filter.before_checkpoint =
+                    first_checkpoint_at_or_after(latest, end, timestamp_of).await?;
+            }
+        }
+        self.paginate_all(limit.map(|l| l as usize), move |cursor| {
+            self.query_transactions(Some(filter.clone()), Some(page_size(limit)), cursor)
+        })
+        .await
+        .context("query transactions for address")
     }
 
     /// Compliance query: Get all events for a transaction digest
@@ -487,6 +643,81 @@ impl GraphQLRpc {
         Ok(connection.nodes)
     }
 
+    /// Fill volume and VWAP for `pool` over `[start_ms, end_ms)`, paging
+    /// through events in order until the window has passed. An empty window
+    /// yields zero volume and no VWAP.
+    pub async fn get_pool_volume(
+        &self,
+        pool: &str,
+        start_ms: u64,
+        end_ms: u64,
+    ) -> Result<PoolVolume> {
+        self.get_pool_volume_with(pool, start_ms, end_ms, |event_pool| {
+            event_pool.eq_ignore_ascii_case(pool)
+        })
+        .await
+    }
+
+    /// Like `get_pool_volume`, with `same_pool` deciding whether the pool an
+    /// event names (key or object id) is `pool`
+    pub async fn get_pool_volume_with<F>(
+        &self,
+        pool: &str,
+        start_ms: u64,
+        end_ms: u64,
+        same_pool: F,
+    ) -> Result<PoolVolume>
+    where
+        F: Fn(&str) -> bool,
+    {
+        anyhow::ensure!(start_ms < end_ms, "start_ms must be before end_ms");
+        let mut events = Vec::new();
+        let mut cursor = None;
+        for page in 0.. {
+            if page == MAX_HISTORY_PAGES {
+                warn!(
+                    pool,
+                    start_ms, end_ms, "volume scan hit the page limit; result is partial"
+                );
+                break;
+            }
+            let connection = self
+                .query_events(None, Some(HISTORY_PAGE_SIZE), cursor.take())
+                .await
+                .context("query events for pool volume")?;
+            let past_window = connection
+                .nodes
+                .iter()
+                .any(|event| event.timestamp_ms.is_some_and(|ts| ts >= end_ms));
+            events.extend(connection.nodes);
+            if past_window || !connection.page_info.has_next_page {
+                break;
+            }
+            match connection.page_info.end_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        Ok(aggregate_pool_volume(
+            pool, start_ms, end_ms, &events, same_pool,
+        ))
+    }
+
+    /// Minimal query confirming the endpoint answers GraphQL. Never retried,
+    /// so an unreachable endpoint is reported promptly.
+    pub async fn health_check(&self) -> Result<()> {
+        let probe = self.clone().without_retry();
+        let _: serde_json::Value = probe
+            .execute_query(
+                "query HealthCheck { checkpoint { sequenceNumber } }",
+                serde_json::json!({}),
+                "HealthCheck",
+            )
+            .await
+            .with_context(|| format!("GraphQL endpoint {} unreachable", self.endpoint))?;
+        Ok(())
+    }
+
     /// Get latest checkpoint sequence number
     /// Useful for tracking chain progress and determining query ranges
     pub async fn get_latest_checkpoint(&self) -> Result<Option<Checkpoint>> {
@@ -496,6 +727,157 @@ impl GraphQLRpc {
     }
 }
 
+/// GraphQL endpoint state as reported by the health endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphQLStatus {
+    /// No endpoint configured
+    NotConfigured,
+    /// Configured but not probed yet
+    Unchecked,
+    Healthy,
+    /// Configured but the last health check failed
+    Unreachable,
+}
+
+/// Result of the latest GraphQL health check, shared with the health endpoint
+#[derive(Debug, Clone, Default)]
+pub struct GraphQLHealth {
+    checked: Arc<AtomicBool>,
+    healthy: Arc<AtomicBool>,
+}
+
+impl GraphQLHealth {
+    /// Record a health check outcome; returns whether reachability changed
+    pub fn record(&self, reachable: bool) -> bool {
+        let was_checked = self.checked.swap(true, Ordering::Relaxed);
+        let was_healthy = self.healthy.swap(reachable, Ordering::Relaxed);
+        !was_checked || was_healthy != reachable
+    }
+
+    pub fn status(&self) -> GraphQLStatus {
+        if !self.checked.load(Ordering::Relaxed) {
+            GraphQLStatus::Unchecked
+        } else if self.healthy.load(Ordering::Relaxed) {
+            GraphQLStatus::Healthy
+        } else {
+            GraphQLStatus::Unreachable
+        }
+    }
+}
+
+/// Status for an optional endpoint; `None` means none is configured
+pub fn graphql_status(health: Option<&GraphQLHealth>) -> GraphQLStatus {
+    health.map_or(GraphQLStatus::NotConfigured, GraphQLHealth::status)
+}
+
+/// Probe `client` once and record the outcome, logging reachability changes
+pub async fn check_graphql_health(client: &GraphQLRpc, health: &GraphQLHealth) -> bool {
+    let result = client.health_check().await;
+    let reachable = result.is_ok();
+    if health.record(reachable) {
+        match result {
+            Ok(()) => info!(endpoint = %client.endpoint, "GraphQL endpoint reachable"),
+            Err(err) => warn!(
+                endpoint = %client.endpoint,
+                error = %err,
+                "GraphQL endpoint configured but unreachable"
+            ),
+        }
+    }
+    reachable
+}
+
+/// Re-check the GraphQL endpoint every `interval`
+pub fn spawn_graphql_health_check(
+    client: GraphQLRpc,
+    health: GraphQLHealth,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            check_graphql_health(&client, &health).await;
+        }
+    })
+}
+
+/// Fill volume for one pool over a time window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolVolume {
+    pub pool: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub fills: usize,
+    pub base_volume: f64,
+    pub quote_volume: f64,
+    /// Volume-weighted average fill price over the window; `None` without priced fills
+    pub vwap: Option<f64>,
+}
+
+/// Sum the fills among `events` timestamped within `[start_ms, end_ms)` whose
+/// pool `same_pool` accepts. Events without a timestamp, type or pool are skipped.
+pub fn aggregate_pool_volume<F>(
+    pool: &str,
+    start_ms: u64,
+    end_ms: u64,
+    events: &[Event],
+    same_pool: F,
+) -> PoolVolume
+where
+    F: Fn(&str) -> bool,
+{
+    let mut volume = PoolVolume {
+        pool: pool.to_string(),
+        start_ms,
+        end_ms,
+        fills: 0,
+        base_volume: 0.0,
+        quote_volume: 0.0,
+        vwap: None,
+    };
+    let mut priced_base = 0.0;
+    for event in events {
+        if !event
+            .timestamp_ms
+            .is_some_and(|ts| (start_ms..end_ms).contains(&ts))
+        {
+            continue;
+        }
+        let (Some((module, name)), Some(fields)) = (event.module_and_name(), &event.json) else {
+            continue;
+        };
+        let Some(fill) = decode_fill(&VenueEvent {
+            module,
+            name,
+            fields,
+        }) else {
+            continue;
+        };
+        if !fill.pool.as_deref().is_some_and(&same_pool) {
+            continue;
+        }
+        volume.fills += 1;
+        volume.base_volume += fill.base;
+        if let Some(quote) = fill.quote {
+            volume.quote_volume += quote;
+            priced_base += fill.base;
+        }
+    }
+    volume.vwap = (priced_base > 0.0).then(|| volume.quote_volume / priced_base);
+    volume
+}
+
+/// Server-side and throttling statuses that may succeed on a later attempt
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
 // GraphQL Response wrappers
 
 #[derive(Debug, Deserialize)]
@@ -552,9 +934,38 @@ pub struct CheckpointFilter {
     pub checkpoint_sequence_number: Option<u64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TransactionFilter {
     pub transaction_digest: Option<String>,
+    /// Transactions sent by this address
+    pub sender: Option<String>,
+    /// Only transactions in checkpoints after this one
+    pub after_checkpoint: Option<u64>,
+    /// Only transactions in checkpoints before this one
+    pub before_checkpoint: Option<u64>,
+}
+
+impl TransactionFilter {
+    /// The `filter` variable for this filter, `None` when nothing is set
+    pub fn to_variables(&self) -> Option<serde_json::Value> {
+        let mut filter = serde_json::Map::new();
+        if let Some(digest) = &self.transaction_digest {
+            filter.insert(
+                "transactionDigest".to_string(),
+                serde_json::json!({ "eq": digest }),
+            );
+        }
+        if let Some(address) = &self.sender {
+            filter.insert("sentAddress".to_string(), serde_json::json!(address));
+        }
+        if let Some(seq) = self.after_checkpoint {
+            filter.insert("afterCheckpoint".to_string(), serde_json::json!(seq));
+        }
+        if let Some(seq) = self.before_checkpoint {
+            filter.insert("beforeCheckpoint".to_string(), serde_json::json!(seq));
+        }
+        (!filter.is_empty()).then_some(serde_json::Value::Object(filter))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -612,6 +1023,32 @@ pub struct PageInfo {
     pub end_cursor: Option<String>,
 }
 
+/// One page of a GraphQL connection, as followed by `GraphQLRpc::paginate_all`
+pub trait Connection {
+    type Node;
+
+    fn into_page(self) -> (Vec<Self::Node>, PageInfo);
+}
+
+macro_rules! impl_connection {
+    ($($connection:ty => $node:ty),* $(,)?) => {
+        $(impl Connection for $connection {
+            type Node = $node;
+
+            fn into_page(self) -> (Vec<$node>, PageInfo) {
+                (self.nodes, self.page_info)
+            }
+        })*
+    };
+}
+
+impl_connection!(
+    CheckpointConnection => Checkpoint,
+    TransactionConnection => Transaction,
+    ObjectConnection => Object,
+    EventConnection => Event,
+);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Checkpoint {
@@ -719,9 +1156,32 @@ pub struct Event {
     pub transaction_digest: Option<String>,
     pub sender: Option<EventSenderAddress>,
     pub timestamp_ms: Option<u64>,
+    /// Move type, e.g. `0x..::clob_v2::OrderFilled`
+    #[serde(rename = "type", default)]
+    pub event_type: Option<MoveTypeRepr>,
+    /// Event fields as JSON
+    #[serde(default)]
+    pub json: Option<serde_json::Value>,
     pub bcs: Option<String>,
 }
 
+impl Event {
+    /// Module and struct name of the event type, without type parameters
+    pub fn module_and_name(&self) -> Option<(&str, &str)> {
+        let repr = self.event_type.as_ref()?.repr.as_str();
+        let base = repr.split('<').next()?;
+        let mut parts = base.rsplit("::");
+        let name = parts.next()?;
+        let module = parts.next()?;
+        Some((module, name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveTypeRepr {
+    pub repr: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EventSenderAddress {
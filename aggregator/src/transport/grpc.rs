@@ -38,7 +38,10 @@ use sui::rpc::v2::{
     subscription_service_client::SubscriptionServiceClient,
 };
 
-use sui::rpc::v2::{SubscribeCheckpointsRequest, SubscribeCheckpointsResponse};
+use sui::rpc::v2::{
+    get_checkpoint_request::CheckpointId, GetCheckpointRequest, GetEpochRequest,
+    GetServiceInfoRequest, SubscribeCheckpointsRequest, SubscribeCheckpointsResponse,
+};
 
 #[cfg(feature = "grpc-exec")]
 use sui::rpc::v2::{
@@ -46,6 +49,61 @@ use sui::rpc::v2::{
     ExecuteTransactionRequest, SimulateTransactionRequest, Transaction,
 };
 
+/// Fields requested from ExecuteTransaction; `effects` includes the raw BCS bytes
+#[cfg(feature = "grpc-exec")]
+const EXECUTE_READ_MASK: &[&str] = &[
+    "digest",
+    "effects",
+    "events",
+    "checkpoint",
+    "timestamp",
+    "balance_changes",
+];
+
+/// Fields requested per streamed checkpoint; transaction digests drive confirmations
+/// and changed object ids invalidate cached order book snapshots
+const CHECKPOINT_READ_MASK: &[&str] = &[
+    "sequence_number",
+    "digest",
+    "summary.timestamp",
+    "transactions.digest",
+    "transactions.effects.changed_objects.object_id",
+];
+
+/// Fields requested from GetEpoch
+const EPOCH_READ_MASK: &[&str] = &[
+    "epoch",
+    "reference_gas_price",
+    "protocol_config.protocol_version",
+];
+
+/// Current epoch as reported by the ledger service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochInfo {
+    pub epoch: u64,
+    /// Reference gas price in MIST, when the node reported one
+    pub reference_gas_price: Option<u64>,
+    pub protocol_version: Option<u64>,
+}
+
+impl EpochInfo {
+    /// Build from a GetEpoch result, taking the epoch number from service info
+    /// when the epoch message omits it. Returns `None` if neither has an epoch.
+    pub fn from_parts(
+        epoch: Option<sui::rpc::v2::Epoch>,
+        service_epoch: Option<u64>,
+    ) -> Option<Self> {
+        let epoch = epoch.unwrap_or_default();
+        Some(Self {
+            epoch: epoch.epoch.or(service_epoch)?,
+            reference_gas_price: epoch.reference_gas_price,
+            protocol_version: epoch
+                .protocol_config
+                .and_then(|config| config.protocol_version),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct GrpcClients {
     pub ledger: LedgerServiceClient<Channel>,
@@ -78,15 +136,63 @@ impl GrpcClients {
 
     pub async fn readiness_probe(&mut self) -> anyhow::Result<()> {
         self.ledger
-            .get_service_info(sui::rpc::v2::GetServiceInfoRequest::default())
+            .get_service_info(GetServiceInfoRequest::default())
             .await
             .map(|_| ())
             .map_err(|status| status.into())
     }
 
+    /// Current epoch, reference gas price and protocol version from the ledger service.
+    /// Falls back to service info for the epoch number if GetEpoch leaves it out.
+    pub async fn get_epoch_info(&mut self) -> anyhow::Result<EpochInfo> {
+        let request = GetEpochRequest {
+            epoch: None,
+            read_mask: Some(prost_types::FieldMask {
+                paths: EPOCH_READ_MASK.iter().map(|p| p.to_string()).collect(),
+            }),
+        };
+        let epoch = self.ledger.get_epoch(request).await?.into_inner().epoch;
+        let service_epoch = match epoch.as_ref().and_then(|epoch| epoch.epoch) {
+            Some(_) => None,
+            None => {
+                self.ledger
+                    .get_service_info(GetServiceInfoRequest::default())
+                    .await?
+                    .into_inner()
+                    .epoch
+            }
+        };
+        EpochInfo::from_parts(epoch, service_epoch)
+            .ok_or_else(|| anyhow::anyhow!("ledger service did not report the current epoch"))
+    }
+
+    /// Fetch a single checkpoint by sequence number with the same fields as the stream.
+    /// Used to backfill checkpoints missed while the subscription was disconnected.
+    pub async fn get_checkpoint(
+        &mut self,
+        sequence_number: u64,
+    ) -> anyhow::Result<Option<sui::rpc::v2::Checkpoint>> {
+        let request = GetCheckpointRequest {
+            checkpoint_id: Some(CheckpointId::SequenceNumber(sequence_number)),
+            read_mask: Some(prost_types::FieldMask {
+                paths: CHECKPOINT_READ_MASK.iter().map(|p| p.to_string()).collect(),
+            }),
+        };
+        Ok(self
+            .ledger
+            .get_checkpoint(request)
+            .await?
+            .into_inner()
+            .checkpoint)
+    }
+
     /// Dry-run a PTB using gRPC v2 (requires the `grpc-exec` feature).
+    /// Returns the simulated transaction, including effects and execution status.
     #[cfg(feature = "grpc-exec")]
-    pub async fn simulate_ptb(&mut self, tx_bcs: Vec<u8>) -> anyhow::Result<()> {
+    pub async fn simulate_ptb(
+        &mut self,
+        tx_bcs: Vec<u8>,
+    ) -> anyhow::Result<Option<sui::rpc::v2::ExecutedTransaction>> {
         let _timer = REQ_LATENCY
             .with_label_values(&["grpc", "SimulateTransaction"])
             .start_timer();
@@ -102,28 +208,32 @@ impl GrpcClients {
             ..Default::default()
         };
 
-        if let Err(status) = self
+        match self
             .exec
             .simulate_transaction(tonic::Request::new(request))
             .await
         {
-            REQ_ERRORS
-                .with_label_values(&["grpc", "SimulateTransaction"])
-                .inc();
-            return Err(status.into());
+            Ok(resp) => Ok(Some(resp.into_inner().transaction.unwrap_or_default())),
+            Err(status) => {
+                REQ_ERRORS
+                    .with_label_values(&["grpc", "SimulateTransaction"])
+                    .inc();
+                Err(status.into())
+            }
         }
-
-        Ok(())
     }
 
     /// Fallback implementation when gRPC execution client is not enabled.
     #[cfg(not(feature = "grpc-exec"))]
-    pub async fn simulate_ptb(&mut self, tx_bcs: Vec<u8>) -> anyhow::Result<()> {
+    pub async fn simulate_ptb(
+        &mut self,
+        tx_bcs: Vec<u8>,
+    ) -> anyhow::Result<Option<sui::rpc::v2::ExecutedTransaction>> {
         warn!(
             bytes = tx_bcs.len(),
             "simulate_ptb requires the 'grpc-exec' feature; skipping gRPC dry-run"
         );
-        Ok(())
+        Ok(None)
     }
 
     /// Execute via gRPC v2 Transaction Execution Service (enable with `--features grpc-exec`).
@@ -155,7 +265,9 @@ impl GrpcClients {
                 ..Default::default()
             }),
             signatures,
-            ..Default::default()
+            read_mask: Some(prost_types::FieldMask {
+                paths: EXECUTE_READ_MASK.iter().map(|p| p.to_string()).collect(),
+            }),
         };
 
         match self
@@ -178,7 +290,11 @@ impl GrpcClients {
     pub async fn subscribe_checkpoints(
         &mut self,
     ) -> anyhow::Result<tonic::Streaming<SubscribeCheckpointsResponse>> {
-        let req = SubscribeCheckpointsRequest { read_mask: None };
+        let req = SubscribeCheckpointsRequest {
+            read_mask: Some(prost_types::FieldMask {
+                paths: CHECKPOINT_READ_MASK.iter().map(|p| p.to_string()).collect(),
+            }),
+        };
         let resp = self
             .subs
             .subscribe_checkpoints(tonic::Request::new(req))
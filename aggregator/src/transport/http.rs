@@ -0,0 +1,124 @@
+// Shared HTTP transport helpers
+// This file holds client construction and response handling shared by the
+// reqwest-based JSON-RPC and GraphQL clients
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Response};
+use std::time::Duration;
+
+/// Default upper bound on a single HTTP response body (16 MiB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Settings applied to every outbound HTTP client
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Whole-request deadline, including reading the body
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// How long idle pooled connections are kept open
+    pub pool_idle_timeout: Duration,
+    pub pool_max_idle_per_host: usize,
+    /// TCP keep-alive probe interval, so idle pooled connections survive NAT
+    /// and load balancer timeouts
+    pub tcp_keepalive: Duration,
+    /// Extra headers sent with every request
+    pub headers: Vec<(String, String)>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(5),
+            pool_idle_timeout: Duration::from_secs(90),
+            pool_max_idle_per_host: 32,
+            tcp_keepalive: Duration::from_secs(60),
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_pool_idle_timeout(mut self, idle: Duration) -> Self {
+        self.pool_idle_timeout = idle;
+        self
+    }
+
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = interval;
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Build a reqwest client with timeouts, compression, pooling and headers
+/// taken from `config`
+pub fn http_client(config: &HttpClientConfig) -> Result<Client, AggrError> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| AggrError::Transport(format!("invalid header name {name:?}: {e}")))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| AggrError::Transport(format!("invalid value for header {name}: {e}")))?;
+        headers.insert(name, value);
+    }
+    Client::builder()
+        .timeout(config.timeout)
+        .connect_timeout(config.connect_timeout)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .tcp_keepalive(config.tcp_keepalive)
+        .default_headers(headers)
+        .gzip(true)
+        .brotli(true)
+        .zstd(true)
+        .build()
+        .map_err(|e| AggrError::Transport(format!("build HTTP client: {e}")))
+}
+
+/// Read a response body, failing once it grows beyond `max_bytes`.
+///
+/// A declared `Content-Length` above the cap is rejected before any bytes are
+/// read; otherwise the body is streamed chunk by chunk so an endpoint that
+/// omits or lies about the length cannot exhaust memory.
+pub async fn read_capped_body(
+    mut response: Response,
+    max_bytes: usize,
+) -> Result<Vec<u8>, AggrError> {
+    if let Some(declared) = response.content_length() {
+        if declared > max_bytes as u64 {
+            return Err(AggrError::ResponseTooLarge(max_bytes));
+        }
+    }
+
+    let mut body = Vec::with_capacity(
+        response
+            .content_length()
+            .map(|len| len as usize)
+            .unwrap_or(0)
+            .min(max_bytes),
+    );
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AggrError::Transport(format!("read body: {e}")))?
+    {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(AggrError::ResponseTooLarge(max_bytes));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
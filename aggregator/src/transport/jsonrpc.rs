@@ -5,29 +5,99 @@
 // Numan Thabit 2025 Nov
 
 use crate::errors::AggrError;
+use crate::retry::RetryPolicy;
+use crate::transport::http::{
+    http_client, read_capped_body, HttpClientConfig, DEFAULT_MAX_RESPONSE_BYTES,
+};
+use backoff::future::retry_notify;
 use base64::{engine::general_purpose::STANDARD_NO_PAD as B64, Engine as _};
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
+use std::time::Duration;
+use tracing::warn;
 
 #[derive(Debug, Clone)]
 pub struct JsonRpc {
     http: Client,
     url: String,
+    max_response_bytes: usize,
+    empty_result_retry: Option<RetryPolicy>,
 }
 
 impl JsonRpc {
     pub fn new(url: impl Into<String>) -> Self {
         Self {
-            http: Client::new(),
+            // Like `Client::new`, only fails if the TLS backend cannot initialize
+            http: http_client(&HttpClientConfig::default())
+                .expect("default HTTP client configuration"),
             url: url.into(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            empty_result_retry: Some(RetryPolicy::default()),
         }
     }
 
+    /// Rebuild the HTTP client with shared transport settings
+    pub fn with_http_config(mut self, config: &HttpClientConfig) -> Result<Self, AggrError> {
+        self.http = http_client(config)?;
+        Ok(self)
+    }
+
+    /// Cap the size of response bodies accepted from the endpoint
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Resubmit with `policy` when a node answers with a null `result`, which some
+    /// providers do transiently under load. Resubmitting is safe: the signed bytes,
+    /// and so the digest, are unchanged.
+    pub fn with_empty_result_retry(mut self, policy: RetryPolicy) -> Self {
+        self.empty_result_retry = Some(policy);
+        self
+    }
+
+    /// Fail immediately on a null `result`
+    pub fn without_empty_result_retry(mut self) -> Self {
+        self.empty_result_retry = None;
+        self
+    }
+
     pub fn endpoint(&self) -> &str {
         &self.url
     }
 
+    /// Open a pooled connection before the first submission by asking for the
+    /// chain identifier, so the first order does not pay for TCP and TLS setup
+    pub async fn warm_up(&self) -> Result<String, AggrError> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sui_getChainIdentifier",
+            "params": []
+        });
+        let resp = self
+            .http
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| AggrError::Transport(format!("jsonrpc warm-up: {e}")))?;
+        if !resp.status().is_success() {
+            return Err(AggrError::Provider(format!("http {}", resp.status())));
+        }
+        let bytes = read_capped_body(resp, self.max_response_bytes).await?;
+        let body: serde_json::Value = serde_json::from_slice(&bytes)
+            .map_err(|e| AggrError::Transport(format!("json parse: {e}")))?;
+        if let Some(err) = body.get("error") {
+            return Err(AggrError::Provider(err.to_string()));
+        }
+        body["result"]
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| AggrError::Provider("missing chain identifier".into()))
+    }
+
     pub async fn execute_tx_block(
         &self,
         tx_bcs: &[u8],
@@ -44,25 +114,81 @@ impl JsonRpc {
                 "WaitForLocalExecution"
             ]
         });
+        let attempt = || self.execute_once(&payload);
+        match &self.empty_result_retry {
+            Some(policy) => {
+                retry_notify(policy.to_backoff(), attempt, |err, wait: Duration| {
+                    warn!(
+                        error = %err,
+                        retry_in_ms = wait.as_millis() as u64,
+                        "empty JSON-RPC execution result; retrying"
+                    );
+                })
+                .await
+            }
+            None => attempt().await.map_err(|err| match err {
+                backoff::Error::Permanent(e) | backoff::Error::Transient { err: e, .. } => e,
+            }),
+        }
+    }
+
+    /// Dry-run unsigned transaction bytes with `sui_dryRunTransactionBlock`
+    pub async fn dry_run_tx_block(&self, tx_bcs: &[u8]) -> Result<DryRunResp, AggrError> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sui_dryRunTransactionBlock",
+            "params": [B64.encode(tx_bcs)]
+        });
         let resp = self
             .http
             .post(&self.url)
             .json(&payload)
             .send()
             .await
-            .map_err(|e| AggrError::Transport(format!("jsonrpc send: {e}")))?;
+            .map_err(|e| AggrError::Transport(format!("jsonrpc dry run: {e}")))?;
         if !resp.status().is_success() {
             return Err(AggrError::Provider(format!("http {}", resp.status())));
         }
-        let body: serde_json::Value = resp
-            .json()
-            .await
+        let bytes = read_capped_body(resp, self.max_response_bytes).await?;
+        let body: serde_json::Value = serde_json::from_slice(&bytes)
             .map_err(|e| AggrError::Transport(format!("json parse: {e}")))?;
         if let Some(err) = body.get("error") {
             return Err(AggrError::Provider(err.to_string()));
         }
         serde_json::from_value(body["result"].clone())
-            .map_err(|e| AggrError::Provider(format!("decode result: {e}")))
+            .map_err(|e| AggrError::Provider(format!("decode dry run result: {e}")))
+    }
+
+    /// Single submission. Only a null `result` is transient; an `error` field is a
+    /// verdict on the transaction and is returned without retry.
+    async fn execute_once(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<ExecuteResp, backoff::Error<AggrError>> {
+        let resp = self
+            .http
+            .post(&self.url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| AggrError::Transport(format!("jsonrpc send: {e}")))?;
+        if !resp.status().is_success() {
+            return Err(AggrError::Provider(format!("http {}", resp.status())).into());
+        }
+        let bytes = read_capped_body(resp, self.max_response_bytes).await?;
+        let body: serde_json::Value = serde_json::from_slice(&bytes)
+            .map_err(|e| AggrError::Transport(format!("json parse: {e}")))?;
+        if let Some(err) = body.get("error") {
+            return Err(AggrError::Provider(err.to_string()).into());
+        }
+        if body["result"].is_null() {
+            return Err(backoff::Error::transient(AggrError::Provider(
+                "empty execution result".into(),
+            )));
+        }
+        serde_json::from_value(body["result"].clone())
+            .map_err(|e| AggrError::Provider(format!("decode result: {e}")).into())
     }
 }
 
@@ -72,3 +198,35 @@ pub struct ExecuteResp {
     pub effects: Option<serde_json::Value>,
     pub events: Option<serde_json::Value>,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DryRunResp {
+    pub effects: Option<serde_json::Value>,
+}
+
+impl DryRunResp {
+    /// Why the dry run aborted; `None` when it succeeded
+    pub fn failure(&self) -> Option<String> {
+        let status = self.effects.as_ref()?.get("status")?;
+        if status.get("status").and_then(|s| s.as_str()) == Some("success") {
+            return None;
+        }
+        Some(
+            status
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown execution error")
+                .to_string(),
+        )
+    }
+
+    /// Gas charged before the storage rebate, which is what the budget must cover
+    pub fn gas_cost(&self) -> Option<u64> {
+        let gas_used = self.effects.as_ref()?.get("gasUsed")?;
+        let cost = |field: &str| match gas_used.get(field)? {
+            serde_json::Value::String(s) => s.parse::<u64>().ok(),
+            other => other.as_u64(),
+        };
+        cost("computationCost")?.checked_add(cost("storageCost")?)
+    }
+}
@@ -1,5 +1,6 @@
 pub mod graphql;
 pub mod grpc;
+pub mod http;
 pub mod jsonrpc;
 
 pub use graphql::GraphQLRpc;
@@ -5,38 +5,96 @@
 // Numan Thabit 2025 Nov
 
 use crate::config::DeepBookSettings;
-use crate::metrics::{
-    DEEPBOOK_CACHE_HITS, DEEPBOOK_CACHE_MISSES, DEEPBOOK_INDEXER_REQUESTS,
-    DEEPBOOK_RECONCILIATION_MISMATCHES,
-};
+use crate::errors::AggrError;
+use crate::metrics::{DEEPBOOK_INDEXER_REQUESTS, DEEPBOOK_RECONCILIATION_MISMATCHES};
 use anyhow::{anyhow, bail, Context, Result};
 use backoff::{future::retry, ExponentialBackoff};
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use sui_deepbookv3::client::{DeepBookClient, PoolBookParams, PoolDeepPrice};
 use sui_deepbookv3::utils::config::DeepBookPackageOverride;
-use sui_deepbookv3::utils::config::{GAS_BUDGET, MAX_TIMESTAMP};
+use sui_deepbookv3::utils::config::{Environment, GAS_BUDGET, MAX_TIMESTAMP};
+use sui_deepbookv3::utils::constants::{
+    MAINNET_COINS, MAINNET_POOLS, TESTNET_COINS, TESTNET_POOLS,
+};
 use sui_deepbookv3::utils::types::{
-    BalanceManager, Coin, OrderType, PlaceLimitOrderParams, Pool, SelfMatchingOptions,
+    BalanceManager, Coin, OrderType, PlaceLimitOrderParams, PlaceMarketOrderParams, Pool,
+    SelfMatchingOptions,
 };
-use sui_sdk::rpc_types::SuiEvent;
+use sui_sdk::rpc_types::{SuiEvent, SuiObjectDataOptions, SuiParsedData};
 use sui_sdk::types::base_types::ObjectRef;
-use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use sui_sdk::types::object::Owner;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_sdk::types::transaction::{InputObjectKind, TransactionData, TransactionKind};
 use sui_sdk::{SuiClient, SuiClientBuilder};
-use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 use url::Url;
 
-use crate::quant::{quantize_price, quantize_size, PoolParams};
+use crate::amounts::{CoinUnits, PoolUnits};
+use crate::quant::{quantize_price_for_side, quantize_size, resolve_pool_params, PoolParams};
+use crate::retry::RetryPolicy;
+use crate::transport::grpc::sui::rpc::v2::Checkpoint;
+use crate::transport::http::{http_client, HttpClientConfig};
+use crate::venues::book::{sweep_for_taker, BookSweep, PostOnlyFallback};
+use crate::venues::cache::TimedCache;
+use crate::venues::gas_price::GasPriceCache;
+use crate::venues::managers::{BalanceManagers, TradeAuthority, TradeCapHolding};
+use crate::venues::order_events::{select_order_id, OrderEvent};
+use crate::venues::pools::{PoolNormalizer, PoolStatus};
+use crate::venues::snapshots::{BookCacheSettings, BookSnapshotCache};
+
+/// How long a limit order may rest, as accepted over the API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitOrderType {
+    /// Good till cancelled: take what crosses and rest the remainder
+    #[default]
+    #[serde(rename = "GTC")]
+    Gtc,
+    /// Immediate or cancel: take what crosses and cancel the remainder
+    #[serde(rename = "IOC")]
+    Ioc,
+    /// Fill or kill: fill the whole quantity immediately or abort
+    #[serde(rename = "FOK")]
+    Fok,
+    /// Only rest on the book; never take
+    PostOnly,
+}
+
+impl LimitOrderType {
+    /// DeepBook order restriction for this type
+    pub fn to_sdk(self) -> OrderType {
+        match self {
+            LimitOrderType::Gtc => OrderType::NoRestriction,
+            LimitOrderType::Ioc => OrderType::ImmediateOrCancel,
+            LimitOrderType::Fok => OrderType::FillOrKill,
+            LimitOrderType::PostOnly => OrderType::PostOnly,
+        }
+    }
+}
+
+impl FromStr for LimitOrderType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "GTC" => Ok(LimitOrderType::Gtc),
+            "IOC" => Ok(LimitOrderType::Ioc),
+            "FOK" => Ok(LimitOrderType::Fok),
+            "PostOnly" => Ok(LimitOrderType::PostOnly),
+            other => Err(format!(
+                "unknown order_type {other:?}; expected GTC, IOC, FOK or PostOnly"
+            )),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct LimitReq {
@@ -47,6 +105,102 @@ pub struct LimitReq {
     pub client_order_id: String,
     pub pay_with_deep: bool,
     pub expiration_ms: Option<u64>,
+    /// Only allow the order to shrink the tracked net position for the pool
+    pub reduce_only: bool,
+    /// Rest on the book without taking; the policy says what to do if it would cross
+    pub post_only: Option<PostOnlyFallback>,
+    /// Time in force; a set `post_only` takes precedence
+    pub order_type: LimitOrderType,
+    /// BalanceManager key to trade from; the configured default when unset
+    pub manager: Option<String>,
+}
+
+impl LimitReq {
+    /// DeepBook order restriction for this request
+    pub fn sdk_order_type(&self) -> OrderType {
+        if self.post_only.is_some() {
+            OrderType::PostOnly
+        } else {
+            self.order_type.to_sdk()
+        }
+    }
+
+    /// SDK order parameters at an already quantized price and quantity, placed
+    /// through the BalanceManager registered as `balance_manager_key`
+    pub fn place_params(
+        &self,
+        balance_manager_key: String,
+        price: f64,
+        quantity: f64,
+    ) -> Result<PlaceLimitOrderParams> {
+        let client_order_id = self
+            .client_order_id
+            .parse::<u64>()
+            .context("client_order_id must parse to u64")?;
+        Ok(PlaceLimitOrderParams {
+            pool_key: self.pool.clone(),
+            balance_manager_key,
+            client_order_id,
+            price,
+            quantity,
+            is_bid: self.is_bid,
+            expiration: Some(self.expiration_ms.unwrap_or(MAX_TIMESTAMP)),
+            order_type: Some(self.sdk_order_type()),
+            self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
+            pay_with_deep: Some(self.pay_with_deep),
+        })
+    }
+}
+
+/// Market order: takes `quantity` from the opposite side of the book at
+/// whatever prices it rests at, bounded only by `max_slippage_bps`
+#[derive(Debug, Clone)]
+pub struct MarketReq {
+    pub pool: String,
+    pub quantity: f64,
+    pub is_bid: bool,
+    pub client_order_id: String,
+    pub pay_with_deep: bool,
+    /// Largest adverse move of the expected average fill from mid, in basis
+    /// points; unbounded when unset
+    pub max_slippage_bps: Option<f64>,
+    /// BalanceManager key to trade from; the configured default when unset
+    pub manager: Option<String>,
+}
+
+impl MarketReq {
+    /// Reject the order when the expected slippage exceeds the caller's bound
+    pub fn check_slippage(&self, expected_bps: f64) -> Result<(), AggrError> {
+        match self.max_slippage_bps {
+            Some(limit_bps) if expected_bps > limit_bps => Err(AggrError::SlippageExceeded {
+                expected_bps,
+                limit_bps,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// SDK order parameters at an already quantized quantity, placed through
+    /// the BalanceManager registered as `balance_manager_key`
+    pub fn place_params(
+        &self,
+        balance_manager_key: String,
+        quantity: f64,
+    ) -> Result<PlaceMarketOrderParams> {
+        let client_order_id = self
+            .client_order_id
+            .parse::<u64>()
+            .context("client_order_id must parse to u64")?;
+        Ok(PlaceMarketOrderParams {
+            pool_key: self.pool.clone(),
+            balance_manager_key,
+            client_order_id,
+            quantity,
+            is_bid: self.is_bid,
+            self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
+            pay_with_deep: Some(self.pay_with_deep),
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -72,51 +226,17 @@ pub struct DeepPrice {
     pub deep_per_quote: Option<f64>,
 }
 
-const POOL_PARAMS_TTL: Duration = Duration::from_secs(300);
+/// Default time a pool's tick, lot and minimum size are reused
+pub const DEFAULT_POOL_PARAMS_TTL: Duration = Duration::from_secs(300);
+/// Default time a pool's mid price is reused; matches the book snapshot TTL
+pub const DEFAULT_MID_PRICE_TTL: Duration = Duration::from_millis(250);
 const TRADE_PARAMS_TTL: Duration = Duration::from_secs(120);
 const BALANCE_TTL: Duration = Duration::from_secs(3);
 const DEEP_PRICE_TTL: Duration = Duration::from_secs(30);
-
-#[derive(Clone)]
-struct TimedCache<T> {
-    ttl: Duration,
-    label: &'static str,
-    store: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
-}
-
-struct CacheEntry<T> {
-    value: T,
-    expires_at: Instant,
-}
-
-#[derive(Debug, Clone)]
-struct RetryConfig {
-    initial: Duration,
-    max: Duration,
-    max_elapsed: Duration,
-    multiplier: f64,
-}
-
-impl RetryConfig {
-    fn new(initial: Duration, max: Duration, max_elapsed: Duration, multiplier: f64) -> Self {
-        Self {
-            initial,
-            max,
-            max_elapsed,
-            multiplier,
-        }
-    }
-
-    fn to_backoff(&self) -> ExponentialBackoff {
-        ExponentialBackoff {
-            initial_interval: self.initial,
-            max_interval: self.max,
-            max_elapsed_time: Some(self.max_elapsed),
-            multiplier: self.multiplier,
-            ..ExponentialBackoff::default()
-        }
-    }
-}
+const TRADE_AUTHORITY_TTL: Duration = Duration::from_secs(60);
+const POOL_STATUS_TTL: Duration = Duration::from_secs(30);
+/// Book depth (ticks from mid) walked when pricing a target size
+pub const VWAP_BOOK_TICKS: u64 = 100;
 
 #[derive(Clone)]
 struct DeepBookIndexer {
@@ -126,12 +246,7 @@ struct DeepBookIndexer {
 
 impl DeepBookIndexer {
     fn new(base: Url, timeout: Duration) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(timeout)
-            .gzip(true)
-            .brotli(true)
-            .zstd(true)
-            .build()
+        let client = http_client(&HttpClientConfig::default().with_timeout(timeout))
             .context("build HTTP client for DeepBook indexer")?;
         Ok(Self { base, client })
     }
@@ -148,6 +263,18 @@ impl DeepBookIndexer {
         parse_pool_params(payload)
     }
 
+    async fn fetch_pool_status(&self, pool: &str) -> Result<PoolStatus> {
+        let url = self
+            .base
+            .join("v1/pools/status")
+            .context("construct pool status URL")?;
+        let payload = self
+            .get_json(url, &[("pool", pool)])
+            .await
+            .context("fetch pool status from indexer")?;
+        parse_pool_status(payload)
+    }
+
     async fn fetch_trade_params(&self, pool: &str) -> Result<TradeParams> {
         let url = self
             .base
@@ -336,6 +463,11 @@ fn parse_pool_params(value: Value) -> Result<PoolParams> {
     })
 }
 
+fn parse_pool_status(value: Value) -> Result<PoolStatus> {
+    let fields: Value = extract_payload(value)?;
+    PoolStatus::from_fields(&fields).context("pool status missing from indexer response")
+}
+
 fn parse_trade_params(value: Value) -> Result<TradeParams> {
     let dto: TradeParamsDto = extract_payload(value)?;
     Ok(TradeParams {
@@ -422,66 +554,56 @@ fn leak_str(value: &str) -> &'static str {
     Box::leak(value.to_string().into_boxed_str())
 }
 
-impl<T: Clone> TimedCache<T> {
-    fn new(ttl: Duration, label: &'static str) -> Self {
-        Self {
-            ttl,
-            label,
-            store: Arc::new(RwLock::new(HashMap::new())),
-        }
+/// Order ids a cancel-all in `pool` should cancel, in ascending order and without
+/// duplicates. An empty set is [`AggrError::NoOpenOrders`].
+pub fn cancel_all_order_ids(pool: &str, mut open_orders: Vec<u128>) -> Result<Vec<u128>> {
+    open_orders.sort_unstable();
+    open_orders.dedup();
+    if open_orders.is_empty() {
+        return Err(AggrError::NoOpenOrders(pool.to_string()).into());
     }
+    Ok(open_orders)
+}
 
-    async fn get_or_try_insert_with<E, Fut, F>(&self, key: &str, loader: F) -> Result<T, E>
-    where
-        F: FnOnce() -> Fut,
-        Fut: Future<Output = Result<T, E>>,
-    {
-        let now = Instant::now();
-        let mut stale = false;
-        {
-            let guard = self.store.read().await;
-            if let Some(entry) = guard.get(key) {
-                if entry.expires_at > now {
-                    DEEPBOOK_CACHE_HITS.with_label_values(&[self.label]).inc();
-                    return Ok(entry.value.clone());
-                } else {
-                    stale = true;
-                }
-            }
-        }
-
-        if stale {
-            let mut guard = self.store.write().await;
-            if let Some(entry) = guard.get(key) {
-                if entry.expires_at <= Instant::now() {
-                    guard.remove(key);
-                }
-            }
-        }
-
-        DEEPBOOK_CACHE_MISSES.with_label_values(&[self.label]).inc();
-        let value = loader().await?;
-        let expires_at = Instant::now() + self.ttl;
-        let mut guard = self.store.write().await;
-        guard.insert(
-            key.to_owned(),
-            CacheEntry {
-                value: value.clone(),
-                expires_at,
-            },
-        );
-        Ok(value)
+fn default_pools(environment: Environment) -> &'static HashMap<&'static str, Pool> {
+    match environment {
+        Environment::Mainnet => &MAINNET_POOLS,
+        Environment::Testnet => &TESTNET_POOLS,
     }
+}
 
-    async fn invalidate(&self, key: &str) {
-        let mut guard = self.store.write().await;
-        guard.remove(key);
+fn default_coins(environment: Environment) -> &'static HashMap<&'static str, Coin> {
+    match environment {
+        Environment::Mainnet => &MAINNET_COINS,
+        Environment::Testnet => &TESTNET_COINS,
     }
+}
 
-    async fn invalidate_all(&self) {
-        let mut guard = self.store.write().await;
-        guard.clear();
-    }
+/// Base and quote units of every pool whose coins are known. Pools naming an
+/// unknown coin or one with a malformed scalar are left out.
+pub fn pool_units(
+    pools: &HashMap<&'static str, Pool>,
+    coins: &HashMap<&'static str, Coin>,
+) -> HashMap<String, PoolUnits> {
+    let units = |key: &str| {
+        let coin = coins.get(key)?;
+        CoinUnits::from_scalar(key, coin.scalar)
+            .inspect_err(|err| warn!(coin = key, error = %err, "ignoring coin decimals"))
+            .ok()
+    };
+    pools
+        .iter()
+        .filter_map(|(key, pool)| {
+            let base = units(&pool.base_coin)?;
+            let quote = units(&pool.quote_coin)?;
+            Some((key.to_string(), PoolUnits { base, quote }))
+        })
+        .collect()
+}
+
+/// Balance cache key; snapshots are per (BalanceManager, pool)
+fn balance_cache_key(pool: &str, manager: &str) -> String {
+    format!("{manager}:{pool}")
 }
 
 #[derive(Clone)]
@@ -489,15 +611,28 @@ pub struct DeepBookAdapter {
     sui: SuiClient,
     pub(crate) db: DeepBookClient,
     sender: SuiAddress,
-    pub(crate) manager_key: String, // key used inside DeepBookClient config, e.g. "MANAGER_1"
+    /// Keys registered inside the DeepBookClient config, e.g. "MANAGER_1"
+    managers: BalanceManagers,
+    /// Object ids (manager and caps) behind each registered manager key
+    manager_objects: HashMap<String, BalanceManager>,
+    trade_authority_cache: TimedCache<TradeAuthority>,
     pool_params_cache: TimedCache<PoolParams>,
+    pool_status_cache: TimedCache<PoolStatus>,
     trade_params_cache: TimedCache<TradeParams>,
     balance_cache: TimedCache<BalanceSnapshot>,
     deep_price_cache: TimedCache<DeepPrice>,
+    mid_price_cache: TimedCache<f64>,
+    gas_price_cache: GasPriceCache,
+    book_cache: BookSnapshotCache<sui_deepbookv3::client::Level2TicksFromMid>,
     indexer: Option<DeepBookIndexer>,
-    retry_config: RetryConfig,
+    retry_policy: RetryPolicy,
     fallback_use_fullnode: bool,
     monitored_pools: Vec<String>,
+    /// Params used for a pool when the indexer and fullnode report invalid ones
+    pool_param_overrides: Arc<HashMap<String, PoolParams>>,
+    pool_normalizer: PoolNormalizer,
+    /// Coin symbols and decimals per pool, for human-readable amounts
+    pool_units: Arc<HashMap<String, PoolUnits>>,
     reconcile_interval: Duration,
 }
 
@@ -515,11 +650,13 @@ impl DeepBookAdapter {
         );
 
         let mut managers: HashMap<&'static str, BalanceManager> = HashMap::new();
+        let mut manager_keys = BalanceManagers::new(&settings.balance_manager_label);
         let mut coins: HashMap<&'static str, Coin> = HashMap::new();
         let mut pools: HashMap<&'static str, Pool> = HashMap::new();
 
         if let Some(overrides) = &settings.overrides {
             for manager in &overrides.balance_managers {
+                manager_keys = manager_keys.with_manager(&manager.key);
                 let key = leak_str(&manager.key);
                 managers.insert(
                     key,
@@ -562,11 +699,45 @@ impl DeepBookAdapter {
             .entry(manager_key_static)
             .or_insert_with(|| BalanceManager {
                 address: settings.balance_manager_object.clone(),
-                trade_cap: None,
+                trade_cap: settings.balance_manager_trade_cap.clone(),
                 deposit_cap: None,
                 withdraw_cap: None,
             });
 
+        let known_pools: Vec<(&str, &str)> = if pools.is_empty() {
+            default_pools(settings.environment)
+                .iter()
+                .map(|(key, pool)| (*key, pool.address.as_str()))
+                .collect()
+        } else {
+            pools
+                .iter()
+                .map(|(key, pool)| (*key, pool.address.as_str()))
+                .collect()
+        };
+        let mut pool_normalizer = PoolNormalizer::from_pools(known_pools);
+        for (alias, key) in &settings.pool_aliases {
+            pool_normalizer = pool_normalizer
+                .with_alias(alias, key)
+                .with_context(|| format!("pool alias {alias}"))?;
+        }
+
+        let manager_objects = managers
+            .iter()
+            .map(|(key, manager)| (key.to_string(), manager.clone()))
+            .collect();
+        let pool_units = pool_units(
+            if pools.is_empty() {
+                default_pools(settings.environment)
+            } else {
+                &pools
+            },
+            if coins.is_empty() {
+                default_coins(settings.environment)
+            } else {
+                &coins
+            },
+        );
         let coins_opt = if coins.is_empty() { None } else { Some(coins) };
         let pools_opt = if pools.is_empty() { None } else { Some(pools) };
 
@@ -603,7 +774,7 @@ impl DeepBookAdapter {
             }
         };
 
-        let retry_config = RetryConfig::new(
+        let retry_policy = RetryPolicy::new(
             settings.retry.initial,
             settings.retry.max,
             settings.retry.max_elapsed,
@@ -614,21 +785,30 @@ impl DeepBookAdapter {
             sui,
             db,
             sender,
-            manager_key: settings.balance_manager_label.clone(),
-            pool_params_cache: TimedCache::new(POOL_PARAMS_TTL, "pool_params"),
+            managers: manager_keys,
+            manager_objects,
+            trade_authority_cache: TimedCache::new(TRADE_AUTHORITY_TTL, "trade_authority"),
+            pool_params_cache: TimedCache::new(settings.pool_params_ttl, "pool_params"),
+            pool_status_cache: TimedCache::new(POOL_STATUS_TTL, "pool_status"),
             trade_params_cache: TimedCache::new(TRADE_PARAMS_TTL, "trade_params"),
             balance_cache: TimedCache::new(BALANCE_TTL, "balances"),
             deep_price_cache: TimedCache::new(DEEP_PRICE_TTL, "deep_price"),
+            mid_price_cache: TimedCache::new(settings.mid_price_ttl, "mid_price"),
+            gas_price_cache: GasPriceCache::new(settings.gas_price_ttl),
+            book_cache: BookSnapshotCache::new(settings.book_cache),
             indexer,
-            retry_config,
+            retry_policy,
             fallback_use_fullnode: settings.fallback_use_fullnode,
             monitored_pools: settings.monitored_pools.clone(),
+            pool_param_overrides: Arc::new(settings.pool_params.clone()),
+            pool_normalizer,
+            pool_units: Arc::new(pool_units),
             reconcile_interval: settings.reconcile_interval,
         })
     }
 
     fn new_backoff(&self) -> ExponentialBackoff {
-        self.retry_config.to_backoff()
+        self.retry_policy.to_backoff()
     }
 
     async fn retry_with_backoff<T, F, Fut>(
@@ -650,6 +830,13 @@ impl DeepBookAdapter {
     }
 
     async fn load_pool_params(&self, pool: &str) -> Result<PoolParams> {
+        let fetched = self.fetch_pool_params(pool).await?;
+        resolve_pool_params(pool, fetched, self.pool_param_overrides.get(pool))
+    }
+
+    /// Pool params from the indexer, or from the fullnode when the indexer
+    /// fails or reports invalid values
+    async fn fetch_pool_params(&self, pool: &str) -> Result<PoolParams> {
         if let Some(indexer) = &self.indexer {
             let indexer = indexer.clone();
             let pool_key = pool.to_string();
@@ -665,7 +852,14 @@ impl DeepBookAdapter {
                     DEEPBOOK_INDEXER_REQUESTS
                         .with_label_values(&["pool_params", "ok"])
                         .inc();
-                    return Ok(params);
+                    match params.validate() {
+                        Ok(()) => return Ok(params),
+                        Err(err) => warn!(
+                            pool = pool,
+                            error = %err,
+                            "DeepBook indexer returned invalid pool params; falling back to fullnode"
+                        ),
+                    }
                 }
                 Err(err) => {
                     DEEPBOOK_INDEXER_REQUESTS
@@ -704,6 +898,34 @@ impl DeepBookAdapter {
         .await
     }
 
+    /// Status from the indexer, tried once so a missing status never holds up
+    /// an order. The fullnode exposes no status, so pools are assumed active
+    /// when the indexer is absent or failing.
+    async fn load_pool_status(&self, pool: &str) -> Result<PoolStatus> {
+        let Some(indexer) = &self.indexer else {
+            return Ok(PoolStatus::Active);
+        };
+        match indexer.fetch_pool_status(pool).await {
+            Ok(status) => {
+                DEEPBOOK_INDEXER_REQUESTS
+                    .with_label_values(&["pool_status", "ok"])
+                    .inc();
+                Ok(status)
+            }
+            Err(err) => {
+                DEEPBOOK_INDEXER_REQUESTS
+                    .with_label_values(&["pool_status", "error"])
+                    .inc();
+                warn!(
+                    pool = pool,
+                    error = %err,
+                    "DeepBook pool status lookup failed; assuming active"
+                );
+                Ok(PoolStatus::Active)
+            }
+        }
+    }
+
     async fn load_trade_params(&self, pool: &str) -> Result<TradeParams> {
         if let Some(indexer) = &self.indexer {
             let indexer = indexer.clone();
@@ -759,11 +981,11 @@ impl DeepBookAdapter {
         .await
     }
 
-    async fn load_balance_snapshot(&self, pool: &str) -> Result<BalanceSnapshot> {
+    async fn load_balance_snapshot(&self, pool: &str, manager: &str) -> Result<BalanceSnapshot> {
         if let Some(indexer) = &self.indexer {
             let indexer = indexer.clone();
             let pool_key = pool.to_string();
-            let manager_key = self.manager_key.clone();
+            let manager_key = manager.to_string();
             match self
                 .retry_with_backoff("deepbook_indexer_balances", move || {
                     let indexer = indexer.clone();
@@ -798,7 +1020,7 @@ impl DeepBookAdapter {
 
         let db = self.db.clone();
         let pool_key = pool.to_string();
-        let manager_key = self.manager_key.clone();
+        let manager_key = manager.to_string();
         self.retry_with_backoff("deepbook_fullnode_balances", move || {
             let db = db.clone();
             let pool = pool_key.clone();
@@ -881,10 +1103,10 @@ impl DeepBookAdapter {
         .await
     }
 
-    async fn load_open_orders_fullnode(&self, pool: &str) -> Result<Vec<u128>> {
+    async fn load_open_orders_fullnode(&self, pool: &str, manager: &str) -> Result<Vec<u128>> {
         let db = self.db.clone();
         let pool_key = pool.to_string();
-        let manager_key = self.manager_key.clone();
+        let manager_key = manager.to_string();
         self.retry_with_backoff("deepbook_fullnode_open_orders", move || {
             let db = db.clone();
             let pool = pool_key.clone();
@@ -898,14 +1120,14 @@ impl DeepBookAdapter {
         .await
     }
 
-    async fn fetch_indexer_open_orders(&self, pool: &str) -> Result<Vec<u128>> {
+    async fn fetch_indexer_open_orders(&self, pool: &str, manager: &str) -> Result<Vec<u128>> {
         let indexer = self
             .indexer
             .as_ref()
             .ok_or_else(|| anyhow!("DeepBook indexer not configured"))?
             .clone();
         let pool_key = pool.to_string();
-        let manager_key = self.manager_key.clone();
+        let manager_key = manager.to_string();
         let result = self
             .retry_with_backoff("deepbook_indexer_open_orders", move || {
                 let indexer = indexer.clone();
@@ -945,29 +1167,14 @@ impl DeepBookAdapter {
         }
         // 1) Quantize to pool constraints (tick, lot, min)
         let params = self.pool_params(&req.pool).await?;
-        let q_px = quantize_price(req.price, params.tick_size)?;
+        let q_px = quantize_price_for_side(req.price, params.tick_size, req.is_bid)?;
         let q_sz = quantize_size(req.quantity, params.lot_size, params.min_size)?;
 
         // 2) Compose a programmable transaction with the SDK's DeepBook contract
         let mut ptb = ProgrammableTransactionBuilder::new();
 
-        let client_order_id = req
-            .client_order_id
-            .parse::<u64>()
-            .context("client_order_id must parse to u64")?;
-
-        let place_params = PlaceLimitOrderParams {
-            pool_key: req.pool.clone(),
-            balance_manager_key: self.manager_key.clone(),
-            client_order_id,
-            price: q_px,
-            quantity: q_sz,
-            is_bid: req.is_bid,
-            expiration: Some(req.expiration_ms.unwrap_or(MAX_TIMESTAMP)),
-            order_type: Some(OrderType::NoRestriction),
-            self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
-            pay_with_deep: Some(req.pay_with_deep),
-        };
+        let manager_key = self.manager_key_for(req.manager.as_deref())?;
+        let place_params = req.place_params(manager_key, q_px, q_sz)?;
 
         self.db
             .deep_book
@@ -984,12 +1191,7 @@ impl DeepBookAdapter {
             .map(|obj| InputObjectKind::object_id(&obj))
             .collect();
 
-        let gas_price = self
-            .sui
-            .read_api()
-            .get_reference_gas_price()
-            .await
-            .context("fetch reference gas price")?;
+        let gas_price = self.reference_gas_price().await?;
 
         let gas = self
             .sui
@@ -1017,29 +1219,14 @@ impl DeepBookAdapter {
     ) -> Result<(sui_sdk::types::transaction::TransactionKind, SuiAddress)> {
         // 1) Quantize to pool constraints (tick, lot, min)
         let params = self.pool_params(&req.pool).await?;
-        let q_px = quantize_price(req.price, params.tick_size)?;
+        let q_px = quantize_price_for_side(req.price, params.tick_size, req.is_bid)?;
         let q_sz = quantize_size(req.quantity, params.lot_size, params.min_size)?;
 
         // 2) Compose a programmable transaction with the SDK's DeepBook contract
         let mut ptb = ProgrammableTransactionBuilder::new();
 
-        let client_order_id = req
-            .client_order_id
-            .parse::<u64>()
-            .context("client_order_id must parse to u64")?;
-
-        let place_params = PlaceLimitOrderParams {
-            pool_key: req.pool.clone(),
-            balance_manager_key: self.manager_key.clone(),
-            client_order_id,
-            price: q_px,
-            quantity: q_sz,
-            is_bid: req.is_bid,
-            expiration: Some(req.expiration_ms.unwrap_or(MAX_TIMESTAMP)),
-            order_type: Some(OrderType::NoRestriction),
-            self_matching_option: Some(SelfMatchingOptions::SelfMatchingAllowed),
-            pay_with_deep: Some(req.pay_with_deep),
-        };
+        let manager_key = self.manager_key_for(req.manager.as_deref())?;
+        let place_params = req.place_params(manager_key, q_px, q_sz)?;
 
         self.db
             .deep_book
@@ -1054,6 +1241,25 @@ impl DeepBookAdapter {
         Ok((tx_kind, self.sender))
     }
 
+    /// Build a PTB for a DeepBook market order and return BCS TransactionData bytes
+    pub async fn build_market_order_ptb_bcs(&self, req: &MarketReq) -> Result<Vec<u8>> {
+        let params = self.pool_params(&req.pool).await?;
+        let q_sz = quantize_size(req.quantity, params.lot_size, params.min_size)?;
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+
+        let manager_key = self.manager_key_for(req.manager.as_deref())?;
+        let place_params = req.place_params(manager_key, q_sz)?;
+
+        self.db
+            .deep_book
+            .place_market_order(&mut ptb, place_params)
+            .await
+            .context("build deepbook market order PTB")?;
+
+        self.finish_ptb(ptb, "market order").await
+    }
+
     /// Resolve a list of ObjectIDs into ObjectRefs using the node's read API.
     pub async fn object_refs_for_ids(
         &self,
@@ -1080,6 +1286,37 @@ impl DeepBookAdapter {
         Ok(refs)
     }
 
+    /// Resolve SUI coin ids into ObjectRefs with their balances in MIST
+    pub async fn coin_refs_with_balances(&self, ids: &[ObjectID]) -> Result<Vec<(ObjectRef, u64)>> {
+        let mut coins = Vec::with_capacity(ids.len());
+        for id in ids {
+            let resp = self
+                .sui
+                .read_api()
+                .get_object_with_options(*id, SuiObjectDataOptions::new().with_content())
+                .await
+                .with_context(|| format!("fetch coin {id}"))?;
+            let data = resp
+                .data
+                .with_context(|| format!("coin {id} not found or does not exist"))?;
+            let object_ref = (data.object_id, data.version, data.digest);
+            let balance = match data.content {
+                Some(SuiParsedData::MoveObject(object)) => object
+                    .fields
+                    .to_json_value()
+                    .get("balance")
+                    .and_then(|balance| match balance {
+                        serde_json::Value::String(s) => s.parse().ok(),
+                        other => other.as_u64(),
+                    }),
+                _ => None,
+            }
+            .with_context(|| format!("object {id} is not a coin"))?;
+            coins.push((object_ref, balance));
+        }
+        Ok(coins)
+    }
+
     /// Fetch pool parameters from the indexer or cache.
     pub async fn pool_params(&self, pool: &str) -> Result<PoolParams> {
         self.pool_params_cache
@@ -1091,12 +1328,115 @@ impl DeepBookAdapter {
             .await
     }
 
+    /// Registered BalanceManager keys
+    pub fn balance_managers(&self) -> &BalanceManagers {
+        &self.managers
+    }
+
+    /// Manager key an order trades from; the default when `requested` is unset
+    pub fn manager_key_for(&self, requested: Option<&str>) -> Result<String> {
+        Ok(self.managers.resolve(requested)?)
+    }
+
+    /// Owner and TradeCap state of the BalanceManager registered as `manager`
+    pub async fn trade_authority(&self, manager: &str) -> Result<TradeAuthority> {
+        self.trade_authority_cache
+            .get_or_try_insert_with(manager, || async {
+                let object = self
+                    .manager_objects
+                    .get(manager)
+                    .with_context(|| format!("balance manager {manager} is not registered"))?;
+                let (_, fields) = self.fetch_move_object(&object.address).await?;
+                let owner = fields
+                    .get("owner")
+                    .and_then(Value::as_str)
+                    .context("BalanceManager object has no owner field")?;
+                let owner = SuiAddress::from_str(owner)
+                    .with_context(|| format!("invalid BalanceManager owner {owner}"))?;
+                let allow_listed = fields
+                    .get("allow_listed")
+                    .and_then(|set| set.get("contents"))
+                    .and_then(Value::as_array)
+                    .map(|ids| {
+                        ids.iter()
+                            .filter_map(|id| id.as_str().map(str::to_owned))
+                            .collect()
+                    });
+                let trade_cap = match &object.trade_cap {
+                    Some(cap_id) => {
+                        let (holder, cap) = self.fetch_move_object(cap_id).await?;
+                        let balance_manager_id = cap
+                            .get("balance_manager_id")
+                            .and_then(Value::as_str)
+                            .context("TradeCap object has no balance_manager_id field")?;
+                        Some(TradeCapHolding {
+                            cap_id: cap_id.clone(),
+                            holder,
+                            balance_manager_id: balance_manager_id.to_string(),
+                        })
+                    }
+                    None => None,
+                };
+                Ok(TradeAuthority {
+                    manager_id: object.address.clone(),
+                    owner,
+                    trade_cap,
+                    allow_listed,
+                })
+            })
+            .await
+    }
+
+    /// Fail with `MissingTradeCap` unless the sender may place orders from
+    /// `manager` (the default when unset)
+    pub async fn verify_trade_authority(&self, manager: Option<&str>) -> Result<()> {
+        let key = self.manager_key_for(manager)?;
+        self.trade_authority(&key)
+            .await?
+            .verify(&key, self.sender)?;
+        Ok(())
+    }
+
+    /// Address owner and Move fields of object `id`
+    async fn fetch_move_object(&self, id: &str) -> Result<(Option<SuiAddress>, Value)> {
+        let object_id =
+            ObjectID::from_hex_literal(id).with_context(|| format!("invalid object id {id}"))?;
+        let resp = self
+            .sui
+            .read_api()
+            .get_object_with_options(
+                object_id,
+                SuiObjectDataOptions::new().with_owner().with_content(),
+            )
+            .await
+            .with_context(|| format!("fetch object {id}"))?;
+        let data = resp
+            .data
+            .with_context(|| format!("object {id} not found or does not exist"))?;
+        let holder = match data.owner {
+            Some(Owner::AddressOwner(address)) => Some(address),
+            _ => None,
+        };
+        match data.content {
+            Some(SuiParsedData::MoveObject(object)) => Ok((holder, object.fields.to_json_value())),
+            _ => bail!("object {id} is not a Move object"),
+        }
+    }
+
+    /// Balances of the default BalanceManager in `pool`
     pub async fn balance_manager_balances(&self, pool: &str) -> Result<BalanceSnapshot> {
+        self.manager_balances(pool, self.managers.default_key())
+            .await
+    }
+
+    /// Balances of the BalanceManager registered as `manager` in `pool`
+    pub async fn manager_balances(&self, pool: &str, manager: &str) -> Result<BalanceSnapshot> {
         self.balance_cache
-            .get_or_try_insert_with(pool, || {
+            .get_or_try_insert_with(&balance_cache_key(pool, manager), || {
                 let adapter = self.clone();
                 let pool_key = pool.to_string();
-                async move { adapter.load_balance_snapshot(&pool_key).await }
+                let manager_key = manager.to_string();
+                async move { adapter.load_balance_snapshot(&pool_key, &manager_key).await }
             })
             .await
     }
@@ -1117,8 +1457,20 @@ impl DeepBookAdapter {
         self.deep_price_cache.invalidate(pool).await;
     }
 
+    /// Drop everything cached about `pool` so the next read refetches it:
+    /// params, trade params, DEEP price, mid price and book snapshots
+    pub async fn invalidate_pool(&self, pool: &str) {
+        self.invalidate_pool_metadata(pool).await;
+        self.mid_price_cache.invalidate(pool).await;
+        self.book_cache.invalidate_pool(pool).await;
+    }
+
     pub async fn invalidate_pool_balances(&self, pool: &str) {
-        self.balance_cache.invalidate(pool).await;
+        for manager in self.managers.keys() {
+            self.balance_cache
+                .invalidate(&balance_cache_key(pool, manager))
+                .await;
+        }
     }
 
     pub async fn invalidate_all_caches(&self) {
@@ -1126,22 +1478,18 @@ impl DeepBookAdapter {
         self.trade_params_cache.invalidate_all().await;
         self.balance_cache.invalidate_all().await;
         self.deep_price_cache.invalidate_all().await;
-    }
-
-    fn is_order_placed_event(event: &SuiEvent) -> bool {
-        let module = event.type_.module.as_str();
-        let name = event.type_.name.as_str();
-        (module == "clob_v2" && name == "OrderPlaced")
-            || (module == "clob" && (name == "OrderPlacedV2" || name == "OrderPlaced"))
+        self.mid_price_cache.invalidate_all().await;
+        self.gas_price_cache.invalidate().await;
+        self.book_cache.invalidate_all().await;
     }
 
     fn is_deepbook_module(module: &str) -> bool {
-        matches!(module, "clob" | "clob_v2")
+        matches!(module, "order_info" | "order" | "clob" | "clob_v2")
     }
 
     fn matches_owner(event: &SuiEvent, sender: &SuiAddress) -> bool {
         if let Some(owner) =
-            Self::extract_string_field(&event.parsed_json, &["owner", "accountOwner"])
+            Self::extract_string_field(&event.parsed_json, &["trader", "owner", "accountOwner"])
         {
             if let Ok(owner_addr) = SuiAddress::from_str(&owner) {
                 return &owner_addr == sender;
@@ -1150,8 +1498,13 @@ impl DeepBookAdapter {
         true
     }
 
-    fn pool_key_matches(event_pool: &str, target_pool: &str) -> bool {
-        event_pool.eq_ignore_ascii_case(target_pool)
+    /// Whether an event's pool (object id, or key on older events) is `pool`
+    fn event_in_pool(&self, event_pool: &str, pool: &str) -> bool {
+        event_pool.eq_ignore_ascii_case(pool)
+            || self
+                .pool_normalizer
+                .resolve(event_pool)
+                .is_ok_and(|key| key == pool)
     }
 
     fn extract_string_field(value: &Value, keys: &[&str]) -> Option<String> {
@@ -1185,56 +1538,64 @@ impl DeepBookAdapter {
         None
     }
 
-    fn extract_u64_field(value: &Value, keys: &[&str]) -> Option<u64> {
-        for key in keys {
-            if let Some(v) = value.get(*key) {
-                match v {
-                    Value::Number(n) => {
-                        if let Some(u) = n.as_u64() {
-                            return Some(u);
-                        }
-                    }
-                    Value::String(s) => {
-                        if let Ok(u) = s.parse::<u64>() {
-                            return Some(u);
-                        }
-                    }
-                    Value::Object(obj) => {
-                        if let Some(Value::String(inner)) = obj.get("value") {
-                            if let Ok(u) = inner.parse::<u64>() {
-                                return Some(u);
-                            }
-                        } else if let Some(Value::Number(inner)) = obj.get("value") {
-                            if let Some(u) = inner.as_u64() {
-                                return Some(u);
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        None
-    }
-
     /// Get mid price for a pool
     pub async fn mid_price(&self, pool: &str) -> Result<f64> {
-        self.db
-            .mid_price(pool)
+        self.mid_price_cache
+            .get_or_try_insert_with(pool, || async {
+                self.db
+                    .mid_price(pool)
+                    .await
+                    .with_context(|| format!("fetch mid price for {pool}"))
+            })
             .await
-            .with_context(|| format!("fetch mid price for {pool}"))
     }
 
-    /// Get level 2 order book data (ticks from mid)
+    /// Get level 2 order book data (ticks from mid), served from the snapshot cache
     pub async fn level2_ticks_from_mid(
         &self,
         pool: &str,
         ticks: u64,
-    ) -> Result<sui_deepbookv3::client::Level2TicksFromMid> {
-        self.db
-            .get_level2_ticks_from_mid(pool, ticks)
+    ) -> Result<Arc<sui_deepbookv3::client::Level2TicksFromMid>> {
+        self.book_cache
+            .get_or_fetch(pool, ticks, || async {
+                self.db
+                    .get_level2_ticks_from_mid(pool, ticks)
+                    .await
+                    .with_context(|| format!("fetch level2 order book for {pool}"))
+            })
             .await
-            .with_context(|| format!("fetch level2 order book for {pool}"))
+    }
+
+    /// Drop cached book snapshots and mid prices for pools changed in `checkpoint`
+    pub async fn invalidate_books_for_checkpoint(&self, checkpoint: &Checkpoint) -> Vec<String> {
+        let pools = self
+            .book_cache
+            .apply_checkpoint(checkpoint, &self.pool_normalizer)
+            .await;
+        for pool in &pools {
+            self.mid_price_cache.invalidate(pool).await;
+        }
+        pools
+    }
+
+    pub async fn invalidate_book_snapshots(&self) {
+        self.book_cache.invalidate_all().await;
+        self.mid_price_cache.invalidate_all().await;
+    }
+
+    pub fn book_cache_settings(&self) -> &BookCacheSettings {
+        self.book_cache.settings()
+    }
+
+    /// VWAP and worst fill price for taking `quantity` from the visible book
+    pub async fn vwap_for_size(
+        &self,
+        pool: &str,
+        quantity: f64,
+        is_bid: bool,
+    ) -> Result<Option<BookSweep>> {
+        let level2 = self.level2_ticks_from_mid(pool, VWAP_BOOK_TICKS).await?;
+        Ok(sweep_for_taker(&level2, quantity, is_bid))
     }
 
     /// Get level 2 order book data (price range)
@@ -1252,6 +1613,18 @@ impl DeepBookAdapter {
     }
 
     /// Get pool trade parameters (fees, stake requirements)
+    /// Trading status of `pool`, refreshed every `POOL_STATUS_TTL`
+    pub async fn pool_status(&self, pool: &str) -> PoolStatus {
+        self.pool_status_cache
+            .get_or_try_insert_with(pool, || {
+                let adapter = self.clone();
+                let pool_key = pool.to_string();
+                async move { adapter.load_pool_status(&pool_key).await }
+            })
+            .await
+            .unwrap_or(PoolStatus::Active)
+    }
+
     pub async fn trade_params(&self, pool: &str) -> Result<TradeParams> {
         self.trade_params_cache
             .get_or_try_insert_with(pool, || {
@@ -1262,13 +1635,22 @@ impl DeepBookAdapter {
             .await
     }
 
-    /// Get reference gas price from the network
+    /// Reference gas price, fetched from the network at most once per cache TTL
     pub async fn reference_gas_price(&self) -> Result<u64> {
-        self.sui
-            .read_api()
-            .get_reference_gas_price()
+        self.gas_price_cache
+            .get_or_fetch(|| async {
+                self.sui
+                    .read_api()
+                    .get_reference_gas_price()
+                    .await
+                    .context("fetch reference gas price")
+            })
             .await
-            .context("fetch reference gas price")
+    }
+
+    /// Prime the gas price cache with a value obtained elsewhere (e.g. gRPC epoch info)
+    pub async fn seed_reference_gas_price(&self, price: u64) {
+        self.gas_price_cache.seed(price).await;
     }
 
     /// Build a cancel order command for a PTB
@@ -1277,46 +1659,86 @@ impl DeepBookAdapter {
         &self,
         ptb: &mut ProgrammableTransactionBuilder,
         pool: &str,
+        manager: Option<&str>,
         order_id: u128,
     ) -> Result<sui_sdk::types::transaction::Argument> {
+        let manager_key = self.manager_key_for(manager)?;
         self.db
             .deep_book
-            .cancel_order(ptb, pool, &self.manager_key, order_id)
+            .cancel_order(ptb, pool, &manager_key, order_id)
             .await
             .context("build cancel order command")
     }
 
     /// Build a standalone PTB for canceling a DeepBook order.
-    pub async fn build_cancel_order_ptb_bcs(&self, pool: &str, order_id: u128) -> Result<Vec<u8>> {
+    pub async fn build_cancel_order_ptb_bcs(
+        &self,
+        pool: &str,
+        manager: Option<&str>,
+        order_id: u128,
+    ) -> Result<Vec<u8>> {
         let mut ptb = ProgrammableTransactionBuilder::new();
 
+        let manager_key = self.manager_key_for(manager)?;
         self.db
             .deep_book
-            .cancel_order(&mut ptb, pool, &self.manager_key, order_id)
+            .cancel_order(&mut ptb, pool, &manager_key, order_id)
             .await
             .with_context(|| format!("build cancel order command for {pool}"))?;
 
+        self.finish_ptb(ptb, "cancel order").await
+    }
+
+    /// Build one PTB cancelling every open order the BalanceManager has in `pool`,
+    /// one cancel command per order. Fails with [`AggrError::NoOpenOrders`] rather
+    /// than building an empty transaction.
+    pub async fn build_cancel_all_orders_ptb_bcs(
+        &self,
+        pool: &str,
+        manager: Option<&str>,
+    ) -> Result<Vec<u8>> {
+        let manager_key = self.manager_key_for(manager)?;
+        let open = self.load_open_orders_fullnode(pool, &manager_key).await?;
+        let order_ids = cancel_all_order_ids(pool, open)?;
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        for order_id in &order_ids {
+            self.db
+                .deep_book
+                .cancel_order(&mut ptb, pool, &manager_key, *order_id)
+                .await
+                .with_context(|| format!("build cancel command for order {order_id} in {pool}"))?;
+        }
+        debug!(pool, orders = order_ids.len(), "built cancel-all PTB");
+
+        self.finish_ptb(ptb, "cancel all orders").await
+    }
+
+    /// Select gas for a user-paid PTB and serialize its TransactionData
+    async fn finish_ptb(
+        &self,
+        ptb: ProgrammableTransactionBuilder,
+        action: &str,
+    ) -> Result<Vec<u8>> {
         let programmable = ptb.finish();
         let input_objects: Vec<_> = programmable
             .input_objects()
-            .context("collect input objects for cancel PTB")?
+            .with_context(|| format!("collect input objects for {action} PTB"))?
             .into_iter()
             .map(|obj| InputObjectKind::object_id(&obj))
             .collect();
 
         let gas_price = self
-            .sui
-            .read_api()
-            .get_reference_gas_price()
+            .reference_gas_price()
             .await
-            .context("fetch reference gas price for cancel order")?;
+            .with_context(|| format!("reference gas price for {action}"))?;
 
         let gas = self
             .sui
             .transaction_builder()
             .select_gas(self.sender, None, GAS_BUDGET, input_objects, gas_price)
             .await
-            .context("select gas coin for cancel order")?;
+            .with_context(|| format!("select gas coin for {action}"))?;
 
         let tx_data = TransactionData::new(
             TransactionKind::programmable(programmable),
@@ -1326,7 +1748,8 @@ impl DeepBookAdapter {
             gas_price,
         );
 
-        let tx_bcs = bcs::to_bytes(&tx_data).context("serialize cancel order transaction")?;
+        let tx_bcs =
+            bcs::to_bytes(&tx_data).with_context(|| format!("serialize {action} transaction"))?;
         Ok(tx_bcs)
     }
 
@@ -1364,7 +1787,39 @@ impl DeepBookAdapter {
         Ok(filtered)
     }
 
-    pub async fn get_order_id_from_digest(&self, digest: &str, pool: &str) -> Result<Option<u128>> {
+    /// Fetch the raw BCS-encoded effects of a transaction from the read API
+    pub async fn raw_effects_for_digest(&self, digest: &str) -> Result<Option<Vec<u8>>> {
+        use sui_sdk::types::digests::TransactionDigest;
+
+        let tx_digest = TransactionDigest::from_str(digest)
+            .map_err(|e| anyhow::anyhow!("invalid transaction digest: {}", e))?;
+
+        let tx = self
+            .sui
+            .read_api()
+            .get_transaction_with_options(
+                tx_digest,
+                sui_sdk::rpc_types::SuiTransactionBlockResponseOptions::new().with_raw_effects(),
+            )
+            .await
+            .context("query raw effects by digest")?;
+
+        if tx.raw_effects.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(tx.raw_effects))
+        }
+    }
+
+    /// Id of the order `digest` placed (or modified) in `pool`, read from its
+    /// OrderPlaced / OrderModified events. `client_order_id` picks one out
+    /// when the transaction placed several orders in the pool.
+    pub async fn get_order_id_from_digest(
+        &self,
+        digest: &str,
+        pool: &str,
+        client_order_id: Option<u64>,
+    ) -> Result<Option<u128>> {
         let events = self.deepbook_events_for_digest(digest).await?;
         if events.is_empty() {
             debug!(
@@ -1375,13 +1830,13 @@ impl DeepBookAdapter {
             return Ok(None);
         }
 
-        let order_id = Self::extract_order_id_from_events(&events, pool, &self.sender, digest);
-
+        let order_id = self.order_id_from_events(&events, pool, client_order_id, digest)?;
         if order_id.is_none() {
             warn!(
-                            digest = digest,
-                            pool = pool,
-                            sender = %self.sender,
+                digest = digest,
+                pool = pool,
+                client_order_id = ?client_order_id,
+                sender = %self.sender,
                 "failed to locate DeepBook OrderPlaced event for transaction"
             );
         }
@@ -1389,57 +1844,51 @@ impl DeepBookAdapter {
         Ok(order_id)
     }
 
-    fn extract_order_id_from_events(
-        events: &[SuiEvent],
-        pool: &str,
-        sender: &SuiAddress,
-        digest: &str,
-    ) -> Option<u128> {
-        for event in events {
-            if !Self::is_order_placed_event(event) {
-                continue;
-            }
-
-            if !Self::matches_owner(event, sender) {
-                continue;
-            }
-
-            if let Some(event_pool) =
-                Self::extract_string_field(&event.parsed_json, &["poolKey", "pool", "pool_id"])
-            {
-                if !Self::pool_key_matches(&event_pool, pool) {
-                    continue;
-                }
-            }
-
-            if let Some(order_id) =
-                Self::extract_u64_field(&event.parsed_json, &["orderId", "order_id"])
-            {
-                debug!(
-                    digest = digest,
-                    pool = pool,
-                    order_id = order_id,
-                    "derived DeepBook order id from cached events"
-                );
-                return Some(order_id as u128);
-            }
-        }
-        None
-    }
-
     pub(crate) fn order_id_from_events(
         &self,
         events: &[SuiEvent],
         pool: &str,
+        client_order_id: Option<u64>,
         digest: &str,
-    ) -> Option<u128> {
-        Self::extract_order_id_from_events(events, pool, &self.sender, digest)
+    ) -> Result<Option<u128>> {
+        let orders: Vec<OrderEvent> = events
+            .iter()
+            .filter(|event| Self::matches_owner(event, &self.sender))
+            .filter_map(|event| {
+                OrderEvent::parse(
+                    event.type_.module.as_str(),
+                    event.type_.name.as_str(),
+                    &event.parsed_json,
+                )
+            })
+            .collect();
+        let order_id = select_order_id(
+            &orders,
+            |event_pool| self.event_in_pool(event_pool, pool),
+            client_order_id,
+        )?;
+        if let Some(order_id) = order_id {
+            debug!(
+                digest = digest,
+                pool = pool,
+                order_id = order_id,
+                "derived DeepBook order id from transaction events"
+            );
+        }
+        Ok(order_id)
     }
 
     /// Get open order IDs for the account in a pool
+    /// Open orders of the default BalanceManager in `pool`
     pub async fn get_open_order_ids(&self, pool: &str) -> Result<Vec<u128>> {
+        self.open_order_ids_for(pool, self.managers.default_key())
+            .await
+    }
+
+    /// Open orders of the BalanceManager registered as `manager` in `pool`
+    pub async fn open_order_ids_for(&self, pool: &str, manager: &str) -> Result<Vec<u128>> {
         if self.indexer.is_some() {
-            match self.fetch_indexer_open_orders(pool).await {
+            match self.fetch_indexer_open_orders(pool, manager).await {
                 Ok(ids) => return Ok(ids),
                 Err(err) => {
                     warn!(
@@ -1455,7 +1904,7 @@ impl DeepBookAdapter {
             bail!("DeepBook open orders unavailable and fallback disabled");
         }
 
-        self.load_open_orders_fullnode(pool).await
+        self.load_open_orders_fullnode(pool, manager).await
     }
 
     pub async fn reconcile_open_orders(&self) -> Result<Vec<OpenOrderDiscrepancy>> {
@@ -1466,7 +1915,10 @@ impl DeepBookAdapter {
 
         let mut discrepancies = Vec::new();
         for pool in &self.monitored_pools {
-            let indexer_orders = match self.fetch_indexer_open_orders(pool).await {
+            let indexer_orders = match self
+                .fetch_indexer_open_orders(pool, self.managers.default_key())
+                .await
+            {
                 Ok(ids) => ids,
                 Err(err) => {
                     warn!(
@@ -1478,7 +1930,10 @@ impl DeepBookAdapter {
                 }
             };
 
-            let on_chain_orders = match self.load_open_orders_fullnode(pool).await {
+            let on_chain_orders = match self
+                .load_open_orders_fullnode(pool, self.managers.default_key())
+                .await
+            {
                 Ok(ids) => ids,
                 Err(err) => {
                     warn!(
@@ -1548,6 +2003,18 @@ impl DeepBookAdapter {
         &self.monitored_pools
     }
 
+    /// Canonicalizer for client-supplied pool identifiers
+    pub fn pool_normalizer(&self) -> &PoolNormalizer {
+        &self.pool_normalizer
+    }
+
+    /// Base and quote units of a canonical pool key
+    pub fn pool_units(&self, pool: &str) -> Result<&PoolUnits, AggrError> {
+        self.pool_units.get(pool).ok_or_else(|| {
+            AggrError::InvalidAmount(format!("no coin decimals known for pool {pool}"))
+        })
+    }
+
     pub fn reconciliation_interval(&self) -> Duration {
         self.reconcile_interval
     }
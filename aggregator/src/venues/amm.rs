@@ -1,76 +1,583 @@
 // AMM venue adapter module
-// This file implements the adapter pattern for AMM DEX venues (e.g., Cetus, Turbos)
-// Currently a placeholder structure for future implementation
+// This file implements the adapter pattern for AMM DEX venues, with Cetus
+// CLMM pools as the first implementation
 //
 // Numan Thabit 2025 Nov
 
-use anyhow::Result;
-use sui_sdk::types::base_types::SuiAddress;
+use crate::config::CetusSettings;
+use crate::venues::cache::TimedCache;
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use sui_deepbookv3::utils::config::GAS_BUDGET;
+use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiParsedData};
+use sui_sdk::types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_sdk::types::gas_coin::GAS;
+use sui_sdk::types::object::Owner;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{
+    Argument, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+};
+use sui_sdk::types::{
+    Identifier, TypeTag, SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION,
+    SUI_FRAMEWORK_PACKAGE_ID,
+};
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use tracing::info;
 
-/// AMM adapter trait - defines interface for AMM venue interactions
+/// AMM adapter trait - defines interface for AMM venue interactions.
+/// Pools are named by the same keys DeepBook routes use (e.g. `SUI_USDC`),
+/// prices are quote per base and amounts are in whole coin units.
 #[allow(async_fn_in_trait)]
 pub trait AmmAdapter: Send + Sync {
     /// Get the pool's current price
     async fn get_price(&self, pool_id: &str) -> Result<f64>;
 
-    /// Get available liquidity for a swap
+    /// Estimated output for swapping `amount_in`: quote in for base out when
+    /// `is_buy`, base in for quote out otherwise
     async fn get_liquidity(&self, pool_id: &str, amount_in: f64, is_buy: bool) -> Result<f64>;
 
+    /// Estimated input needed to receive exactly `amount_out`: quote in for
+    /// base out when `is_buy`, base in for quote out otherwise
+    async fn get_amount_in(&self, pool_id: &str, amount_out: f64, is_buy: bool) -> Result<f64>;
+
     /// Build a swap PTB (programmable transaction)
-    async fn build_swap_ptb(
-        &self,
-        pool_id: &str,
-        amount_in: f64,
-        amount_out_min: f64,
-        is_buy: bool,
-    ) -> Result<Vec<u8>>;
+    async fn build_swap_ptb(&self, swap: &AmmSwapReq) -> Result<Vec<u8>>;
+}
+
+/// Cetus `sqrt_price` fixed-point scale (Q64.64)
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+/// Cetus fee rates are parts per million
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+/// Lowest sqrt price a Cetus swap may move to; the limit for a2b swaps
+const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+/// Highest sqrt price a Cetus swap may move to; the limit for b2a swaps
+const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+/// How long a pool's price and liquidity are reused across quotes
+const POOL_STATE_TTL: Duration = Duration::from_millis(500);
+/// Shared object versions never change, so they are kept for as long as possible
+const SHARED_VERSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A Cetus pool trading the same pair as a DeepBook pool key
+#[derive(Debug, Clone)]
+pub struct CetusPool {
+    pub pool_id: ObjectID,
+    /// Full Move type of the pool's coin A
+    pub coin_a: String,
+    /// Full Move type of the pool's coin B
+    pub coin_b: String,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// Whether coin A is the pair's base coin; otherwise coin B is
+    pub a_is_base: bool,
+}
+
+impl CetusPool {
+    /// Whether a swap in direction `is_buy` sells coin A for coin B
+    pub fn a2b(&self, is_buy: bool) -> bool {
+        // Buying base spends quote: a2b only when A is the quote coin
+        is_buy != self.a_is_base
+    }
+
+    /// Decimals of the coin a swap in direction `a2b` spends and receives
+    fn decimals(&self, a2b: bool) -> (u8, u8) {
+        if a2b {
+            (self.decimals_a, self.decimals_b)
+        } else {
+            (self.decimals_b, self.decimals_a)
+        }
+    }
+}
+
+/// On-chain state of a Cetus pool needed to price swaps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CetusPoolState {
+    /// sqrt(price of A in B) in Q64.64, in atomic units
+    pub sqrt_price_x64: u128,
+    /// Active liquidity around the current price
+    pub liquidity: u128,
+    /// Swap fee in parts per million
+    pub fee_rate: u64,
+    pub is_paused: bool,
+}
+
+impl CetusPoolState {
+    /// State of a pool holding `reserve_a` and `reserve_b` atomic units as
+    /// one full-range position
+    pub fn from_reserves(reserve_a: f64, reserve_b: f64, fee_rate: u64) -> Self {
+        Self {
+            sqrt_price_x64: ((reserve_b / reserve_a).sqrt() * Q64) as u128,
+            liquidity: (reserve_a * reserve_b).sqrt() as u128,
+            fee_rate,
+            is_paused: false,
+        }
+    }
+
+    /// Read the state from a pool object's Move fields
+    pub fn from_fields(fields: &Value) -> Result<Self> {
+        Ok(Self {
+            sqrt_price_x64: u128_field(fields, "current_sqrt_price")?,
+            liquidity: u128_field(fields, "liquidity")?,
+            fee_rate: u128_field(fields, "fee_rate")?
+                .try_into()
+                .context("fee_rate out of range")?,
+            is_paused: fields
+                .get("is_pause")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Price of one atomic unit of A in atomic units of B
+    pub fn raw_price(&self) -> f64 {
+        let sqrt_price = self.sqrt_price_x64 as f64 / Q64;
+        sqrt_price * sqrt_price
+    }
+
+    /// Price of one whole A in whole B
+    pub fn price(&self, decimals_a: u8, decimals_b: u8) -> f64 {
+        self.raw_price() * 10f64.powi(i32::from(decimals_a) - i32::from(decimals_b))
+    }
+
+    /// Reserves of A and B, in atomic units, that the active liquidity
+    /// behaves as at the current price
+    pub fn virtual_reserves(&self) -> (f64, f64) {
+        let sqrt_price = self.sqrt_price_x64 as f64 / Q64;
+        let liquidity = self.liquidity as f64;
+        (liquidity / sqrt_price, liquidity * sqrt_price)
+    }
+
+    /// Atomic units received for `amount_in` atomic units of A (`a2b`) or B,
+    /// after the pool fee. Assumes the swap stays within the active range, so
+    /// swaps that cross initialized ticks are priced optimistically.
+    pub fn amount_out(&self, amount_in: f64, a2b: bool) -> f64 {
+        let (reserve_a, reserve_b) = self.virtual_reserves();
+        let (reserve_in, reserve_out) = if a2b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        constant_product_out(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            self.fee_rate as f64 / FEE_RATE_DENOMINATOR,
+        )
+    }
+
+    /// Atomic units of A (`a2b`) or B needed to receive exactly `amount_out`
+    /// atomic units, fee included. Infinite when the active liquidity cannot
+    /// provide `amount_out`; the same in-range assumption as `amount_out` applies.
+    pub fn amount_in(&self, amount_out: f64, a2b: bool) -> f64 {
+        let (reserve_a, reserve_b) = self.virtual_reserves();
+        let (reserve_in, reserve_out) = if a2b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        constant_product_in(
+            reserve_in,
+            reserve_out,
+            amount_out,
+            self.fee_rate as f64 / FEE_RATE_DENOMINATOR,
+        )
+    }
+}
+
+/// Output of a constant-product swap of `amount_in` against the given
+/// reserves, with `fee_rate` of the input kept by the pool
+pub fn constant_product_out(
+    reserve_in: f64,
+    reserve_out: f64,
+    amount_in: f64,
+    fee_rate: f64,
+) -> f64 {
+    if !(reserve_in > 0.0 && reserve_out > 0.0 && amount_in > 0.0) {
+        return 0.0;
+    }
+    let effective_in = amount_in * (1.0 - fee_rate);
+    reserve_out * effective_in / (reserve_in + effective_in)
+}
+
+/// Input a constant-product swap needs to pay out exactly `amount_out`
+/// against the given reserves, with `fee_rate` of the input kept by the pool.
+/// Infinite when `amount_out` would drain the output reserve.
+pub fn constant_product_in(
+    reserve_in: f64,
+    reserve_out: f64,
+    amount_out: f64,
+    fee_rate: f64,
+) -> f64 {
+    if !(reserve_in > 0.0 && amount_out > 0.0) {
+        return 0.0;
+    }
+    if amount_out >= reserve_out || fee_rate >= 1.0 {
+        return f64::INFINITY;
+    }
+    let effective_in = reserve_in * amount_out / (reserve_out - amount_out);
+    effective_in / (1.0 - fee_rate)
+}
+
+/// Whole coin units to atomic units
+fn to_atomic(amount: f64, decimals: u8) -> Result<u64> {
+    let atomic = (amount * 10f64.powi(i32::from(decimals))).floor();
+    if !(atomic.is_finite() && atomic >= 0.0 && atomic <= u64::MAX as f64) {
+        bail!("amount {amount} out of range");
+    }
+    Ok(atomic as u64)
+}
+
+fn u128_field(fields: &Value, name: &str) -> Result<u128> {
+    match fields.get(name) {
+        Some(Value::String(s)) => s
+            .parse()
+            .with_context(|| format!("parse pool field {name}")),
+        Some(Value::Number(n)) => n
+            .as_u64()
+            .map(u128::from)
+            .with_context(|| format!("pool field {name} is not an unsigned integer")),
+        _ => bail!("pool field {name} missing"),
+    }
+}
+
+/// A swap on an AMM pool, as compiled into a multi-venue PTB
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmmSwapReq {
+    /// Pool key shared with DeepBook, e.g. `SUI_USDC`
+    pub pool: String,
+    /// Exact amount of the swap: spent when `by_amount_in`, received otherwise
+    pub amount: f64,
+    /// Bound on the other side: least received when `by_amount_in`, most
+    /// spent otherwise
+    pub amount_limit: f64,
+    /// Whether `amount` is the input (exact-in) rather than the output (exact-out)
+    pub by_amount_in: bool,
+    pub is_buy: bool,
+}
+
+impl AmmSwapReq {
+    /// Swap for a limit order of `quantity` base at no worse than `price`.
+    /// Buys receive exactly `quantity` base for at most the limit notional in
+    /// quote; sells spend exactly `quantity` base for at least that notional.
+    pub fn limit(pool: &str, price: f64, quantity: f64, is_buy: bool) -> Self {
+        Self {
+            pool: pool.to_string(),
+            amount: quantity,
+            amount_limit: price * quantity,
+            by_amount_in: !is_buy,
+            is_buy,
+        }
+    }
 }
 
-/// Generic AMM adapter placeholder
-/// This will be extended with specific AMM implementations (Cetus, Turbos, etc.)
-pub struct GenericAmmAdapter {
-    #[allow(dead_code)]
+/// Cetus CLMM adapter. Swaps go through the integrate package's
+/// `pool_script_v2`, which returns the output and any unspent input to the sender.
+#[derive(Clone)]
+pub struct CetusAdapter {
     sui: SuiClient,
-    #[allow(dead_code)]
     sender: SuiAddress,
-    // Future: AMM-specific configuration
+    package_id: ObjectID,
+    global_config: ObjectID,
+    pools: HashMap<String, CetusPool>,
+    state_cache: TimedCache<CetusPoolState>,
+    shared_versions: TimedCache<SequenceNumber>,
 }
 
-impl GenericAmmAdapter {
-    pub async fn new(fullnode_url: &str, sender: SuiAddress) -> Result<Self> {
+impl CetusAdapter {
+    pub async fn new(
+        fullnode_url: &str,
+        sender: SuiAddress,
+        settings: &CetusSettings,
+    ) -> Result<Self> {
         let sui = SuiClientBuilder::default().build(fullnode_url).await?;
 
-        info!("Generic AMM adapter initialized");
+        info!(pools = settings.pools.len(), "Cetus adapter initialized");
 
-        Ok(Self { sui, sender })
+        Ok(Self {
+            sui,
+            sender,
+            package_id: settings.package_id,
+            global_config: settings.global_config,
+            pools: settings.pools.clone(),
+            state_cache: TimedCache::new(POOL_STATE_TTL, "cetus_pool_state"),
+            shared_versions: TimedCache::new(SHARED_VERSION_TTL, "cetus_shared_version"),
+        })
+    }
+
+    /// Cetus pool configured for DeepBook pool key `pool`, matched
+    /// case-insensitively as environment config lowercases keys
+    pub fn pool(&self, pool: &str) -> Option<&CetusPool> {
+        self.pools
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(pool))
+            .map(|(_, cetus)| cetus)
+    }
+
+    fn configured_pool(&self, pool: &str) -> Result<&CetusPool> {
+        self.pool(pool)
+            .with_context(|| format!("no Cetus pool configured for {pool}"))
+    }
+
+    pub fn sui_client(&self) -> &SuiClient {
+        &self.sui
+    }
+
+    pub async fn reference_gas_price(&self) -> Result<u64> {
+        self.sui
+            .read_api()
+            .get_reference_gas_price()
+            .await
+            .context("fetch reference gas price")
+    }
+
+    /// Current price and liquidity of `pool`'s Cetus pool
+    pub async fn pool_state(&self, pool: &str) -> Result<CetusPoolState> {
+        let pool_id = self.configured_pool(pool)?.pool_id;
+        self.state_cache
+            .get_or_try_insert_with(pool, || self.load_pool_state(pool_id))
+            .await
+    }
+
+    async fn load_pool_state(&self, pool_id: ObjectID) -> Result<CetusPoolState> {
+        let resp = self
+            .sui
+            .read_api()
+            .get_object_with_options(pool_id, SuiObjectDataOptions::new().with_content())
+            .await
+            .with_context(|| format!("fetch Cetus pool {pool_id}"))?;
+        match resp.data.and_then(|data| data.content) {
+            Some(SuiParsedData::MoveObject(object)) => {
+                CetusPoolState::from_fields(&object.fields.to_json_value())
+                    .with_context(|| format!("decode Cetus pool {pool_id}"))
+            }
+            _ => bail!("Cetus pool {pool_id} not found or not a Move object"),
+        }
+    }
+
+    /// Initial shared version of shared object `id`
+    async fn shared_version(&self, id: ObjectID) -> Result<SequenceNumber> {
+        self.shared_versions
+            .get_or_try_insert_with(&id.to_string(), || self.load_shared_version(id))
+            .await
     }
-}
 
-impl AmmAdapter for GenericAmmAdapter {
-    async fn get_price(&self, _pool_id: &str) -> Result<f64> {
-        // TODO: Implement price fetching from AMM
-        anyhow::bail!("AMM price fetching not yet implemented")
+    async fn load_shared_version(&self, id: ObjectID) -> Result<SequenceNumber> {
+        let resp = self
+            .sui
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::new().with_owner())
+            .await
+            .with_context(|| format!("fetch object {id}"))?;
+        match resp.data.and_then(|data| data.owner) {
+            Some(Owner::Shared {
+                initial_shared_version,
+            }) => Ok(initial_shared_version),
+            _ => bail!("object {id} is not shared"),
+        }
     }
 
-    async fn get_liquidity(&self, _pool_id: &str, _amount_in: f64, _is_buy: bool) -> Result<f64> {
-        // TODO: Implement liquidity calculation
-        anyhow::bail!("AMM liquidity calculation not yet implemented")
+    /// Add the commands for `swap`, paid by `owner`, to `ptb`
+    pub async fn add_swap_commands(
+        &self,
+        ptb: &mut ProgrammableTransactionBuilder,
+        owner: SuiAddress,
+        swap: &AmmSwapReq,
+    ) -> Result<()> {
+        let cetus = self.configured_pool(&swap.pool)?;
+        let a2b = cetus.a2b(swap.is_buy);
+        let (decimals_in, decimals_out) = cetus.decimals(a2b);
+        let (decimals_amount, decimals_limit) = if swap.by_amount_in {
+            (decimals_in, decimals_out)
+        } else {
+            (decimals_out, decimals_in)
+        };
+        let amount = to_atomic(swap.amount, decimals_amount).context("swap amount")?;
+        let amount_limit =
+            to_atomic(swap.amount_limit, decimals_limit).context("swap amount limit")?;
+        if amount == 0 {
+            bail!("swap amount rounds to zero");
+        }
+        // Exact-output swaps may spend up to the limit; the pool returns the rest
+        let max_in = if swap.by_amount_in {
+            amount
+        } else {
+            amount_limit
+        };
+
+        let coin_in_type = if a2b { &cetus.coin_a } else { &cetus.coin_b };
+        let type_a = TypeTag::from_str(&cetus.coin_a)
+            .with_context(|| format!("invalid coin type {}", cetus.coin_a))?;
+        let type_b = TypeTag::from_str(&cetus.coin_b)
+            .with_context(|| format!("invalid coin type {}", cetus.coin_b))?;
+        let type_in = if a2b { &type_a } else { &type_b };
+        let type_out = if a2b { &type_b } else { &type_a };
+
+        let coin_in = self
+            .input_coin(ptb, owner, coin_in_type, type_in, max_in)
+            .await?;
+        let coin_out = ptb.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("coin").expect("valid identifier"),
+            Identifier::new("zero").expect("valid identifier"),
+            vec![type_out.clone()],
+            vec![],
+        );
+        let (coin_a, coin_b) = if a2b {
+            (coin_in, coin_out)
+        } else {
+            (coin_out, coin_in)
+        };
+
+        let config = ptb.obj(ObjectArg::SharedObject {
+            id: self.global_config,
+            initial_shared_version: self.shared_version(self.global_config).await?,
+            mutable: false,
+        })?;
+        let pool = ptb.obj(ObjectArg::SharedObject {
+            id: cetus.pool_id,
+            initial_shared_version: self.shared_version(cetus.pool_id).await?,
+            mutable: true,
+        })?;
+        let clock = ptb.obj(ObjectArg::SharedObject {
+            id: SUI_CLOCK_OBJECT_ID,
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+            mutable: false,
+        })?;
+        let by_amount_in = ptb.pure(swap.by_amount_in)?;
+        let amount = ptb.pure(amount)?;
+        let amount_limit = ptb.pure(amount_limit)?;
+        let sqrt_price_limit = ptb.pure(if a2b {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        })?;
+
+        ptb.programmable_move_call(
+            self.package_id,
+            Identifier::new("pool_script_v2").expect("valid identifier"),
+            Identifier::new(if a2b { "swap_a2b" } else { "swap_b2a" }).expect("valid identifier"),
+            vec![type_a, type_b],
+            vec![
+                config,
+                pool,
+                coin_a,
+                coin_b,
+                by_amount_in,
+                amount,
+                amount_limit,
+                sqrt_price_limit,
+                clock,
+            ],
+        );
+        Ok(())
     }
 
-    async fn build_swap_ptb(
+    /// A coin of `coin_type` worth at least `amount` owned by `owner`: split
+    /// from gas for SUI, otherwise `owner`'s coins merged into one
+    async fn input_coin(
         &self,
-        _pool_id: &str,
-        _amount_in: f64,
-        _amount_out_min: f64,
-        _is_buy: bool,
-    ) -> Result<Vec<u8>> {
-        // TODO: Implement swap PTB building
-        anyhow::bail!("AMM swap PTB building not yet implemented")
+        ptb: &mut ProgrammableTransactionBuilder,
+        owner: SuiAddress,
+        coin_type: &str,
+        type_tag: &TypeTag,
+        amount: u64,
+    ) -> Result<Argument> {
+        if *type_tag == GAS::type_tag() {
+            let amount = ptb.pure(amount)?;
+            return Ok(ptb.command(Command::SplitCoins(Argument::GasCoin, vec![amount])));
+        }
+
+        let coins = self
+            .sui
+            .coin_read_api()
+            .select_coins(
+                owner,
+                Some(coin_type.to_string()),
+                u128::from(amount),
+                vec![],
+            )
+            .await
+            .with_context(|| format!("select {coin_type} coins worth {amount}"))?;
+        let mut args = Vec::with_capacity(coins.len());
+        for coin in &coins {
+            args.push(ptb.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref()))?);
+        }
+        let Some((first, rest)) = args.split_first() else {
+            bail!("no {coin_type} coins to swap");
+        };
+        if !rest.is_empty() {
+            ptb.command(Command::MergeCoins(*first, rest.to_vec()));
+        }
+        Ok(*first)
     }
 }
 
-// Future: Specific AMM implementations
-// pub struct CetusAdapter { ... }
-// pub struct TurbosAdapter { ... }
+impl AmmAdapter for CetusAdapter {
+    async fn get_price(&self, pool_id: &str) -> Result<f64> {
+        let cetus = self.configured_pool(pool_id)?;
+        let state = self.pool_state(pool_id).await?;
+        let price_a_in_b = state.price(cetus.decimals_a, cetus.decimals_b);
+        if cetus.a_is_base {
+            Ok(price_a_in_b)
+        } else if price_a_in_b > 0.0 {
+            Ok(1.0 / price_a_in_b)
+        } else {
+            bail!("Cetus pool for {pool_id} has no price")
+        }
+    }
+
+    async fn get_liquidity(&self, pool_id: &str, amount_in: f64, is_buy: bool) -> Result<f64> {
+        let cetus = self.configured_pool(pool_id)?;
+        let state = self.pool_state(pool_id).await?;
+        if state.is_paused {
+            bail!("Cetus pool for {pool_id} is paused");
+        }
+        let a2b = cetus.a2b(is_buy);
+        let (decimals_in, decimals_out) = cetus.decimals(a2b);
+        let atomic_in = amount_in * 10f64.powi(i32::from(decimals_in));
+        Ok(state.amount_out(atomic_in, a2b) / 10f64.powi(i32::from(decimals_out)))
+    }
+
+    async fn get_amount_in(&self, pool_id: &str, amount_out: f64, is_buy: bool) -> Result<f64> {
+        let cetus = self.configured_pool(pool_id)?;
+        let state = self.pool_state(pool_id).await?;
+        if state.is_paused {
+            bail!("Cetus pool for {pool_id} is paused");
+        }
+        let a2b = cetus.a2b(is_buy);
+        let (decimals_in, decimals_out) = cetus.decimals(a2b);
+        let atomic_out = amount_out * 10f64.powi(i32::from(decimals_out));
+        Ok(state.amount_in(atomic_out, a2b) / 10f64.powi(i32::from(decimals_in)))
+    }
+
+    async fn build_swap_ptb(&self, swap: &AmmSwapReq) -> Result<Vec<u8>> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        self.add_swap_commands(&mut ptb, self.sender, swap).await?;
+
+        let programmable = ptb.finish();
+        let input_objects: Vec<_> = programmable
+            .input_objects()
+            .context("collect input objects for swap PTB")?
+            .into_iter()
+            .map(|obj| InputObjectKind::object_id(&obj))
+            .collect();
+        let gas_price = self.reference_gas_price().await?;
+        let gas = self
+            .sui
+            .transaction_builder()
+            .select_gas(self.sender, None, GAS_BUDGET, input_objects, gas_price)
+            .await
+            .context("select gas coin for swap")?;
+
+        let tx_data = TransactionData::new(
+            TransactionKind::programmable(programmable),
+            self.sender,
+            gas,
+            GAS_BUDGET,
+            gas_price,
+        );
+        bcs::to_bytes(&tx_data).context("serialize swap transaction")
+    }
+}
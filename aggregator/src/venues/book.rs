@@ -0,0 +1,430 @@
+// Order book math helpers
+// Pure functions over level2 snapshots shared by quoting and route scoring
+//
+// Numan Thabit 2025 Nov
+
+use crate::quant::Precision;
+use serde::{Deserialize, Serialize};
+use sui_deepbookv3::client::Level2TicksFromMid;
+
+/// Price statistics for taking a target size from one side of the book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BookSweep {
+    /// Size requested
+    pub quantity: f64,
+    /// Size the visible book can fill (may be below `quantity`)
+    pub filled: f64,
+    /// Volume-weighted average price over the filled size
+    pub vwap: f64,
+    /// Price of the last level touched
+    pub worst_price: f64,
+}
+
+impl BookSweep {
+    /// Whether visible depth covers the whole requested size
+    pub fn is_complete(&self) -> bool {
+        self.filled >= self.quantity
+    }
+
+    /// Round prices and sizes for display
+    pub fn rounded(self, precision: &Precision) -> Self {
+        Self {
+            quantity: precision.round_size(self.quantity),
+            filled: precision.round_size(self.filled),
+            vwap: precision.round_price(self.vwap),
+            worst_price: precision.round_price(self.worst_price),
+        }
+    }
+}
+
+/// Price/quantity levels on the side an order would rest on
+pub fn own_side(level2: &Level2TicksFromMid, is_bid: bool) -> (&[f64], &[f64]) {
+    if is_bid {
+        (&level2.bid_prices, &level2.bid_quantities)
+    } else {
+        (&level2.ask_prices, &level2.ask_quantities)
+    }
+}
+
+/// Price/quantity levels an order would take liquidity from
+pub fn opposite_side(level2: &Level2TicksFromMid, is_bid: bool) -> (&[f64], &[f64]) {
+    own_side(level2, !is_bid)
+}
+
+/// Best price on a side, assuming levels are ordered best first
+pub fn best_price(prices: &[f64]) -> Option<f64> {
+    prices.first().copied()
+}
+
+/// Midpoint of the best bid and ask, if both sides have liquidity
+pub fn mid_price(level2: &Level2TicksFromMid) -> Option<f64> {
+    match (
+        best_price(&level2.bid_prices),
+        best_price(&level2.ask_prices),
+    ) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+        _ => None,
+    }
+}
+
+/// Resting quantity on the order's own side that has priority over a new
+/// order at `price` (same or better price)
+pub fn queue_ahead(prices: &[f64], quantities: &[f64], price: f64, is_bid: bool) -> f64 {
+    prices
+        .iter()
+        .zip(quantities)
+        .filter(|(p, _)| if is_bid { **p >= price } else { **p <= price })
+        .map(|(_, q)| q.max(0.0))
+        .sum()
+}
+
+/// Whether a limit order at `price` would trade against the opposite side
+pub fn crosses(level2: &Level2TicksFromMid, price: f64, is_bid: bool) -> bool {
+    let (prices, _) = opposite_side(level2, is_bid);
+    match best_price(prices) {
+        Some(best) if is_bid => price >= best,
+        Some(best) => price <= best,
+        None => false,
+    }
+}
+
+/// What a post-only order does when it would take liquidity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostOnlyFallback {
+    /// Refuse the order
+    #[default]
+    Reject,
+    /// Move the price to one tick inside the opposite best so it rests
+    Reprice,
+    /// Submit as a regular limit order, which may take
+    Limit,
+}
+
+/// Outcome of checking a post-only order against the book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostOnlyDecision {
+    /// Does not cross; rests at its own price
+    Rest,
+    /// Crossing; rest at this best-maker price instead
+    Reprice(f64),
+    /// Crossing; submit without the post-only restriction
+    Downgrade,
+    /// Crossing and the policy refuses it
+    Reject { best: f64 },
+}
+
+/// Apply `fallback` to a post-only order at `price` given the best opposite price
+pub fn apply_post_only(
+    fallback: PostOnlyFallback,
+    price: f64,
+    is_bid: bool,
+    best_opposite: Option<f64>,
+    tick_size: f64,
+) -> PostOnlyDecision {
+    let Some(best) = best_opposite else {
+        return PostOnlyDecision::Rest;
+    };
+    let crossing = if is_bid { price >= best } else { price <= best };
+    if !crossing {
+        return PostOnlyDecision::Rest;
+    }
+    match fallback {
+        PostOnlyFallback::Reject => PostOnlyDecision::Reject { best },
+        PostOnlyFallback::Limit => PostOnlyDecision::Downgrade,
+        PostOnlyFallback::Reprice => {
+            let maker = if is_bid {
+                best - tick_size
+            } else {
+                best + tick_size
+            };
+            if tick_size > 0.0 && maker > 0.0 {
+                PostOnlyDecision::Reprice(maker)
+            } else {
+                PostOnlyDecision::Reject { best }
+            }
+        }
+    }
+}
+
+/// `(price, quantity)` taken from each level, ordered best first, when taking
+/// `quantity`. Stops at `quantity` or when the levels run out, so the taken
+/// quantities sum to the smaller of `quantity` and the visible depth.
+pub fn take_levels(prices: &[f64], quantities: &[f64], quantity: f64) -> Vec<(f64, f64)> {
+    let mut fills = Vec::new();
+    if quantity.is_nan() || quantity <= 0.0 {
+        return fills;
+    }
+    let mut filled = 0.0;
+    for (price, available) in prices.iter().zip(quantities) {
+        if filled >= quantity {
+            break;
+        }
+        let take = (quantity - filled).min(available.max(0.0));
+        if take <= 0.0 {
+            continue;
+        }
+        filled += take;
+        fills.push((*price, take));
+    }
+    fills
+}
+
+/// Take `quantity` from levels ordered best first. Returns `None` when the side
+/// is empty or the quantity is not positive.
+pub fn sweep(prices: &[f64], quantities: &[f64], quantity: f64) -> Option<BookSweep> {
+    summarize_fills(quantity, &take_levels(prices, quantities, quantity))
+}
+
+/// Sweep totals of per-level fills; `None` when nothing was taken
+fn summarize_fills(quantity: f64, fills: &[(f64, f64)]) -> Option<BookSweep> {
+    let &(worst_price, _) = fills.last()?;
+    let filled: f64 = fills.iter().map(|(_, take)| take).sum();
+    let notional: f64 = fills.iter().map(|(price, take)| price * take).sum();
+    Some(BookSweep {
+        quantity,
+        filled,
+        vwap: notional / filled,
+        worst_price,
+    })
+}
+
+/// Sweep the side a taker order on `is_bid` would consume
+pub fn sweep_for_taker(
+    level2: &Level2TicksFromMid,
+    quantity: f64,
+    is_bid: bool,
+) -> Option<BookSweep> {
+    let (prices, quantities) = opposite_side(level2, is_bid);
+    sweep(prices, quantities, quantity)
+}
+
+/// Price move, as a share of the last visible price, per multiple of visible
+/// depth an order takes beyond the book
+pub const DEFAULT_IMPACT_COEFFICIENT: f64 = 0.01;
+/// Share of notional assumed lost when the side being taken is empty
+const EMPTY_BOOK_SLIPPAGE: f64 = 0.01;
+/// Share of notional the unfilled remainder of a marketable order is assumed
+/// to cost: it rests, or is worked again after the price has moved
+pub const DEFAULT_PARTIAL_FILL_PENALTY: f64 = 0.005;
+
+/// Expected cost of filling a limit order against the opposite side
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlippageEstimate {
+    /// Cost versus the limit price in quote units; never negative
+    pub slippage: f64,
+    /// Average price across visible and extrapolated fills
+    pub avg_fill_price: f64,
+    /// Quantity filled from visible levels
+    pub visible_filled: f64,
+    /// `(price, quantity)` taken from each visible level, best first; the
+    /// extrapolated remainder beyond visible depth is not listed
+    pub fills: Vec<(f64, f64)>,
+}
+
+/// Walk the side an order on `is_bid` takes from (asks for bids, bids for
+/// asks). Quantity beyond visible depth fills from one tick past the last
+/// level, with the price moving linearly by `impact_coefficient` of the last
+/// price per multiple of visible depth.
+pub fn estimate_slippage(
+    level2: &Level2TicksFromMid,
+    price: f64,
+    quantity: f64,
+    is_bid: bool,
+    tick_size: f64,
+    impact_coefficient: f64,
+) -> SlippageEstimate {
+    let direction = if is_bid { 1.0 } else { -1.0 };
+    let (prices, quantities) = opposite_side(level2, is_bid);
+    let fills = take_levels(prices, quantities, quantity);
+    let Some(sweep) = summarize_fills(quantity, &fills) else {
+        let quantity = quantity.max(0.0);
+        return SlippageEstimate {
+            slippage: price * quantity * EMPTY_BOOK_SLIPPAGE,
+            avg_fill_price: price * (1.0 + direction * EMPTY_BOOK_SLIPPAGE),
+            visible_filled: 0.0,
+            fills,
+        };
+    };
+    let mut notional = sweep.vwap * sweep.filled;
+    let remaining = quantity - sweep.filled;
+    if remaining > 0.0 {
+        // Visible depth is exhausted, so `filled` is all of it
+        let depth_multiple = remaining / sweep.filled;
+        let start = sweep.worst_price + direction * tick_size;
+        let drift = sweep.worst_price * impact_coefficient * depth_multiple;
+        // Average of a linear walk from `start` over `drift`
+        let extrapolated = (start + direction * drift / 2.0).max(0.0);
+        notional += remaining * extrapolated;
+    }
+    let avg_fill_price = notional / quantity;
+    let slippage = if is_bid {
+        (avg_fill_price - price).max(0.0) * quantity
+    } else {
+        (price - avg_fill_price).max(0.0) * quantity
+    };
+    SlippageEstimate {
+        slippage,
+        avg_fill_price,
+        visible_filled: sweep.filled,
+        fills,
+    }
+}
+
+/// Share of `quantity` the visible opposite side fills at or inside
+/// `limit_price`, between 0 and 1. Orders that do not cross fill nothing now.
+pub fn expected_fill_fraction(
+    level2: &Level2TicksFromMid,
+    limit_price: f64,
+    quantity: f64,
+    is_bid: bool,
+) -> f64 {
+    if quantity.is_nan() || quantity <= 0.0 {
+        return 0.0;
+    }
+    let (prices, quantities) = opposite_side(level2, is_bid);
+    let within_limit: f64 = prices
+        .iter()
+        .zip(quantities)
+        .take_while(|(price, _)| {
+            if is_bid {
+                **price <= limit_price
+            } else {
+                **price >= limit_price
+            }
+        })
+        .map(|(_, available)| available.max(0.0))
+        .sum();
+    (within_limit / quantity).min(1.0)
+}
+
+/// Expected cost of the part of a marketable order visible depth leaves
+/// unfilled: `penalty` of the remainder's notional at `price`. Orders that
+/// fill nothing now are resting orders and carry no penalty.
+pub fn partial_fill_cost(price: f64, quantity: f64, fill_fraction: f64, penalty: f64) -> f64 {
+    if fill_fraction.is_nan() || fill_fraction <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - fill_fraction.min(1.0)) * quantity * price * penalty
+}
+
+/// Adverse move of `avg_fill_price` from `reference` in basis points. Fills
+/// better than the reference count as zero.
+pub fn slippage_bps(reference: f64, avg_fill_price: f64, is_bid: bool) -> f64 {
+    if reference.is_nan() || reference <= 0.0 {
+        return 0.0;
+    }
+    let adverse = if is_bid {
+        avg_fill_price - reference
+    } else {
+        reference - avg_fill_price
+    };
+    adverse.max(0.0) / reference * 10_000.0
+}
+
+/// Cost of taking one size point of a depth curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DepthPoint {
+    /// Share of visible opposite-side depth taken
+    pub fraction: f64,
+    pub quantity: f64,
+    pub vwap: f64,
+    pub worst_price: f64,
+    /// Cost versus mid in quote units
+    pub cost: f64,
+    /// Average cost versus mid in basis points
+    pub slippage_bps: f64,
+}
+
+/// Marginal cost curve for a taker on `is_bid`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepthCurve {
+    pub mid: f64,
+    /// Total visible quantity on the side being taken
+    pub available: f64,
+    pub points: Vec<DepthPoint>,
+}
+
+impl DepthCurve {
+    /// Round prices and sizes for display. Mid (which may sit between ticks)
+    /// and costs keep full precision.
+    pub fn rounded(self, precision: &Precision) -> Self {
+        Self {
+            mid: self.mid,
+            available: precision.round_size(self.available),
+            points: self
+                .points
+                .into_iter()
+                .map(|point| DepthPoint {
+                    quantity: precision.round_size(point.quantity),
+                    vwap: precision.round_price(point.vwap),
+                    worst_price: precision.round_price(point.worst_price),
+                    ..point
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Price taking each `fractions` share of visible depth in a single walk of
+/// the book. Fractions outside (0, 1] are ignored; points are returned in
+/// ascending size order. Returns `None` without a two-sided book.
+pub fn depth_curve(
+    level2: &Level2TicksFromMid,
+    is_bid: bool,
+    fractions: &[f64],
+) -> Option<DepthCurve> {
+    let mid = mid_price(level2)?;
+    let (prices, quantities) = opposite_side(level2, is_bid);
+    let available: f64 = quantities.iter().map(|q| q.max(0.0)).sum();
+    if available <= 0.0 {
+        return None;
+    }
+
+    let mut targets: Vec<f64> = fractions
+        .iter()
+        .copied()
+        .filter(|f| *f > 0.0 && *f <= 1.0)
+        .collect();
+    targets.sort_by(f64::total_cmp);
+    targets.dedup();
+    let mut targets = targets.into_iter().peekable();
+
+    // Absorbs rounding when a target lands exactly on the end of a level
+    let tolerance = available * 1e-12;
+    let mut points = Vec::new();
+    let mut filled = 0.0;
+    let mut notional = 0.0;
+    for (price, quantity) in prices.iter().zip(quantities) {
+        let mut remaining = quantity.max(0.0);
+        while let Some(&fraction) = targets.peek() {
+            let target = fraction * available;
+            let take = (target - filled).max(0.0).min(remaining);
+            filled += take;
+            notional += take * price;
+            remaining -= take;
+            if target - filled > tolerance {
+                break;
+            }
+            let vwap = notional / filled;
+            let cost_per_unit = if is_bid { vwap - mid } else { mid - vwap };
+            points.push(DepthPoint {
+                fraction,
+                quantity: filled,
+                vwap,
+                worst_price: *price,
+                cost: cost_per_unit * filled,
+                slippage_bps: cost_per_unit / mid * 10_000.0,
+            });
+            targets.next();
+        }
+        filled += remaining;
+        notional += remaining * price;
+    }
+
+    Some(DepthCurve {
+        mid,
+        available,
+        points,
+    })
+}
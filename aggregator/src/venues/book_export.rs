@@ -0,0 +1,235 @@
+// Order book export
+// Dumps level2 snapshots with pool params and mid price to timestamped
+// JSON or CSV files for offline analysis and backtesting
+//
+// Numan Thabit 2025 Nov
+
+use crate::quant::PoolParams;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sui_deepbookv3::client::Level2TicksFromMid;
+
+/// File format for exported snapshots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl SnapshotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::Csv => "csv",
+        }
+    }
+}
+
+/// One price level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    pub quantity: f64,
+}
+
+/// Tick, lot and minimum size of the pool at export time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotParams {
+    pub tick_size: f64,
+    pub lot_size: f64,
+    pub min_size: f64,
+}
+
+impl From<&PoolParams> for SnapshotParams {
+    fn from(params: &PoolParams) -> Self {
+        Self {
+            tick_size: params.tick_size,
+            lot_size: params.lot_size,
+            min_size: params.min_size,
+        }
+    }
+}
+
+/// Level2 book of one pool at a point in time. Bids are best (highest) first,
+/// asks best (lowest) first, as the book returns them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookSnapshot {
+    pub pool: String,
+    pub timestamp_ms: u64,
+    /// Ticks from mid requested on each side
+    pub ticks: u64,
+    pub mid_price: Option<f64>,
+    pub params: Option<SnapshotParams>,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+impl BookSnapshot {
+    pub fn from_level2(
+        pool: &str,
+        timestamp_ms: u64,
+        ticks: u64,
+        mid_price: Option<f64>,
+        params: Option<&PoolParams>,
+        level2: &Level2TicksFromMid,
+    ) -> Self {
+        let levels = |prices: &[f64], quantities: &[f64]| {
+            prices
+                .iter()
+                .zip(quantities)
+                .map(|(&price, &quantity)| BookLevel { price, quantity })
+                .collect()
+        };
+        Self {
+            pool: pool.to_string(),
+            timestamp_ms,
+            ticks,
+            mid_price,
+            params: params.map(SnapshotParams::from),
+            bids: levels(&level2.bid_prices, &level2.bid_quantities),
+            asks: levels(&level2.ask_prices, &level2.ask_quantities),
+        }
+    }
+}
+
+const CSV_HEADER: &str =
+    "pool,timestamp_ms,side,level,price,quantity,mid_price,tick_size,lot_size,min_size";
+
+/// One row per level; pool-wide columns repeat on every row and are empty when unknown
+pub fn snapshots_to_csv(snapshots: &[BookSnapshot]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    for snapshot in snapshots {
+        let mid = optional(snapshot.mid_price);
+        let tick = optional(snapshot.params.map(|p| p.tick_size));
+        let lot = optional(snapshot.params.map(|p| p.lot_size));
+        let min = optional(snapshot.params.map(|p| p.min_size));
+        for (side, levels) in [("bid", &snapshot.bids), ("ask", &snapshot.asks)] {
+            for (level, BookLevel { price, quantity }) in levels.iter().enumerate() {
+                let _ = writeln!(
+                    csv,
+                    "{},{},{side},{level},{price},{quantity},{mid},{tick},{lot},{min}",
+                    snapshot.pool, snapshot.timestamp_ms
+                );
+            }
+        }
+    }
+    csv
+}
+
+/// Write `snapshots` to `book-<timestamp_ms>.<ext>` under `dir`, creating the
+/// directory if needed. Returns the file path.
+pub async fn write_snapshots(
+    dir: &Path,
+    snapshots: &[BookSnapshot],
+    format: SnapshotFormat,
+    timestamp_ms: u64,
+) -> Result<PathBuf> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("create snapshot directory {}", dir.display()))?;
+    let path = dir.join(format!("book-{timestamp_ms}.{}", format.extension()));
+    let contents = match format {
+        SnapshotFormat::Json => serde_json::to_vec_pretty(snapshots)?,
+        SnapshotFormat::Csv => snapshots_to_csv(snapshots).into_bytes(),
+    };
+    tokio::fs::write(&path, contents)
+        .await
+        .with_context(|| format!("write book snapshot {}", path.display()))?;
+    Ok(path)
+}
+
+/// Snapshot files (`book-<timestamp_ms>.json|csv`) already under `dir`; a
+/// directory that does not exist yet holds none
+pub async fn count_snapshots(dir: &Path) -> Result<usize> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => {
+            return Err(err).with_context(|| format!("list snapshot directory {}", dir.display()))
+        }
+    };
+    let mut count = 0;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .with_context(|| format!("list snapshot directory {}", dir.display()))?
+    {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_snapshot = name.starts_with("book-")
+            && [SnapshotFormat::Json, SnapshotFormat::Csv]
+                .iter()
+                .any(|format| name.ends_with(&format!(".{}", format.extension())));
+        if is_snapshot {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Where and how the export endpoint writes snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookExportSettings {
+    pub dir: PathBuf,
+    /// Ticks from mid per side when a request does not say
+    pub ticks: u64,
+    pub format: SnapshotFormat,
+    /// Bearer token callers must present
+    pub token: String,
+    /// Shortest gap between two exports
+    pub min_interval: Duration,
+    /// Exports are refused once `dir` holds this many snapshot files
+    pub max_files: usize,
+}
+
+/// Spaces exports at least `min_interval` apart, so callers cannot turn the
+/// endpoint into a disk writer
+#[derive(Debug)]
+pub struct ExportThrottle {
+    min_interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl ExportThrottle {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Claim the export slot at `now`, or return how long until it reopens
+    pub fn try_acquire(&self, now: Instant) -> Result<(), Duration> {
+        let mut last = self
+            .last
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(previous) = *last {
+            let elapsed = now.saturating_duration_since(previous);
+            if elapsed < self.min_interval {
+                return Err(self.min_interval - elapsed);
+            }
+        }
+        *last = Some(now);
+        Ok(())
+    }
+}
+
+/// Default ticks from mid exported per side
+pub const DEFAULT_EXPORT_TICKS: u64 = 50;
+/// Most ticks per side a single export may request
+pub const MAX_EXPORT_TICKS: u64 = 1_000;
+/// Most pools a single export may include
+pub const MAX_EXPORT_POOLS: usize = 32;
+/// Default shortest gap between two exports
+pub const DEFAULT_EXPORT_MIN_INTERVAL: Duration = Duration::from_secs(10);
+/// Default most snapshot files kept in the export directory
+pub const DEFAULT_EXPORT_MAX_FILES: usize = 1_000;
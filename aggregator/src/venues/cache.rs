@@ -0,0 +1,139 @@
+// Keyed TTL cache for venue metadata
+// Bounded per-key cache used by the DeepBook adapter for pool params, mid
+// prices and other values fetched from the indexer or fullnode
+//
+// Numan Thabit 2025 Nov
+
+use crate::metrics::{DEEPBOOK_CACHE_HITS, DEEPBOOK_CACHE_MISSES};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Most keys one cache holds before evicting
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Time source for cache expiry, replaceable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// `Instant::now`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonotonicClock;
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+struct CacheEntry<T> {
+    value: T,
+    expires_at: Instant,
+}
+
+/// Values by key, each served for `ttl` after it was loaded. A zero TTL
+/// disables caching. When full, expired entries are dropped first, then the
+/// entry closest to expiry.
+#[derive(Clone)]
+pub struct TimedCache<T> {
+    ttl: Duration,
+    label: &'static str,
+    capacity: usize,
+    clock: Arc<dyn Clock>,
+    store: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
+}
+
+impl<T: Clone> TimedCache<T> {
+    /// `label` names the cache in the hit/miss metrics
+    pub fn new(ttl: Duration, label: &'static str) -> Self {
+        Self {
+            ttl,
+            label,
+            capacity: DEFAULT_CACHE_CAPACITY,
+            clock: Arc::new(MonotonicClock),
+            store: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Hold at most `capacity` keys (minimum 1)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached value for `key`, calling `loader` when it is absent or expired.
+    /// Failed loads are not cached.
+    pub async fn get_or_try_insert_with<E, Fut, F>(&self, key: &str, loader: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if self.ttl.is_zero() {
+            return loader().await;
+        }
+
+        {
+            let guard = self.store.read().await;
+            if let Some(entry) = guard.get(key) {
+                if entry.expires_at > self.clock.now() {
+                    DEEPBOOK_CACHE_HITS.with_label_values(&[self.label]).inc();
+                    return Ok(entry.value.clone());
+                }
+            }
+        }
+
+        DEEPBOOK_CACHE_MISSES.with_label_values(&[self.label]).inc();
+        let value = loader().await?;
+        let now = self.clock.now();
+        let mut guard = self.store.write().await;
+        if !guard.contains_key(key) && guard.len() >= self.capacity {
+            guard.retain(|_, entry| entry.expires_at > now);
+            if guard.len() >= self.capacity {
+                let soonest = guard
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(soonest) = soonest {
+                    guard.remove(&soonest);
+                }
+            }
+        }
+        guard.insert(
+            key.to_owned(),
+            CacheEntry {
+                value: value.clone(),
+                expires_at: now + self.ttl,
+            },
+        );
+        Ok(value)
+    }
+
+    pub async fn invalidate(&self, key: &str) {
+        self.store.write().await.remove(key);
+    }
+
+    pub async fn invalidate_all(&self) {
+        self.store.write().await.clear();
+    }
+
+    /// Keys currently held, including expired ones not yet replaced
+    pub async fn len(&self) -> usize {
+        self.store.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
@@ -0,0 +1,91 @@
+// Reference gas price cache
+// Holds the network reference gas price for a TTL and refreshes it lazily,
+// letting one caller fetch while concurrent callers wait for its result
+//
+// Numan Thabit 2025 Nov
+
+use crate::metrics::{DEEPBOOK_CACHE_HITS, DEEPBOOK_CACHE_MISSES};
+use anyhow::Result;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// The price only changes at epoch boundaries, where the epoch watcher
+/// reseeds the cache, so a minute between refreshes is safe
+pub const DEFAULT_GAS_PRICE_TTL: Duration = Duration::from_secs(60);
+
+const CACHE_LABEL: &str = "gas_price";
+
+#[derive(Clone, Copy)]
+struct CachedPrice {
+    price: u64,
+    expires_at: Instant,
+}
+
+/// Reference gas price with single-flight refresh
+#[derive(Clone)]
+pub struct GasPriceCache {
+    ttl: Duration,
+    // Held across the fetch so only one refresh reaches the node at a time
+    slot: Arc<Mutex<Option<CachedPrice>>>,
+}
+
+impl Default for GasPriceCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_GAS_PRICE_TTL)
+    }
+}
+
+impl GasPriceCache {
+    /// Cache serving a price for `ttl`; zero fetches on every call
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slot: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached price, calling `fetch` when it is absent or expired. Callers
+    /// arriving during a fetch wait for it and share its result; a failed
+    /// fetch is not cached, so the next caller retries.
+    pub async fn get_or_fetch<F, Fut>(&self, fetch: F) -> Result<u64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64>>,
+    {
+        let mut slot = self.slot.lock().await;
+        if let Some(cached) = *slot {
+            if cached.expires_at > Instant::now() {
+                DEEPBOOK_CACHE_HITS.with_label_values(&[CACHE_LABEL]).inc();
+                return Ok(cached.price);
+            }
+        }
+
+        DEEPBOOK_CACHE_MISSES
+            .with_label_values(&[CACHE_LABEL])
+            .inc();
+        let price = fetch().await?;
+        *slot = Some(CachedPrice {
+            price,
+            expires_at: Instant::now() + self.ttl,
+        });
+        Ok(price)
+    }
+
+    /// Store a price obtained elsewhere (e.g. gRPC epoch info) for a full TTL
+    pub async fn seed(&self, price: u64) {
+        *self.slot.lock().await = Some(CachedPrice {
+            price,
+            expires_at: Instant::now() + self.ttl,
+        });
+    }
+
+    pub async fn invalidate(&self) {
+        *self.slot.lock().await = None;
+    }
+}
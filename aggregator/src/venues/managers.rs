@@ -0,0 +1,124 @@
+// BalanceManager registry
+// Keys of the BalanceManagers registered with the DeepBook SDK, the
+// per-order selection between them and the sender's authority to trade
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use std::collections::BTreeSet;
+use sui_sdk::types::base_types::SuiAddress;
+
+/// BalanceManager keys an order may trade from, plus the default for orders
+/// that do not name one
+#[derive(Debug, Clone)]
+pub struct BalanceManagers {
+    default_key: String,
+    keys: BTreeSet<String>,
+}
+
+impl BalanceManagers {
+    pub fn new(default_key: &str) -> Self {
+        Self {
+            default_key: default_key.to_string(),
+            keys: BTreeSet::from([default_key.to_string()]),
+        }
+    }
+
+    /// Register another manager key
+    pub fn with_manager(mut self, key: &str) -> Self {
+        self.keys.insert(key.to_string());
+        self
+    }
+
+    pub fn default_key(&self) -> &str {
+        &self.default_key
+    }
+
+    /// Registered keys in sorted order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    /// Key an order trades from: `requested` if registered, the default when
+    /// unset, `UnknownManager` otherwise
+    pub fn resolve(&self, requested: Option<&str>) -> Result<String, AggrError> {
+        match requested.map(str::trim).filter(|key| !key.is_empty()) {
+            None => Ok(self.default_key.clone()),
+            Some(key) if self.keys.contains(key) => Ok(key.to_string()),
+            Some(key) => Err(AggrError::UnknownManager(key.to_string())),
+        }
+    }
+}
+
+/// Who may trade from a BalanceManager, as read from chain
+#[derive(Debug, Clone)]
+pub struct TradeAuthority {
+    pub manager_id: String,
+    pub owner: SuiAddress,
+    /// The TradeCap configured for the manager, if any
+    pub trade_cap: Option<TradeCapHolding>,
+    /// Cap ids the manager still honours; `None` when the list could not be read
+    pub allow_listed: Option<Vec<String>>,
+}
+
+/// A TradeCap object and where it currently sits
+#[derive(Debug, Clone)]
+pub struct TradeCapHolding {
+    pub cap_id: String,
+    /// Address owning the cap; `None` for shared or wrapped objects
+    pub holder: Option<SuiAddress>,
+    pub balance_manager_id: String,
+}
+
+impl TradeAuthority {
+    /// Ok when `sender` owns the manager registered as `key` or holds a live
+    /// TradeCap for it; otherwise `MissingTradeCap` explaining what is wrong
+    pub fn verify(&self, key: &str, sender: SuiAddress) -> Result<(), AggrError> {
+        if self.owner == sender {
+            return Ok(());
+        }
+        let missing = |reason: String| AggrError::MissingTradeCap {
+            manager: key.to_string(),
+            sender: sender.to_string(),
+            reason,
+        };
+        let Some(cap) = &self.trade_cap else {
+            return Err(missing(format!(
+                "it is owned by {} and no trade_cap is configured",
+                self.owner
+            )));
+        };
+        if cap.holder != Some(sender) {
+            let holder = cap
+                .holder
+                .map_or_else(|| "no address".to_string(), |holder| holder.to_string());
+            return Err(missing(format!(
+                "TradeCap {} is held by {holder}",
+                cap.cap_id
+            )));
+        }
+        if !same_id(&cap.balance_manager_id, &self.manager_id) {
+            return Err(missing(format!(
+                "TradeCap {} was minted for balance manager {}",
+                cap.cap_id, cap.balance_manager_id
+            )));
+        }
+        if let Some(allowed) = &self.allow_listed {
+            if !allowed.iter().any(|id| same_id(id, &cap.cap_id)) {
+                return Err(missing(format!("TradeCap {} has been revoked", cap.cap_id)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Object ids compare by value regardless of case or leading zeros
+fn same_id(a: &str, b: &str) -> bool {
+    let normalize = |id: &str| {
+        id.trim()
+            .trim_start_matches("0x")
+            .trim_start_matches('0')
+            .to_ascii_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
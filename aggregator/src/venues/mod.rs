@@ -1,3 +1,11 @@
 pub mod adapter;
 pub mod amm;
+pub mod book;
+pub mod book_export;
+pub mod cache;
 pub mod deepbook;
+pub mod gas_price;
+pub mod managers;
+pub mod order_events;
+pub mod pools;
+pub mod snapshots;
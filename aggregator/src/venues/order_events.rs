@@ -0,0 +1,97 @@
+// DeepBook order events
+// Reads order ids out of the OrderPlaced / OrderModified events a transaction
+// emitted, so orders can be cancelled or replaced by the digest that placed them
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use serde_json::Value;
+
+/// Whether `module::name` is an event that carries a resting order's id
+pub fn is_order_event(module: &str, name: &str) -> bool {
+    matches!(
+        (module, name),
+        ("order_info", "OrderPlaced")
+            | ("order", "OrderModified")
+            | ("clob_v2", "OrderPlaced")
+            | ("clob", "OrderPlaced" | "OrderPlacedV2")
+    )
+}
+
+/// Identifying fields of an order placed or modified by a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderEvent {
+    pub order_id: u128,
+    pub client_order_id: Option<u64>,
+    /// Pool object id (or key, for older events) the order rests in
+    pub pool: Option<String>,
+    /// Address that placed the order
+    pub trader: Option<String>,
+}
+
+impl OrderEvent {
+    /// Parse the parsed-JSON payload of a `module::name` event. `None` for
+    /// other event types and payloads without an order id.
+    pub fn parse(module: &str, name: &str, json: &Value) -> Option<Self> {
+        if !is_order_event(module, name) {
+            return None;
+        }
+        Some(Self {
+            order_id: u128_field(json, &["order_id", "orderId"])?,
+            client_order_id: u128_field(json, &["client_order_id", "clientOrderId"])
+                .and_then(|id| u64::try_from(id).ok()),
+            pool: string_field(json, &["pool_id", "poolKey", "pool"]),
+            trader: string_field(json, &["trader", "owner", "accountOwner"]),
+        })
+    }
+}
+
+/// Id of the order `events` placed in the pool `in_pool` accepts. With several
+/// orders in that pool, `client_order_id` must pick one out; it is an error
+/// otherwise. `None` only when no order event matches.
+pub fn select_order_id(
+    events: &[OrderEvent],
+    in_pool: impl Fn(&str) -> bool,
+    client_order_id: Option<u64>,
+) -> Result<Option<u128>, AggrError> {
+    let mut ids: Vec<u128> = events
+        .iter()
+        .filter(|event| match &event.pool {
+            Some(pool) => in_pool(pool),
+            None => true,
+        })
+        .filter(|event| client_order_id.is_none() || event.client_order_id == client_order_id)
+        .map(|event| event.order_id)
+        .collect();
+    // A modify right after a place repeats the same id
+    ids.sort_unstable();
+    ids.dedup();
+    match ids.as_slice() {
+        [] => Ok(None),
+        [order_id] => Ok(Some(*order_id)),
+        _ => Err(AggrError::AmbiguousOrder(ids.len())),
+    }
+}
+
+/// u64 and u128 fields arrive as decimal strings; small values may be numbers
+fn u128_field(json: &Value, keys: &[&str]) -> Option<u128> {
+    keys.iter().find_map(|key| match json.get(*key)? {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64().map(u128::from),
+        Value::Object(obj) => match obj.get("value")? {
+            Value::String(s) => s.parse().ok(),
+            Value::Number(n) => n.as_u64().map(u128::from),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// IDs arrive as hex strings, or wrapped as `{ "id": ... }` by older nodes
+fn string_field(json: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match json.get(*key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(obj) => obj.get("id")?.as_str().map(str::to_owned),
+        _ => None,
+    })
+}
@@ -0,0 +1,139 @@
+// Pool identifier normalization
+// Maps the pool spellings clients send (case variants, separators, aliases,
+// object ids) onto the canonical keys the DeepBook SDK config uses
+//
+// Numan Thabit 2025 Nov
+
+use crate::errors::AggrError;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Whether a pool currently accepts orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolStatus {
+    Active,
+    Paused,
+    NotLaunched,
+}
+
+impl PoolStatus {
+    pub fn is_tradable(self) -> bool {
+        self == PoolStatus::Active
+    }
+
+    /// Read a status from indexer fields: a `status` string, or `paused` and
+    /// `launched` flags. `None` when the payload carries neither.
+    pub fn from_fields(fields: &Value) -> Option<Self> {
+        if let Some(status) = fields.get("status").and_then(Value::as_str) {
+            return match status
+                .trim()
+                .to_ascii_lowercase()
+                .replace(['-', ' '], "_")
+                .as_str()
+            {
+                "active" | "live" | "trading" => Some(PoolStatus::Active),
+                "paused" | "halted" | "suspended" => Some(PoolStatus::Paused),
+                "not_launched" | "pending" | "scheduled" => Some(PoolStatus::NotLaunched),
+                _ => None,
+            };
+        }
+        let paused = fields.get("paused").and_then(Value::as_bool);
+        let launched = fields.get("launched").and_then(Value::as_bool);
+        match (paused, launched) {
+            (_, Some(false)) => Some(PoolStatus::NotLaunched),
+            (Some(true), _) => Some(PoolStatus::Paused),
+            (Some(false), _) | (None, Some(true)) => Some(PoolStatus::Active),
+            (None, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for PoolStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PoolStatus::Active => "active",
+            PoolStatus::Paused => "paused",
+            PoolStatus::NotLaunched => "not launched",
+        })
+    }
+}
+
+/// Resolves client-supplied pool identifiers to canonical pool keys
+#[derive(Debug, Clone, Default)]
+pub struct PoolNormalizer {
+    /// Folded key, alias or object id -> canonical key
+    lookup: HashMap<String, String>,
+}
+
+impl PoolNormalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from `(key, object id)` pairs taken from the SDK pool config
+    pub fn from_pools<'a, I>(pools: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        pools
+            .into_iter()
+            .fold(Self::new(), |normalizer, (key, address)| {
+                normalizer.with_pool(key, address)
+            })
+    }
+
+    /// Register a canonical pool key and its object id
+    pub fn with_pool(mut self, key: &str, address: &str) -> Self {
+        self.lookup.insert(fold_key(key), key.to_string());
+        if let Some(id) = fold_object_id(address) {
+            self.lookup.insert(id, key.to_string());
+        }
+        self
+    }
+
+    /// Register an extra spelling for an already known pool
+    pub fn with_alias(mut self, alias: &str, key: &str) -> Result<Self, AggrError> {
+        let canonical = self.resolve(key)?;
+        self.lookup.insert(fold_key(alias), canonical);
+        Ok(self)
+    }
+
+    /// Canonical key for `input`, or `UnknownPool` if it matches nothing
+    pub fn resolve(&self, input: &str) -> Result<String, AggrError> {
+        let folded = fold_object_id(input).unwrap_or_else(|| fold_key(input));
+        self.lookup
+            .get(&folded)
+            .cloned()
+            .ok_or_else(|| AggrError::UnknownPool(input.trim().to_string()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lookup.is_empty()
+    }
+}
+
+/// Case- and separator-insensitive form of a pool key ("sui-usdc" == "SUI_USDC")
+fn fold_key(key: &str) -> String {
+    key.trim()
+        .chars()
+        .map(|c| match c {
+            '-' | '/' | ' ' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .collect()
+}
+
+/// Full-width lowercase object id, or `None` if `input` is not a hex address
+fn fold_object_id(input: &str) -> Option<String> {
+    let hex = input
+        .trim()
+        .strip_prefix("0x")
+        .or_else(|| input.trim().strip_prefix("0X"))?;
+    if hex.is_empty() || hex.len() > 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("0x{:0>64}", hex.to_ascii_lowercase()))
+}
@@ -0,0 +1,139 @@
+// Order book snapshot cache
+// Keeps recently fetched level2 snapshots per pool for a short TTL and drops
+// them early when the checkpoint stream shows the pool object changed
+//
+// Numan Thabit 2025 Nov
+
+use crate::metrics::{DEEPBOOK_CACHE_HITS, DEEPBOOK_CACHE_MISSES};
+use crate::transport::grpc::sui::rpc::v2::Checkpoint;
+use crate::venues::pools::PoolNormalizer;
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+pub const DEFAULT_BOOK_CACHE_TTL: Duration = Duration::from_millis(250);
+
+const CACHE_LABEL: &str = "level2";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookCacheSettings {
+    /// How long a snapshot is served; zero disables caching
+    pub ttl: Duration,
+    /// Drop a pool's snapshots when a checkpoint mutates the pool object
+    pub invalidate_on_checkpoint: bool,
+}
+
+impl Default for BookCacheSettings {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_BOOK_CACHE_TTL,
+            invalidate_on_checkpoint: true,
+        }
+    }
+}
+
+struct Snapshot<T> {
+    value: Arc<T>,
+    expires_at: Instant,
+}
+
+/// Level2 snapshots keyed by pool and tick depth
+#[derive(Clone)]
+pub struct BookSnapshotCache<T> {
+    settings: BookCacheSettings,
+    store: Arc<RwLock<HashMap<(String, u64), Snapshot<T>>>>,
+}
+
+impl<T> BookSnapshotCache<T> {
+    pub fn new(settings: BookCacheSettings) -> Self {
+        Self {
+            settings,
+            store: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn settings(&self) -> &BookCacheSettings {
+        &self.settings
+    }
+
+    /// Cached snapshot for `pool` at `ticks` depth, fetching when absent or expired
+    pub async fn get_or_fetch<F, Fut>(&self, pool: &str, ticks: u64, fetch: F) -> Result<Arc<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.settings.ttl.is_zero() {
+            return fetch().await.map(Arc::new);
+        }
+
+        let key = (pool.to_string(), ticks);
+        {
+            let store = self.store.read().await;
+            if let Some(snapshot) = store.get(&key) {
+                if snapshot.expires_at > Instant::now() {
+                    DEEPBOOK_CACHE_HITS.with_label_values(&[CACHE_LABEL]).inc();
+                    return Ok(snapshot.value.clone());
+                }
+            }
+        }
+
+        DEEPBOOK_CACHE_MISSES
+            .with_label_values(&[CACHE_LABEL])
+            .inc();
+        let value = Arc::new(fetch().await?);
+        self.store.write().await.insert(
+            key,
+            Snapshot {
+                value: value.clone(),
+                expires_at: Instant::now() + self.settings.ttl,
+            },
+        );
+        Ok(value)
+    }
+
+    /// Drop every snapshot of `pool`; returns how many were removed
+    pub async fn invalidate_pool(&self, pool: &str) -> usize {
+        let mut store = self.store.write().await;
+        let before = store.len();
+        store.retain(|(cached_pool, _), _| cached_pool != pool);
+        before - store.len()
+    }
+
+    pub async fn invalidate_all(&self) {
+        self.store.write().await.clear();
+    }
+
+    /// Invalidate pools whose objects `checkpoint` changed, when enabled.
+    /// Returns the pools that were invalidated.
+    pub async fn apply_checkpoint(
+        &self,
+        checkpoint: &Checkpoint,
+        pools: &PoolNormalizer,
+    ) -> Vec<String> {
+        if !self.settings.invalidate_on_checkpoint {
+            return Vec::new();
+        }
+        let touched = pools_touched(checkpoint, pools);
+        for pool in &touched {
+            self.invalidate_pool(pool).await;
+        }
+        touched
+    }
+}
+
+/// Canonical keys of known pools whose objects were changed in `checkpoint`
+pub fn pools_touched(checkpoint: &Checkpoint, pools: &PoolNormalizer) -> Vec<String> {
+    checkpoint
+        .transactions
+        .iter()
+        .filter_map(|tx| tx.effects.as_ref())
+        .flat_map(|effects| effects.changed_objects.iter())
+        .filter_map(|changed| changed.object_id.as_deref())
+        .filter_map(|object_id| pools.resolve(object_id).ok())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
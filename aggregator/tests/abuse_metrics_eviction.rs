@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use ultra_aggr::signing::LocalEd25519Signer;
+use ultra_aggr::sponsorship::{AbuseConfig, SponsorshipManager, SponsorshipRequest};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+fn manager(window: Duration, max_tracked_users: usize) -> SponsorshipManager {
+    SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig {
+            max_tx_per_window: 2,
+            max_gas_per_window: 1_000_000,
+            window_duration: window,
+            max_tracked_users,
+        },
+    )
+    .unwrap()
+}
+
+fn request(user: SuiAddress) -> SponsorshipRequest {
+    SponsorshipRequest {
+        user_address: user,
+        route_plan_id: "DeepBookSingle".to_string(),
+        estimated_gas: 10,
+        created_at: Instant::now(),
+    }
+}
+
+#[tokio::test]
+async fn expired_idle_entries_are_evicted_while_active_ones_persist() {
+    let sponsor = manager(Duration::from_millis(100), 1_000);
+    sponsor
+        .apply_spending(SuiAddress::random_for_testing_only(), None, 10)
+        .await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    let active = SuiAddress::random_for_testing_only();
+    sponsor.apply_spending(active, None, 10).await;
+    assert_eq!(sponsor.tracked_users().await, 2);
+
+    assert_eq!(sponsor.evict_idle_abuse_metrics().await, 1);
+    assert_eq!(sponsor.tracked_users().await, 1);
+    assert_eq!(sponsor.evict_idle_abuse_metrics().await, 0);
+}
+
+#[tokio::test]
+async fn map_size_is_capped_and_spares_users_over_their_limits() {
+    let sponsor = manager(Duration::from_secs(3600), 2);
+    sponsor.update_gas_coins(vec![ObjectID::random()]).await;
+    let abuser = SuiAddress::random_for_testing_only();
+    for _ in 0..3 {
+        sponsor.apply_spending(abuser, None, 10).await;
+    }
+
+    // Spam of fresh addresses only ever displaces well-behaved entries
+    for _ in 0..10 {
+        sponsor
+            .apply_spending(SuiAddress::random_for_testing_only(), None, 10)
+            .await;
+        assert_eq!(sponsor.tracked_users().await, 2);
+    }
+    assert!(!sponsor.can_sponsor(&request(abuser)).await.unwrap());
+    assert!(sponsor
+        .can_sponsor(&request(SuiAddress::random_for_testing_only()))
+        .await
+        .unwrap());
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use ultra_aggr::control::{AdaptiveRate, AdmissionControl, BreakerClass, CircuitBreakers};
+
+fn params() -> AdaptiveRate {
+    AdaptiveRate {
+        error_threshold: 0.2,
+        decrease_factor: 0.5,
+        increase_step: 20,
+        min_rate_per_sec: 10,
+        interval: Duration::from_millis(100),
+    }
+}
+
+#[tokio::test]
+async fn rising_error_rate_tightens_and_recovery_loosens() {
+    let admission = AdmissionControl::new(64, Some(100)).with_adaptive_rate(params());
+    let breakers = CircuitBreakers::new();
+    let class = BreakerClass::new("deepbook", Some("SUI_USDC"));
+
+    // Too few outcomes to judge: stays at the configured rate
+    breakers.record_failure_for(&class).await;
+    assert_eq!(breakers.recent_error_rate().await, None);
+    assert_eq!(
+        admission.adapt(breakers.recent_error_rate().await).await,
+        100
+    );
+
+    // Half of recent executions fail
+    for _ in 0..19 {
+        breakers.record_success_for(&class).await;
+        breakers.record_failure_for(&class).await;
+    }
+    let error_rate = breakers.recent_error_rate().await.unwrap();
+    assert!(error_rate > 0.2, "{error_rate}");
+    assert_eq!(admission.adapt(Some(error_rate)).await, 50);
+    assert_eq!(admission.adapt(Some(error_rate)).await, 25);
+    assert_eq!(admission.adapt(Some(error_rate)).await, 12);
+    assert_eq!(
+        admission.adapt(Some(error_rate)).await,
+        10,
+        "never below the floor"
+    );
+    assert_eq!(admission.effective_rate().await, 10);
+
+    // The node recovers: successes wash out the failures
+    for _ in 0..200 {
+        breakers.record_success_for(&class).await;
+    }
+    let error_rate = breakers.recent_error_rate().await.unwrap();
+    assert_eq!(error_rate, 0.0);
+    let mut restored = Vec::new();
+    for _ in 0..6 {
+        restored.push(admission.adapt(Some(error_rate)).await);
+    }
+    assert_eq!(restored, vec![30, 50, 70, 90, 100, 100], "additive, capped");
+}
+
+#[tokio::test]
+async fn tightened_rate_throttles_admissions() {
+    let admission = AdmissionControl::new(64, Some(50)).with_adaptive_rate(AdaptiveRate {
+        decrease_factor: 0.1,
+        min_rate_per_sec: 5,
+        ..params()
+    });
+    assert_eq!(admission.adapt(Some(1.0)).await, 5);
+
+    for _ in 0..5 {
+        drop(admission.acquire().await);
+    }
+    let blocked = tokio::time::timeout(Duration::from_millis(100), admission.acquire()).await;
+    assert!(blocked.is_err(), "sixth admission within the second waits");
+}
+
+#[tokio::test]
+async fn static_limit_ignores_error_rate() {
+    let admission = AdmissionControl::new(8, Some(100));
+    assert_eq!(admission.adapt(Some(1.0)).await, 100);
+    assert_eq!(admission.effective_rate().await, 100);
+}
@@ -0,0 +1,60 @@
+use std::str::FromStr;
+use sui_sdk::types::base_types::SuiAddress;
+use ultra_aggr::config::AppConfig;
+use ultra_aggr::signing::ed25519_address;
+
+// RFC 8032 test vector 1 secret key
+const SECRET_HEX: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const DERIVED: &str = "0x304af458e90e97c841685b8cbbc59b909f3e2cf150df590ada4c81452c29737d";
+
+fn config(address: Option<&str>, secret: Option<&str>) -> AppConfig {
+    serde_json::from_value(serde_json::json!({
+        "grpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "jsonrpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "max_inflight": 8,
+        "address": address,
+        "ed25519_secret_hex": secret,
+    }))
+    .expect("config")
+}
+
+#[test]
+fn address_is_blake2b_of_flagged_public_key() {
+    assert_eq!(
+        ed25519_address(SECRET_HEX).unwrap(),
+        SuiAddress::from_str(DERIVED).unwrap()
+    );
+}
+
+#[test]
+fn omitted_address_is_derived_from_key() {
+    let address = config(None, Some(SECRET_HEX)).sui_address().unwrap();
+    assert_eq!(address, SuiAddress::from_str(DERIVED).unwrap());
+}
+
+#[test]
+fn matching_address_is_accepted() {
+    let address = config(Some(DERIVED), Some(SECRET_HEX))
+        .sui_address()
+        .unwrap();
+    assert_eq!(address, SuiAddress::from_str(DERIVED).unwrap());
+}
+
+#[test]
+fn mismatched_address_is_rejected() {
+    let other = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+    let err = config(Some(other), Some(SECRET_HEX))
+        .sui_address()
+        .unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err}");
+}
+
+#[test]
+fn observer_without_key_still_needs_an_address() {
+    assert!(config(None, None).sui_address().is_err());
+    let observer = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+    assert_eq!(
+        config(Some(observer), None).sui_address().unwrap(),
+        SuiAddress::from_str(observer).unwrap()
+    );
+}
@@ -0,0 +1,19 @@
+use ultra_aggr::control::AdmissionControl;
+use ultra_aggr::metrics::{INFLIGHT_PERMITS, MAX_INFLIGHT_PERMITS};
+
+#[tokio::test]
+async fn inflight_gauge_tracks_held_permits() {
+    let admission = AdmissionControl::new(4, None);
+    assert_eq!(MAX_INFLIGHT_PERMITS.get(), 4);
+    assert_eq!(INFLIGHT_PERMITS.get(), 0);
+
+    let first = admission.acquire().await;
+    let second = admission.acquire().await;
+    assert_eq!(INFLIGHT_PERMITS.get(), 2);
+    assert_eq!(admission.inflight(), 2);
+
+    drop(first);
+    assert_eq!(INFLIGHT_PERMITS.get(), 1);
+    drop(second);
+    assert_eq!(INFLIGHT_PERMITS.get(), 0);
+}
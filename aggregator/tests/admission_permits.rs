@@ -0,0 +1,140 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use ultra_aggr::control::AdmissionControl;
+use ultra_aggr::router::execution::{ExecutionAccounting, ExecutionResult};
+use ultra_aggr::router::router::submit_replace;
+use ultra_aggr::router::routes::ReplaceMode;
+use ultra_aggr::transport::grpc::sui::rpc::v2::ExecutedTransaction;
+
+const MAX_INFLIGHT: usize = 4;
+const CALLS: usize = 32;
+
+/// Stand-in for the executor: tracks how many submissions overlap
+#[derive(Default)]
+struct MockExecutor {
+    inflight: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl MockExecutor {
+    async fn execute(&self, call: usize) -> anyhow::Result<usize> {
+        let now = self.inflight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        self.inflight.fetch_sub(1, Ordering::SeqCst);
+        // Every third submission fails
+        if call % 3 == 0 {
+            anyhow::bail!("submission {call} failed");
+        }
+        Ok(call)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_executions_never_exceed_max_inflight() {
+    let admission = Arc::new(AdmissionControl::new(MAX_INFLIGHT, None));
+    let executor = Arc::new(MockExecutor::default());
+
+    let handles: Vec<_> = (0..CALLS)
+        .map(|call| {
+            let admission = admission.clone();
+            let executor = executor.clone();
+            tokio::spawn(async move { admission.admit(executor.execute(call)).await })
+        })
+        .collect();
+
+    let mut failures = 0;
+    for handle in handles {
+        if handle.await.unwrap().is_err() {
+            failures += 1;
+        }
+    }
+
+    assert_eq!(failures, CALLS.div_ceil(3));
+    assert!(executor.peak.load(Ordering::SeqCst) <= MAX_INFLIGHT);
+    assert_eq!(executor.peak.load(Ordering::SeqCst), MAX_INFLIGHT);
+    // Failed submissions released their permits too
+    assert_eq!(admission.inflight(), 0);
+}
+
+#[tokio::test]
+async fn cancelled_work_releases_its_permit() {
+    let admission = AdmissionControl::new(1, None);
+
+    let stalled = tokio::time::timeout(
+        Duration::from_millis(10),
+        admission.admit(std::future::pending::<()>()),
+    )
+    .await;
+    assert!(stalled.is_err());
+    assert_eq!(admission.inflight(), 0);
+
+    // The single permit is available again
+    assert_eq!(admission.admit(async { 1 }).await, 1);
+}
+
+fn executed(call: usize) -> ExecutionResult {
+    ExecutionResult {
+        digest: format!("digest-{call}"),
+        executed: ExecutedTransaction::default(),
+        effects_time_ms: 5.0,
+        checkpoint_time_ms: None,
+        accounting: ExecutionAccounting::default(),
+        orders: Vec::new(),
+        dry_run: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn replaces_share_the_inflight_limit_with_orders() {
+    let admission = Arc::new(AdmissionControl::new(MAX_INFLIGHT, None));
+    let executor = Arc::new(MockExecutor::default());
+    let transactions = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..CALLS)
+        .map(|call| {
+            let admission = admission.clone();
+            let executor = executor.clone();
+            let transactions = transactions.clone();
+            tokio::spawn(async move {
+                if call % 2 == 0 {
+                    // Place-then-cancel: both transactions under one permit
+                    let replace =
+                        submit_replace(call as u128, ReplaceMode::PlaceThenCancel, |_| {
+                            let executor = executor.clone();
+                            let transactions = transactions.clone();
+                            async move {
+                                transactions.fetch_add(1, Ordering::SeqCst);
+                                // Never a multiple of three, so replaces never fail
+                                executor.execute(3 * call + 1).await.map(executed)
+                            }
+                        });
+                    admission.admit(replace).await.map(|_| call)
+                } else {
+                    transactions.fetch_add(1, Ordering::SeqCst);
+                    admission.admit(executor.execute(call)).await
+                }
+            })
+        })
+        .collect();
+
+    let mut failures = 0;
+    for handle in handles {
+        if handle.await.unwrap().is_err() {
+            failures += 1;
+        }
+    }
+
+    // Odd orders divisible by three fail; every replace succeeds
+    let expected_failures = (0..CALLS)
+        .filter(|call| call % 2 == 1 && call % 3 == 0)
+        .count();
+    assert_eq!(failures, expected_failures);
+    assert_eq!(
+        transactions.load(Ordering::SeqCst),
+        CALLS / 2 * 2 + CALLS / 2
+    );
+    assert!(executor.peak.load(Ordering::SeqCst) <= MAX_INFLIGHT);
+    assert_eq!(admission.inflight(), 0);
+}
@@ -0,0 +1,39 @@
+use std::net::SocketAddr;
+use ultra_aggr::config::AppConfig;
+
+fn config(api_listen_addr: Option<&str>) -> AppConfig {
+    serde_json::from_value(serde_json::json!({
+        "grpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "jsonrpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "max_inflight": 8,
+        "api_listen_addr": api_listen_addr,
+    }))
+    .expect("config")
+}
+
+#[test]
+fn defaults_to_all_interfaces_on_8080() {
+    let addr = config(None).api_listen_addr().unwrap();
+    assert_eq!(addr, "0.0.0.0:8080".parse::<SocketAddr>().unwrap());
+}
+
+#[test]
+fn configured_address_is_used() {
+    let addr = config(Some("127.0.0.1:9000")).api_listen_addr().unwrap();
+    assert_eq!(addr, "127.0.0.1:9000".parse::<SocketAddr>().unwrap());
+
+    let addr = config(Some("[::1]:8081")).api_listen_addr().unwrap();
+    assert!(addr.is_ipv6());
+    assert_eq!(addr.port(), 8081);
+}
+
+#[test]
+fn invalid_address_is_a_startup_error() {
+    for bad in ["localhost", "0.0.0.0", "0.0.0.0:http", "300.0.0.1:8080"] {
+        let err = config(Some(bad)).api_listen_addr().unwrap_err();
+        assert!(
+            err.to_string().contains("invalid API listen address"),
+            "{bad}: {err}"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::venues::adapter::LimitReq;
+use ultra_aggr::venues::managers::BalanceManagers;
+
+fn managers() -> BalanceManagers {
+    BalanceManagers::new("MANAGER_1").with_manager("DESK_B")
+}
+
+fn order(client_order_id: &str, manager: Option<&str>) -> LimitReq {
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.25,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": client_order_id,
+        "manager": manager,
+    }))
+    .unwrap();
+    LimitReq::from(req)
+}
+
+#[test]
+fn orders_are_placed_through_their_selected_manager() {
+    let managers = managers();
+    let default_order = order("1", None);
+    let desk_order = order("2", Some("DESK_B"));
+
+    let placed: Vec<_> = [&default_order, &desk_order]
+        .into_iter()
+        .map(|req| {
+            let key = managers.resolve(req.manager.as_deref()).unwrap();
+            req.place_params(key, req.price, req.quantity).unwrap()
+        })
+        .collect();
+
+    assert_eq!(placed[0].balance_manager_key, "MANAGER_1");
+    assert_eq!(placed[0].client_order_id, 1);
+    assert_eq!(placed[1].balance_manager_key, "DESK_B");
+    assert_eq!(placed[1].client_order_id, 2);
+    // Everything but the manager comes from the order itself
+    assert_eq!(placed[0].pool_key, placed[1].pool_key);
+    assert_eq!(placed[0].price, placed[1].price);
+}
+
+#[test]
+fn unset_or_blank_manager_uses_the_default() {
+    let managers = managers();
+    assert_eq!(managers.default_key(), "MANAGER_1");
+    assert_eq!(managers.resolve(None).unwrap(), "MANAGER_1");
+    assert_eq!(managers.resolve(Some("  ")).unwrap(), "MANAGER_1");
+    assert_eq!(
+        managers.keys().collect::<Vec<_>>(),
+        vec!["DESK_B", "MANAGER_1"]
+    );
+}
+
+#[test]
+fn unregistered_manager_is_rejected() {
+    assert!(matches!(
+        managers().resolve(Some("DESK_C")),
+        Err(AggrError::UnknownManager(key)) if key == "DESK_C"
+    ));
+}
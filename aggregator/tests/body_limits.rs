@@ -0,0 +1,68 @@
+use axum::{routing::post, Json, Router};
+use reqwest::StatusCode;
+use serde_json::Value;
+use ultra_aggr::router::middleware::{limit_body, with_body_limit};
+
+async fn echo_len(Json(body): Json<Value>) -> String {
+    body.to_string().len().to_string()
+}
+
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test server");
+    let addr = listener.local_addr().expect("test server address");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    format!("http://{addr}")
+}
+
+fn body_of(bytes: usize) -> String {
+    // {"pad":"..."} with the padding sized so the whole body is `bytes` long
+    format!("{{\"pad\":\"{}\"}}", "x".repeat(bytes - 10))
+}
+
+async fn post_json(base: &str, path: &str, body: String) -> StatusCode {
+    reqwest::Client::new()
+        .post(format!("{base}{path}"))
+        .header("content-type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("request")
+        .status()
+}
+
+async fn api() -> String {
+    let app = Router::new()
+        .route("/order", limit_body(post(echo_len), 256))
+        .route("/batch", post(echo_len));
+    serve(with_body_limit(app, 4096)).await
+}
+
+#[tokio::test]
+async fn oversized_order_body_is_rejected_with_413() {
+    let base = api().await;
+    assert_eq!(
+        post_json(&base, "/order", body_of(200)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        post_json(&base, "/order", body_of(1024)).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn batch_routes_use_the_larger_router_limit() {
+    let base = api().await;
+    assert_eq!(
+        post_json(&base, "/batch", body_of(1024)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        post_json(&base, "/batch", body_of(8192)).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
@@ -0,0 +1,194 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::config::AppConfig;
+use ultra_aggr::quant::PoolParams;
+use ultra_aggr::venues::book_export::{
+    count_snapshots, write_snapshots, BookLevel, BookSnapshot, ExportThrottle, SnapshotFormat,
+    SnapshotParams, DEFAULT_EXPORT_MAX_FILES, DEFAULT_EXPORT_MIN_INTERVAL,
+};
+
+const TS: u64 = 1_762_128_000_000;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![1.99, 1.98, 1.97],
+        bid_quantities: vec![100.0, 250.0, 400.0],
+        ask_prices: vec![2.01, 2.02],
+        ask_quantities: vec![80.0, 300.0],
+    }
+}
+
+fn snapshot() -> BookSnapshot {
+    let params = PoolParams {
+        tick_size: 0.01,
+        lot_size: 0.1,
+        min_size: 1.0,
+    };
+    BookSnapshot::from_level2("SUI_USDC", TS, 3, Some(2.0), Some(&params), &book())
+}
+
+fn export_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{name}-{}", std::process::id()))
+}
+
+#[tokio::test]
+async fn json_dump_holds_bids_asks_mid_and_params() {
+    let dir = export_dir("book-export-json");
+    let path = write_snapshots(&dir, &[snapshot()], SnapshotFormat::Json, TS)
+        .await
+        .unwrap();
+    assert_eq!(path, dir.join(format!("book-{TS}.json")));
+
+    let written: Vec<BookSnapshot> =
+        serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(written.len(), 1);
+    let dump = &written[0];
+    assert_eq!(dump.pool, "SUI_USDC");
+    assert_eq!(dump.timestamp_ms, TS);
+    assert_eq!(dump.mid_price, Some(2.0));
+    assert_eq!(
+        dump.params,
+        Some(SnapshotParams {
+            tick_size: 0.01,
+            lot_size: 0.1,
+            min_size: 1.0
+        })
+    );
+    assert_eq!(
+        dump.bids,
+        vec![
+            BookLevel {
+                price: 1.99,
+                quantity: 100.0
+            },
+            BookLevel {
+                price: 1.98,
+                quantity: 250.0
+            },
+            BookLevel {
+                price: 1.97,
+                quantity: 400.0
+            },
+        ]
+    );
+    assert_eq!(dump.asks.len(), 2);
+    assert_eq!(dump.asks[0].price, 2.01);
+    assert!(
+        dump.bids[0].price < dump.asks[0].price,
+        "book is not crossed"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn csv_dump_has_one_row_per_level() {
+    let dir = export_dir("book-export-csv");
+    let mut unknown = snapshot();
+    unknown.pool = "DEEP_SUI".to_string();
+    unknown.mid_price = None;
+    unknown.params = None;
+    unknown.asks.clear();
+    let path = write_snapshots(&dir, &[snapshot(), unknown], SnapshotFormat::Csv, TS)
+        .await
+        .unwrap();
+    assert_eq!(path.extension().unwrap(), "csv");
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        rows[0],
+        "pool,timestamp_ms,side,level,price,quantity,mid_price,tick_size,lot_size,min_size"
+    );
+    assert_eq!(
+        rows.len(),
+        1 + 5 + 3,
+        "header, SUI_USDC levels, DEEP_SUI bids"
+    );
+    assert_eq!(
+        rows[1],
+        format!("SUI_USDC,{TS},bid,0,1.99,100,2,0.01,0.1,1")
+    );
+    assert_eq!(rows[4], format!("SUI_USDC,{TS},ask,0,2.01,80,2,0.01,0.1,1"));
+    assert_eq!(
+        rows[5],
+        format!("SUI_USDC,{TS},ask,1,2.02,300,2,0.01,0.1,1")
+    );
+    assert_eq!(rows[8], format!("DEEP_SUI,{TS},bid,2,1.97,400,,,,"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn exports_are_spaced_by_the_minimum_interval() {
+    let throttle = ExportThrottle::new(Duration::from_secs(10));
+    let start = Instant::now();
+    assert_eq!(throttle.try_acquire(start), Ok(()));
+    assert_eq!(
+        throttle.try_acquire(start + Duration::from_secs(4)),
+        Err(Duration::from_secs(6))
+    );
+    assert_eq!(
+        throttle.try_acquire(start + Duration::from_secs(10)),
+        Ok(())
+    );
+    assert!(throttle
+        .try_acquire(start + Duration::from_secs(11))
+        .is_err());
+}
+
+#[tokio::test]
+async fn only_snapshot_files_count_toward_the_cap() {
+    let dir = export_dir("book-export-count");
+    assert_eq!(count_snapshots(&dir).await.unwrap(), 0, "missing directory");
+
+    write_snapshots(&dir, &[snapshot()], SnapshotFormat::Json, TS)
+        .await
+        .unwrap();
+    write_snapshots(&dir, &[snapshot()], SnapshotFormat::Csv, TS + 1)
+        .await
+        .unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a snapshot").unwrap();
+    assert_eq!(count_snapshots(&dir).await.unwrap(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+fn config(extra: serde_json::Value) -> AppConfig {
+    let mut value = serde_json::json!({
+        "grpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "jsonrpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "max_inflight": 8,
+        "book_export_dir": "./book-snapshots",
+    });
+    value
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(value).expect("config")
+}
+
+#[test]
+fn export_requires_a_token_and_defaults_its_limits() {
+    assert!(config(serde_json::json!({})).book_export().is_err());
+    assert!(config(serde_json::json!({ "book_export_token": "short" }))
+        .book_export()
+        .is_err());
+
+    let settings = config(serde_json::json!({ "book_export_token": "0123456789abcdef-token" }))
+        .book_export()
+        .unwrap()
+        .unwrap();
+    assert_eq!(settings.token, "0123456789abcdef-token");
+    assert_eq!(settings.min_interval, DEFAULT_EXPORT_MIN_INTERVAL);
+    assert_eq!(settings.max_files, DEFAULT_EXPORT_MAX_FILES);
+
+    assert!(config(serde_json::json!({
+        "book_export_token": "0123456789abcdef-token",
+        "book_export_max_files": 0,
+    }))
+    .book_export()
+    .is_err());
+}
@@ -0,0 +1,89 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::venues::book::estimate_slippage;
+
+const TICK: f64 = 0.01;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98, 0.97],
+        bid_quantities: vec![50.0, 100.0, 200.0],
+        ask_prices: vec![1.01, 1.02, 1.03],
+        ask_quantities: vec![50.0, 100.0, 200.0],
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn bids_walk_the_asks() {
+    let estimate = estimate_slippage(&book(), 1.0, 100.0, true, TICK, 0.01);
+
+    // 50 @ 1.01 + 50 @ 1.02
+    assert!(close(estimate.avg_fill_price, 1.015), "{estimate:?}");
+    assert!(close(estimate.slippage, 1.5), "{estimate:?}");
+    assert_eq!(estimate.visible_filled, 100.0);
+}
+
+#[test]
+fn asks_walk_the_bids() {
+    let estimate = estimate_slippage(&book(), 1.0, 100.0, false, TICK, 0.01);
+
+    // 50 @ 0.99 + 50 @ 0.98
+    assert!(close(estimate.avg_fill_price, 0.985), "{estimate:?}");
+    assert!(close(estimate.slippage, 1.5), "{estimate:?}");
+}
+
+#[test]
+fn fills_better_than_the_limit_cost_nothing() {
+    let estimate = estimate_slippage(&book(), 1.05, 100.0, true, TICK, 0.01);
+
+    assert!(close(estimate.avg_fill_price, 1.015), "{estimate:?}");
+    assert_eq!(estimate.slippage, 0.0);
+}
+
+#[test]
+fn depth_beyond_the_book_is_extrapolated_linearly() {
+    // Visible asks hold 350; the other 350 is one more book's worth
+    let estimate = estimate_slippage(&book(), 1.0, 700.0, true, TICK, 0.01);
+
+    let visible = 50.0 * 1.01 + 100.0 * 1.02 + 200.0 * 1.03;
+    // From one tick past 1.03, drifting 1% of 1.03 over one depth multiple
+    let extrapolated = (1.03 + TICK + 1.03 * 0.01 / 2.0) * 350.0;
+    let expected = (visible + extrapolated) / 700.0;
+    assert!(close(estimate.avg_fill_price, expected), "{estimate:?}");
+    assert!(close(estimate.slippage, (expected - 1.0) * 700.0));
+    assert_eq!(estimate.visible_filled, 350.0);
+}
+
+#[test]
+fn zero_impact_fills_the_remainder_one_tick_past_the_book() {
+    let estimate = estimate_slippage(&book(), 1.0, 450.0, false, TICK, 0.0);
+
+    let visible = 50.0 * 0.99 + 100.0 * 0.98 + 200.0 * 0.97;
+    let expected = (visible + 100.0 * 0.96) / 450.0;
+    assert!(close(estimate.avg_fill_price, expected), "{estimate:?}");
+}
+
+#[test]
+fn larger_impact_coefficients_cost_more() {
+    let mild = estimate_slippage(&book(), 1.0, 1_000.0, true, TICK, 0.01);
+    let steep = estimate_slippage(&book(), 1.0, 1_000.0, true, TICK, 0.1);
+
+    assert!(steep.slippage > mild.slippage);
+    assert!(steep.avg_fill_price > mild.avg_fill_price);
+}
+
+#[test]
+fn empty_opposite_side_assumes_one_percent() {
+    let mut book = book();
+    book.ask_prices.clear();
+    book.ask_quantities.clear();
+
+    let estimate = estimate_slippage(&book, 2.0, 10.0, true, TICK, 0.01);
+
+    assert!(close(estimate.slippage, 0.2), "{estimate:?}");
+    assert!(close(estimate.avg_fill_price, 2.02), "{estimate:?}");
+    assert_eq!(estimate.visible_filled, 0.0);
+}
@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use ultra_aggr::transport::grpc::sui::rpc::v2::{
+    ChangedObject, Checkpoint, ExecutedTransaction, TransactionEffects,
+};
+use ultra_aggr::venues::pools::PoolNormalizer;
+use ultra_aggr::venues::snapshots::{pools_touched, BookCacheSettings, BookSnapshotCache};
+
+const SUI_USDC_ID: &str = "0xe05dafb5133bcffb8d59f4e12465dc0e9faeaa05e3e342a08fe135800e3e4407";
+const DEEP_SUI_ID: &str = "0xb663828d6217467c8a1838a03793da896cbe745b150ebd57d82f814ca579fc22";
+
+fn normalizer() -> PoolNormalizer {
+    PoolNormalizer::from_pools([("SUI_USDC", SUI_USDC_ID), ("DEEP_SUI", DEEP_SUI_ID)])
+}
+
+fn checkpoint_changing(object_ids: &[&str]) -> Checkpoint {
+    Checkpoint {
+        sequence_number: Some(42),
+        transactions: vec![ExecutedTransaction {
+            effects: Some(TransactionEffects {
+                changed_objects: object_ids
+                    .iter()
+                    .map(|id| ChangedObject {
+                        object_id: Some(id.to_string()),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn cache(ttl: Duration) -> BookSnapshotCache<usize> {
+    BookSnapshotCache::new(BookCacheSettings {
+        ttl,
+        invalidate_on_checkpoint: true,
+    })
+}
+
+async fn fetch_counted(cache: &BookSnapshotCache<usize>, fetches: &AtomicUsize) -> usize {
+    *cache
+        .get_or_fetch("SUI_USDC", 20, || async {
+            Ok(fetches.fetch_add(1, Ordering::SeqCst) + 1)
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn snapshot_is_reused_within_ttl() {
+    let cache = cache(Duration::from_secs(60));
+    let fetches = AtomicUsize::new(0);
+
+    assert_eq!(fetch_counted(&cache, &fetches).await, 1);
+    assert_eq!(fetch_counted(&cache, &fetches).await, 1);
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn snapshot_expires_after_ttl() {
+    let cache = cache(Duration::from_millis(10));
+    let fetches = AtomicUsize::new(0);
+
+    fetch_counted(&cache, &fetches).await;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(fetch_counted(&cache, &fetches).await, 2);
+}
+
+#[tokio::test]
+async fn relevant_checkpoint_invalidates_snapshot() {
+    let cache = cache(Duration::from_secs(60));
+    let fetches = AtomicUsize::new(0);
+    fetch_counted(&cache, &fetches).await;
+
+    let unrelated = checkpoint_changing(&[DEEP_SUI_ID, "0x5"]);
+    assert_eq!(
+        cache.apply_checkpoint(&unrelated, &normalizer()).await,
+        vec!["DEEP_SUI"]
+    );
+    assert_eq!(fetch_counted(&cache, &fetches).await, 1);
+
+    let relevant = checkpoint_changing(&[SUI_USDC_ID]);
+    assert_eq!(
+        cache.apply_checkpoint(&relevant, &normalizer()).await,
+        vec!["SUI_USDC"]
+    );
+    assert_eq!(fetch_counted(&cache, &fetches).await, 2);
+}
+
+#[tokio::test]
+async fn checkpoint_invalidation_can_be_disabled() {
+    let cache = BookSnapshotCache::new(BookCacheSettings {
+        ttl: Duration::from_secs(60),
+        invalidate_on_checkpoint: false,
+    });
+    let fetches = AtomicUsize::new(0);
+    fetch_counted(&cache, &fetches).await;
+
+    let relevant = checkpoint_changing(&[SUI_USDC_ID]);
+    assert!(cache
+        .apply_checkpoint(&relevant, &normalizer())
+        .await
+        .is_empty());
+    assert_eq!(fetch_counted(&cache, &fetches).await, 1);
+}
+
+#[tokio::test]
+async fn zero_ttl_disables_caching() {
+    let cache = cache(Duration::ZERO);
+    let fetches = AtomicUsize::new(0);
+
+    fetch_counted(&cache, &fetches).await;
+    assert_eq!(fetch_counted(&cache, &fetches).await, 2);
+}
+
+#[test]
+fn touched_pools_are_deduplicated() {
+    let checkpoint = checkpoint_changing(&[SUI_USDC_ID, SUI_USDC_ID, "0x6"]);
+    assert_eq!(pools_touched(&checkpoint, &normalizer()), vec!["SUI_USDC"]);
+}
@@ -0,0 +1,31 @@
+use ultra_aggr::venues::book::sweep;
+
+#[test]
+fn vwap_matches_hand_computation() {
+    // Asks best first
+    let prices = [1.00, 1.01, 1.03];
+    let quantities = [100.0, 50.0, 200.0];
+
+    let fill = sweep(&prices, &quantities, 200.0).unwrap();
+    // 100 @ 1.00 + 50 @ 1.01 + 50 @ 1.03 = 202.0 over 200
+    assert!((fill.vwap - 202.0 / 200.0).abs() < 1e-12);
+    assert_eq!(fill.worst_price, 1.03);
+    assert_eq!(fill.filled, 200.0);
+    assert!(fill.is_complete());
+}
+
+#[test]
+fn single_level_fill_prices_at_best() {
+    let fill = sweep(&[2.5, 2.6], &[10.0, 10.0], 4.0).unwrap();
+    assert_eq!(fill.vwap, 2.5);
+    assert_eq!(fill.worst_price, 2.5);
+}
+
+#[test]
+fn thin_book_reports_partial_fill() {
+    let fill = sweep(&[1.0, 1.1], &[5.0, 5.0], 20.0).unwrap();
+    assert_eq!(fill.filled, 10.0);
+    assert!(!fill.is_complete());
+    assert!((fill.vwap - 1.05).abs() < 1e-12);
+    assert!(sweep(&[], &[], 1.0).is_none());
+}
@@ -0,0 +1,127 @@
+use anyhow::Context;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use ultra_aggr::control::{counts_as_breaker_failure, BreakerClass, CircuitBreakers};
+use ultra_aggr::errors::AggrError;
+
+// Enough failures to fill the minimum sample window and trip a breaker
+const TRIPPING_FAILURES: usize = 20;
+
+#[tokio::test]
+async fn repeated_failures_short_circuit_later_attempts() {
+    let breakers = CircuitBreakers::new();
+    let class = BreakerClass::new("deepbook", Some("SUI_USDC"));
+    let attempts = AtomicUsize::new(0);
+
+    for _ in 0..TRIPPING_FAILURES {
+        let result: anyhow::Result<()> = breakers
+            .guard(&class, async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(anyhow::anyhow!("submission failed"))
+            })
+            .await;
+        assert!(result.is_err());
+    }
+    assert_eq!(attempts.load(Ordering::SeqCst), TRIPPING_FAILURES);
+
+    // During the cooldown nothing is submitted
+    let err = breakers
+        .guard(&class, async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<AggrError>(),
+        Some(AggrError::CircuitOpen(key)) if key == "deepbook:SUI_USDC"
+    ));
+    assert_eq!(attempts.load(Ordering::SeqCst), TRIPPING_FAILURES);
+}
+
+#[tokio::test]
+async fn successes_keep_the_breaker_closed() {
+    let breakers = CircuitBreakers::new();
+    let class = BreakerClass::new("deepbook", Some("SUI_USDC"));
+
+    for _ in 0..TRIPPING_FAILURES {
+        breakers
+            .guard(&class, async { anyhow::Ok(()) })
+            .await
+            .unwrap();
+    }
+    assert!(!breakers.is_open_for(&class).await);
+    assert_eq!(breakers.recent_error_rate().await, Some(0.0));
+}
+
+#[tokio::test]
+async fn open_breaker_only_blocks_its_class() {
+    let breakers = CircuitBreakers::new();
+    let failing = BreakerClass::new("cetus", Some("SUI_USDC"));
+    let healthy = BreakerClass::new("deepbook", Some("SUI_USDC"));
+
+    for _ in 0..TRIPPING_FAILURES {
+        let _ = breakers
+            .guard(&failing, async {
+                anyhow::Result::<()>::Err(anyhow::anyhow!("abort"))
+            })
+            .await;
+    }
+
+    assert!(breakers
+        .guard(&healthy, async { anyhow::Ok(7) })
+        .await
+        .is_ok());
+    assert!(breakers
+        .guard(&failing, async { anyhow::Ok(7) })
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn rejected_requests_do_not_trip_the_breaker() {
+    let breakers = CircuitBreakers::new();
+    let class = BreakerClass::new("deepbook", Some("SUI_USDC"));
+    let rejections = || {
+        [
+            AggrError::SimulationRejected("MoveAbort".to_string()),
+            AggrError::InsufficientSponsorGas {
+                available: 1,
+                required: 2,
+            },
+            AggrError::NoHealthyValidators {
+                healthy: 1,
+                required: 3,
+            },
+            AggrError::RiskCheck("position limit".to_string()),
+        ]
+    };
+
+    for _ in 0..TRIPPING_FAILURES {
+        for rejection in rejections() {
+            let err = breakers
+                .guard(&class, async {
+                    Err::<(), _>(anyhow::Error::from(rejection)).context("execute route")
+                })
+                .await
+                .unwrap_err();
+            // The rejection reaches the caller unchanged
+            assert!(err.downcast_ref::<AggrError>().is_some());
+        }
+    }
+    assert!(!breakers.is_open_for(&class).await);
+    assert_eq!(breakers.recent_error_rate().await, None);
+}
+
+#[test]
+fn infrastructure_failures_count_against_the_breaker() {
+    let transport = anyhow::Error::from(AggrError::Transport("connection reset".to_string()));
+    assert!(counts_as_breaker_failure(
+        &transport.context("gRPC execute transaction")
+    ));
+    assert!(counts_as_breaker_failure(&anyhow::anyhow!(
+        "transaction execution failed"
+    )));
+    assert!(!counts_as_breaker_failure(&anyhow::Error::from(
+        AggrError::SimulationRejected("InsufficientGas".to_string())
+    )));
+}
@@ -0,0 +1,71 @@
+use ultra_aggr::control::{BreakerClass, BreakerScope, CircuitBreakers};
+use ultra_aggr::router::routes::RoutePlan;
+
+// Enough failures to fill the minimum sample window and trip a breaker
+const TRIPPING_FAILURES: usize = 20;
+
+async fn fail(breakers: &CircuitBreakers, class: &BreakerClass) {
+    for _ in 0..TRIPPING_FAILURES {
+        breakers.record_failure_for(class).await;
+    }
+}
+
+#[test]
+fn class_keys_follow_the_scope() {
+    let class = BreakerClass::new("deepbook", Some("SUI_USDC"));
+    assert_eq!(class.to_string(), "deepbook:SUI_USDC");
+    assert_eq!(BreakerScope::Pool.keys(&class), vec!["deepbook:SUI_USDC"]);
+    assert_eq!(BreakerScope::Venue.keys(&class), vec!["deepbook"]);
+    assert_eq!(
+        BreakerScope::Hierarchical.keys(&class),
+        vec!["deepbook:SUI_USDC", "deepbook"]
+    );
+
+    // Routes without a pool fall back to the venue key
+    let venue_only = BreakerClass::new("flash_loan", None);
+    assert_eq!(BreakerScope::Pool.keys(&venue_only), vec!["flash_loan"]);
+}
+
+#[test]
+fn cancels_and_orders_share_a_class() {
+    let cancel = RoutePlan::cancel_deepbook("SUI_USDC".to_string(), 7, None, 0);
+    let class = BreakerClass::new(cancel.route.venue(), cancel.route.pool());
+    assert_eq!(class, BreakerClass::new("deepbook", Some("SUI_USDC")));
+}
+
+#[tokio::test]
+async fn hierarchical_pool_failures_open_the_venue() {
+    let breakers = CircuitBreakers::new().with_scope(BreakerScope::Hierarchical);
+    let failing = BreakerClass::new("deepbook", Some("SUI_USDC"));
+    let sibling = BreakerClass::new("deepbook", Some("DEEP_SUI"));
+
+    fail(&breakers, &failing).await;
+
+    assert!(breakers.is_open_for(&failing).await);
+    assert!(breakers.is_open("deepbook").await);
+    assert!(breakers.is_open_for(&sibling).await);
+}
+
+#[tokio::test]
+async fn pool_scope_isolates_pools() {
+    let breakers = CircuitBreakers::new().with_scope(BreakerScope::Pool);
+    let failing = BreakerClass::new("deepbook", Some("SUI_USDC"));
+    let sibling = BreakerClass::new("deepbook", Some("DEEP_SUI"));
+
+    fail(&breakers, &failing).await;
+
+    assert!(breakers.is_open_for(&failing).await);
+    assert!(!breakers.is_open_for(&sibling).await);
+    assert!(!breakers.is_open("deepbook").await);
+}
+
+#[tokio::test]
+async fn venue_scope_shares_one_breaker() {
+    let breakers = CircuitBreakers::new().with_scope(BreakerScope::Venue);
+    fail(&breakers, &BreakerClass::new("deepbook", Some("SUI_USDC"))).await;
+    assert!(
+        breakers
+            .is_open_for(&BreakerClass::new("deepbook", Some("DEEP_SUI")))
+            .await
+    );
+}
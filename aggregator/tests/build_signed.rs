@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use ultra_aggr::router::execution::{sign_for_broadcast, transaction_digest, SignedTransaction};
+use ultra_aggr::signing::sign_tx_bcs_ed25519_to_serialized_signature;
+
+const KEY: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+/// BCS of an empty programmable `TransactionData` from 0x7
+const TX_HEX: &str = "000000000000000000000000000000000000000000000000000000000000000000000007\
+                      000000000000000000000000000000000000000000000000000000000000000007\
+                      e80300000000000080f0fa020000000000";
+
+fn sample_tx() -> Vec<u8> {
+    hex::decode(TX_HEX).unwrap()
+}
+
+/// Blake2b-256 of the transaction-data intent header and bytes
+fn intent_digest(tx_bcs: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([0x00, 0x00, 0x00]);
+    hasher.update(tx_bcs);
+    hasher.finalize().into()
+}
+
+fn sign(tx_bcs: &[u8], _sponsored: bool) -> anyhow::Result<Vec<Vec<u8>>> {
+    let (signature, _) = sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, KEY)?;
+    Ok(vec![signature])
+}
+
+#[tokio::test]
+async fn returned_signature_verifies_over_the_returned_bytes() {
+    let signed = sign_for_broadcast(|| async { Ok((sample_tx(), false)) }, sign)
+        .await
+        .unwrap();
+
+    // Round-trip through JSON as a client would receive it
+    let signed: SignedTransaction =
+        serde_json::from_str(&serde_json::to_string(&signed).unwrap()).unwrap();
+    let tx_bcs = B64.decode(&signed.tx_bytes).unwrap();
+    assert_eq!(tx_bcs, sample_tx());
+    assert_eq!(signed.digest, transaction_digest(&tx_bcs).unwrap());
+    assert!(!signed.sponsored);
+    assert_eq!(signed.signatures.len(), 1);
+
+    let serialized = B64.decode(&signed.signatures[0]).unwrap();
+    assert_eq!(serialized.len(), 97);
+    assert_eq!(serialized[0], 0x00, "Ed25519 flag");
+    let signature = Signature::from_bytes(serialized[1..65].try_into().unwrap());
+    let key = VerifyingKey::from_bytes(serialized[65..].try_into().unwrap()).unwrap();
+    key.verify(&intent_digest(&tx_bcs), &signature)
+        .expect("signature verifies");
+}
+
+#[tokio::test]
+async fn sponsored_orders_carry_both_signatures() {
+    let built = AtomicU32::new(0);
+    let signed_count = AtomicU32::new(0);
+
+    let signed = sign_for_broadcast(
+        || async {
+            built.fetch_add(1, Ordering::SeqCst);
+            Ok((sample_tx(), true))
+        },
+        |tx_bcs, sponsored| {
+            signed_count.fetch_add(1, Ordering::SeqCst);
+            assert!(sponsored);
+            // Sender and sponsor signatures, sender first
+            Ok(vec![sign(tx_bcs, sponsored)?.remove(0), vec![0u8; 97]])
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(built.load(Ordering::SeqCst), 1);
+    assert_eq!(signed_count.load(Ordering::SeqCst), 1);
+    assert!(signed.sponsored);
+    assert_eq!(signed.signatures.len(), 2);
+}
+
+#[tokio::test]
+async fn signing_failures_surface_to_the_caller() {
+    let result = sign_for_broadcast(
+        || async { Ok((sample_tx(), false)) },
+        |tx_bcs, _| {
+            sign_tx_bcs_ed25519_to_serialized_signature(tx_bcs, "not hex")?;
+            Ok(vec![])
+        },
+    )
+    .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use ultra_aggr::router::fanout::{bounded_fan_out, FanOutLimits};
+
+#[tokio::test]
+async fn many_pools_never_exceed_worker_cap() {
+    let in_flight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let limits = FanOutLimits {
+        workers: 3,
+        item_timeout: Duration::from_secs(5),
+    };
+    let pools: Vec<usize> = (0..40).collect();
+
+    let results = bounded_fan_out(pools, limits, |pool| {
+        let (in_flight, peak) = (&in_flight, &peak);
+        async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            pool * 10
+        }
+    })
+    .await;
+
+    assert_eq!(results.len(), 40);
+    for (pool, result) in results.into_iter().enumerate() {
+        assert_eq!(result, Some(pool * 10), "results keep request order");
+    }
+    let peak = peak.load(Ordering::SeqCst);
+    assert!(peak <= 3, "peak concurrency {peak} exceeded the worker cap");
+    assert!(peak > 1, "pools should run concurrently");
+}
+
+#[tokio::test]
+async fn slow_pool_times_out_without_blocking_others() {
+    let limits = FanOutLimits {
+        workers: 2,
+        item_timeout: Duration::from_millis(100),
+    };
+    let pools = vec!["SLOW", "A", "B", "C", "D", "E"];
+    let started = Instant::now();
+
+    let results = bounded_fan_out(pools, limits, |pool| async move {
+        if pool == "SLOW" {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        } else {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        pool.to_lowercase()
+    })
+    .await;
+
+    assert_eq!(results[0], None, "slow pool is reported as timed out");
+    assert_eq!(
+        results[1..],
+        [
+            Some("a".to_string()),
+            Some("b".to_string()),
+            Some("c".to_string()),
+            Some("d".to_string()),
+            Some("e".to_string()),
+        ]
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "the slow pool held up the batch for {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn zero_workers_still_makes_progress() {
+    let limits = FanOutLimits {
+        workers: 0,
+        item_timeout: Duration::from_secs(1),
+    };
+    let results = bounded_fan_out(vec![1, 2], limits, |n| async move { n + 1 }).await;
+    assert_eq!(results, vec![Some(2), Some(3)]);
+}
@@ -0,0 +1,33 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::routes::Route;
+use ultra_aggr::router::RoutePlan;
+use ultra_aggr::venues::adapter::cancel_all_order_ids;
+
+#[test]
+fn cancels_each_open_order_once() {
+    let open = vec![42, 7, 1_000_000_000_000_000_000_000, 7];
+    let ids = cancel_all_order_ids("SUI_USDC", open).unwrap();
+    assert_eq!(ids, vec![7, 42, 1_000_000_000_000_000_000_000]);
+}
+
+#[test]
+fn empty_book_is_a_clear_error() {
+    let err = cancel_all_order_ids("SUI_USDC", Vec::new()).unwrap_err();
+    match err.downcast_ref::<AggrError>() {
+        Some(AggrError::NoOpenOrders(pool)) => assert_eq!(pool, "SUI_USDC"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(err.to_string(), "no open orders in SUI_USDC to cancel");
+}
+
+#[test]
+fn cancel_all_plan_targets_the_pool() {
+    let plan = RoutePlan::cancel_all("SUI_USDC".to_string(), Some("desk".to_string()), 5_000_000);
+    assert_eq!(plan.route.venue(), "deepbook");
+    assert_eq!(plan.route.pool(), Some("SUI_USDC"));
+    assert_eq!(plan.estimated_gas, 5_000_000);
+    match &plan.route {
+        Route::CancelAll { manager, .. } => assert_eq!(manager.as_deref(), Some("desk")),
+        other => panic!("unexpected route: {other:?}"),
+    }
+}
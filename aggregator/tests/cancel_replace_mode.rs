@@ -0,0 +1,42 @@
+use ultra_aggr::router::routes::{ReplaceCommand, ReplaceMode};
+
+#[test]
+fn atomic_is_the_default() {
+    assert_eq!(ReplaceMode::default(), ReplaceMode::Atomic);
+}
+
+#[test]
+fn modes_parse_from_snake_case() {
+    let mode: ReplaceMode = serde_json::from_str("\"place_then_cancel\"").unwrap();
+    assert_eq!(mode, ReplaceMode::PlaceThenCancel);
+    let mode: ReplaceMode = serde_json::from_str("\"atomic\"").unwrap();
+    assert_eq!(mode, ReplaceMode::Atomic);
+    assert!(serde_json::from_str::<ReplaceMode>("\"cancel_then_place\"").is_err());
+}
+
+#[test]
+fn atomic_cancels_before_placing_in_one_ptb() {
+    assert_eq!(
+        ReplaceMode::Atomic.transactions(),
+        vec![vec![ReplaceCommand::Cancel, ReplaceCommand::Place]]
+    );
+}
+
+#[test]
+fn place_then_cancel_places_in_its_own_transaction_first() {
+    assert_eq!(
+        ReplaceMode::PlaceThenCancel.transactions(),
+        vec![vec![ReplaceCommand::Place], vec![ReplaceCommand::Cancel]]
+    );
+}
+
+#[test]
+fn every_mode_cancels_and_places_exactly_once() {
+    for mode in [ReplaceMode::Atomic, ReplaceMode::PlaceThenCancel] {
+        let commands = mode.transactions().concat();
+        for command in [ReplaceCommand::Cancel, ReplaceCommand::Place] {
+            let count = commands.iter().filter(|c| **c == command).count();
+            assert_eq!(count, 1, "{mode:?} issues {command:?} {count} times");
+        }
+    }
+}
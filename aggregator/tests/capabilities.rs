@@ -0,0 +1,48 @@
+use ultra_aggr::router::capabilities::{grpc_exec_enabled, Capabilities, GRPC_EXEC_COMPILED};
+
+#[test]
+fn grpc_exec_follows_the_build_feature() {
+    assert_eq!(GRPC_EXEC_COMPILED, cfg!(feature = "grpc-exec"));
+    assert_eq!(grpc_exec_enabled(true), cfg!(feature = "grpc-exec"));
+    assert!(
+        !grpc_exec_enabled(false),
+        "off unless switched on at runtime"
+    );
+}
+
+#[test]
+fn document_lists_every_feature() {
+    let capabilities = Capabilities {
+        version: "0.1.0".to_string(),
+        execution: true,
+        sponsors: vec!["desk_a".to_string()],
+        websocket_sessions: true,
+        ..Capabilities::default()
+    };
+
+    let json = serde_json::to_value(&capabilities).unwrap();
+
+    for feature in [
+        "execution",
+        "grpc_exec",
+        "deepbook",
+        "amm_venues",
+        "sponsorship",
+        "simulation",
+        "dry_run",
+        "websocket_sessions",
+        "build_signed",
+        "sign_message",
+        "history",
+        "book_export",
+    ] {
+        assert!(json[feature].is_boolean(), "{feature} missing from {json}");
+    }
+    assert_eq!(json["execution"], true);
+    assert_eq!(json["sponsorship"], false);
+    assert_eq!(json["sponsors"], serde_json::json!(["desk_a"]));
+    assert_eq!(
+        serde_json::from_value::<Capabilities>(json).unwrap(),
+        capabilities
+    );
+}
@@ -0,0 +1,150 @@
+use serde_json::json;
+use sui_sdk::types::base_types::ObjectID;
+use ultra_aggr::venues::amm::{
+    constant_product_in, constant_product_out, AmmSwapReq, CetusPool, CetusPoolState,
+};
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() <= expected.abs() * 1e-9
+}
+
+// 1,000 SUI (9 decimals) against 2,000 USDC (6 decimals), 0.25% fee
+fn sui_usdc() -> CetusPoolState {
+    CetusPoolState::from_reserves(1_000e9, 2_000e6, 2_500)
+}
+
+#[test]
+fn price_follows_reserve_ratio() {
+    let state = sui_usdc();
+    assert!(close(state.price(9, 6), 2.0));
+    assert!(close(state.raw_price(), 2e-3));
+
+    let (reserve_a, reserve_b) = state.virtual_reserves();
+    assert!(close(reserve_a, 1_000e9));
+    assert!(close(reserve_b, 2_000e6));
+}
+
+#[test]
+fn sqrt_price_is_q64() {
+    // sqrt_price of 2^64 is a raw price of exactly one
+    let state = CetusPoolState {
+        sqrt_price_x64: 1u128 << 64,
+        liquidity: 1_000_000,
+        fee_rate: 0,
+        is_paused: false,
+    };
+    assert_eq!(state.raw_price(), 1.0);
+    assert!(close(state.price(9, 6), 1_000.0));
+}
+
+#[test]
+fn constant_product_output() {
+    assert!(close(
+        constant_product_out(100.0, 200.0, 10.0, 0.0),
+        200.0 / 11.0
+    ));
+    // Fee comes off the input before it reaches the curve
+    assert!(close(
+        constant_product_out(100.0, 200.0, 10.0, 0.01),
+        200.0 * 9.9 / 109.9
+    ));
+    assert_eq!(constant_product_out(0.0, 200.0, 10.0, 0.0), 0.0);
+    assert_eq!(constant_product_out(100.0, 200.0, 0.0, 0.0), 0.0);
+}
+
+#[test]
+fn swap_output_against_known_reserves() {
+    let state = sui_usdc();
+
+    // Sell 10 SUI for USDC
+    let effective_in = 10e9 * 0.9975;
+    let usdc_out = state.amount_out(10e9, true);
+    assert!(close(
+        usdc_out,
+        2_000e6 * effective_in / (1_000e9 + effective_in)
+    ));
+    assert!(usdc_out < 20e6);
+
+    // Buy SUI with 20 USDC
+    let effective_in = 20e6 * 0.9975;
+    let sui_out = state.amount_out(20e6, false);
+    assert!(close(
+        sui_out,
+        1_000e9 * effective_in / (2_000e6 + effective_in)
+    ));
+    assert!(sui_out < 10e9);
+}
+
+#[test]
+fn constant_product_input_inverts_output() {
+    let amount_in = constant_product_in(100.0, 200.0, 10.0, 0.01);
+    assert!(close(
+        constant_product_out(100.0, 200.0, amount_in, 0.01),
+        10.0
+    ));
+    // The whole output reserve cannot be bought
+    assert_eq!(constant_product_in(100.0, 200.0, 200.0, 0.0), f64::INFINITY);
+    assert_eq!(constant_product_in(100.0, 200.0, 0.0, 0.0), 0.0);
+}
+
+#[test]
+fn limit_buy_below_the_limit_fills_exactly_quantity() {
+    // Buy 10 SUI at up to 2.10 from a pool priced at 2.00
+    let swap = AmmSwapReq::limit("SUI_USDC", 2.10, 10.0, true);
+    assert!(!swap.by_amount_in);
+    assert_eq!(swap.amount, 10.0);
+    assert!(close(swap.amount_limit, 21.0));
+
+    // Quote needed for exactly 10 SUI stays under the limit notional...
+    let state = sui_usdc();
+    let usdc_in = state.amount_in(10e9, false) / 1e6;
+    assert!(usdc_in < swap.amount_limit);
+    assert!(usdc_in > 20.0);
+    // ...and buys exactly 10 SUI, where spending the whole notional buys more
+    assert!(close(state.amount_out(usdc_in * 1e6, false), 10e9));
+    assert!(state.amount_out(swap.amount_limit * 1e6, false) > 10.3e9);
+}
+
+#[test]
+fn limit_sell_spends_exactly_quantity() {
+    let swap = AmmSwapReq::limit("SUI_USDC", 1.90, 10.0, false);
+    assert!(swap.by_amount_in);
+    assert_eq!(swap.amount, 10.0);
+    assert!(close(swap.amount_limit, 19.0));
+}
+
+#[test]
+fn swap_direction_follows_base_coin() {
+    let pool = |a_is_base| CetusPool {
+        pool_id: ObjectID::ZERO,
+        coin_a: "0x2::sui::SUI".to_string(),
+        coin_b: "0x5::usdc::USDC".to_string(),
+        decimals_a: 9,
+        decimals_b: 6,
+        a_is_base,
+    };
+    // A is base: buying spends B
+    assert!(!pool(true).a2b(true));
+    assert!(pool(true).a2b(false));
+    // A is quote: buying spends A
+    assert!(pool(false).a2b(true));
+    assert!(!pool(false).a2b(false));
+}
+
+#[test]
+fn state_is_read_from_pool_fields() {
+    let fields = json!({
+        "current_sqrt_price": "18446744073709551616",
+        "liquidity": "5000000000",
+        "fee_rate": "2500",
+        "is_pause": false,
+        "tick_spacing": 60,
+    });
+    let state = CetusPoolState::from_fields(&fields).unwrap();
+    assert_eq!(state.sqrt_price_x64, 1u128 << 64);
+    assert_eq!(state.liquidity, 5_000_000_000);
+    assert_eq!(state.fee_rate, 2_500);
+    assert!(!state.is_paused);
+
+    assert!(CetusPoolState::from_fields(&json!({ "liquidity": "1" })).is_err());
+}
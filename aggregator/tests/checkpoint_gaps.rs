@@ -0,0 +1,58 @@
+use ultra_aggr::state::CheckpointState;
+
+#[tokio::test]
+async fn out_of_order_sequence_records_and_fills_gaps() {
+    let state = CheckpointState::new(64);
+    let mut updates = state.subscribe();
+
+    for cursor in [1, 2, 5, 3, 9, 4, 4, 10] {
+        state.advance(cursor, None).await;
+    }
+
+    let mut seen = Vec::new();
+    while let Ok(update) = updates.try_recv() {
+        seen.push((update.cursor, update.gap));
+    }
+    assert_eq!(
+        seen,
+        vec![
+            (1, None),
+            (2, None),
+            (5, Some((3, 4))),
+            (3, None),
+            (9, Some((6, 8))),
+            (4, None),
+            (10, None),
+        ]
+    );
+    // The late cursors never move the cursor backwards
+    assert_eq!(state.last_cursor().await, Some(10));
+    assert_eq!(state.missed_ranges().await, vec![(6, 8)]);
+}
+
+#[tokio::test]
+async fn late_cursor_splits_missed_range() {
+    let state = CheckpointState::new(16);
+    assert!(state.advance(10, None).await);
+    assert!(state.advance(20, None).await);
+    assert_eq!(state.missed_ranges().await, vec![(11, 19)]);
+
+    assert!(state.advance(15, None).await);
+    assert_eq!(state.missed_ranges().await, vec![(11, 14), (16, 19)]);
+    assert!(state.advance(11, None).await);
+    assert!(state.advance(19, None).await);
+    assert_eq!(state.missed_ranges().await, vec![(12, 14), (16, 18)]);
+
+    // Already delivered cursors are dropped
+    assert!(!state.advance(15, None).await);
+    assert!(!state.advance(20, None).await);
+}
+
+#[tokio::test]
+async fn first_checkpoint_is_never_a_gap() {
+    let state = CheckpointState::new(16);
+    let mut updates = state.subscribe();
+    state.advance(1_000, None).await;
+    assert_eq!(updates.try_recv().unwrap().gap, None);
+    assert!(state.missed_ranges().await.is_empty());
+}
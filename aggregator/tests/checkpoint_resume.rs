@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+use ultra_aggr::metrics::CHECKPOINT_GAPS;
+use ultra_aggr::state::{missing_cursors, CheckpointState, MAX_CHECKPOINT_BACKFILL};
+use ultra_aggr::transport::grpc::sui::rpc::v2::Checkpoint;
+
+fn checkpoint(seq: u64) -> Checkpoint {
+    Checkpoint {
+        sequence_number: Some(seq),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn missing_cursors_reports_only_real_gaps() {
+    assert_eq!(missing_cursors(None, 10), None);
+    assert_eq!(missing_cursors(Some(10), 11), None);
+    assert_eq!(missing_cursors(Some(10), 10), None);
+    assert_eq!(missing_cursors(Some(10), 14), Some(11..=13));
+    assert_eq!(missing_cursors(Some(u64::MAX), 0), None);
+}
+
+#[tokio::test]
+async fn dropped_stream_replays_every_missed_cursor() {
+    let state = CheckpointState::new(64);
+    let mut updates = state.subscribe();
+
+    // First connection processes 8..=10, then drops
+    for seq in 8..=10 {
+        state.advance(seq, Some(checkpoint(seq))).await;
+    }
+    assert_eq!(state.last_cursor().await, Some(10));
+
+    // The reconnected stream starts at 14; 11..=13 come from the ledger
+    let gaps_before = CHECKPOINT_GAPS.get();
+    let fetched = Arc::new(Mutex::new(Vec::new()));
+    let log = fetched.clone();
+    let replayed = state
+        .backfill(14, move |seq| {
+            log.lock().unwrap().push(seq);
+            async move { Ok(Some(checkpoint(seq))) }
+        })
+        .await;
+    assert_eq!(*fetched.lock().unwrap(), vec![11, 12, 13]);
+    assert_eq!(CHECKPOINT_GAPS.get(), gaps_before + 1);
+
+    for (seq, cp) in replayed {
+        state.advance(seq, cp).await;
+    }
+    state.advance(14, Some(checkpoint(14))).await;
+
+    let mut seen = Vec::new();
+    while let Ok(update) = updates.try_recv() {
+        assert_eq!(
+            update.checkpoint.and_then(|cp| cp.sequence_number),
+            Some(update.cursor)
+        );
+        seen.push(update.cursor);
+    }
+    assert_eq!(seen, (8..=14).collect::<Vec<_>>());
+    assert_eq!(state.last_cursor().await, Some(14));
+}
+
+#[tokio::test]
+async fn contiguous_reconnect_fetches_nothing() {
+    let state = CheckpointState::new(16);
+    state.advance(5, None).await;
+    let mut fetches = 0;
+    let replayed = state
+        .backfill(6, |seq| {
+            fetches += 1;
+            async move { Ok(Some(checkpoint(seq))) }
+        })
+        .await;
+    assert!(replayed.is_empty());
+    assert_eq!(fetches, 0);
+}
+
+#[tokio::test]
+async fn rejected_replay_falls_back_to_live_stream() {
+    let state = CheckpointState::new(16);
+    state.advance(20, None).await;
+    let replayed = state
+        .backfill(25, |seq| async move {
+            if seq < 23 {
+                Ok(Some(checkpoint(seq)))
+            } else {
+                Err(anyhow::anyhow!("checkpoint pruned"))
+            }
+        })
+        .await;
+    let cursors: Vec<u64> = replayed.iter().map(|(seq, _)| *seq).collect();
+    assert_eq!(cursors, vec![21, 22]);
+}
+
+#[tokio::test]
+async fn oversized_gap_is_not_replayed() {
+    let state = CheckpointState::new(16);
+    state.advance(0, None).await;
+    let mut fetches = 0;
+    let replayed = state
+        .backfill(MAX_CHECKPOINT_BACKFILL + 2, |seq| {
+            fetches += 1;
+            async move { Ok(Some(checkpoint(seq))) }
+        })
+        .await;
+    assert!(replayed.is_empty());
+    assert_eq!(fetches, 0);
+}
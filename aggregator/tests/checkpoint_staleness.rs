@@ -0,0 +1,31 @@
+use std::time::Duration;
+use ultra_aggr::metrics::CHECKPOINT_STALE_ALERTS;
+use ultra_aggr::state::CheckpointState;
+
+#[tokio::test]
+async fn silence_past_threshold_flips_health() {
+    let state = CheckpointState::new(16).with_stale_after(Duration::from_millis(50));
+    assert!(state.check_staleness().await);
+    assert!(state.is_healthy());
+
+    let alerts_before = CHECKPOINT_STALE_ALERTS.get();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(!state.check_staleness().await);
+    assert!(!state.is_healthy());
+    assert_eq!(CHECKPOINT_STALE_ALERTS.get(), alerts_before + 1);
+
+    // A continued stall does not re-alert
+    assert!(!state.check_staleness().await);
+    assert_eq!(CHECKPOINT_STALE_ALERTS.get(), alerts_before + 1);
+
+    state.record_update().await;
+    assert!(state.is_healthy());
+    assert!(state.check_staleness().await);
+}
+
+#[tokio::test]
+async fn no_threshold_never_goes_stale() {
+    let state = CheckpointState::new(16);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(state.check_staleness().await);
+}
@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use ultra_aggr::router::children::{submit_children, ChildFailurePolicy, ChildSubmissionPolicy};
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+fn slices(count: usize) -> Vec<LimitReq> {
+    (0..count)
+        .map(|i| LimitReq {
+            pool: "SUI_USDC".to_string(),
+            price: 1.0,
+            quantity: 2.0,
+            is_bid: true,
+            client_order_id: format!("twap-{i}"),
+            pay_with_deep: false,
+            expiration_ms: None,
+            reduce_only: false,
+            post_only: None,
+            order_type: LimitOrderType::Gtc,
+            manager: None,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Tracker {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+    started: Mutex<Vec<String>>,
+}
+
+impl Tracker {
+    async fn submit(&self, child: LimitReq, fail: Option<&str>) -> anyhow::Result<String> {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        self.started
+            .lock()
+            .unwrap()
+            .push(child.client_order_id.clone());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        if fail == Some(child.client_order_id.as_str()) {
+            anyhow::bail!("rejected");
+        }
+        Ok(child.client_order_id)
+    }
+}
+
+#[tokio::test]
+async fn cap_of_one_serializes_twap_children() {
+    let tracker = Tracker::default();
+    let policy = ChildSubmissionPolicy {
+        max_parallel: 1,
+        on_failure: ChildFailurePolicy::Abort,
+    };
+
+    let report = submit_children(slices(4), &policy, |child| tracker.submit(child, None)).await;
+
+    assert_eq!(tracker.peak.load(Ordering::SeqCst), 1);
+    assert_eq!(report.skipped, 0);
+    let ids: Vec<String> = report.submitted.into_iter().map(|r| r.unwrap()).collect();
+    assert_eq!(ids, vec!["twap-0", "twap-1", "twap-2", "twap-3"]);
+}
+
+#[tokio::test]
+async fn cap_bounds_children_in_flight() {
+    let tracker = Tracker::default();
+    let policy = ChildSubmissionPolicy {
+        max_parallel: 3,
+        on_failure: ChildFailurePolicy::Continue,
+    };
+
+    let report = submit_children(slices(9), &policy, |child| tracker.submit(child, None)).await;
+
+    assert!(tracker.peak.load(Ordering::SeqCst) <= 3);
+    assert_eq!(report.submitted.len(), 9);
+}
+
+#[tokio::test]
+async fn abort_policy_skips_remaining_children() {
+    let tracker = Tracker::default();
+    let policy = ChildSubmissionPolicy {
+        max_parallel: 1,
+        on_failure: ChildFailurePolicy::Abort,
+    };
+
+    let report = submit_children(slices(5), &policy, |child| {
+        tracker.submit(child, Some("twap-1"))
+    })
+    .await;
+
+    assert_eq!(report.submitted.len(), 2);
+    assert_eq!(report.failures(), 1);
+    assert_eq!(report.skipped, 3);
+    assert_eq!(*tracker.started.lock().unwrap(), vec!["twap-0", "twap-1"]);
+}
+
+#[tokio::test]
+async fn continue_policy_submits_every_child() {
+    let tracker = Tracker::default();
+    let policy = ChildSubmissionPolicy {
+        max_parallel: 1,
+        on_failure: ChildFailurePolicy::Continue,
+    };
+
+    let report = submit_children(slices(5), &policy, |child| {
+        tracker.submit(child, Some("twap-1"))
+    })
+    .await;
+
+    assert_eq!(report.submitted.len(), 5);
+    assert_eq!(report.failures(), 1);
+    assert_eq!(report.skipped, 0);
+}
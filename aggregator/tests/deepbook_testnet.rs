@@ -1,11 +1,17 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 
 use anyhow::Result;
 use sui_deepbookv3::utils::config::Environment;
 use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::transaction::{TransactionData, TransactionDataAPI};
 use ultra_aggr::config::{DeepBookRetrySettings, DeepBookSettings};
-use ultra_aggr::venues::adapter::DeepBookAdapter;
+use ultra_aggr::venues::adapter::{
+    DeepBookAdapter, DEFAULT_MID_PRICE_TTL, DEFAULT_POOL_PARAMS_TTL,
+};
+use ultra_aggr::venues::gas_price::DEFAULT_GAS_PRICE_TTL;
+use ultra_aggr::venues::snapshots::BookCacheSettings;
 use url::Url;
 
 fn required_env(key: &str) -> Option<String> {
@@ -14,27 +20,26 @@ fn required_env(key: &str) -> Option<String> {
         .filter(|value| !value.trim().is_empty())
 }
 
-#[tokio::test]
-#[ignore]
-async fn deepbook_testnet_adapter_smoke() -> Result<()> {
+/// Adapter against the testnet environment described by `TEST_*` variables, if set
+async fn testnet_adapter() -> Result<Option<(DeepBookAdapter, String)>> {
     let fullnode = match required_env("TEST_SUI_FULLNODE_URL") {
         Some(url) => url,
-        None => return Ok(()),
+        None => return Ok(None),
     };
 
     let indexer = match required_env("TEST_DEEPBOOK_INDEXER_URL") {
         Some(url) => url,
-        None => return Ok(()),
+        None => return Ok(None),
     };
 
     let manager_object = match required_env("TEST_DEEPBOOK_MANAGER_OBJECT") {
         Some(id) => id,
-        None => return Ok(()),
+        None => return Ok(None),
     };
 
     let sender_hex = match required_env("TEST_SUI_ADDRESS") {
         Some(addr) => addr,
-        None => return Ok(()),
+        None => return Ok(None),
     };
 
     let pool_key = required_env("TEST_DEEPBOOK_POOL_KEY").unwrap_or_else(|| "SUI_USDC".to_string());
@@ -49,16 +54,58 @@ async fn deepbook_testnet_adapter_smoke() -> Result<()> {
         environment: Environment::Testnet,
         balance_manager_object: manager_object,
         balance_manager_label: manager_label,
+        balance_manager_trade_cap: None,
         overrides: None,
-        monitored_pools: vec![pool_key],
+        monitored_pools: vec![pool_key.clone()],
+        pool_aliases: HashMap::new(),
         reconcile_interval: Duration::from_secs(60),
         indexer_timeout: Duration::from_secs(10),
         retry: DeepBookRetrySettings::default(),
         fallback_use_fullnode: true,
+        book_cache: BookCacheSettings::default(),
+        gas_price_ttl: DEFAULT_GAS_PRICE_TTL,
+        pool_params_ttl: DEFAULT_POOL_PARAMS_TTL,
+        mid_price_ttl: DEFAULT_MID_PRICE_TTL,
+        pool_params: HashMap::new(),
     };
 
     let adapter = DeepBookAdapter::new(&fullnode, sender, &settings).await?;
+    Ok(Some((adapter, pool_key)))
+}
+
+#[tokio::test]
+#[ignore]
+async fn deepbook_testnet_adapter_smoke() -> Result<()> {
+    let Some((adapter, _)) = testnet_adapter().await? else {
+        return Ok(());
+    };
     adapter.reference_gas_price().await?;
 
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn deepbook_testnet_single_cancel_ptb_compiles() -> Result<()> {
+    let Some((adapter, pool_key)) = testnet_adapter().await? else {
+        return Ok(());
+    };
+    let order_id = required_env("TEST_DEEPBOOK_ORDER_ID")
+        .map(|id| id.parse::<u128>())
+        .transpose()?
+        .unwrap_or(1);
+
+    let tx_bcs = adapter
+        .build_cancel_order_ptb_bcs(&pool_key, order_id)
+        .await?;
+    let tx: TransactionData = bcs::from_bytes(&tx_bcs)?;
+    assert_eq!(tx.sender(), adapter_sender()?);
+
+    Ok(())
+}
+
+fn adapter_sender() -> Result<SuiAddress> {
+    Ok(SuiAddress::from_str(
+        &required_env("TEST_SUI_ADDRESS").unwrap_or_default(),
+    )?)
+}
@@ -0,0 +1,50 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::venues::book::{depth_curve, sweep_for_taker};
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98, 0.96],
+        bid_quantities: vec![40.0, 60.0, 100.0],
+        ask_prices: vec![1.01, 1.02, 1.04],
+        ask_quantities: vec![40.0, 60.0, 100.0],
+    }
+}
+
+#[test]
+fn cost_increases_with_size() {
+    for is_bid in [true, false] {
+        let curve = depth_curve(&book(), is_bid, &[0.1, 0.25, 0.5, 1.0]).unwrap();
+        assert_eq!(curve.mid, 1.0);
+        assert_eq!(curve.available, 200.0);
+        assert_eq!(curve.points.len(), 4);
+        for pair in curve.points.windows(2) {
+            assert!(pair[1].quantity > pair[0].quantity);
+            assert!(pair[1].cost > pair[0].cost);
+            assert!(pair[1].slippage_bps >= pair[0].slippage_bps);
+        }
+        let full = curve.points.last().unwrap();
+        assert!((full.quantity - 200.0).abs() < 1e-9);
+        assert_eq!(full.worst_price, if is_bid { 1.04 } else { 0.96 });
+    }
+}
+
+#[test]
+fn points_match_independent_sweeps() {
+    let curve = depth_curve(&book(), true, &[1.0, 0.5, 0.1, 0.5, 1.5]).unwrap();
+    // Sorted, deduplicated and limited to (0, 1]
+    let fractions: Vec<f64> = curve.points.iter().map(|p| p.fraction).collect();
+    assert_eq!(fractions, vec![0.1, 0.5, 1.0]);
+    for point in &curve.points {
+        let sweep = sweep_for_taker(&book(), point.quantity, true).unwrap();
+        assert!((sweep.vwap - point.vwap).abs() < 1e-12);
+        assert_eq!(sweep.worst_price, point.worst_price);
+    }
+}
+
+#[test]
+fn one_sided_book_has_no_curve() {
+    let mut level2 = book();
+    level2.bid_prices.clear();
+    level2.bid_quantities.clear();
+    assert!(depth_curve(&level2, true, &[0.5]).is_none());
+}
@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ultra_aggr::router::digests::{DigestStore, FileDigestStore, MemoryDigestStore, WallClock};
+
+const TTL: Duration = Duration::from_secs(60);
+const DIGEST: &str = "B4mcSoYQj4NVmsmFy2U37ftKqfCfGxh7JHMTupkmtszw";
+const OTHER: &str = "9oGyAx6K8fB5y7x9ZtDh4QxWmKcB1cPdY3Xn2uRvLs8e";
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{name}-{}.jsonl", std::process::id()))
+}
+
+/// A clock the test advances by hand
+fn manual_clock(start_ms: u64) -> (Arc<AtomicU64>, WallClock) {
+    let now = Arc::new(AtomicU64::new(start_ms));
+    let clock = now.clone();
+    (now, Arc::new(move || clock.load(Ordering::SeqCst)))
+}
+
+#[test]
+fn memory_store_forgets_digests_after_the_ttl() {
+    let (now, clock) = manual_clock(1_000_000);
+    let store = MemoryDigestStore::new(TTL).with_clock(clock);
+    assert!(!store.contains(DIGEST));
+
+    store.insert(DIGEST).unwrap();
+    assert!(store.contains(DIGEST));
+    assert!(!store.contains(OTHER));
+
+    now.fetch_add(59_999, Ordering::SeqCst);
+    assert!(store.contains(DIGEST));
+    now.fetch_add(1, Ordering::SeqCst);
+    assert!(!store.contains(DIGEST));
+
+    // Expired entries are dropped on the next insert
+    store.insert(OTHER).unwrap();
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn file_store_remembers_digests_across_a_restart() {
+    let path = temp_path("digests-restart");
+    let _ = std::fs::remove_file(&path);
+    let (_, clock) = manual_clock(1_000_000);
+
+    let store = FileDigestStore::open_with_clock(&path, TTL, clock.clone()).unwrap();
+    store.insert(DIGEST).unwrap();
+    drop(store);
+
+    let reopened = FileDigestStore::open_with_clock(&path, TTL, clock).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(reopened.contains(DIGEST));
+    assert!(!reopened.contains(OTHER));
+    assert_eq!(reopened.len(), 1);
+}
+
+#[test]
+fn expired_digests_are_dropped_on_reopen() {
+    let path = temp_path("digests-expired");
+    let _ = std::fs::remove_file(&path);
+    let (now, clock) = manual_clock(1_000_000);
+
+    let store = FileDigestStore::open_with_clock(&path, TTL, clock.clone()).unwrap();
+    store.insert(DIGEST).unwrap();
+    now.fetch_add(30_000, Ordering::SeqCst);
+    store.insert(OTHER).unwrap();
+    drop(store);
+
+    now.fetch_add(45_000, Ordering::SeqCst);
+    let reopened = FileDigestStore::open_with_clock(&path, TTL, clock).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(!reopened.contains(DIGEST));
+    assert!(reopened.contains(OTHER));
+    assert_eq!(reopened.len(), 1);
+    assert_eq!(contents.lines().count(), 1);
+}
+
+#[test]
+fn a_line_cut_short_by_a_crash_is_skipped() {
+    let path = temp_path("digests-truncated");
+    let (_, clock) = manual_clock(1_000_000);
+    std::fs::write(
+        &path,
+        format!("{{\"digest\":\"{DIGEST}\",\"at_ms\":999000}}\n{{\"digest\":\"{OTHER}\",\"at"),
+    )
+    .unwrap();
+
+    let store = FileDigestStore::open_with_clock(&path, TTL, clock).unwrap();
+    assert!(store.contains(DIGEST));
+    assert!(!store.contains(OTHER));
+
+    // Appends after the rewrite land on their own line
+    store.insert(OTHER).unwrap();
+    drop(store);
+    let (_, clock) = manual_clock(1_000_000);
+    let reopened = FileDigestStore::open_with_clock(&path, TTL, clock).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(reopened.contains(DIGEST));
+    assert!(reopened.contains(OTHER));
+}
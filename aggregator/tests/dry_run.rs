@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use ultra_aggr::router::execution::submit_or_simulate;
+use ultra_aggr::router::router::OrderActionResponse;
+
+#[tokio::test]
+async fn dry_run_never_calls_the_submitter() {
+    let simulated = AtomicU32::new(0);
+    let submitted = AtomicU32::new(0);
+    let result = submit_or_simulate(
+        true,
+        || async {
+            simulated.fetch_add(1, Ordering::SeqCst);
+            Ok("simulated effects")
+        },
+        || async {
+            submitted.fetch_add(1, Ordering::SeqCst);
+            Ok("executed effects")
+        },
+    )
+    .await;
+    assert_eq!(result.unwrap(), "simulated effects");
+    assert_eq!(simulated.load(Ordering::SeqCst), 1);
+    assert_eq!(submitted.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn dry_run_surfaces_simulation_failures_without_submitting() {
+    let submitted = AtomicU32::new(0);
+    let result: anyhow::Result<()> = submit_or_simulate(
+        true,
+        || async { Err(anyhow::anyhow!("simulation aborted: InsufficientGas")) },
+        || async {
+            submitted.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        },
+    )
+    .await;
+    assert!(result.is_err());
+    assert_eq!(submitted.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn live_mode_submits_without_simulating() {
+    let simulated = AtomicU32::new(0);
+    let result = submit_or_simulate(
+        false,
+        || async {
+            simulated.fetch_add(1, Ordering::SeqCst);
+            Ok(0)
+        },
+        || async { Ok(1) },
+    )
+    .await;
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(simulated.load(Ordering::SeqCst), 0);
+}
+
+fn response(dry_run: bool) -> OrderActionResponse {
+    OrderActionResponse {
+        digest: "digest".to_string(),
+        effects_time_ms: 1.0,
+        checkpoint_time_ms: None,
+        accounting: None,
+        orders: Vec::new(),
+        raw_effects: None,
+        tags: None,
+        dry_run,
+        cancel_digest: None,
+        amounts: None,
+    }
+}
+
+#[test]
+fn responses_flag_dry_runs() {
+    let json = serde_json::to_value(response(true)).unwrap();
+    assert_eq!(json["dry_run"], true);
+
+    // Live responses keep their existing shape
+    let json = serde_json::to_value(response(false)).unwrap();
+    assert!(json.get("dry_run").is_none());
+}
@@ -0,0 +1,59 @@
+use ultra_aggr::state::EpochTracker;
+use ultra_aggr::transport::grpc::sui::rpc::v2::{Epoch, ProtocolConfig};
+use ultra_aggr::transport::grpc::EpochInfo;
+
+fn mock_epoch(epoch: Option<u64>, gas_price: Option<u64>, protocol: Option<u64>) -> Epoch {
+    Epoch {
+        epoch,
+        reference_gas_price: gas_price,
+        protocol_config: protocol.map(|version| ProtocolConfig {
+            protocol_version: Some(version),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn epoch_info_reads_gas_price_and_protocol_version() {
+    let info = EpochInfo::from_parts(Some(mock_epoch(Some(812), Some(750), Some(98))), None)
+        .expect("epoch info");
+
+    assert_eq!(
+        info,
+        EpochInfo {
+            epoch: 812,
+            reference_gas_price: Some(750),
+            protocol_version: Some(98),
+        }
+    );
+}
+
+#[test]
+fn epoch_number_falls_back_to_service_info() {
+    let info = EpochInfo::from_parts(Some(mock_epoch(None, Some(1_000), None)), Some(813))
+        .expect("epoch info");
+
+    assert_eq!(info.epoch, 813);
+    assert_eq!(info.reference_gas_price, Some(1_000));
+    assert_eq!(info.protocol_version, None);
+}
+
+#[test]
+fn missing_epoch_everywhere_yields_none() {
+    assert!(EpochInfo::from_parts(None, None).is_none());
+    assert!(EpochInfo::from_parts(Some(mock_epoch(None, Some(750), None)), None).is_none());
+}
+
+#[tokio::test]
+async fn tracker_reports_epoch_changes_once() {
+    let tracker = EpochTracker::new();
+    let first = EpochInfo::from_parts(Some(mock_epoch(Some(10), Some(750), None)), None).unwrap();
+    let same = EpochInfo::from_parts(Some(mock_epoch(Some(10), Some(760), None)), None).unwrap();
+    let next = EpochInfo::from_parts(Some(mock_epoch(Some(11), Some(800), None)), None).unwrap();
+
+    assert_eq!(tracker.observe(first).await, None);
+    assert_eq!(tracker.observe(same).await, None);
+    assert_eq!(tracker.observe(next).await, Some(10));
+    assert_eq!(tracker.current().await, Some(next));
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use ultra_aggr::router::execution::ExecutionTally;
+
+const WRITERS: usize = 8;
+const EXECUTIONS_PER_WRITER: u64 = 5_000;
+
+#[test]
+fn outcomes_never_exceed_attempts_under_parallel_updates() {
+    let tally = Arc::new(ExecutionTally::default());
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let tally = Arc::clone(&tally);
+        let done = Arc::clone(&done);
+        thread::spawn(move || {
+            let mut snapshots = 0u64;
+            loop {
+                let stats = tally.snapshot();
+                assert!(
+                    stats.successful_executions + stats.failed_executions <= stats.total_executions,
+                    "inconsistent snapshot: {stats:?}"
+                );
+                assert!((0.0..=1.0).contains(&stats.success_rate), "{stats:?}");
+                snapshots += 1;
+                if done.load(Ordering::Acquire) {
+                    return snapshots;
+                }
+            }
+        })
+    };
+
+    let writers: Vec<_> = (0..WRITERS)
+        .map(|writer| {
+            let tally = Arc::clone(&tally);
+            thread::spawn(move || {
+                for i in 0..EXECUTIONS_PER_WRITER {
+                    tally.record_attempt();
+                    if (i + writer as u64) % 3 == 0 {
+                        tally.record_failure();
+                    } else {
+                        tally.record_success(10.0, Some(20.0));
+                    }
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    done.store(true, Ordering::Release);
+    assert!(reader.join().unwrap() > 0);
+
+    let stats = tally.snapshot();
+    assert_eq!(
+        stats.total_executions,
+        WRITERS as u64 * EXECUTIONS_PER_WRITER
+    );
+    assert_eq!(
+        stats.successful_executions + stats.failed_executions,
+        stats.total_executions
+    );
+    assert_eq!(stats.avg_effects_time_ms, Some(10.0));
+    assert_eq!(stats.avg_checkpoint_time_ms, Some(20.0));
+}
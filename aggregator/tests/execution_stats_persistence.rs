@@ -0,0 +1,72 @@
+use ultra_aggr::router::execution::ExecutionCounters;
+
+fn earlier_run() -> ExecutionCounters {
+    ExecutionCounters {
+        total_executions: 40,
+        successful_executions: 30,
+        failed_executions: 10,
+        total_effects_time_ms: 3_000.0,
+        total_quote_fees: 1.5,
+        total_sponsor_gas: 9_000,
+        ..Default::default()
+    }
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{name}-{}.json", std::process::id()))
+}
+
+#[tokio::test]
+async fn stats_are_restored_from_a_persisted_snapshot() {
+    let path = temp_path("execution-stats");
+    earlier_run().save(&path).await.unwrap();
+
+    let restored = ExecutionCounters::load(&path).await.unwrap().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(restored, earlier_run());
+
+    // Lifetime stats continue from the snapshot
+    let this_run = ExecutionCounters {
+        total_executions: 10,
+        successful_executions: 10,
+        total_effects_time_ms: 500.0,
+        ..Default::default()
+    };
+    let lifetime = restored.merged(&this_run).snapshot();
+    assert_eq!(lifetime.total_executions, 50);
+    assert_eq!(lifetime.successful_executions, 40);
+    assert_eq!(lifetime.failed_executions, 10);
+    assert!((lifetime.success_rate - 0.8).abs() < 1e-9);
+    assert_eq!(lifetime.avg_effects_time_ms, Some(87.5));
+    assert_eq!(lifetime.total_sponsored_gas, 9_000);
+}
+
+#[tokio::test]
+async fn missing_snapshot_is_not_an_error() {
+    let path = temp_path("execution-stats-missing");
+    assert_eq!(ExecutionCounters::load(&path).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn saves_replace_the_previous_snapshot_without_leftovers() {
+    let path = temp_path("execution-stats-replace");
+    earlier_run().save(&path).await.unwrap();
+    ExecutionCounters::default().save(&path).await.unwrap();
+
+    assert_eq!(
+        ExecutionCounters::load(&path).await.unwrap(),
+        Some(ExecutionCounters::default())
+    );
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    assert!(!std::path::Path::new(&tmp).exists());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn corrupt_snapshot_is_reported() {
+    let path = temp_path("execution-stats-corrupt");
+    std::fs::write(&path, b"{\"total_executions\": ").unwrap();
+    assert!(ExecutionCounters::load(&path).await.is_err());
+    let _ = std::fs::remove_file(&path);
+}
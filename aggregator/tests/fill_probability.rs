@@ -0,0 +1,32 @@
+use ultra_aggr::router::flow::fill_probability;
+use ultra_aggr::venues::book::queue_ahead;
+
+#[test]
+fn closer_to_mid_bids_fill_more_likely() {
+    // Bids rest best-first below a mid of 1.00
+    let prices = [0.99, 0.98, 0.97, 0.96];
+    let quantities = [100.0, 200.0, 300.0, 400.0];
+    let rate_per_ms = 0.05;
+
+    let mut last = f64::INFINITY;
+    for price in [0.995, 0.985, 0.975, 0.965] {
+        let ahead = queue_ahead(&prices, &quantities, price, true);
+        let p = fill_probability(ahead, 50.0, rate_per_ms, 10_000);
+        assert!(
+            p <= last,
+            "probability rose moving away from mid: {p} > {last}"
+        );
+        assert!((0.0..=1.0).contains(&p));
+        last = p;
+    }
+}
+
+#[test]
+fn longer_horizons_and_more_flow_raise_probability() {
+    let short = fill_probability(500.0, 50.0, 0.05, 1_000);
+    let long = fill_probability(500.0, 50.0, 0.05, 60_000);
+    let busy = fill_probability(500.0, 50.0, 0.5, 1_000);
+    assert!(long > short);
+    assert!(busy > short);
+    assert_eq!(fill_probability(500.0, 50.0, 0.0, 60_000), 0.0);
+}
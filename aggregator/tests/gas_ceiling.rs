@@ -0,0 +1,26 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::validation::check_gas_price;
+
+#[test]
+fn prices_at_or_below_ceiling_pass() {
+    assert!(check_gas_price(750, 1000, false).is_ok());
+    assert!(check_gas_price(1000, 1000, false).is_ok());
+}
+
+#[test]
+fn price_above_ceiling_is_rejected_with_current_price() {
+    let err = check_gas_price(1001, 1000, false).unwrap_err();
+    assert!(matches!(
+        err,
+        AggrError::GasPriceTooHigh {
+            price: 1001,
+            ceiling: 1000
+        }
+    ));
+    assert!(err.to_string().contains("1001"));
+}
+
+#[test]
+fn override_accepts_price_above_ceiling() {
+    assert!(check_gas_price(5000, 1000, true).is_ok());
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Instant;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use ultra_aggr::signing::LocalEd25519Signer;
+use ultra_aggr::sponsorship::{
+    usable_gas_coins, AbuseConfig, GasCoinRefresh, SponsorshipManager, SponsorshipRequest,
+};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+fn coins() -> Vec<(ObjectID, u64)> {
+    vec![
+        (ObjectID::from_single_byte(1), 10_000_000),
+        (ObjectID::from_single_byte(2), 2_000_000_000),
+        (ObjectID::from_single_byte(3), 50_000_000),
+        (ObjectID::from_single_byte(4), 0),
+        (ObjectID::from_single_byte(5), 900_000_000),
+    ]
+}
+
+fn refresh(min_balance: u64, low_water: usize) -> GasCoinRefresh {
+    GasCoinRefresh {
+        min_balance,
+        low_water,
+        ..GasCoinRefresh::default()
+    }
+}
+
+fn request(user: SuiAddress) -> SponsorshipRequest {
+    SponsorshipRequest {
+        user_address: user,
+        route_plan_id: "deepbook".to_string(),
+        estimated_gas: 1_000,
+        created_at: Instant::now(),
+    }
+}
+
+#[test]
+fn coins_below_the_minimum_are_dropped_and_the_rest_ordered_largest_first() {
+    let usable = usable_gas_coins(&coins(), 50_000_000);
+
+    assert_eq!(
+        usable,
+        vec![
+            ObjectID::from_single_byte(2),
+            ObjectID::from_single_byte(5),
+            ObjectID::from_single_byte(3),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn refresh_populates_the_pool_so_sponsorship_is_possible() {
+    let sponsor = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+    let user = SuiAddress::random_for_testing_only();
+    assert!(!sponsor.can_sponsor(&request(user)).await.unwrap());
+
+    let usable = sponsor
+        .refresh_gas_coins(&coins(), &refresh(100_000_000, 2))
+        .await;
+
+    assert_eq!(usable, 2);
+    assert_eq!(
+        sponsor.gas_coin_ids().await,
+        vec![ObjectID::from_single_byte(2), ObjectID::from_single_byte(5)]
+    );
+    assert!(sponsor.can_sponsor(&request(user)).await.unwrap());
+}
+
+#[tokio::test]
+async fn spent_coins_leave_the_pool_on_the_next_refresh() {
+    let sponsor = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+    sponsor
+        .refresh_gas_coins(&coins(), &refresh(100_000_000, 2))
+        .await;
+
+    let drained = vec![(ObjectID::from_single_byte(2), 1_000)];
+    let usable = sponsor
+        .refresh_gas_coins(&drained, &refresh(100_000_000, 2))
+        .await;
+
+    assert_eq!(usable, 0);
+    assert!(sponsor.gas_coin_ids().await.is_empty());
+}
@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use ultra_aggr::venues::gas_price::GasPriceCache;
+
+#[tokio::test]
+async fn concurrent_callers_share_one_fetch() {
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+    let fetches = AtomicUsize::new(0);
+
+    let fetch = || async {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        // Slow node: every caller arrives while the first fetch is in flight
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        anyhow::Ok(750)
+    };
+    let prices = futures::future::join_all((0..32).map(|_| cache.get_or_fetch(fetch))).await;
+
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    assert!(prices.into_iter().all(|price| price.unwrap() == 750));
+
+    // Still fresh: served without touching the node
+    assert_eq!(cache.get_or_fetch(fetch).await.unwrap(), 750);
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn expired_price_is_refetched() {
+    let cache = GasPriceCache::new(Duration::from_millis(10));
+    let fetches = AtomicUsize::new(0);
+    let fetch = || async { anyhow::Ok(fetches.fetch_add(1, Ordering::SeqCst) as u64 + 1_000) };
+
+    assert_eq!(cache.get_or_fetch(fetch).await.unwrap(), 1_000);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(cache.get_or_fetch(fetch).await.unwrap(), 1_001);
+}
+
+#[tokio::test]
+async fn failed_fetch_is_not_cached() {
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+
+    let failed = cache
+        .get_or_fetch(|| async { anyhow::bail!("node unavailable") })
+        .await;
+    assert!(failed.is_err());
+    assert_eq!(cache.get_or_fetch(|| async { Ok(900) }).await.unwrap(), 900);
+}
+
+#[tokio::test]
+async fn seeded_price_is_served_until_invalidated() {
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+    cache.seed(1_200).await;
+
+    let price = cache
+        .get_or_fetch(|| async { anyhow::bail!("should not fetch") })
+        .await;
+    assert_eq!(price.unwrap(), 1_200);
+
+    cache.invalidate().await;
+    assert_eq!(
+        cache.get_or_fetch(|| async { Ok(1_300) }).await.unwrap(),
+        1_300
+    );
+}
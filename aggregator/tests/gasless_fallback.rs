@@ -0,0 +1,59 @@
+use anyhow::anyhow;
+use ultra_aggr::router::execution::sponsored_or_user_paid;
+use ultra_aggr::sponsorship::GaslessFallback;
+
+const USER_PAID: &[u8] = b"user-paid";
+
+async fn gasless_unsupported() -> anyhow::Result<Vec<u8>> {
+    Err(anyhow!("gasless PTB build unsupported"))
+}
+
+async fn user_paid() -> anyhow::Result<Vec<u8>> {
+    Ok(USER_PAID.to_vec())
+}
+
+async fn not_called() -> anyhow::Result<Vec<u8>> {
+    panic!("the user-paid build only runs after a gasless failure under the fallback")
+}
+
+#[tokio::test]
+async fn failed_gasless_build_falls_back_to_user_paid() {
+    let (tx_bcs, sponsored) =
+        sponsored_or_user_paid(GaslessFallback::UserPaid, gasless_unsupported, user_paid)
+            .await
+            .unwrap();
+
+    assert_eq!(tx_bcs, USER_PAID);
+    assert!(!sponsored);
+}
+
+#[tokio::test]
+async fn failed_gasless_build_fails_the_order_by_default() {
+    assert_eq!(GaslessFallback::default(), GaslessFallback::Fail);
+
+    let err = sponsored_or_user_paid(GaslessFallback::default(), gasless_unsupported, not_called)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("unsupported"), "{err}");
+}
+
+#[tokio::test]
+async fn successful_gasless_build_stays_sponsored() {
+    let (tx_bcs, sponsored) = sponsored_or_user_paid(
+        GaslessFallback::UserPaid,
+        || async { Ok(b"gasless".to_vec()) },
+        not_called,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(tx_bcs, b"gasless");
+    assert!(sponsored);
+}
+
+#[test]
+fn policy_parses_from_config() {
+    let policy: GaslessFallback = serde_json::from_str("\"user_paid\"").unwrap();
+    assert_eq!(policy, GaslessFallback::UserPaid);
+}
@@ -0,0 +1,58 @@
+use serde_json::json;
+use ultra_aggr::transport::graphql::{first_checkpoint_at_or_after, TransactionFilter};
+
+/// Checkpoint timestamps indexed by sequence number
+const TIMESTAMPS: [u64; 6] = [1_000, 2_000, 2_000, 3_000, 4_000, 5_000];
+
+async fn timestamp_of(seq: u64) -> anyhow::Result<Option<u64>> {
+    Ok(TIMESTAMPS.get(seq as usize).copied())
+}
+
+#[test]
+fn sender_filter_is_sent_to_the_indexer() {
+    let filter = TransactionFilter {
+        sender: Some("0xa11ce".to_string()),
+        ..TransactionFilter::default()
+    };
+
+    assert_eq!(
+        filter.to_variables(),
+        Some(json!({ "sentAddress": "0xa11ce" }))
+    );
+}
+
+#[test]
+fn checkpoint_bounds_join_the_sender_filter() {
+    let filter = TransactionFilter {
+        sender: Some("0xa11ce".to_string()),
+        after_checkpoint: Some(9),
+        before_checkpoint: Some(20),
+        ..TransactionFilter::default()
+    };
+
+    assert_eq!(
+        filter.to_variables(),
+        Some(json!({
+            "sentAddress": "0xa11ce",
+            "afterCheckpoint": 9,
+            "beforeCheckpoint": 20,
+        }))
+    );
+}
+
+#[test]
+fn empty_filter_sends_no_variable() {
+    assert_eq!(TransactionFilter::default().to_variables(), None);
+}
+
+#[tokio::test]
+async fn time_bounds_resolve_to_the_first_checkpoint_at_or_after() {
+    let latest = TIMESTAMPS.len() as u64 - 1;
+    let first = |ts| first_checkpoint_at_or_after(latest, ts, timestamp_of);
+
+    assert_eq!(first(0).await.unwrap(), Some(0));
+    assert_eq!(first(2_000).await.unwrap(), Some(1));
+    assert_eq!(first(2_500).await.unwrap(), Some(3));
+    assert_eq!(first(5_000).await.unwrap(), Some(5));
+    assert_eq!(first(5_001).await.unwrap(), None);
+}
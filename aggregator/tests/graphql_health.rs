@@ -0,0 +1,118 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use axum::http::StatusCode;
+use ultra_aggr::router::router::HealthReport;
+use ultra_aggr::transport::graphql::{
+    check_graphql_health, graphql_status, GraphQLHealth, GraphQLRpc, GraphQLStatus,
+};
+use url::Url;
+
+/// Answer one request with `status` and `body`.
+fn serve_once(status: &'static str, body: &'static str) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 8192];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    Url::parse(&format!("http://{addr}")).unwrap()
+}
+
+/// An address nothing listens on
+fn unreachable_url() -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    Url::parse(&format!("http://{addr}")).unwrap()
+}
+
+#[tokio::test]
+async fn health_check_succeeds_against_a_live_endpoint() {
+    let url = serve_once("200 OK", r#"{"data":{"checkpoint":{"sequenceNumber":42}}}"#);
+    let client = GraphQLRpc::new(url).unwrap();
+
+    client.health_check().await.unwrap();
+}
+
+#[tokio::test]
+async fn health_check_fails_on_server_errors_and_graphql_errors() {
+    let client = GraphQLRpc::new(serve_once("500 Internal Server Error", "{}")).unwrap();
+    assert!(client.health_check().await.is_err());
+
+    let client = GraphQLRpc::new(serve_once(
+        "200 OK",
+        r#"{"data":null,"errors":[{"message":"unknown field"}]}"#,
+    ))
+    .unwrap();
+    assert!(client.health_check().await.is_err());
+}
+
+#[tokio::test]
+async fn unreachable_endpoint_is_reported_as_such() {
+    let client = GraphQLRpc::new(unreachable_url()).unwrap();
+    let health = GraphQLHealth::default();
+    assert_eq!(health.status(), GraphQLStatus::Unchecked);
+
+    assert!(!check_graphql_health(&client, &health).await);
+
+    assert_eq!(health.status(), GraphQLStatus::Unreachable);
+    assert_eq!(graphql_status(Some(&health)), GraphQLStatus::Unreachable);
+}
+
+#[tokio::test]
+async fn recovery_is_recorded() {
+    let health = GraphQLHealth::default();
+    assert!(health.record(false));
+    assert!(!health.record(false), "no change while still down");
+
+    let client = GraphQLRpc::new(serve_once(
+        "200 OK",
+        r#"{"data":{"checkpoint":{"sequenceNumber":1}}}"#,
+    ))
+    .unwrap();
+    assert!(check_graphql_health(&client, &health).await);
+    assert_eq!(health.status(), GraphQLStatus::Healthy);
+}
+
+#[test]
+fn not_configured_differs_from_unreachable() {
+    assert_eq!(graphql_status(None), GraphQLStatus::NotConfigured);
+
+    let report = |graphql| HealthReport {
+        checkpoint_stream_healthy: Some(true),
+        graphql,
+    };
+    assert_eq!(
+        report(GraphQLStatus::NotConfigured).status_code(true),
+        StatusCode::OK
+    );
+    assert_eq!(
+        report(GraphQLStatus::Unreachable).status_code(false),
+        StatusCode::OK
+    );
+    assert_eq!(
+        report(GraphQLStatus::Unreachable).status_code(true),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    let json = serde_json::to_value(report(GraphQLStatus::NotConfigured)).unwrap();
+    assert_eq!(json["graphql"], "not_configured");
+}
+
+#[test]
+fn stalled_checkpoint_stream_is_unhealthy() {
+    let report = HealthReport {
+        checkpoint_stream_healthy: Some(false),
+        graphql: GraphQLStatus::Healthy,
+    };
+    assert_eq!(report.status_code(false), StatusCode::SERVICE_UNAVAILABLE);
+}
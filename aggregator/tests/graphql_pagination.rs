@@ -0,0 +1,140 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde_json::{json, Value};
+use ultra_aggr::transport::graphql::GraphQLRpc;
+use url::Url;
+
+const SENDER: &str = "0xa11ce";
+
+/// Read one HTTP request and return its JSON body
+fn read_body(stream: &mut impl Read) -> Value {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = stream.read(&mut buf).unwrap_or(0);
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length {
+                return serde_json::from_slice(&request[end + 4..end + 4 + length]).unwrap();
+            }
+        }
+    }
+    Value::Null
+}
+
+/// Serve each body in order, one per connection, recording request bodies.
+fn serve(bodies: Vec<String>) -> (Url, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+    thread::spawn(move || {
+        for body in bodies {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            seen.lock().unwrap().push(read_body(&mut stream));
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (Url::parse(&format!("http://{addr}")).unwrap(), requests)
+}
+
+fn transaction(digest: &str) -> Value {
+    json!({
+        "digest": digest,
+        "effects": null,
+        "sender": { "address": SENDER },
+        "gasInput": null,
+        "kind": null,
+    })
+}
+
+fn page(digests: &[&str], next: Option<&str>) -> String {
+    json!({
+        "data": {
+            "transactions": {
+                "nodes": digests.iter().map(|d| transaction(d)).collect::<Vec<_>>(),
+                "pageInfo": { "hasNextPage": next.is_some(), "endCursor": next },
+            }
+        }
+    })
+    .to_string()
+}
+
+fn digests(transactions: &[ultra_aggr::transport::graphql::Transaction]) -> Vec<&str> {
+    transactions.iter().map(|tx| tx.digest.as_str()).collect()
+}
+
+#[tokio::test]
+async fn address_transactions_follow_every_page() {
+    let (url, requests) = serve(vec![page(&["d1", "d2"], Some("c1")), page(&["d3"], None)]);
+    let client = GraphQLRpc::new(url).unwrap();
+
+    let transactions = client
+        .get_address_transactions(SENDER, None, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(digests(&transactions), vec!["d1", "d2", "d3"]);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0]["variables"]["filter"]["sentAddress"], SENDER);
+    assert!(requests[0]["variables"].get("after").is_none());
+    assert_eq!(requests[1]["variables"]["after"], "c1");
+    assert_eq!(requests[1]["variables"]["filter"]["sentAddress"], SENDER);
+}
+
+#[tokio::test]
+async fn pagination_stops_at_the_limit() {
+    let (url, requests) = serve(vec![
+        page(&["d1", "d2"], Some("c1")),
+        page(&["d3", "d4"], Some("c2")),
+        page(&["d5"], None),
+    ]);
+    let client = GraphQLRpc::new(url).unwrap();
+
+    let transactions = client
+        .get_address_transactions(SENDER, None, None, Some(3))
+        .await
+        .unwrap();
+
+    assert_eq!(digests(&transactions), vec!["d1", "d2", "d3"]);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn checkpoint_range_is_one_paginated_query() {
+    let (url, requests) = serve(vec![page(&["d1"], Some("c1")), page(&["d2"], None)]);
+    let client = GraphQLRpc::new(url).unwrap();
+
+    let transactions = client
+        .get_transactions_in_checkpoint_range(10, 12, None)
+        .await
+        .unwrap();
+
+    assert_eq!(digests(&transactions), vec!["d1", "d2"]);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0]["variables"]["filter"]["afterCheckpoint"], 9);
+    assert_eq!(requests[0]["variables"]["filter"]["beforeCheckpoint"], 13);
+    assert_eq!(requests[1]["variables"]["after"], "c1");
+}
@@ -0,0 +1,70 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use ultra_aggr::retry::RetryPolicy;
+use ultra_aggr::transport::graphql::GraphQLRpc;
+use url::Url;
+
+const CHECKPOINT_OK: &str = r#"{"data":{"checkpoints":{"nodes":[{"sequenceNumber":7,"digest":"abc"}],"pageInfo":{"hasNextPage":false,"endCursor":null}}}}"#;
+const QUERY_ERROR: &str = r#"{"data":null,"errors":[{"message":"Unknown field"}]}"#;
+
+/// Serve `responses` in order, one per connection, counting requests.
+fn serve(responses: Vec<(u16, &'static str)>) -> (Url, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0u8; 8192];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (Url::parse(&format!("http://{addr}")).unwrap(), hits)
+}
+
+fn fast_retry() -> RetryPolicy {
+    RetryPolicy::new(
+        Duration::from_millis(5),
+        Duration::from_millis(20),
+        Duration::from_secs(2),
+        2.0,
+    )
+}
+
+#[tokio::test]
+async fn service_unavailable_is_retried() {
+    let (url, hits) = serve(vec![(503, "{}"), (200, CHECKPOINT_OK)]);
+    let client = GraphQLRpc::new(url)
+        .unwrap()
+        .with_retry_policy(fast_retry());
+
+    let checkpoint = client.get_latest_checkpoint().await.unwrap();
+    assert_eq!(checkpoint.unwrap().sequence_number, 7);
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn graphql_error_payload_is_not_retried() {
+    let (url, hits) = serve(vec![(200, QUERY_ERROR), (200, CHECKPOINT_OK)]);
+    let client = GraphQLRpc::new(url)
+        .unwrap()
+        .with_retry_policy(fast_retry());
+
+    let err = client.get_latest_checkpoint().await.unwrap_err();
+    assert!(err.to_string().contains("Unknown field"), "{err}");
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,60 @@
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::transport::graphql::GraphQLRpc;
+use ultra_aggr::transport::http::HttpClientConfig;
+use ultra_aggr::transport::jsonrpc::JsonRpc;
+use url::Url;
+
+/// Accept connections and never answer them
+fn silent_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    thread::spawn(move || {
+        let mut held = Vec::new();
+        for stream in listener.incoming().flatten() {
+            held.push(stream);
+        }
+    });
+    format!("http://{addr}")
+}
+
+fn short_timeout() -> HttpClientConfig {
+    HttpClientConfig::default().with_timeout(Duration::from_millis(200))
+}
+
+#[tokio::test]
+async fn jsonrpc_uses_configured_timeout() {
+    let client = JsonRpc::new(silent_server())
+        .with_http_config(&short_timeout())
+        .unwrap();
+
+    let started = Instant::now();
+    let err = client.execute_tx_block(&[0u8; 4], &[]).await.unwrap_err();
+    assert!(matches!(err, AggrError::Transport(_)), "{err}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn graphql_uses_configured_timeout() {
+    let url = Url::parse(&silent_server()).unwrap();
+    let client = GraphQLRpc::new(url)
+        .unwrap()
+        .with_http_config(&short_timeout())
+        .unwrap()
+        .without_retry();
+
+    let started = Instant::now();
+    assert!(client.get_latest_checkpoint().await.is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn invalid_header_is_rejected() {
+    let config = HttpClientConfig::default().with_header("bad header", "value");
+    assert!(JsonRpc::new("http://127.0.0.1:1")
+        .with_http_config(&config)
+        .is_err());
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use sui_deepbookv3::utils::types::{Coin, Pool};
+use ultra_aggr::amounts::{format_units, CoinAmount, CoinUnits, PoolUnits};
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::venues::adapter::pool_units;
+
+fn sui() -> CoinUnits {
+    CoinUnits::new("SUI", 9)
+}
+
+fn usdc() -> CoinUnits {
+    CoinUnits::new("USDC", 6)
+}
+
+fn coin(scalar: u64) -> Coin {
+    Coin {
+        address: "0x1".to_string(),
+        type_name: "0x1::coin::COIN".to_string(),
+        scalar,
+    }
+}
+
+#[test]
+fn nine_decimal_amounts_convert_exactly() {
+    let amount = CoinAmount::parse("100.5 SUI", &sui()).unwrap();
+    assert_eq!(amount.raw, 100_500_000_000);
+    assert_eq!(amount.human, "100.5");
+    assert_eq!(amount.value(), 100.5);
+
+    let amount = CoinAmount::parse("0.000000001", &sui()).unwrap();
+    assert_eq!(amount.raw, 1);
+}
+
+#[test]
+fn six_decimal_amounts_convert_exactly() {
+    let amount = CoinAmount::parse("2.25 usdc", &usdc()).unwrap();
+    assert_eq!(amount.raw, 2_250_000);
+    assert_eq!(amount.symbol, "USDC");
+
+    assert_eq!(CoinAmount::parse("7", &usdc()).unwrap().raw, 7_000_000);
+    assert_eq!(CoinAmount::parse(".5", &usdc()).unwrap().raw, 500_000);
+    assert_eq!(
+        CoinAmount::parse("1.500000", &usdc()).unwrap().raw,
+        1_500_000
+    );
+}
+
+#[test]
+fn malformed_amounts_are_rejected() {
+    for text in [
+        "1.0000001",
+        "2.25 SUI",
+        "",
+        "-1",
+        "1e3",
+        ".",
+        "1 USDC extra",
+        "18446744073709.551616",
+    ] {
+        let err = CoinAmount::parse(text, &usdc()).unwrap_err();
+        assert!(
+            matches!(err, AggrError::InvalidAmount(_)),
+            "{text:?}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn raw_units_format_without_trailing_zeros() {
+    assert_eq!(format_units(100_500_000_000, 9), "100.5");
+    assert_eq!(format_units(1, 6), "0.000001");
+    assert_eq!(format_units(3_000_000, 6), "3");
+    assert_eq!(format_units(42, 0), "42");
+}
+
+#[test]
+fn decimals_come_from_the_coin_scalar() {
+    assert_eq!(CoinUnits::from_scalar("SUI", 1_000_000_000).unwrap(), sui());
+    assert_eq!(CoinUnits::from_scalar("X", 1).unwrap().decimals, 0);
+    assert!(CoinUnits::from_scalar("X", 0).is_err());
+    assert!(CoinUnits::from_scalar("X", 1_500).is_err());
+}
+
+#[test]
+fn pools_pick_up_their_coins_units() {
+    let coins: HashMap<&'static str, Coin> =
+        HashMap::from([("SUI", coin(1_000_000_000)), ("USDC", coin(1_000_000))]);
+    let pools: HashMap<&'static str, Pool> = HashMap::from([
+        (
+            "SUI_USDC",
+            Pool {
+                address: "0x2".to_string(),
+                base_coin: "SUI".to_string(),
+                quote_coin: "USDC".to_string(),
+            },
+        ),
+        (
+            "WAL_USDC",
+            Pool {
+                address: "0x3".to_string(),
+                base_coin: "WAL".to_string(),
+                quote_coin: "USDC".to_string(),
+            },
+        ),
+    ]);
+
+    let units = pool_units(&pools, &coins);
+    assert_eq!(units.len(), 1);
+    let sui_usdc = &units["SUI_USDC"];
+    assert_eq!(
+        sui_usdc,
+        &PoolUnits {
+            base: sui(),
+            quote: usdc()
+        }
+    );
+
+    let amounts = sui_usdc.order_amounts("2.25 USDC", "100.5 SUI").unwrap();
+    assert_eq!(amounts.price.raw, 2_250_000);
+    assert_eq!(amounts.quantity.raw, 100_500_000_000);
+    assert!(sui_usdc.order_amounts("2.25", "100.5 USDC").is_err());
+}
+
+#[test]
+fn requests_accept_human_amounts_without_numbers() {
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price_human": "2.25 USDC",
+        "quantity_human": "100.5 SUI",
+        "is_bid": true,
+        "client_order_id": "1"
+    }))
+    .unwrap();
+    assert_eq!(req.price, 0.0);
+    assert_eq!(req.price_human.as_deref(), Some("2.25 USDC"));
+    assert_eq!(req.quantity_human.as_deref(), Some("100.5 SUI"));
+}
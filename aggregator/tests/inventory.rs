@@ -0,0 +1,53 @@
+use ultra_aggr::router::inventory::{
+    apply_reduce_only, max_reduce_quantity, InventoryTracker, ReduceOnlyDecision,
+};
+
+#[test]
+fn reduce_only_limits_follow_position_sign() {
+    assert_eq!(max_reduce_quantity(10.0, false), 10.0);
+    assert_eq!(max_reduce_quantity(10.0, true), 0.0);
+    assert_eq!(max_reduce_quantity(-4.0, true), 4.0);
+    assert_eq!(max_reduce_quantity(-4.0, false), 0.0);
+    assert_eq!(max_reduce_quantity(0.0, true), 0.0);
+}
+
+#[test]
+fn reduce_only_accepts_and_rejects() {
+    assert_eq!(
+        apply_reduce_only(10.0, false, 6.0),
+        ReduceOnlyDecision::Accept(6.0)
+    );
+    assert_eq!(
+        apply_reduce_only(10.0, false, 10.0),
+        ReduceOnlyDecision::Accept(10.0)
+    );
+    // Selling 15 against a 10 long would flip to a 5 short
+    assert_eq!(
+        apply_reduce_only(10.0, false, 15.0),
+        ReduceOnlyDecision::Reject
+    );
+    assert_eq!(
+        apply_reduce_only(-4.0, true, 4.5),
+        ReduceOnlyDecision::Reject
+    );
+    assert_eq!(
+        apply_reduce_only(10.0, true, 1.0),
+        ReduceOnlyDecision::Reject
+    );
+    assert_eq!(
+        apply_reduce_only(0.0, false, 1.0),
+        ReduceOnlyDecision::Reject
+    );
+}
+
+#[tokio::test]
+async fn tracker_nets_fills_per_pool() {
+    let tracker = InventoryTracker::new();
+    tracker.record_fill("SUI_USDC", true, 5.0).await;
+    tracker.record_fill("SUI_USDC", false, 2.0).await;
+    tracker.record_fill("DEEP_SUI", false, 1.0).await;
+
+    assert_eq!(tracker.position("SUI_USDC").await, 3.0);
+    assert_eq!(tracker.position("DEEP_SUI").await, -1.0);
+    assert_eq!(tracker.position("WAL_USDC").await, 0.0);
+}
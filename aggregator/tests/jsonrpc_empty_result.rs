@@ -0,0 +1,75 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use ultra_aggr::retry::RetryPolicy;
+use ultra_aggr::transport::jsonrpc::JsonRpc;
+
+const EMPTY_RESULT: &str = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+const EXECUTED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"digest":"abc"}}"#;
+const REJECTED: &str = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32002,"message":"Transaction validator signing failed"}}"#;
+
+/// Serve `bodies` in order, one per connection, counting requests.
+fn serve(bodies: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    thread::spawn(move || {
+        for body in bodies {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0u8; 8192];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (format!("http://{addr}"), hits)
+}
+
+fn fast_retry() -> RetryPolicy {
+    RetryPolicy::new(
+        Duration::from_millis(5),
+        Duration::from_millis(20),
+        Duration::from_secs(2),
+        2.0,
+    )
+}
+
+#[tokio::test]
+async fn null_result_is_retried_until_a_result_arrives() {
+    let (url, hits) = serve(vec![EMPTY_RESULT, EXECUTED]);
+    let client = JsonRpc::new(url).with_empty_result_retry(fast_retry());
+
+    let resp = client.execute_tx_block(&[1, 2, 3], &[]).await.unwrap();
+    assert_eq!(resp.digest.as_deref(), Some("abc"));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn error_field_is_not_retried() {
+    let (url, hits) = serve(vec![REJECTED, EXECUTED]);
+    let client = JsonRpc::new(url).with_empty_result_retry(fast_retry());
+
+    let err = client.execute_tx_block(&[1, 2, 3], &[]).await.unwrap_err();
+    assert!(err.to_string().contains("signing failed"));
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn null_result_fails_immediately_without_retry() {
+    let (url, hits) = serve(vec![EMPTY_RESULT, EXECUTED]);
+    let client = JsonRpc::new(url).without_empty_result_retry();
+
+    assert!(client.execute_tx_block(&[1, 2, 3], &[]).await.is_err());
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
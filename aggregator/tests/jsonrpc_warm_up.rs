@@ -0,0 +1,102 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use ultra_aggr::transport::http::HttpClientConfig;
+use ultra_aggr::transport::jsonrpc::JsonRpc;
+
+const CHAIN_ID: &str = r#"{"jsonrpc":"2.0","id":1,"result":"4c78adac"}"#;
+const EXECUTED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"digest":"abc"}}"#;
+
+/// Keep-alive server: answers every request on a connection, recording the
+/// JSON-RPC method of each and the number of connections accepted.
+fn serve() -> (String, Arc<Mutex<Vec<String>>>, Arc<Mutex<usize>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let methods = Arc::new(Mutex::new(Vec::new()));
+    let connections = Arc::new(Mutex::new(0));
+    let (seen, accepted) = (methods.clone(), connections.clone());
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            *accepted.lock().unwrap() += 1;
+            let seen = seen.clone();
+            thread::spawn(move || {
+                let mut writer = stream.try_clone().expect("clone stream");
+                let mut reader = BufReader::new(stream);
+                loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                    }
+                    let mut body = vec![0u8; content_length];
+                    if reader.read_exact(&mut body).is_err() {
+                        return;
+                    }
+                    let request: serde_json::Value =
+                        serde_json::from_slice(&body).unwrap_or_default();
+                    let method = request["method"].as_str().unwrap_or_default().to_string();
+                    let reply = if method == "sui_getChainIdentifier" {
+                        CHAIN_ID
+                    } else {
+                        EXECUTED
+                    };
+                    seen.lock().unwrap().push(method);
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{reply}",
+                        reply.len()
+                    );
+                    if writer.write_all(response.as_bytes()).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    (format!("http://{addr}"), methods, connections)
+}
+
+#[tokio::test]
+async fn warm_up_issues_chain_identifier_request() {
+    let (url, methods, _) = serve();
+    let client = JsonRpc::new(url);
+
+    let chain_id = client.warm_up().await.unwrap();
+    assert_eq!(chain_id, "4c78adac");
+    assert_eq!(*methods.lock().unwrap(), vec!["sui_getChainIdentifier"]);
+}
+
+#[tokio::test]
+async fn first_submission_reuses_the_warmed_connection() {
+    let (url, methods, connections) = serve();
+    let client = JsonRpc::new(url)
+        .with_http_config(
+            &HttpClientConfig::default()
+                .with_pool_idle_timeout(Duration::from_secs(30))
+                .with_tcp_keepalive(Duration::from_secs(15)),
+        )
+        .unwrap();
+
+    client.warm_up().await.unwrap();
+    let resp = client.execute_tx_block(&[1, 2, 3], &[]).await.unwrap();
+    assert_eq!(resp.digest.as_deref(), Some("abc"));
+
+    assert_eq!(
+        *methods.lock().unwrap(),
+        vec!["sui_getChainIdentifier", "sui_executeTransactionBlock"]
+    );
+    assert_eq!(*connections.lock().unwrap(), 1, "connection was not reused");
+}
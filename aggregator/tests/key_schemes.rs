@@ -0,0 +1,99 @@
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use ultra_aggr::config::AppConfig;
+use ultra_aggr::router::execution::user_signature;
+use ultra_aggr::signing::{
+    address_for, secp256k1_address, sign_tx_bcs_secp256k1_to_serialized_signature,
+    sign_tx_bcs_to_serialized_signature, KeyScheme,
+};
+use ultra_aggr::transport::grpc::sui::rpc::v2::SignatureScheme;
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const TX: &[u8] = b"transaction data bytes";
+
+fn tx_digest(payload: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([0x00, 0x00, 0x00]);
+    hasher.update(payload);
+    hasher.finalize().into()
+}
+
+#[test]
+fn secp256k1_layout_is_flag_signature_compressed_key() {
+    let (serialized, public_key) = sign_tx_bcs_secp256k1_to_serialized_signature(TX, KEY).unwrap();
+    assert_eq!(serialized.len(), 1 + 64 + 33);
+    assert_eq!(serialized[0], 0x01, "secp256k1 flag");
+    assert_eq!(&serialized[65..], public_key.as_slice());
+    assert!(matches!(public_key[0], 0x02 | 0x03), "compressed SEC1 key");
+
+    let signature = Signature::from_slice(&serialized[1..65]).unwrap();
+    assert!(signature.normalize_s().is_none(), "low-s signature");
+    let key = VerifyingKey::from_sec1_bytes(&public_key).unwrap();
+    key.verify(&tx_digest(TX), &signature)
+        .expect("signature verifies over the intent digest");
+}
+
+#[test]
+fn ed25519_layout_is_unchanged() {
+    let serialized = sign_tx_bcs_to_serialized_signature(KeyScheme::Ed25519, TX, KEY).unwrap();
+    assert_eq!(serialized.len(), 1 + 64 + 32);
+    assert_eq!(serialized[0], 0x00, "Ed25519 flag");
+}
+
+#[test]
+fn scheme_selects_flag_byte() {
+    for scheme in [KeyScheme::Ed25519, KeyScheme::Secp256k1] {
+        let serialized = sign_tx_bcs_to_serialized_signature(scheme, TX, KEY).unwrap();
+        assert_eq!(serialized[0], scheme.flag());
+    }
+    assert_eq!(KeyScheme::default(), KeyScheme::Ed25519);
+}
+
+#[test]
+fn secp256k1_address_hashes_flagged_compressed_key() {
+    let (_, public_key) = sign_tx_bcs_secp256k1_to_serialized_signature(TX, KEY).unwrap();
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([0x01]);
+    hasher.update(public_key);
+    let expected: [u8; 32] = hasher.finalize().into();
+
+    let address = secp256k1_address(KEY).unwrap();
+    assert_eq!(address.as_ref(), expected.as_slice());
+    assert_eq!(address_for(KeyScheme::Secp256k1, KEY).unwrap(), address);
+    assert_ne!(address_for(KeyScheme::Ed25519, KEY).unwrap(), address);
+}
+
+#[test]
+fn config_derives_address_with_the_configured_scheme() {
+    let config: AppConfig = serde_json::from_value(serde_json::json!({
+        "grpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "jsonrpc_endpoint": "https://fullnode.testnet.sui.io:443",
+        "max_inflight": 8,
+        "ed25519_secret_hex": KEY,
+        "key_scheme": "secp256k1",
+    }))
+    .expect("config");
+    assert_eq!(config.key_scheme(), KeyScheme::Secp256k1);
+    assert_eq!(
+        config.sui_address().unwrap(),
+        secp256k1_address(KEY).unwrap()
+    );
+}
+
+#[test]
+fn grpc_submissions_carry_the_signing_scheme() {
+    let (secp256k1, _) = sign_tx_bcs_secp256k1_to_serialized_signature(TX, KEY).unwrap();
+    let submitted = user_signature(&secp256k1);
+    assert_eq!(submitted.scheme, Some(SignatureScheme::Secp256k1 as i32));
+    assert_eq!(submitted.bcs.unwrap().value, Some(secp256k1));
+
+    let ed25519 = sign_tx_bcs_to_serialized_signature(KeyScheme::Ed25519, TX, KEY).unwrap();
+    assert_eq!(
+        user_signature(&ed25519).scheme,
+        Some(SignatureScheme::Ed25519 as i32)
+    );
+
+    // Unknown flags are left for the node to derive
+    assert_eq!(user_signature(&[0x7f; 97]).scheme, None);
+}
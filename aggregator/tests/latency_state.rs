@@ -0,0 +1,42 @@
+use ultra_aggr::router::selector::LatencySnapshot;
+use ultra_aggr::router::RouteSelector;
+
+async fn trained_selector() -> RouteSelector {
+    let selector = RouteSelector::new(None, 100, 400);
+    for _ in 0..20 {
+        selector.record_latency(900.0, true).await;
+        selector.record_latency(50.0, false).await;
+    }
+    selector
+}
+
+#[tokio::test]
+async fn export_import_round_trip_restores_estimates() {
+    let trained = trained_selector().await;
+    let snapshot = trained.export_state().await;
+    assert_ne!(trained.get_latency_estimates(), (100, 400));
+
+    let restored = RouteSelector::new(None, 100, 400);
+    restored.import_state(snapshot.clone()).await;
+
+    assert_eq!(
+        restored.get_latency_estimates(),
+        trained.get_latency_estimates()
+    );
+    assert_eq!(restored.export_state().await, snapshot);
+}
+
+#[tokio::test]
+async fn state_file_survives_restart() {
+    let path = std::env::temp_dir().join(format!("latency-state-{}.json", std::process::id()));
+    let trained = trained_selector().await;
+    trained.save_state(&path).await.unwrap();
+
+    let restored = RouteSelector::new(None, 100, 400);
+    assert!(restored.load_state(&path).await.unwrap());
+    let _ = std::fs::remove_file(&path);
+
+    let expected: LatencySnapshot = trained.export_state().await;
+    assert_eq!(restored.export_state().await, expected);
+    assert!(!restored.load_state(&path).await.unwrap());
+}
@@ -0,0 +1,69 @@
+use serde_json::{json, Value};
+use ultra_aggr::router::execution::{attribute_deepbook_fills, VenueEvent};
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::venues::adapter::LimitReq;
+
+fn leg(pool: &str, client_order_id: &str) -> LimitReq {
+    let req: LimitOrderRequest = serde_json::from_value(json!({
+        "pool": pool,
+        "price": 1.0,
+        "quantity": 100.0,
+        "is_bid": true,
+        "client_order_id": client_order_id,
+    }))
+    .unwrap();
+    LimitReq::from(req)
+}
+
+fn event<'a>(name: &'a str, fields: &'a Value) -> VenueEvent<'a> {
+    VenueEvent {
+        module: "clob_v2",
+        name,
+        fields,
+    }
+}
+
+#[test]
+fn two_leg_effects_are_attributed_per_leg() {
+    let sui = leg("SUI_USDC", "11");
+    let deep = leg("DEEP_USDC", "12");
+    let payload = [
+        json!({"poolKey": "SUI_USDC", "clientOrderId": "11", "baseFilled": "40", "quoteFilled": "50", "takerFee": "0.05"}),
+        json!({"poolKey": "DEEP_USDC", "clientOrderId": "12", "baseFilled": 10, "price": "0.2", "takerFee": 0.002}),
+        json!({"poolKey": "sui_usdc", "baseFilled": "60", "quoteFilled": "78", "takerFee": "0.078"}),
+        json!({"poolKey": "SUI_USDC", "orderId": "7"}),
+    ];
+    let events = [
+        event("OrderFilled", &payload[0]),
+        event("OrderFilledV2", &payload[1]),
+        event("OrderFilled", &payload[2]),
+        event("OrderPlaced", &payload[3]),
+    ];
+
+    let legs = attribute_deepbook_fills(&[&sui, &deep], &events);
+    assert_eq!(legs.len(), 2);
+
+    assert_eq!((legs[0].leg, legs[0].pool.as_str()), (0, "SUI_USDC"));
+    assert_eq!(legs[0].venue, "deepbook");
+    assert_eq!(legs[0].filled_quantity, 100.0);
+    assert!((legs[0].avg_price.unwrap() - 1.28).abs() < 1e-9);
+    assert!((legs[0].fee - 0.128).abs() < 1e-9);
+
+    assert_eq!((legs[1].leg, legs[1].pool.as_str()), (1, "DEEP_USDC"));
+    assert_eq!(legs[1].filled_quantity, 10.0);
+    assert!((legs[1].avg_price.unwrap() - 0.2).abs() < 1e-9);
+    assert!((legs[1].fee - 0.002).abs() < 1e-9);
+}
+
+#[test]
+fn unfilled_legs_are_reported_empty() {
+    let sui = leg("SUI_USDC", "11");
+    let deep = leg("DEEP_USDC", "12");
+    let fill = json!({"poolKey": "SUI_USDC", "baseFilled": 5, "quoteFilled": 6});
+    let legs = attribute_deepbook_fills(&[&sui, &deep], &[event("OrderFilled", &fill)]);
+
+    assert_eq!(legs[0].filled_quantity, 5.0);
+    assert_eq!(legs[1].filled_quantity, 0.0);
+    assert_eq!(legs[1].avg_price, None);
+    assert_eq!(legs[1].fee, 0.0);
+}
@@ -0,0 +1,96 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::maintenance::{MaintenanceSchedule, MaintenanceWindow, Recurrence};
+
+const HOUR_MS: u64 = 3_600_000;
+const DAY_MS: u64 = 24 * HOUR_MS;
+/// 2025-11-03 00:00 UTC, a Monday
+const MONDAY: u64 = 1_762_128_000_000;
+
+#[test]
+fn order_inside_a_window_is_rejected_and_outside_proceeds() {
+    let schedule: MaintenanceSchedule = "1762135200000-1762142400000".parse().unwrap();
+
+    // 02:30 on the day is inside 02:00-04:00
+    let err = schedule.ensure_open(MONDAY + 5 * HOUR_MS / 2).unwrap_err();
+    assert!(matches!(err, AggrError::Maintenance { until_ms } if until_ms == MONDAY + 4 * HOUR_MS));
+
+    assert!(schedule.ensure_open(MONDAY + HOUR_MS).is_ok());
+    assert!(
+        schedule.ensure_open(MONDAY + 4 * HOUR_MS).is_ok(),
+        "end is exclusive"
+    );
+}
+
+#[test]
+fn daily_windows_recur_and_may_cross_midnight() {
+    let schedule: MaintenanceSchedule = "daily 23:30-00:30".parse().unwrap();
+
+    for day in [0, 1, 40] {
+        let midnight = MONDAY + day * DAY_MS;
+        assert_eq!(
+            schedule.active_until(midnight - HOUR_MS / 4),
+            Some(midnight + HOUR_MS / 2)
+        );
+        assert_eq!(
+            schedule.active_until(midnight + HOUR_MS / 4),
+            Some(midnight + HOUR_MS / 2)
+        );
+        assert_eq!(schedule.active_until(midnight + HOUR_MS), None);
+    }
+}
+
+#[test]
+fn weekly_windows_apply_on_their_day_only() {
+    let schedule: MaintenanceSchedule = "weekly wed 02:00-03:00; weekly sun 23:00-01:00"
+        .parse()
+        .unwrap();
+
+    let wednesday = MONDAY + 2 * DAY_MS;
+    assert_eq!(
+        schedule.active_until(wednesday + 2 * HOUR_MS),
+        Some(wednesday + 3 * HOUR_MS)
+    );
+    assert_eq!(schedule.active_until(MONDAY + 2 * HOUR_MS), None);
+    assert_eq!(
+        schedule.active_until(wednesday + 7 * DAY_MS + 2 * HOUR_MS + 1),
+        Some(wednesday + 7 * DAY_MS + 3 * HOUR_MS)
+    );
+
+    // Sunday night wraps into the next Monday
+    let next_monday = MONDAY + 7 * DAY_MS;
+    assert_eq!(
+        schedule.active_until(next_monday - HOUR_MS / 2),
+        Some(next_monday + HOUR_MS)
+    );
+    assert_eq!(
+        schedule.active_until(next_monday + HOUR_MS / 2),
+        Some(next_monday + HOUR_MS)
+    );
+    assert_eq!(schedule.active_until(next_monday + 2 * HOUR_MS), None);
+}
+
+#[test]
+fn parses_and_rejects_window_specs() {
+    let window: MaintenanceWindow = "daily 03:00-03:30".parse().unwrap();
+    assert_eq!(window.recurrence, Recurrence::Daily);
+    assert_eq!(window.to_string(), "daily 03:00-03:30");
+    assert_eq!(
+        "weekly Sunday 22:00-02:00"
+            .parse::<MaintenanceWindow>()
+            .unwrap()
+            .to_string(),
+        "weekly sun 22:00-02:00"
+    );
+
+    for bad in [
+        "daily 03:00",
+        "daily 25:00-26:00",
+        "daily 03:00-03:00",
+        "weekly someday 01:00-02:00",
+        "200-100",
+        "monthly 01:00-02:00",
+    ] {
+        assert!(bad.parse::<MaintenanceWindow>().is_err(), "{bad}");
+    }
+    assert!(MaintenanceSchedule::default().ensure_open(MONDAY).is_ok());
+}
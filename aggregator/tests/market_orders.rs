@@ -0,0 +1,101 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::routes::Route;
+use ultra_aggr::router::RoutePlan;
+use ultra_aggr::venues::adapter::MarketReq;
+use ultra_aggr::venues::book::{estimate_slippage, slippage_bps};
+
+const TICK: f64 = 0.01;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98, 0.97],
+        bid_quantities: vec![50.0, 100.0, 200.0],
+        ask_prices: vec![1.01, 1.02, 1.03],
+        ask_quantities: vec![50.0, 100.0, 200.0],
+    }
+}
+
+fn market(is_bid: bool, max_slippage_bps: Option<f64>) -> MarketReq {
+    MarketReq {
+        pool: "SUI_USDC".to_string(),
+        quantity: 100.0,
+        is_bid,
+        client_order_id: "42".to_string(),
+        pay_with_deep: false,
+        max_slippage_bps,
+        manager: None,
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn slippage_is_measured_from_mid() {
+    // 50 @ 1.01 + 50 @ 1.02 against a mid of 1.00
+    let estimate = estimate_slippage(&book(), 1.0, 100.0, true, TICK, 0.01);
+    assert!(close(
+        slippage_bps(1.0, estimate.avg_fill_price, true),
+        150.0
+    ));
+
+    let estimate = estimate_slippage(&book(), 1.0, 100.0, false, TICK, 0.01);
+    assert!(close(
+        slippage_bps(1.0, estimate.avg_fill_price, false),
+        150.0
+    ));
+}
+
+#[test]
+fn fills_better_than_mid_count_as_zero() {
+    assert_eq!(slippage_bps(1.0, 0.99, true), 0.0);
+    assert_eq!(slippage_bps(1.0, 1.01, false), 0.0);
+    assert_eq!(slippage_bps(0.0, 1.01, true), 0.0);
+}
+
+#[test]
+fn order_beyond_its_bound_is_rejected() {
+    let err = market(true, Some(100.0)).check_slippage(150.0).unwrap_err();
+    match err {
+        AggrError::SlippageExceeded {
+            expected_bps,
+            limit_bps,
+        } => {
+            assert_eq!(expected_bps, 150.0);
+            assert_eq!(limit_bps, 100.0);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    assert!(market(true, Some(150.0)).check_slippage(150.0).is_ok());
+    assert!(market(true, None).check_slippage(10_000.0).is_ok());
+}
+
+#[test]
+fn place_params_carry_the_order() {
+    let params = market(false, None)
+        .place_params("desk".to_string(), 100.0)
+        .unwrap();
+    assert_eq!(params.pool_key, "SUI_USDC");
+    assert_eq!(params.balance_manager_key, "desk");
+    assert_eq!(params.client_order_id, 42);
+    assert_eq!(params.quantity, 100.0);
+    assert!(!params.is_bid);
+    assert_eq!(params.pay_with_deep, Some(false));
+
+    let mut bad = market(true, None);
+    bad.client_order_id = "not-a-number".to_string();
+    assert!(bad.place_params("desk".to_string(), 100.0).is_err());
+}
+
+#[test]
+fn market_plan_targets_deepbook() {
+    let plan = RoutePlan::market_order(market(true, Some(50.0)), 1.0, 1.5, 0.01, 400, 250, 0.001)
+        .with_expected_fill_price(1.015);
+    assert_eq!(plan.route.venue(), "deepbook");
+    assert_eq!(plan.route.pool(), Some("SUI_USDC"));
+    assert_eq!(plan.expected_fill_price, Some(1.015));
+    assert!(matches!(plan.route, Route::MarketOrder(_)));
+}
@@ -0,0 +1,59 @@
+use axum::{routing::get, Router};
+use ultra_aggr::metrics::{record_execution_stats, record_latency_estimates, REQ_LATENCY};
+use ultra_aggr::router::execution::ExecutionCounters;
+use ultra_aggr::router::router::metrics_endpoint;
+
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test server");
+    let addr = listener.local_addr().expect("test server address");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn metrics_expose_request_latency_and_execution_stats() {
+    REQ_LATENCY
+        .with_label_values(&["http", "order"])
+        .observe(0.01);
+    let stats = ExecutionCounters {
+        total_executions: 4,
+        successful_executions: 3,
+        failed_executions: 1,
+        ..ExecutionCounters::default()
+    }
+    .snapshot();
+    record_execution_stats(&stats);
+    record_latency_estimates(100, 400);
+    let base = serve(Router::new().route("/metrics", get(metrics_endpoint))).await;
+
+    let response = reqwest::get(format!("{base}/metrics")).await.unwrap();
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let content_type = response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(content_type.starts_with("text/plain"), "{content_type}");
+    let body = response.text().await.unwrap();
+    assert!(
+        body.contains("# TYPE aggr_request_latency_seconds histogram"),
+        "{body}"
+    );
+    assert!(
+        body.contains("aggr_executions{outcome=\"total\"} 4"),
+        "{body}"
+    );
+    assert!(
+        body.contains("aggr_executions{outcome=\"failed\"} 1"),
+        "{body}"
+    );
+    assert!(body.contains("aggr_execution_success_rate 0.75"), "{body}");
+    assert!(
+        body.contains("aggr_latency_estimate_ms{objects=\"shared\"} 400"),
+        "{body}"
+    );
+}
@@ -0,0 +1,18 @@
+use axum::http::StatusCode;
+use ultra_aggr::router::ApiSettings;
+
+#[test]
+fn observer_mode_disables_execution() {
+    let settings = ApiSettings {
+        observer_mode: true,
+        ..ApiSettings::default()
+    };
+    let (status, body) = settings.ensure_execution_enabled().unwrap_err();
+    assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+    assert_eq!(body.code, "OBSERVER_MODE");
+}
+
+#[test]
+fn execution_enabled_by_default() {
+    assert!(ApiSettings::default().ensure_execution_enabled().is_ok());
+}
@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+use ultra_aggr::control::OrderCeiling;
+use ultra_aggr::errors::AggrError;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[tokio::test]
+async fn orders_over_the_ceiling_are_shed_until_the_window_rolls() {
+    let ceiling = OrderCeiling::new(3, WINDOW);
+    let start = Instant::now();
+
+    for i in 0..3 {
+        ceiling
+            .try_admit_at(start + Duration::from_secs(i))
+            .await
+            .unwrap();
+    }
+
+    let shed = ceiling
+        .try_admit_at(start + Duration::from_secs(10))
+        .await
+        .unwrap_err();
+    match shed {
+        AggrError::Overloaded {
+            limit,
+            window_ms,
+            retry_after_ms,
+        } => {
+            assert_eq!(limit, 3);
+            assert_eq!(window_ms, 60_000);
+            // The oldest order leaves the window 50s later
+            assert_eq!(retry_after_ms, 50_000);
+        }
+        other => panic!("unexpected error {other:?}"),
+    }
+    assert!(ceiling
+        .try_admit_at(start + Duration::from_secs(59))
+        .await
+        .is_err());
+
+    // The first order has rolled out; one slot frees up
+    ceiling
+        .try_admit_at(start + Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(ceiling
+        .try_admit_at(start + Duration::from_millis(60_500))
+        .await
+        .is_err());
+    ceiling
+        .try_admit_at(start + Duration::from_secs(61))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn shed_orders_do_not_take_a_slot() {
+    let ceiling = OrderCeiling::new(1, WINDOW);
+    let start = Instant::now();
+
+    ceiling.try_admit_at(start).await.unwrap();
+    for i in 1..10 {
+        assert!(ceiling
+            .try_admit_at(start + Duration::from_secs(i))
+            .await
+            .is_err());
+    }
+
+    ceiling.try_admit_at(start + WINDOW).await.unwrap();
+}
+
+#[tokio::test]
+async fn ceiling_rolls_in_real_time() {
+    let ceiling = OrderCeiling::new(2, Duration::from_millis(50));
+
+    ceiling.try_admit().await.unwrap();
+    ceiling.try_admit().await.unwrap();
+    assert!(matches!(
+        ceiling.try_admit().await,
+        Err(AggrError::Overloaded { .. })
+    ));
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    ceiling.try_admit().await.unwrap();
+}
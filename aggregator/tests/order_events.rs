@@ -0,0 +1,132 @@
+use serde_json::{json, Value};
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::venues::order_events::{select_order_id, OrderEvent};
+use ultra_aggr::venues::pools::PoolNormalizer;
+
+const SUI_USDC: &str = "0xe05dafb5133bcffb8d59f4e12465dc0e9faeaa05e3e342a08fe135800e3e4407";
+const DEEP_SUI: &str = "0xb663828d6217467c8a1838a03793da896cbe745b150ebd57d82f814ca579fc22";
+const ASK_ORDER_ID: u128 = 170141183460533057466182338764475478074;
+
+/// `parsed_json` of an `order_info::OrderPlaced` event as the fullnode
+/// returns it: u64/u128 fields are decimal strings, ids are hex strings
+fn order_placed(pool_id: &str, order_id: u128, client_order_id: u64) -> Value {
+    json!({
+        "balance_manager_id": "0x5b2f5b8ac1f4b03c1e1b93e6ec0d0b8f0c57c33b0e5e1c33b31e0d1c3f0a6d41",
+        "pool_id": pool_id,
+        "order_id": order_id.to_string(),
+        "client_order_id": client_order_id.to_string(),
+        "trader": "0x8b3c2f1e5a0f0fbc30a6ef1ad6f9b7e3b7a9d36e6b3e3f0c1a2b4c5d6e7f8091",
+        "price": "3460000",
+        "is_bid": false,
+        "placed_quantity": "10000000000",
+        "expire_timestamp": "1844674407370955161",
+        "timestamp": "1731324000000"
+    })
+}
+
+fn normalizer() -> PoolNormalizer {
+    PoolNormalizer::from_pools([("SUI_USDC", SUI_USDC), ("DEEP_SUI", DEEP_SUI)])
+}
+
+fn in_pool<'a>(pools: &'a PoolNormalizer, pool: &'a str) -> impl Fn(&str) -> bool + 'a {
+    move |event_pool| pools.resolve(event_pool).is_ok_and(|key| key == pool)
+}
+
+#[test]
+fn placed_event_payload_yields_the_u128_order_id() {
+    let event = OrderEvent::parse(
+        "order_info",
+        "OrderPlaced",
+        &order_placed(SUI_USDC, ASK_ORDER_ID, 42),
+    )
+    .unwrap();
+    assert_eq!(event.order_id, ASK_ORDER_ID);
+    assert_eq!(event.client_order_id, Some(42));
+    assert_eq!(event.pool.as_deref(), Some(SUI_USDC));
+
+    let pools = normalizer();
+    assert_eq!(
+        select_order_id(&[event], in_pool(&pools, "SUI_USDC"), None).unwrap(),
+        Some(ASK_ORDER_ID)
+    );
+}
+
+#[test]
+fn modified_events_count_and_repeat_ids_collapse() {
+    let payload = order_placed(SUI_USDC, ASK_ORDER_ID, 42);
+    let placed = OrderEvent::parse("order_info", "OrderPlaced", &payload).unwrap();
+    let modified = OrderEvent::parse("order", "OrderModified", &payload).unwrap();
+    assert_eq!(placed, modified);
+
+    let pools = normalizer();
+    assert_eq!(
+        select_order_id(&[placed, modified], in_pool(&pools, "SUI_USDC"), None).unwrap(),
+        Some(ASK_ORDER_ID)
+    );
+}
+
+#[test]
+fn other_event_types_are_ignored() {
+    let payload = order_placed(SUI_USDC, ASK_ORDER_ID, 42);
+    assert!(OrderEvent::parse("order_info", "OrderFilled", &payload).is_none());
+    assert!(OrderEvent::parse("balance_manager", "BalanceEvent", &payload).is_none());
+    assert!(
+        OrderEvent::parse("order_info", "OrderPlaced", &json!({ "pool_id": SUI_USDC })).is_none()
+    );
+}
+
+#[test]
+fn client_order_id_picks_among_several_orders() {
+    let events: Vec<OrderEvent> = [(ASK_ORDER_ID, 7), (ASK_ORDER_ID + 1, 8)]
+        .into_iter()
+        .map(|(order_id, client_order_id)| {
+            OrderEvent::parse(
+                "order_info",
+                "OrderPlaced",
+                &order_placed(SUI_USDC, order_id, client_order_id),
+            )
+            .unwrap()
+        })
+        .collect();
+    let pools = normalizer();
+
+    assert_eq!(
+        select_order_id(&events, in_pool(&pools, "SUI_USDC"), Some(8)).unwrap(),
+        Some(ASK_ORDER_ID + 1)
+    );
+    assert!(matches!(
+        select_order_id(&events, in_pool(&pools, "SUI_USDC"), None),
+        Err(AggrError::AmbiguousOrder(2))
+    ));
+    assert_eq!(
+        select_order_id(&events, in_pool(&pools, "SUI_USDC"), Some(9)).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn orders_in_other_pools_are_absent() {
+    let sui_usdc = OrderEvent::parse(
+        "order_info",
+        "OrderPlaced",
+        &order_placed(SUI_USDC, ASK_ORDER_ID, 1),
+    )
+    .unwrap();
+    let deep_sui =
+        OrderEvent::parse("order_info", "OrderPlaced", &order_placed(DEEP_SUI, 99, 1)).unwrap();
+    let pools = normalizer();
+
+    assert_eq!(
+        select_order_id(
+            &[sui_usdc.clone(), deep_sui.clone()],
+            in_pool(&pools, "DEEP_SUI"),
+            Some(1)
+        )
+        .unwrap(),
+        Some(99)
+    );
+    assert_eq!(
+        select_order_id(&[sui_usdc], in_pool(&pools, "DEEP_SUI"), None).unwrap(),
+        None
+    );
+}
@@ -0,0 +1,49 @@
+use ultra_aggr::router::router::OrderActionResponse;
+use ultra_aggr::router::tags::{format_tags, validate_tags, OrderTags, MAX_TAGS};
+
+fn sample_tags() -> OrderTags {
+    OrderTags::from([
+        ("strategy".to_string(), "mm-7".to_string()),
+        ("account".to_string(), "desk-a".to_string()),
+    ])
+}
+
+#[test]
+fn tags_round_trip_through_response() {
+    let response = OrderActionResponse {
+        digest: "digest".to_string(),
+        effects_time_ms: 1.0,
+        checkpoint_time_ms: None,
+        accounting: None,
+        orders: Vec::new(),
+        raw_effects: None,
+        tags: Some(sample_tags()),
+        dry_run: false,
+        cancel_digest: None,
+        amounts: None,
+    };
+    let json = serde_json::to_value(&response).unwrap();
+    let echoed: OrderTags = serde_json::from_value(json["tags"].clone()).unwrap();
+    assert_eq!(echoed, sample_tags());
+}
+
+#[test]
+fn log_record_lists_tags_in_key_order() {
+    assert_eq!(format_tags(&sample_tags()), "account=desk-a,strategy=mm-7");
+}
+
+#[test]
+fn tag_bounds_are_enforced() {
+    assert!(validate_tags(&sample_tags()).is_ok());
+
+    let too_many: OrderTags = (0..=MAX_TAGS)
+        .map(|i| (format!("k{i}"), "v".to_string()))
+        .collect();
+    assert!(validate_tags(&too_many).is_err());
+
+    let long_value = OrderTags::from([("k".to_string(), "v".repeat(257))]);
+    assert!(validate_tags(&long_value).is_err());
+
+    let empty_key = OrderTags::from([(" ".to_string(), "v".to_string())]);
+    assert!(validate_tags(&empty_key).is_err());
+}
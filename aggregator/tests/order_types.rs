@@ -0,0 +1,71 @@
+use sui_deepbookv3::utils::types::OrderType;
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+use ultra_aggr::venues::book::PostOnlyFallback;
+
+fn order(order_type: Option<&str>) -> LimitReq {
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.0,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": "1",
+        "order_type": order_type,
+    }))
+    .unwrap();
+    LimitReq::from(req)
+}
+
+#[test]
+fn api_names_parse() {
+    assert_eq!("GTC".parse::<LimitOrderType>(), Ok(LimitOrderType::Gtc));
+    assert_eq!("IOC".parse::<LimitOrderType>(), Ok(LimitOrderType::Ioc));
+    assert_eq!("FOK".parse::<LimitOrderType>(), Ok(LimitOrderType::Fok));
+    assert_eq!(
+        "PostOnly".parse::<LimitOrderType>(),
+        Ok(LimitOrderType::PostOnly)
+    );
+
+    let err = "GTD".parse::<LimitOrderType>().unwrap_err();
+    assert!(err.contains("GTD"), "{err}");
+}
+
+#[test]
+fn order_types_map_to_deepbook_restrictions() {
+    assert!(matches!(
+        order(None).sdk_order_type(),
+        OrderType::NoRestriction
+    ));
+    assert!(matches!(
+        order(Some("GTC")).sdk_order_type(),
+        OrderType::NoRestriction
+    ));
+    assert!(matches!(
+        order(Some("IOC")).sdk_order_type(),
+        OrderType::ImmediateOrCancel
+    ));
+    assert!(matches!(
+        order(Some("FOK")).sdk_order_type(),
+        OrderType::FillOrKill
+    ));
+}
+
+#[test]
+fn post_only_type_goes_through_the_crossing_check() {
+    let req = order(Some("PostOnly"));
+    // A crossing post-only order is rejected before it is built
+    assert_eq!(req.post_only, Some(PostOnlyFallback::Reject));
+    assert!(matches!(req.sdk_order_type(), OrderType::PostOnly));
+}
+
+#[test]
+fn place_params_carry_the_order_type() {
+    let req = order(Some("IOC"));
+    let params = req
+        .place_params("MANAGER_1".to_string(), req.price, req.quantity)
+        .unwrap();
+    assert!(matches!(
+        params.order_type,
+        Some(OrderType::ImmediateOrCancel)
+    ));
+}
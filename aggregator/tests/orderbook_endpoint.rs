@@ -0,0 +1,62 @@
+use serde_json::json;
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::quant::Precision;
+use ultra_aggr::router::router::OrderBookResponse;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98],
+        bid_quantities: vec![40.0, 60.5],
+        ask_prices: vec![1.01, 1.03],
+        ask_quantities: vec![25.0, 80.0],
+    }
+}
+
+#[test]
+fn serializes_price_and_quantity_arrays_with_mid() {
+    let response = OrderBookResponse::from_level2("SUI_USDC", 20, &book());
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({
+            "pool": "SUI_USDC",
+            "ticks": 20,
+            "mid_price": 1.0,
+            "bid_prices": [0.99, 0.98],
+            "bid_quantities": [40.0, 60.5],
+            "ask_prices": [1.01, 1.03],
+            "ask_quantities": [25.0, 80.0],
+        })
+    );
+}
+
+#[test]
+fn one_sided_book_has_no_mid() {
+    let mut level2 = book();
+    level2.ask_prices.clear();
+    level2.ask_quantities.clear();
+    let response = OrderBookResponse::from_level2("SUI_USDC", 5, &level2);
+    assert_eq!(response.mid_price, None);
+    let value = serde_json::to_value(&response).unwrap();
+    assert!(value["mid_price"].is_null());
+    assert_eq!(value["ask_prices"], json!([]));
+}
+
+#[test]
+fn rounding_applies_tick_and_lot_precision() {
+    let level2 = Level2TicksFromMid {
+        bid_prices: vec![0.98765],
+        bid_quantities: vec![12.345],
+        ask_prices: vec![1.01234],
+        ask_quantities: vec![7.891],
+    };
+    let precision = Precision {
+        price_decimals: 3,
+        size_decimals: 1,
+    };
+    let response = OrderBookResponse::from_level2("SUI_USDC", 1, &level2).rounded(&precision);
+    assert_eq!(response.bid_prices, vec![0.988]);
+    assert_eq!(response.ask_prices, vec![1.012]);
+    assert_eq!(response.bid_quantities, vec![12.3]);
+    assert_eq!(response.ask_quantities, vec![7.9]);
+    assert_eq!(response.mid_price, Some(1.0));
+}
@@ -0,0 +1,26 @@
+use tracing_subscriber::layer::SubscriberExt;
+use ultra_aggr::telemetry::{otlp_endpoint, otlp_layer, OTLP_ENDPOINT_ENV};
+
+// One test only: the endpoint is read from process-wide environment. Multi-threaded
+// so shutting down the batch exporter cannot block the runtime it runs on.
+#[tokio::test(flavor = "multi_thread")]
+async fn otlp_layer_initializes_when_endpoint_is_configured() {
+    std::env::remove_var(OTLP_ENDPOINT_ENV);
+    assert_eq!(otlp_endpoint(), None);
+    std::env::set_var(OTLP_ENDPOINT_ENV, "  ");
+    assert_eq!(otlp_endpoint(), None);
+
+    std::env::set_var(OTLP_ENDPOINT_ENV, "http://127.0.0.1:4317");
+    let endpoint = otlp_endpoint().expect("endpoint configured");
+    assert_eq!(endpoint, "http://127.0.0.1:4317");
+
+    // The exporter connects lazily, so no collector needs to be listening
+    let layer = otlp_layer(&endpoint).expect("OTLP layer initializes");
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("route_compute", pool = "SUI_USDC");
+        let _entered = span.enter();
+        tracing::info!("inside exported span");
+    });
+    ultra_aggr::telemetry::shutdown();
+}
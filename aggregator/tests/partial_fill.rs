@@ -0,0 +1,116 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::router::RoutePlan;
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+use ultra_aggr::venues::book::{
+    estimate_slippage, expected_fill_fraction, partial_fill_cost, DEFAULT_IMPACT_COEFFICIENT,
+    DEFAULT_PARTIAL_FILL_PENALTY,
+};
+
+const TICK: f64 = 0.01;
+const LIMIT: f64 = 1.10;
+const QUANTITY: f64 = 500.0;
+
+fn asks(prices: Vec<f64>, quantities: Vec<f64>) -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.95],
+        bid_quantities: vec![1_000.0],
+        ask_prices: prices,
+        ask_quantities: quantities,
+    }
+}
+
+/// Best ask 1.00 but only 50 behind it
+fn shallow() -> Level2TicksFromMid {
+    asks(vec![1.00], vec![50.0])
+}
+
+/// Best ask 1.01 with the whole order's size behind it
+fn deep() -> Level2TicksFromMid {
+    asks(vec![1.01, 1.02], vec![400.0, 600.0])
+}
+
+fn buy(pool: &str) -> LimitReq {
+    LimitReq {
+        pool: pool.to_string(),
+        price: LIMIT,
+        quantity: QUANTITY,
+        is_bid: true,
+        client_order_id: "1".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    }
+}
+
+/// Score a venue's book the way the selector scores a DeepBook route
+fn plan(pool: &str, level2: &Level2TicksFromMid, penalty: f64) -> RoutePlan {
+    let estimate = estimate_slippage(
+        level2,
+        LIMIT,
+        QUANTITY,
+        true,
+        TICK,
+        DEFAULT_IMPACT_COEFFICIENT,
+    );
+    let fraction = expected_fill_fraction(level2, LIMIT, QUANTITY, true);
+    let unfilled = partial_fill_cost(LIMIT, QUANTITY, fraction, penalty);
+    RoutePlan::deepbook_single(
+        buy(pool),
+        LIMIT,
+        estimate.slippage + unfilled,
+        0.01,
+        400,
+        100,
+        0.0,
+    )
+}
+
+fn best(mut plans: Vec<RoutePlan>) -> String {
+    plans.sort_by(|a, b| a.compare(b));
+    plans[0].route.pool().unwrap().to_string()
+}
+
+#[test]
+fn fill_fraction_counts_depth_inside_the_limit() {
+    assert_eq!(
+        expected_fill_fraction(&shallow(), LIMIT, QUANTITY, true),
+        0.1
+    );
+    assert_eq!(expected_fill_fraction(&deep(), LIMIT, QUANTITY, true), 1.0);
+    // Only the 1.01 level is inside a 1.015 limit
+    assert_eq!(expected_fill_fraction(&deep(), 1.015, QUANTITY, true), 0.8);
+    // Sells take the bids
+    assert_eq!(expected_fill_fraction(&deep(), 0.95, 500.0, false), 1.0);
+}
+
+#[test]
+fn orders_that_do_not_cross_are_not_penalized() {
+    let fraction = expected_fill_fraction(&deep(), 0.99, QUANTITY, true);
+    assert_eq!(fraction, 0.0);
+    assert_eq!(partial_fill_cost(0.99, QUANTITY, fraction, 0.005), 0.0);
+}
+
+#[test]
+fn unfilled_remainder_costs_its_share_of_notional() {
+    // 90% of 500 @ 1.10 left over, at 0.5%
+    let cost = partial_fill_cost(LIMIT, QUANTITY, 0.1, 0.005);
+    assert!((cost - 2.475).abs() < 1e-9, "{cost}");
+    assert_eq!(partial_fill_cost(LIMIT, QUANTITY, 1.0, 0.005), 0.0);
+}
+
+#[test]
+fn shallow_venue_loses_to_a_deep_one_despite_a_better_top() {
+    // Both books fill inside the limit as modeled, so without the partial
+    // fill penalty the routes tie and the shallow venue stays first
+    let unweighted = vec![plan("SHALLOW", &shallow(), 0.0), plan("DEEP", &deep(), 0.0)];
+    assert_eq!(best(unweighted), "SHALLOW");
+
+    let weighted = vec![
+        plan("SHALLOW", &shallow(), DEFAULT_PARTIAL_FILL_PENALTY),
+        plan("DEEP", &deep(), DEFAULT_PARTIAL_FILL_PENALTY),
+    ];
+    assert_eq!(best(weighted), "DEEP");
+}
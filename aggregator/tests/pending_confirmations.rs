@@ -0,0 +1,44 @@
+use ultra_aggr::state::PendingConfirmations;
+
+#[tokio::test]
+async fn one_checkpoint_resolves_every_included_digest() {
+    let pending = PendingConfirmations::new();
+    let a = pending.register("digest-a").await;
+    let b = pending.register("digest-b").await;
+    let c = pending.register("digest-c").await;
+
+    let included = [("digest-a", 42), ("unrelated", 42), ("digest-b", 42)];
+    assert_eq!(pending.resolve_batch(included).await, 2);
+
+    assert_eq!(a.await.unwrap(), 42);
+    assert_eq!(b.await.unwrap(), 42);
+    assert_eq!(pending.len().await, 1);
+
+    assert_eq!(pending.resolve_batch([("digest-c", 43)]).await, 1);
+    assert_eq!(c.await.unwrap(), 43);
+    assert!(pending.is_empty().await);
+}
+
+#[tokio::test]
+async fn checkpoint_arriving_before_the_response_is_delivered() {
+    let pending = PendingConfirmations::new();
+    // Registered before submission
+    let confirmation = pending.register("digest-a").await;
+
+    // The including checkpoint streams in while the submission is in flight
+    assert_eq!(pending.resolve_batch([("digest-a", 7)]).await, 1);
+
+    // The execution response arrives without checkpoint info
+    assert_eq!(confirmation.await.unwrap(), 7);
+    assert!(pending.is_empty().await);
+}
+
+#[tokio::test]
+async fn failed_submissions_stop_waiting() {
+    let pending = PendingConfirmations::new();
+    let confirmation = pending.register("digest-a").await;
+    pending.forget("digest-a").await;
+
+    assert!(pending.is_empty().await);
+    assert!(confirmation.await.is_err());
+}
@@ -0,0 +1,48 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::venues::pools::PoolNormalizer;
+
+const SUI_USDC_ID: &str = "0xe05dafb5133bcffb8d59f4e12465dc0e9faeaa05e3e342a08fe135800e3e4407";
+
+fn normalizer() -> PoolNormalizer {
+    PoolNormalizer::from_pools([("SUI_USDC", SUI_USDC_ID), ("DEEP_SUI", "0xb663")])
+        .with_alias("sui", "SUI_USDC")
+        .unwrap()
+}
+
+#[test]
+fn canonical_key_and_case_variants() {
+    let pools = normalizer();
+    for input in ["SUI_USDC", "sui_usdc", " Sui_Usdc ", "sui-usdc", "SUI/USDC"] {
+        assert_eq!(pools.resolve(input).unwrap(), "SUI_USDC", "{input}");
+    }
+}
+
+#[test]
+fn alias_resolves_to_canonical_key() {
+    assert_eq!(normalizer().resolve("SUI").unwrap(), "SUI_USDC");
+}
+
+#[test]
+fn object_ids_resolve_regardless_of_case_or_padding() {
+    let pools = normalizer();
+    assert_eq!(pools.resolve(SUI_USDC_ID).unwrap(), "SUI_USDC");
+    assert_eq!(
+        pools
+            .resolve(&SUI_USDC_ID.to_ascii_uppercase().replacen("0X", "0x", 1))
+            .unwrap(),
+        "SUI_USDC"
+    );
+    assert_eq!(
+        pools
+            .resolve("0x000000000000000000000000000000000000000000000000000000000000b663")
+            .unwrap(),
+        "DEEP_SUI"
+    );
+}
+
+#[test]
+fn unknown_pools_and_aliases_are_rejected() {
+    let pools = normalizer();
+    assert!(matches!(pools.resolve("BTC_USDC"), Err(AggrError::UnknownPool(p)) if p == "BTC_USDC"));
+    assert!(pools.clone().with_alias("btc", "BTC_USDC").is_err());
+}
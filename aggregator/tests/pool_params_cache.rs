@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ultra_aggr::venues::cache::{Clock, TimedCache};
+
+/// Clock that only moves when the test advances it
+struct ManualClock(Mutex<Instant>);
+
+impl ManualClock {
+    fn new() -> Arc<Self> {
+        Arc::new(Self(Mutex::new(Instant::now())))
+    }
+
+    fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn served_from_cache_within_ttl() {
+    let clock = ManualClock::new();
+    let cache = TimedCache::new(Duration::from_secs(300), "pool_params").with_clock(clock.clone());
+    let fetches = AtomicUsize::new(0);
+    let fetch = || async { anyhow::Ok(fetches.fetch_add(1, Ordering::SeqCst) as u64) };
+
+    assert_eq!(
+        cache
+            .get_or_try_insert_with("SUI_USDC", fetch)
+            .await
+            .unwrap(),
+        0
+    );
+    clock.advance(Duration::from_secs(299));
+    assert_eq!(
+        cache
+            .get_or_try_insert_with("SUI_USDC", fetch)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+    // Other pools are keyed separately
+    assert_eq!(
+        cache
+            .get_or_try_insert_with("DEEP_SUI", fetch)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn refetched_after_expiry() {
+    let clock = ManualClock::new();
+    let cache = TimedCache::new(Duration::from_millis(250), "mid_price").with_clock(clock.clone());
+    let fetches = AtomicUsize::new(0);
+    let fetch = || async { anyhow::Ok(fetches.fetch_add(1, Ordering::SeqCst) as f64 + 1.5) };
+
+    assert_eq!(
+        cache
+            .get_or_try_insert_with("SUI_USDC", fetch)
+            .await
+            .unwrap(),
+        1.5
+    );
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(
+        cache
+            .get_or_try_insert_with("SUI_USDC", fetch)
+            .await
+            .unwrap(),
+        2.5
+    );
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn invalidated_pool_is_refetched() {
+    let cache = TimedCache::new(Duration::from_secs(300), "pool_params");
+
+    cache
+        .get_or_try_insert_with("SUI_USDC", || async { anyhow::Ok(1) })
+        .await
+        .unwrap();
+    cache.invalidate("SUI_USDC").await;
+    let value = cache
+        .get_or_try_insert_with("SUI_USDC", || async { anyhow::Ok(2) })
+        .await;
+    assert_eq!(value.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn zero_ttl_disables_caching() {
+    let cache = TimedCache::new(Duration::ZERO, "mid_price");
+    let fetches = AtomicUsize::new(0);
+    let fetch = || async { anyhow::Ok(fetches.fetch_add(1, Ordering::SeqCst)) };
+
+    cache
+        .get_or_try_insert_with("SUI_USDC", fetch)
+        .await
+        .unwrap();
+    cache
+        .get_or_try_insert_with("SUI_USDC", fetch)
+        .await
+        .unwrap();
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    assert!(cache.is_empty().await);
+}
+
+#[tokio::test]
+async fn full_cache_evicts_soonest_expiry() {
+    let clock = ManualClock::new();
+    let cache = TimedCache::new(Duration::from_secs(60), "pool_params")
+        .with_capacity(2)
+        .with_clock(clock.clone());
+
+    for (pool, value) in [("A", 1), ("B", 2)] {
+        cache
+            .get_or_try_insert_with(pool, || async { anyhow::Ok(value) })
+            .await
+            .unwrap();
+        clock.advance(Duration::from_secs(1));
+    }
+    cache
+        .get_or_try_insert_with("C", || async { anyhow::Ok(3) })
+        .await
+        .unwrap();
+    assert_eq!(cache.len().await, 2);
+
+    // "A" was loaded first, so it was the one dropped
+    let a = cache
+        .get_or_try_insert_with("A", || async { anyhow::Ok(10) })
+        .await;
+    assert_eq!(a.unwrap(), 10);
+}
@@ -0,0 +1,69 @@
+use ultra_aggr::quant::{quantize_price, resolve_pool_params, PoolParams};
+
+fn params(tick_size: f64, lot_size: f64, min_size: f64) -> PoolParams {
+    PoolParams {
+        tick_size,
+        lot_size,
+        min_size,
+    }
+}
+
+#[test]
+fn valid_params_pass_through() {
+    let fetched = params(0.001, 0.1, 1.0);
+    let override_params = params(0.01, 1.0, 10.0);
+
+    let resolved = resolve_pool_params("SUI_USDC", fetched, Some(&override_params)).unwrap();
+
+    assert_eq!(resolved.tick_size, 0.001);
+    assert_eq!(resolved.lot_size, 0.1);
+    assert_eq!(resolved.min_size, 1.0);
+}
+
+#[test]
+fn zero_tick_size_without_override_is_a_clear_error() {
+    let err = resolve_pool_params("SUI_USDC", params(0.0, 0.1, 1.0), None).unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("SUI_USDC"), "{message}");
+    assert!(message.contains("tick size 0"), "{message}");
+    assert!(message.contains("override"), "{message}");
+}
+
+#[test]
+fn zero_tick_size_falls_back_to_the_override() {
+    let override_params = params(0.01, 1.0, 10.0);
+
+    let resolved =
+        resolve_pool_params("SUI_USDC", params(0.0, 0.1, 1.0), Some(&override_params)).unwrap();
+
+    assert_eq!(resolved.tick_size, 0.01);
+    assert_eq!(resolved.lot_size, 1.0);
+    assert_eq!(resolved.min_size, 10.0);
+    let price = quantize_price(1.2345, resolved.tick_size).unwrap();
+    assert!((price - 1.23).abs() < 1e-9, "{price}");
+}
+
+#[test]
+fn nan_and_negative_params_are_rejected() {
+    for bad in [
+        params(f64::NAN, 0.1, 1.0),
+        params(0.001, -0.1, 1.0),
+        params(0.001, 0.1, f64::INFINITY),
+    ] {
+        assert!(bad.validate().is_err(), "{bad:?}");
+        assert!(resolve_pool_params("SUI_USDC", bad, None).is_err());
+    }
+}
+
+#[test]
+fn invalid_override_is_reported_as_such() {
+    let override_params = params(0.01, 0.0, 10.0);
+
+    let err =
+        resolve_pool_params("SUI_USDC", params(0.0, 0.1, 1.0), Some(&override_params)).unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("override"), "{message}");
+    assert!(message.contains("lot size"), "{message}");
+}
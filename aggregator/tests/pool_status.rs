@@ -0,0 +1,48 @@
+use serde_json::json;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::validation::check_pool_status;
+use ultra_aggr::venues::pools::PoolStatus;
+
+#[test]
+fn paused_pool_rejects_orders_while_active_pool_proceeds() {
+    let paused = PoolStatus::from_fields(&json!({ "status": "paused" })).unwrap();
+    let active = PoolStatus::from_fields(&json!({ "status": "ACTIVE" })).unwrap();
+
+    let err = check_pool_status("DEEP_SUI", paused).unwrap_err();
+    assert!(matches!(
+        &err,
+        AggrError::PoolNotTradable { pool, status } if pool == "DEEP_SUI" && status == "paused"
+    ));
+    assert!(check_pool_status("SUI_USDC", active).is_ok());
+}
+
+#[test]
+fn not_launched_pools_are_rejected() {
+    let pending = PoolStatus::from_fields(&json!({ "status": "not-launched" })).unwrap();
+    assert_eq!(pending, PoolStatus::NotLaunched);
+    let err = check_pool_status("NEW_USDC", pending).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "pool NEW_USDC is not launched and not accepting orders"
+    );
+}
+
+#[test]
+fn status_flags_are_understood() {
+    assert_eq!(
+        PoolStatus::from_fields(&json!({ "paused": true, "launched": true })),
+        Some(PoolStatus::Paused)
+    );
+    assert_eq!(
+        PoolStatus::from_fields(&json!({ "paused": false })),
+        Some(PoolStatus::Active)
+    );
+    assert_eq!(
+        PoolStatus::from_fields(&json!({ "launched": false })),
+        Some(PoolStatus::NotLaunched)
+    );
+    assert_eq!(
+        PoolStatus::from_fields(&json!({ "tick_size": 0.001 })),
+        None
+    );
+}
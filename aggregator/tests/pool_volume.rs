@@ -0,0 +1,147 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Value};
+use ultra_aggr::transport::graphql::{aggregate_pool_volume, Event, GraphQLRpc};
+use url::Url;
+
+const POOL: &str = "0xpool";
+
+fn event(type_repr: &str, ts: u64, fields: Value) -> Value {
+    json!({
+        "id": null,
+        "transactionDigest": null,
+        "sender": null,
+        "timestampMs": ts,
+        "type": { "repr": type_repr },
+        "json": fields,
+        "bcs": null,
+    })
+}
+
+fn fill(ts: u64, pool: &str, base: f64, quote: f64) -> Value {
+    event(
+        "0xdee9::clob_v2::OrderFilled",
+        ts,
+        json!({ "pool_id": pool, "baseFilled": base, "quoteFilled": quote }),
+    )
+}
+
+fn decode(events: Vec<Value>) -> Vec<Event> {
+    serde_json::from_value(Value::Array(events)).unwrap()
+}
+
+fn same_pool(event_pool: &str) -> bool {
+    event_pool.eq_ignore_ascii_case(POOL)
+}
+
+#[test]
+fn aggregates_fills_inside_the_window() {
+    let events = decode(vec![
+        fill(999, POOL, 100.0, 100.0),
+        fill(1_000, POOL, 10.0, 20.0),
+        fill(1_500, "0xPOOL", 30.0, 63.0),
+        // Priced by limit price rather than quote filled
+        event(
+            "0xdee9::clob_v2::OrderFilled<0x2::sui::SUI, 0xusdc::usdc::USDC>",
+            1_700,
+            json!({ "pool_id": POOL, "baseFilled": 5.0, "price": 2.2 }),
+        ),
+        fill(1_800, "0xother", 50.0, 50.0),
+        event(
+            "0xdee9::clob_v2::OrderPlaced",
+            1_900,
+            json!({ "pool_id": POOL, "baseFilled": 7.0, "quoteFilled": 7.0 }),
+        ),
+        fill(2_000, POOL, 100.0, 100.0),
+    ]);
+
+    let volume = aggregate_pool_volume(POOL, 1_000, 2_000, &events, same_pool);
+
+    let base = 10.0 + 30.0 + 5.0;
+    let quote = 20.0 + 63.0 + 5.0 * 2.2;
+    assert_eq!(volume.fills, 3);
+    assert!((volume.base_volume - base).abs() < 1e-9);
+    assert!((volume.quote_volume - quote).abs() < 1e-9);
+    let vwap = volume.vwap.expect("priced fills give a vwap");
+    assert!((vwap - quote / base).abs() < 1e-9, "{vwap}");
+}
+
+#[test]
+fn empty_window_has_zero_volume_and_no_vwap() {
+    let events = decode(vec![fill(5_000, POOL, 10.0, 20.0)]);
+
+    let volume = aggregate_pool_volume(POOL, 1_000, 2_000, &events, same_pool);
+
+    assert_eq!(volume.fills, 0);
+    assert_eq!(volume.base_volume, 0.0);
+    assert_eq!(volume.quote_volume, 0.0);
+    assert_eq!(volume.vwap, None);
+}
+
+/// Serve each body in order, one per connection, counting requests.
+fn serve(bodies: Vec<String>) -> (Url, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    thread::spawn(move || {
+        for body in bodies {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0u8; 8192];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (Url::parse(&format!("http://{addr}")).unwrap(), hits)
+}
+
+fn page(nodes: Vec<Value>, next: Option<&str>) -> String {
+    json!({
+        "data": {
+            "events": {
+                "nodes": nodes,
+                "pageInfo": { "hasNextPage": next.is_some(), "endCursor": next },
+            }
+        }
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn pages_until_the_window_ends() {
+    let (url, hits) = serve(vec![
+        page(vec![fill(1_100, POOL, 4.0, 8.0)], Some("c1")),
+        page(
+            vec![fill(1_200, POOL, 6.0, 15.0), fill(2_100, POOL, 9.0, 9.0)],
+            Some("c2"),
+        ),
+        page(vec![fill(1_300, POOL, 1.0, 1.0)], None),
+    ]);
+    let client = GraphQLRpc::new(url).unwrap();
+
+    let volume = client.get_pool_volume(POOL, 1_000, 2_000).await.unwrap();
+
+    assert_eq!(hits.load(Ordering::SeqCst), 2, "stops once past end_ms");
+    assert_eq!(volume.fills, 2);
+    assert!((volume.base_volume - 10.0).abs() < 1e-9);
+    assert!((volume.quote_volume - 23.0).abs() < 1e-9);
+    assert!((volume.vwap.unwrap() - 2.3).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn rejects_an_empty_range() {
+    let client = GraphQLRpc::new(Url::parse("http://127.0.0.1:9").unwrap()).unwrap();
+
+    assert!(client.get_pool_volume(POOL, 2_000, 2_000).await.is_err());
+}
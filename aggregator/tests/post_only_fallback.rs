@@ -0,0 +1,115 @@
+use sui_deepbookv3::utils::types::OrderType;
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::venues::adapter::LimitReq;
+use ultra_aggr::venues::book::{apply_post_only, PostOnlyDecision, PostOnlyFallback};
+
+const TICK: f64 = 0.01;
+
+#[test]
+fn non_crossing_orders_rest_under_every_policy() {
+    for fallback in [
+        PostOnlyFallback::Reject,
+        PostOnlyFallback::Reprice,
+        PostOnlyFallback::Limit,
+    ] {
+        assert_eq!(
+            apply_post_only(fallback, 0.99, true, Some(1.00), TICK),
+            PostOnlyDecision::Rest
+        );
+        assert_eq!(
+            apply_post_only(fallback, 1.01, false, Some(1.00), TICK),
+            PostOnlyDecision::Rest
+        );
+        // An empty opposite side cannot be crossed
+        assert_eq!(
+            apply_post_only(fallback, 5.0, true, None, TICK),
+            PostOnlyDecision::Rest
+        );
+    }
+}
+
+#[test]
+fn reject_refuses_crossing_orders() {
+    assert_eq!(
+        apply_post_only(PostOnlyFallback::Reject, 1.02, true, Some(1.00), TICK),
+        PostOnlyDecision::Reject { best: 1.00 }
+    );
+    assert_eq!(
+        apply_post_only(PostOnlyFallback::Reject, 1.00, false, Some(1.00), TICK),
+        PostOnlyDecision::Reject { best: 1.00 }
+    );
+}
+
+#[test]
+fn reprice_moves_crossing_orders_one_tick_inside() {
+    let PostOnlyDecision::Reprice(bid) =
+        apply_post_only(PostOnlyFallback::Reprice, 1.05, true, Some(1.00), TICK)
+    else {
+        panic!("crossing bid should be repriced");
+    };
+    assert!((bid - 0.99).abs() < 1e-9);
+
+    let PostOnlyDecision::Reprice(ask) =
+        apply_post_only(PostOnlyFallback::Reprice, 0.95, false, Some(1.00), TICK)
+    else {
+        panic!("crossing ask should be repriced");
+    };
+    assert!((ask - 1.01).abs() < 1e-9);
+
+    // No valid maker price below a one-tick best ask
+    assert_eq!(
+        apply_post_only(PostOnlyFallback::Reprice, 0.05, true, Some(TICK), TICK),
+        PostOnlyDecision::Reject { best: TICK }
+    );
+}
+
+#[test]
+fn limit_downgrades_crossing_orders() {
+    assert_eq!(
+        apply_post_only(PostOnlyFallback::Limit, 1.02, true, Some(1.00), TICK),
+        PostOnlyDecision::Downgrade
+    );
+}
+
+#[test]
+fn request_defaults_to_reject_and_sets_order_type() {
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.0,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": "1",
+        "post_only": true
+    }))
+    .unwrap();
+    let order = LimitReq::from(req);
+    assert_eq!(order.post_only, Some(PostOnlyFallback::Reject));
+    assert!(matches!(order.sdk_order_type(), OrderType::PostOnly));
+
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.0,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": "2",
+        "post_only": true,
+        "post_only_fallback": "reprice"
+    }))
+    .unwrap();
+    assert_eq!(
+        LimitReq::from(req).post_only,
+        Some(PostOnlyFallback::Reprice)
+    );
+
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.0,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": "3"
+    }))
+    .unwrap();
+    let order = LimitReq::from(req);
+    assert_eq!(order.post_only, None);
+    assert!(matches!(order.sdk_order_type(), OrderType::NoRestriction));
+}
@@ -0,0 +1,58 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use anyhow::anyhow;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::execution::is_permanent_submit_error;
+use ultra_aggr::transport::jsonrpc::JsonRpc;
+
+const DRY_RUN_OK: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"effects":{"status":{"status":"success"},"gasUsed":{"computationCost":"1000000","storageCost":"2964000","storageRebate":"978120","nonRefundableStorageFee":"9880"}}}}"#;
+const DRY_RUN_ABORTED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"effects":{"status":{"status":"failure","error":"InsufficientCoinBalance in command 0"},"gasUsed":{"computationCost":"1000000","storageCost":"0","storageRebate":"0"}}}}"#;
+
+/// Serve one JSON-RPC response body
+fn serve(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    thread::spawn(move || {
+        let Ok((mut stream, _)) = listener.accept() else {
+            return;
+        };
+        let mut buf = [0u8; 8192];
+        let _ = stream.read(&mut buf);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = stream.write_all(response.as_bytes());
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn successful_dry_run_reports_gas_before_rebate() {
+    let client = JsonRpc::new(serve(DRY_RUN_OK));
+
+    let resp = client.dry_run_tx_block(&[1, 2, 3]).await.unwrap();
+    assert_eq!(resp.failure(), None);
+    assert_eq!(resp.gas_cost(), Some(3_964_000));
+}
+
+#[tokio::test]
+async fn aborted_dry_run_reports_its_error() {
+    let client = JsonRpc::new(serve(DRY_RUN_ABORTED));
+
+    let resp = client.dry_run_tx_block(&[1, 2, 3]).await.unwrap();
+    assert_eq!(
+        resp.failure().as_deref(),
+        Some("InsufficientCoinBalance in command 0")
+    );
+}
+
+#[test]
+fn rejected_simulation_is_not_retried() {
+    let err = anyhow!(AggrError::SimulationRejected(
+        "InsufficientCoinBalance in command 0".to_string()
+    ));
+    assert!(is_permanent_submit_error(&err));
+}
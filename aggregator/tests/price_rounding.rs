@@ -0,0 +1,67 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::quant::{quantize_price, quantize_price_for_side, QuantConstraint};
+
+const TICK: f64 = 0.01;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+#[test]
+fn bids_floor_and_asks_ceil_between_ticks() {
+    let bid = quantize_price_for_side(1.234, TICK, true).unwrap();
+    let ask = quantize_price_for_side(1.234, TICK, false).unwrap();
+
+    assert!(close(bid, 1.23), "{bid}");
+    assert!(close(ask, 1.24), "{ask}");
+}
+
+#[test]
+fn prices_on_a_tick_are_unchanged_for_both_sides() {
+    // 0.3 / 0.1 is 2.9999999999999996 in floating point
+    for (price, tick) in [(1.23, TICK), (0.3, 0.1), (0.7, 0.1), (5.0, 0.5)] {
+        let bid = quantize_price_for_side(price, tick, true).unwrap();
+        let ask = quantize_price_for_side(price, tick, false).unwrap();
+        assert!(close(bid, price), "bid {price} -> {bid}");
+        assert!(close(ask, price), "ask {price} -> {ask}");
+    }
+}
+
+#[test]
+fn price_a_hair_under_one_tick_rounds_to_one_tick() {
+    let price = 0.1 - 1e-12;
+
+    assert!(close(
+        quantize_price_for_side(price, 0.1, true).unwrap(),
+        0.1
+    ));
+    assert!(close(
+        quantize_price_for_side(price, 0.1, false).unwrap(),
+        0.1
+    ));
+}
+
+#[test]
+fn asks_below_one_tick_round_up_while_bids_are_rejected() {
+    let ask = quantize_price_for_side(0.0005, 0.001, false).unwrap();
+    assert!(close(ask, 0.001), "{ask}");
+
+    let err = quantize_price_for_side(0.0005, 0.001, true).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<AggrError>(),
+        Some(AggrError::Quantization {
+            constraint: QuantConstraint::TickSize,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn floor_only_variant_matches_the_bid_side() {
+    for price in [1.234, 0.3, 2.0, 99.999] {
+        assert_eq!(
+            quantize_price(price, TICK).unwrap(),
+            quantize_price_for_side(price, TICK, true).unwrap()
+        );
+    }
+}
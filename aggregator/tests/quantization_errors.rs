@@ -0,0 +1,62 @@
+use axum::http::StatusCode;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::quant::{quantize_price, quantize_size, QuantConstraint};
+use ultra_aggr::router::router::order_error;
+use ultra_aggr::router::validation::ValidationResult;
+
+fn assert_client_error(err: anyhow::Error, code: &str, value: f64, limit: f64) {
+    let (status, body) = order_error("ORDER_ERROR", err);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body.code, code);
+    let details = body.details.as_ref().expect("quantization details");
+    assert_eq!(details["value"], value);
+    assert_eq!(details["limit"], limit);
+}
+
+#[test]
+fn price_below_one_tick_is_a_client_error() {
+    let err = quantize_price(0.0005, 0.001).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<AggrError>(),
+        Some(AggrError::Quantization {
+            constraint: QuantConstraint::TickSize,
+            ..
+        })
+    ));
+    assert_client_error(err, "PRICE_BELOW_TICK", 0.0005, 0.001);
+}
+
+#[test]
+fn size_below_minimum_is_a_client_error() {
+    let err = quantize_size(0.5, 0.1, 1.0).unwrap_err();
+    assert_eq!(err.to_string(), "0.5 is below the pool's minimum size of 1");
+    assert_client_error(err, "SIZE_BELOW_MINIMUM", 0.5, 1.0);
+}
+
+#[test]
+fn size_below_one_lot_is_a_client_error() {
+    let err = quantize_size(1.5, 2.0, 1.0).unwrap_err();
+    assert_client_error(err, "SIZE_BELOW_LOT", 1.5, 2.0);
+}
+
+#[test]
+fn quantization_failures_survive_pre_trade_validation() {
+    let mut validation = ValidationResult::new();
+    validation.add_quantization_error("price", quantize_price(0.0005, 0.001).unwrap_err());
+    validation.add_quantization_error("size", quantize_size(0.5, 0.1, 1.0).unwrap_err());
+    assert_eq!(validation.errors.len(), 2);
+
+    let err = validation
+        .into_result()
+        .unwrap_err()
+        .context("pre-trade validation failed");
+    assert_client_error(err, "PRICE_BELOW_TICK", 0.0005, 0.001);
+}
+
+#[test]
+fn bad_pool_parameters_remain_server_errors() {
+    let err = quantize_price(1.0, 0.0).unwrap_err();
+    let (status, body) = order_error("ORDER_ERROR", err);
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body.code, "ORDER_ERROR");
+}
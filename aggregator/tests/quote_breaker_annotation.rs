@@ -0,0 +1,54 @@
+use ultra_aggr::control::{BreakerClass, BreakerScope, CircuitBreakers};
+use ultra_aggr::router::router::{Availability, RoutePlanResponse};
+use ultra_aggr::router::routes::RoutePlan;
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+// Enough failures to fill the minimum sample window and trip a breaker
+const TRIPPING_FAILURES: usize = 20;
+
+fn plan(pool: &str) -> RoutePlan {
+    let req = LimitReq {
+        pool: pool.to_string(),
+        price: 1.0,
+        quantity: 10.0,
+        is_bid: true,
+        client_order_id: "1".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    };
+    RoutePlan::deepbook_single(req, 1.0, 0.0, 0.0, 250, 250, 0.0)
+}
+
+#[tokio::test]
+async fn open_breaker_marks_its_alternative_unavailable() {
+    let breakers = CircuitBreakers::new().with_scope(BreakerScope::Pool);
+    let failing = BreakerClass::new("deepbook", Some("DEEP_SUI"));
+    for _ in 0..TRIPPING_FAILURES {
+        breakers.record_failure_for(&failing).await;
+    }
+
+    let healthy = RoutePlanResponse::from_plan(&plan("SUI_USDC"), Some(&breakers)).await;
+    let tripped = RoutePlanResponse::from_plan(&plan("DEEP_SUI"), Some(&breakers)).await;
+
+    assert!(!healthy.breaker_open);
+    assert_eq!(healthy.availability, Availability::Available);
+    assert!(tripped.breaker_open);
+    assert_eq!(tripped.availability, Availability::Unavailable);
+    // The quote itself is still returned
+    assert_eq!(tripped.l2_price, 1.0);
+
+    let json = serde_json::to_value(&tripped).unwrap();
+    assert_eq!(json["breaker_open"], true);
+    assert_eq!(json["availability"], "unavailable");
+}
+
+#[tokio::test]
+async fn routes_are_available_without_breakers() {
+    let response = RoutePlanResponse::from_plan(&plan("SUI_USDC"), None).await;
+    assert!(!response.breaker_open);
+    assert_eq!(response.availability, Availability::Available);
+}
@@ -0,0 +1,93 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::quant::Precision;
+use ultra_aggr::router::router::RoutePlanResponse;
+use ultra_aggr::router::routes::RoutePlan;
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+use ultra_aggr::venues::book::{estimate_slippage, take_levels};
+
+const TICK: f64 = 0.01;
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98, 0.97],
+        bid_quantities: vec![50.0, 100.0, 200.0],
+        ask_prices: vec![1.01, 1.02, 1.03],
+        ask_quantities: vec![50.0, 100.0, 200.0],
+    }
+}
+
+fn total(fills: &[(f64, f64)]) -> f64 {
+    fills.iter().map(|(_, quantity)| quantity).sum()
+}
+
+#[test]
+fn fills_sum_to_the_requested_quantity() {
+    let estimate = estimate_slippage(&book(), 1.0, 120.0, true, TICK, 0.01);
+    assert_eq!(estimate.fills, vec![(1.01, 50.0), (1.02, 70.0)]);
+    assert_eq!(total(&estimate.fills), 120.0);
+
+    let estimate = estimate_slippage(&book(), 1.0, 160.0, false, TICK, 0.01);
+    assert_eq!(
+        estimate.fills,
+        vec![(0.99, 50.0), (0.98, 100.0), (0.97, 10.0)]
+    );
+    assert_eq!(total(&estimate.fills), 160.0);
+}
+
+#[test]
+fn fills_stop_at_visible_depth() {
+    let estimate = estimate_slippage(&book(), 1.0, 1_000.0, true, TICK, 0.01);
+    assert_eq!(total(&estimate.fills), 350.0);
+    assert_eq!(total(&estimate.fills), estimate.visible_filled);
+    assert_eq!(estimate.fills.last(), Some(&(1.03, 200.0)));
+}
+
+#[test]
+fn empty_side_or_size_has_no_fills() {
+    assert!(take_levels(&[], &[], 10.0).is_empty());
+    assert!(take_levels(&[1.0], &[5.0], 0.0).is_empty());
+    // Empty levels are skipped rather than listed
+    assert_eq!(take_levels(&[1.0, 1.1], &[0.0, 5.0], 3.0), vec![(1.1, 3.0)]);
+}
+
+#[tokio::test]
+async fn quote_response_lists_fills() {
+    let req = LimitReq {
+        pool: "SUI_USDC".to_string(),
+        price: 1.0,
+        quantity: 120.0,
+        is_bid: true,
+        client_order_id: "1".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    };
+    let estimate = estimate_slippage(&book(), 1.0, 120.0, true, TICK, 0.01);
+    let plan = RoutePlan::deepbook_single(req, 1.0, estimate.slippage, 0.0, 250, 250, 0.0)
+        .with_expected_fill_price(estimate.avg_fill_price)
+        .with_fill_levels(estimate.fills);
+
+    let precision = Precision {
+        price_decimals: 2,
+        size_decimals: 1,
+    };
+    let response = RoutePlanResponse::from_plan(&plan, None)
+        .await
+        .rounded(&precision);
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(
+        json["fills"],
+        serde_json::json!([
+            { "price": 1.01, "quantity": 50.0 },
+            { "price": 1.02, "quantity": 70.0 },
+        ])
+    );
+
+    // Plans that never walked the book omit the field
+    let bare = RoutePlan::cancel_deepbook("SUI_USDC".to_string(), 1, None, 0);
+    let json = serde_json::to_value(RoutePlanResponse::from_plan(&bare, None).await).unwrap();
+    assert!(json.get("fills").is_none());
+}
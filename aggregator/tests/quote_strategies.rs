@@ -0,0 +1,60 @@
+use sui_deepbookv3::client::Level2TicksFromMid;
+use ultra_aggr::router::strategies::{estimate_strategies, StrategyKind, StrategyParams};
+
+fn book() -> Level2TicksFromMid {
+    Level2TicksFromMid {
+        bid_prices: vec![0.99, 0.98, 0.97],
+        bid_quantities: vec![50.0, 100.0, 200.0],
+        ask_prices: vec![1.01, 1.02, 1.03],
+        ask_quantities: vec![50.0, 100.0, 200.0],
+    }
+}
+
+#[test]
+fn quote_offers_all_strategies_ordered_by_impact() {
+    let strategies =
+        estimate_strategies(&book(), 200.0, true, Some(0.01), StrategyParams::default());
+    let kinds: Vec<_> = strategies.iter().map(|s| s.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            StrategyKind::Immediate,
+            StrategyKind::Twap,
+            StrategyKind::Passive
+        ]
+    );
+
+    let (immediate, twap, passive) = (&strategies[0], &strategies[1], &strategies[2]);
+    // Sweeping 200 reaches the third ask; 40-lot slices stay at the touch
+    assert!(immediate.impact > twap.impact);
+    assert!(twap.impact > passive.impact);
+    assert!(passive.impact < 0.0);
+
+    // Slower strategies take longer and are less certain to complete
+    assert!(immediate.expected_time_ms < twap.expected_time_ms);
+    assert_eq!(immediate.fill_probability, Some(1.0));
+    let passive_prob = passive.fill_probability.unwrap();
+    assert!(passive_prob > 0.0 && passive_prob < 1.0);
+    assert_eq!(twap.slices, StrategyParams::default().twap_slices);
+}
+
+#[test]
+fn passive_estimate_needs_trade_flow() {
+    let strategies = estimate_strategies(&book(), 10.0, false, None, StrategyParams::default());
+    let passive = strategies
+        .iter()
+        .find(|s| s.kind == StrategyKind::Passive)
+        .unwrap();
+    assert!(passive.fill_probability.is_none());
+    assert!(passive.expected_time_ms.is_none());
+}
+
+#[test]
+fn one_sided_book_has_no_strategies() {
+    let mut level2 = book();
+    level2.ask_prices.clear();
+    level2.ask_quantities.clear();
+    assert!(
+        estimate_strategies(&level2, 10.0, true, Some(1.0), StrategyParams::default()).is_empty()
+    );
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::quotes::QuoteRegistry;
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+fn order() -> LimitReq {
+    LimitReq {
+        pool: "SUI_USDC".to_string(),
+        price: 1.0,
+        quantity: 10.0,
+        is_bid: true,
+        client_order_id: "7".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    }
+}
+
+#[tokio::test]
+async fn quote_carries_validity_and_commits_before_expiry() {
+    let quotes = QuoteRegistry::new(Duration::from_millis(500));
+    let ticket = quotes.issue(order(), 10_000).await;
+    assert_eq!(ticket.valid_until_ms, 10_500);
+
+    let committed = quotes.commit(&ticket.quote_id, 10_500).await.unwrap();
+    assert_eq!(committed.client_order_id, "7");
+
+    // A quote is committed at most once
+    assert!(matches!(
+        quotes.commit(&ticket.quote_id, 10_100).await,
+        Err(AggrError::UnknownQuote(_))
+    ));
+}
+
+#[tokio::test]
+async fn commit_after_expiry_is_rejected() {
+    let quotes = QuoteRegistry::new(Duration::from_millis(500));
+    let ticket = quotes.issue(order(), 10_000).await;
+
+    assert!(matches!(
+        quotes.commit(&ticket.quote_id, 10_501).await,
+        Err(AggrError::QuoteExpired {
+            valid_until_ms: 10_500
+        })
+    ));
+}
+
+#[tokio::test]
+async fn expired_quotes_are_purged_on_issue() {
+    let quotes = QuoteRegistry::new(Duration::from_millis(500));
+    let first = quotes.issue(order(), 10_000).await;
+    let second = quotes.issue(order(), 20_000).await;
+    assert_ne!(first.quote_id, second.quote_id);
+    assert_eq!(quotes.len().await, 1);
+}
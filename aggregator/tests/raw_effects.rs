@@ -0,0 +1,74 @@
+use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+use std::sync::atomic::{AtomicU32, Ordering};
+use ultra_aggr::router::execution::{ExecutionAccounting, ExecutionResult};
+use ultra_aggr::router::router::respond_with_raw_effects;
+use ultra_aggr::transport::grpc::sui::rpc::v2::{Bcs, ExecutedTransaction, TransactionEffects};
+
+const EFFECTS: &[u8] = b"bcs transaction effects";
+
+fn executed(embedded: Option<&[u8]>, dry_run: bool) -> ExecutionResult {
+    ExecutionResult {
+        digest: "9mXkPsB1uP3rEz1Nq3Yk2GzJ9xZ3QpLq4dY6pXoYbM1A".to_string(),
+        executed: ExecutedTransaction {
+            effects: Some(TransactionEffects {
+                bcs: embedded.map(|bytes| Bcs {
+                    name: Some("sui.types.TransactionEffects".to_string()),
+                    value: Some(bytes.to_vec()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        effects_time_ms: 12.0,
+        checkpoint_time_ms: None,
+        accounting: ExecutionAccounting::default(),
+        orders: Vec::new(),
+        dry_run,
+    }
+}
+
+#[tokio::test]
+async fn raw_effects_are_present_when_requested() {
+    let response =
+        respond_with_raw_effects(executed(Some(EFFECTS), false), true, |_| async { None }).await;
+    assert_eq!(response.raw_effects, Some(B64.encode(EFFECTS)));
+}
+
+#[tokio::test]
+async fn raw_effects_are_absent_unless_requested() {
+    let fetches = AtomicU32::new(0);
+    let response = respond_with_raw_effects(executed(Some(EFFECTS), false), false, |_| async {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        Some(EFFECTS.to_vec())
+    })
+    .await;
+    assert_eq!(response.raw_effects, None);
+    assert_eq!(fetches.load(Ordering::SeqCst), 0);
+
+    let body = serde_json::to_value(&response).unwrap();
+    assert!(body.get("raw_effects").is_none(), "{body}");
+}
+
+#[tokio::test]
+async fn missing_embedded_effects_are_fetched_by_digest() {
+    let execution = executed(None, false);
+    let expected_digest = execution.digest.clone();
+    let response = respond_with_raw_effects(execution, true, |digest| async move {
+        assert_eq!(digest, expected_digest);
+        Some(EFFECTS.to_vec())
+    })
+    .await;
+    assert_eq!(response.raw_effects, Some(B64.encode(EFFECTS)));
+}
+
+#[tokio::test]
+async fn dry_runs_never_fetch_effects() {
+    let fetches = AtomicU32::new(0);
+    let response = respond_with_raw_effects(executed(None, true), true, |_| async {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        Some(EFFECTS.to_vec())
+    })
+    .await;
+    assert_eq!(response.raw_effects, None);
+    assert_eq!(fetches.load(Ordering::SeqCst), 0);
+}
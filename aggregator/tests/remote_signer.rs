@@ -0,0 +1,234 @@
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::sync::Arc;
+use sui_sdk::types::base_types::SuiAddress;
+use ultra_aggr::signing::{
+    address_for, address_from_public_key, local_signer, sign_personal_message,
+    sign_tx_bcs_ed25519_to_serialized_signature, signature_public_key, KeyScheme,
+    LocalEd25519Signer, RemoteSignRequest, RemoteSignResponse, RemoteSigner, Signer,
+};
+use url::Url;
+
+const KEY: &str = "3333333333333333333333333333333333333333333333333333333333333333";
+const TX: &[u8] = b"transaction data bytes";
+const MESSAGE: &[u8] = b"onboarding challenge";
+
+/// Account the signing service holds the key for
+fn signer_address() -> SuiAddress {
+    address_for(KeyScheme::Ed25519, KEY).unwrap()
+}
+
+async fn serve(app: Router) -> Url {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test server");
+    let addr = listener.local_addr().expect("test server address");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    Url::parse(&format!("http://{addr}/sign")).unwrap()
+}
+
+/// Signing service that holds `KEY` and signs whatever it is sent
+async fn hsm(Json(req): Json<RemoteSignRequest>) -> Json<RemoteSignResponse> {
+    let signature = match req.intent.as_str() {
+        "transaction_data" => {
+            let tx_bcs = STANDARD.decode(req.tx_bytes.unwrap()).unwrap();
+            sign_tx_bcs_ed25519_to_serialized_signature(&tx_bcs, KEY)
+                .unwrap()
+                .0
+        }
+        "personal_message" => {
+            let message = STANDARD.decode(req.message.unwrap()).unwrap();
+            sign_personal_message(KeyScheme::Ed25519, &message, KEY)
+                .unwrap()
+                .0
+        }
+        other => panic!("unexpected intent {other}"),
+    };
+    Json(RemoteSignResponse {
+        signature: STANDARD.encode(signature),
+    })
+}
+
+#[tokio::test]
+async fn local_signer_matches_the_hex_path() {
+    let expected = sign_tx_bcs_ed25519_to_serialized_signature(TX, KEY)
+        .unwrap()
+        .0;
+    let signer: Arc<dyn Signer> = Arc::new(LocalEd25519Signer::new(KEY));
+    assert_eq!(signer.sign_intent(TX).await.unwrap(), expected);
+    assert_eq!(
+        local_signer(KeyScheme::Ed25519, KEY)
+            .sign_intent(TX)
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        local_signer(KeyScheme::Secp256k1, KEY)
+            .sign_intent(TX)
+            .await
+            .unwrap()[0],
+        0x01
+    );
+}
+
+#[tokio::test]
+async fn remote_signer_returns_the_service_signature() {
+    let url = serve(Router::new().route("/sign", post(hsm))).await;
+    let signer = RemoteSigner::new(url, signer_address()).unwrap();
+    let signature = signer.sign_intent(TX).await.unwrap();
+    let expected = sign_tx_bcs_ed25519_to_serialized_signature(TX, KEY)
+        .unwrap()
+        .0;
+    assert_eq!(signature, expected);
+}
+
+#[tokio::test]
+async fn local_signers_sign_personal_messages_for_their_scheme() {
+    for scheme in [KeyScheme::Ed25519, KeyScheme::Secp256k1] {
+        let signature = local_signer(scheme, KEY)
+            .sign_personal_message(MESSAGE)
+            .await
+            .unwrap();
+        assert_eq!(
+            signature,
+            sign_personal_message(scheme, MESSAGE, KEY).unwrap().0
+        );
+
+        let (signed_scheme, public_key) = signature_public_key(&signature).unwrap();
+        assert_eq!(signed_scheme, scheme);
+        assert_eq!(
+            address_from_public_key(scheme, public_key).unwrap(),
+            address_for(scheme, KEY).unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn remote_signer_signs_personal_messages_without_a_local_key() {
+    let url = serve(Router::new().route("/sign", post(hsm))).await;
+    let signature = RemoteSigner::new(url, signer_address())
+        .unwrap()
+        .sign_personal_message(MESSAGE)
+        .await
+        .unwrap();
+    let expected = sign_personal_message(KeyScheme::Ed25519, MESSAGE, KEY)
+        .unwrap()
+        .0;
+    assert_eq!(signature, expected);
+    // Not the transaction-intent signature over the same bytes
+    assert_ne!(
+        signature,
+        sign_tx_bcs_ed25519_to_serialized_signature(MESSAGE, KEY)
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn malformed_signatures_have_no_public_key() {
+    assert!(signature_public_key(&[]).is_err());
+    assert!(signature_public_key(&[0x07; 97]).is_err());
+    assert!(signature_public_key(&[0x00; 98]).is_err());
+    assert!(signature_public_key(&[0x01; 98]).is_ok());
+}
+
+#[tokio::test]
+async fn remote_failures_surface_as_signing_errors() {
+    let url = serve(Router::new().route(
+        "/sign",
+        post(|| async { (StatusCode::SERVICE_UNAVAILABLE, "hsm offline") }),
+    ))
+    .await;
+    let err = RemoteSigner::new(url, signer_address())
+        .unwrap()
+        .sign_intent(TX)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("remote signer request failed"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn unknown_signature_flag_is_rejected() {
+    let url = serve(Router::new().route(
+        "/sign",
+        post(|| async {
+            Json(RemoteSignResponse {
+                signature: STANDARD.encode([0x07u8; 97]),
+            })
+        }),
+    ))
+    .await;
+    let err = RemoteSigner::new(url, signer_address())
+        .unwrap()
+        .sign_intent(TX)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("unsupported signature flag"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn truncated_signatures_are_rejected() {
+    let url = serve(Router::new().route(
+        "/sign",
+        post(|| async {
+            let signature = sign_tx_bcs_ed25519_to_serialized_signature(TX, KEY)
+                .unwrap()
+                .0;
+            Json(RemoteSignResponse {
+                signature: STANDARD.encode(&signature[..signature.len() - 1]),
+            })
+        }),
+    ))
+    .await;
+    let err = RemoteSigner::new(url, signer_address())
+        .unwrap()
+        .sign_intent(TX)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("expected 97"), "{err}");
+}
+
+#[tokio::test]
+async fn signatures_from_another_account_are_rejected() {
+    let url = serve(Router::new().route("/sign", post(hsm))).await;
+    let other = address_for(KeyScheme::Secp256k1, KEY).unwrap();
+    let err = RemoteSigner::new(url, other)
+        .unwrap()
+        .sign_intent(TX)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("not the configured address"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn oversized_replies_are_not_read() {
+    let url = serve(Router::new().route(
+        "/sign",
+        post(|| async {
+            Json(RemoteSignResponse {
+                signature: "A".repeat(64 * 1024),
+            })
+        }),
+    ))
+    .await;
+    let err = RemoteSigner::new(url, signer_address())
+        .unwrap()
+        .sign_intent(TX)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("byte limit"), "{err}");
+}
@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+use ultra_aggr::router::deadline::{begin_submission, run_until_submitted};
+
+#[tokio::test]
+async fn slow_path_is_aborted_at_the_deadline() {
+    let started = Instant::now();
+    let result = run_until_submitted(Duration::from_millis(50), async {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        "finished"
+    })
+    .await;
+    assert_eq!(result, None);
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(50));
+    assert!(elapsed < Duration::from_secs(2), "took {elapsed:?}");
+}
+
+#[tokio::test]
+async fn fast_path_completes_within_the_deadline() {
+    let result = run_until_submitted(Duration::from_secs(5), async { 7 }).await;
+    assert_eq!(result, Some(7));
+}
+
+#[tokio::test]
+async fn submitted_work_runs_past_the_deadline() {
+    let started = Instant::now();
+    let result = run_until_submitted(Duration::from_millis(20), async {
+        begin_submission();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        "digest"
+    })
+    .await;
+    assert_eq!(result, Some("digest"));
+    assert!(started.elapsed() >= Duration::from_millis(120));
+}
+
+#[tokio::test]
+async fn begin_submission_outside_a_deadline_is_a_no_op() {
+    begin_submission();
+}
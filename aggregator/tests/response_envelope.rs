@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use axum::{routing::get, Json, Router};
+use ultra_aggr::router::middleware::{
+    with_response_envelope, with_slow_request_logging, ResponseEnvelope, ENVELOPE_MEDIA_TYPE,
+};
+
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test server");
+    let addr = listener.local_addr().expect("test server address");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    format!("http://{addr}")
+}
+
+async fn app() -> String {
+    let api = Router::new()
+        .route(
+            "/quote",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                Json(serde_json::json!({ "price": 1.5 }))
+            }),
+        )
+        .route("/metrics", get(|| async { "requests_total 1" }));
+    let api = with_slow_request_logging(with_response_envelope(api), Duration::from_secs(5));
+    serve(api).await
+}
+
+#[tokio::test]
+async fn envelope_carries_request_id_and_processing_time() {
+    let base = app().await;
+    let response = reqwest::Client::new()
+        .get(format!("{base}/quote?envelope=true"))
+        .header("x-request-id", "req-42")
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let envelope: ResponseEnvelope = response.json().await.unwrap();
+    assert_eq!(envelope.request_id, "req-42");
+    assert!(
+        envelope.processing_time_ms >= 30,
+        "processing time {} ms should cover the handler",
+        envelope.processing_time_ms
+    );
+    assert_eq!(envelope.data, serde_json::json!({ "price": 1.5 }));
+}
+
+#[tokio::test]
+async fn accept_header_negotiates_envelope() {
+    let base = app().await;
+    let response = reqwest::Client::new()
+        .get(format!("{base}/quote"))
+        .header("accept", ENVELOPE_MEDIA_TYPE)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        ENVELOPE_MEDIA_TYPE
+    );
+
+    let envelope: ResponseEnvelope = response.json().await.unwrap();
+    assert!(!envelope.request_id.is_empty(), "a request id is assigned");
+    assert_eq!(envelope.data["price"], 1.5);
+}
+
+#[tokio::test]
+async fn bare_payload_is_the_default() {
+    let base = app().await;
+    let body: serde_json::Value = reqwest::get(format!("{base}/quote"))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body, serde_json::json!({ "price": 1.5 }));
+
+    let text = reqwest::get(format!("{base}/metrics?envelope=true"))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(
+        text, "requests_total 1",
+        "non-JSON responses are not wrapped"
+    );
+}
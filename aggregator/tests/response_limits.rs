@@ -0,0 +1,47 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::transport::jsonrpc::JsonRpc;
+
+/// Serve a single HTTP response whose body is `body_len` bytes of JSON padding.
+fn serve_once(body_len: usize, declare_length: bool) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let addr = listener.local_addr().expect("mock server address");
+    thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let body = format!("{{\"pad\":\"{}\"}}", "x".repeat(body_len));
+            let header = if declare_length {
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n\r\n"
+                    .to_string()
+            };
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(body.as_bytes());
+        }
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn jsonrpc_rejects_declared_oversized_response() {
+    let url = serve_once(64 * 1024, true);
+    let client = JsonRpc::new(url).with_max_response_bytes(1024);
+    let err = client.execute_tx_block(&[0u8; 4], &[]).await.unwrap_err();
+    assert!(matches!(err, AggrError::ResponseTooLarge(1024)), "{err}");
+}
+
+#[tokio::test]
+async fn jsonrpc_rejects_streamed_oversized_response() {
+    let url = serve_once(64 * 1024, false);
+    let client = JsonRpc::new(url).with_max_response_bytes(1024);
+    let err = client.execute_tx_block(&[0u8; 4], &[]).await.unwrap_err();
+    assert!(matches!(err, AggrError::ResponseTooLarge(1024)), "{err}");
+}
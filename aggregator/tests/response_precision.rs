@@ -0,0 +1,45 @@
+use ultra_aggr::quant::{decimals_for_step, PoolParams, Precision};
+use ultra_aggr::venues::book::BookSweep;
+
+fn precision() -> Precision {
+    Precision::from_pool_params(&PoolParams {
+        tick_size: 0.001,
+        lot_size: 0.1,
+        min_size: 1.0,
+    })
+}
+
+#[test]
+fn tick_of_a_thousandth_formats_three_decimals() {
+    let precision = precision();
+    assert_eq!(precision.price_decimals, 3);
+    assert_eq!(precision.size_decimals, 1);
+    assert_eq!(precision.round_price(1.234_567), 1.235);
+    assert_eq!(precision.round_size(12.345), 12.3);
+
+    let sweep = BookSweep {
+        quantity: 10.0,
+        filled: 10.0,
+        vwap: 1.011_666_666_7,
+        worst_price: 1.012,
+    }
+    .rounded(&precision);
+    assert_eq!(
+        serde_json::to_value(sweep).unwrap(),
+        serde_json::json!({
+            "quantity": 10.0,
+            "filled": 10.0,
+            "vwap": 1.012,
+            "worst_price": 1.012
+        })
+    );
+}
+
+#[test]
+fn step_decimals() {
+    assert_eq!(decimals_for_step(1.0), 0);
+    assert_eq!(decimals_for_step(5.0), 0);
+    assert_eq!(decimals_for_step(0.01), 2);
+    assert_eq!(decimals_for_step(0.0025), 4);
+    assert_eq!(decimals_for_step(0.000_001), 6);
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::time::Instant;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use ultra_aggr::signing::LocalEd25519Signer;
+use ultra_aggr::sponsorship::{
+    route_budget_keys, AbuseConfig, SponsorshipManager, SponsorshipRequest,
+};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const POOL_CLASS: &str = "deepbook:SUI_USDC";
+
+async fn sponsor(user: SuiAddress) -> SponsorshipManager {
+    let manager = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+    manager
+        .update_gas_coins(vec![ObjectID::from_single_byte(1)])
+        .await;
+    manager.set_user_budget(user, 10_000, 1_000, None).await;
+    manager
+}
+
+fn request(user: SuiAddress, route_class: &str) -> SponsorshipRequest {
+    SponsorshipRequest {
+        user_address: user,
+        route_plan_id: route_class.to_string(),
+        estimated_gas: 100,
+        created_at: Instant::now(),
+    }
+}
+
+#[test]
+fn pool_classes_draw_on_their_venue_budget_too() {
+    assert_eq!(route_budget_keys(POOL_CLASS), vec![POOL_CLASS, "deepbook"]);
+    assert_eq!(route_budget_keys("flash_loan"), vec!["flash_loan"]);
+}
+
+#[tokio::test]
+async fn exhausted_route_budget_denies_sponsorship_despite_user_budget() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user).await;
+    sponsor
+        .set_route_budget(POOL_CLASS.to_string(), 250, 1_000, None)
+        .await;
+
+    for _ in 0..2 {
+        assert!(sponsor
+            .can_sponsor(&request(user, POOL_CLASS))
+            .await
+            .unwrap());
+        sponsor.apply_spending(user, Some(POOL_CLASS), 100).await;
+    }
+    assert_eq!(
+        sponsor.get_route_budget_remaining(POOL_CLASS).await,
+        Some(50)
+    );
+
+    assert!(!sponsor
+        .can_sponsor(&request(user, POOL_CLASS))
+        .await
+        .unwrap());
+    assert_eq!(sponsor.get_user_budget_remaining(user).await, Some(9_800));
+    // Other pools are unaffected
+    assert!(sponsor
+        .can_sponsor(&request(user, "deepbook:DEEP_SUI"))
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn venue_budget_caps_every_pool() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user).await;
+    sponsor
+        .set_route_budget("deepbook".to_string(), 150, 1_000, None)
+        .await;
+
+    sponsor
+        .apply_spending(user, Some("deepbook:DEEP_SUI"), 100)
+        .await;
+
+    assert_eq!(
+        sponsor.get_route_budget_remaining("deepbook").await,
+        Some(50)
+    );
+    assert!(!sponsor
+        .can_sponsor(&request(user, POOL_CLASS))
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn spending_debits_user_and_route_budgets_together() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user).await;
+    sponsor
+        .set_route_budget(POOL_CLASS.to_string(), 1_000, 1_000, None)
+        .await;
+
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-1"), user, Some(POOL_CLASS), 300)
+            .await
+    );
+    assert!(
+        !sponsor
+            .apply_spending_once(Some("order-1"), user, Some(POOL_CLASS), 300)
+            .await
+    );
+
+    assert_eq!(sponsor.get_user_budget_remaining(user).await, Some(9_700));
+    assert_eq!(
+        sponsor.get_route_budget_remaining(POOL_CLASS).await,
+        Some(700)
+    );
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ultra_aggr::router::routes::RoutePlan;
+use ultra_aggr::router::selector::{
+    gather_alternatives, gather_until_good_enough, EvaluationPolicy, VenueEvaluation,
+};
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+const NOTIONAL: f64 = 10.0;
+
+/// A plan for 10 units at 1.0 whose costs beyond the price are `overhead`
+fn plan(overhead: f64) -> RoutePlan {
+    let req = LimitReq {
+        pool: "SUI_USDC".to_string(),
+        price: 1.0,
+        quantity: 10.0,
+        is_bid: true,
+        client_order_id: "1".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    };
+    RoutePlan::deepbook_single(req, 1.0, overhead, 0.0, 250, 250, 0.0)
+}
+
+async fn venue(overhead: f64, polled: &AtomicBool) -> anyhow::Result<RoutePlan> {
+    polled.store(true, Ordering::SeqCst);
+    Ok(plan(overhead))
+}
+
+fn threshold(bps: f64) -> EvaluationPolicy {
+    EvaluationPolicy {
+        good_enough_bps: Some(bps),
+        ..EvaluationPolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn good_enough_route_stops_evaluation() {
+    let (cheap, pricier) = (AtomicBool::new(false), AtomicBool::new(false));
+    // 0.001 of overhead on a notional of 10 is 1 bps
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("deepbook", Box::pin(venue(0.001, &cheap))),
+        ("amm", Box::pin(venue(0.0, &pricier))),
+    ];
+
+    let plans = gather_until_good_enough(evaluations, &threshold(5.0), NOTIONAL).await;
+    assert_eq!(plans.len(), 1);
+    assert!(cheap.load(Ordering::SeqCst));
+    assert!(
+        !pricier.load(Ordering::SeqCst),
+        "venues after a good-enough route must not be evaluated"
+    );
+}
+
+#[tokio::test]
+async fn routes_above_threshold_keep_evaluating() {
+    let (first, second) = (AtomicBool::new(false), AtomicBool::new(false));
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("deepbook", Box::pin(venue(0.1, &first))),
+        ("amm", Box::pin(venue(0.002, &second))),
+    ];
+
+    let plans = gather_until_good_enough(evaluations, &threshold(5.0), NOTIONAL).await;
+    assert_eq!(plans.len(), 2);
+    assert!(second.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn max_alternatives_caps_evaluation() {
+    let polled: Vec<AtomicBool> = (0..3).map(|_| AtomicBool::new(false)).collect();
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("a", Box::pin(venue(1.0, &polled[0]))),
+        ("b", Box::pin(venue(1.0, &polled[1]))),
+        ("c", Box::pin(venue(1.0, &polled[2]))),
+    ];
+    let policy = EvaluationPolicy {
+        max_alternatives: Some(2),
+        ..EvaluationPolicy::default()
+    };
+
+    let plans = gather_until_good_enough(evaluations, &policy, NOTIONAL).await;
+    assert_eq!(plans.len(), 2);
+    assert!(!polled[2].load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn venue_order_filters_and_orders_per_pool() {
+    let flags: Vec<AtomicBool> = (0..3).map(|_| AtomicBool::new(false)).collect();
+    let policy = EvaluationPolicy {
+        venue_order: HashMap::from([(
+            "sui_usdc".to_string(),
+            vec!["amm".to_string(), "deepbook".to_string()],
+        )]),
+        ..EvaluationPolicy::default()
+    };
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("deepbook", Box::pin(venue(1.0, &flags[0]))),
+        ("rfq", Box::pin(venue(1.0, &flags[1]))),
+        ("amm", Box::pin(venue(1.0, &flags[2]))),
+    ];
+
+    let ordered = policy.order_for("SUI_USDC", evaluations);
+    let names: Vec<&str> = ordered.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, vec!["amm", "deepbook"]);
+
+    let untouched: Vec<VenueEvaluation<'_>> = vec![("rfq", Box::pin(venue(1.0, &flags[1])))];
+    assert_eq!(policy.order_for("DEEP_SUI", untouched).len(), 1);
+}
+
+#[tokio::test]
+async fn full_evaluation_ignores_the_threshold() {
+    let (cheap, other) = (AtomicBool::new(false), AtomicBool::new(false));
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("deepbook", Box::pin(venue(0.0, &cheap))),
+        ("amm", Box::pin(venue(0.0, &other))),
+    ];
+
+    assert!(!threshold(5.0).is_exhaustive());
+    assert!(EvaluationPolicy::default().is_exhaustive());
+    let plans = gather_alternatives(evaluations).await;
+    assert_eq!(plans.len(), 2);
+    assert!(other.load(Ordering::SeqCst));
+}
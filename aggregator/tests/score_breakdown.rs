@@ -0,0 +1,43 @@
+use ultra_aggr::router::routes::RouteScore;
+
+#[test]
+fn component_shares_sum_to_one_hundred_percent() {
+    let score = RouteScore::new(1.2345, 0.0021, 0.0004, 0.0003, 0.0001);
+    let breakdown = score.breakdown();
+
+    let total_pct: f64 = breakdown
+        .components()
+        .iter()
+        .map(|(_, component)| component.share_pct)
+        .sum();
+    assert!(
+        (total_pct - 100.0).abs() < 1e-9,
+        "shares sum to {total_pct}"
+    );
+
+    let total_value: f64 = breakdown
+        .components()
+        .iter()
+        .map(|(_, component)| component.value)
+        .sum();
+    assert!((total_value - score.total_cost).abs() < 1e-12);
+    assert!(breakdown.l2_price.share_pct > breakdown.slippage.share_pct);
+}
+
+#[test]
+fn zero_cost_route_reports_zero_shares() {
+    let breakdown = RouteScore::new(0.0, 0.0, 0.0, 0.0, 0.0).breakdown();
+    assert!(breakdown
+        .components()
+        .iter()
+        .all(|(_, component)| component.share_pct == 0.0));
+}
+
+#[test]
+fn breakdown_serializes_value_and_share() {
+    let breakdown = RouteScore::new(1.0, 0.0, 0.0, 0.0, 0.0).breakdown();
+    let json = serde_json::to_value(breakdown).unwrap();
+    assert_eq!(json["l2_price"]["value"], 1.0);
+    assert_eq!(json["l2_price"]["share_pct"], 100.0);
+    assert_eq!(json["gas_cost"]["share_pct"], 0.0);
+}
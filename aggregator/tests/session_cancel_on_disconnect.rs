@@ -0,0 +1,134 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ultra_aggr::router::sessions::{
+    cancel_expired_sessions, track_or_cancel, SessionOrder, SessionRegistry,
+};
+
+fn order(pool: &str, order_id: u128) -> SessionOrder {
+    SessionOrder {
+        pool: pool.to_string(),
+        order_id,
+        manager: None,
+    }
+}
+
+#[tokio::test]
+async fn dropped_heartbeat_cancels_the_sessions_orders() {
+    let timeout = Duration::from_secs(5);
+    let sessions = SessionRegistry::new(timeout);
+    let start = Instant::now();
+
+    sessions.heartbeat("mm-1", start).await;
+    sessions.heartbeat("mm-2", start).await;
+    assert!(
+        sessions
+            .track("mm-1", [order("SUI_USDC", 11), order("DEEP_SUI", 12)])
+            .await
+    );
+    assert!(sessions.track("mm-2", [order("SUI_USDC", 21)]).await);
+
+    // mm-2 keeps heartbeating; mm-1 goes silent
+    let later = start + Duration::from_secs(4);
+    sessions.heartbeat("mm-2", later).await;
+    let cancelled = Mutex::new(Vec::new());
+    let cancel = |order: SessionOrder| {
+        cancelled.lock().unwrap().push(order);
+        async { Ok(()) }
+    };
+
+    assert_eq!(cancel_expired_sessions(&sessions, later, cancel).await, 0);
+    assert!(cancelled.lock().unwrap().is_empty(), "within the timeout");
+
+    let after_timeout = start + Duration::from_secs(6);
+    assert_eq!(
+        cancel_expired_sessions(&sessions, after_timeout, cancel).await,
+        2
+    );
+    assert_eq!(
+        *cancelled.lock().unwrap(),
+        vec![order("SUI_USDC", 11), order("DEEP_SUI", 12)]
+    );
+    assert!(!sessions.is_live("mm-1", after_timeout).await);
+    assert!(sessions.is_live("mm-2", after_timeout).await);
+    assert_eq!(sessions.orders("mm-2").await, vec![order("SUI_USDC", 21)]);
+}
+
+#[tokio::test]
+async fn failed_cancels_are_not_counted_and_close_the_session() {
+    let sessions = SessionRegistry::new(Duration::from_millis(10));
+    let start = Instant::now();
+    sessions.heartbeat("mm-1", start).await;
+    sessions
+        .track("mm-1", [order("SUI_USDC", 1), order("SUI_USDC", 2)])
+        .await;
+
+    let cancelled = cancel_expired_sessions(
+        &sessions,
+        start + Duration::from_secs(1),
+        |order| async move {
+            if order.order_id == 1 {
+                anyhow::bail!("order already filled");
+            }
+            Ok(())
+        },
+    )
+    .await;
+    assert_eq!(cancelled, 1);
+    assert!(sessions.orders("mm-1").await.is_empty());
+}
+
+#[tokio::test]
+async fn orders_cannot_join_a_session_that_is_not_open() {
+    let sessions = SessionRegistry::default();
+    assert!(!sessions.is_live("ghost", Instant::now()).await);
+    assert!(!sessions.track("ghost", [order("SUI_USDC", 1)]).await);
+}
+
+#[tokio::test]
+async fn orders_placed_after_their_session_expired_are_cancelled() {
+    let timeout = Duration::from_secs(5);
+    let sessions = SessionRegistry::new(timeout);
+    let start = Instant::now();
+    sessions.heartbeat("mm-1", start).await;
+
+    // The session expires while an order is in flight
+    let expired = cancel_expired_sessions(&sessions, start + Duration::from_secs(6), |_| async {
+        Ok(())
+    })
+    .await;
+    assert_eq!(expired, 0);
+
+    let cancels = Mutex::new(Vec::new());
+    let cancelled = track_or_cancel(
+        &sessions,
+        "mm-1",
+        vec![order("SUI_USDC", 31), order("DEEP_SUI", 32)],
+        |order| {
+            cancels.lock().unwrap().push(order);
+            async { Ok(()) }
+        },
+    )
+    .await;
+
+    assert_eq!(cancelled, 2);
+    assert_eq!(
+        cancels.into_inner().unwrap(),
+        vec![order("SUI_USDC", 31), order("DEEP_SUI", 32)]
+    );
+    assert!(sessions.orders("mm-1").await.is_empty());
+}
+
+#[tokio::test]
+async fn orders_of_a_live_session_are_tracked_not_cancelled() {
+    let sessions = SessionRegistry::default();
+    sessions.heartbeat("mm-1", Instant::now()).await;
+
+    let cancelled = track_or_cancel(&sessions, "mm-1", vec![order("SUI_USDC", 41)], |_| async {
+        Err(anyhow::anyhow!("live session orders must not be cancelled"))
+    })
+    .await;
+
+    assert_eq!(cancelled, 0);
+    assert_eq!(sessions.orders("mm-1").await, vec![order("SUI_USDC", 41)]);
+}
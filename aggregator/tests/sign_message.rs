@@ -0,0 +1,105 @@
+use axum::http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use ultra_aggr::router::middleware::bearer_token_matches;
+use ultra_aggr::signing::{
+    address_for, ed25519_address, sign_personal_message, sign_personal_message_ed25519,
+    sign_tx_bcs_ed25519_to_serialized_signature, KeyScheme,
+};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+/// Blake2b-256 of the intent header and payload, as Sui verifiers compute it
+fn intent_digest(scope: u8, payload: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([scope, 0x00, 0x00]);
+    hasher.update(payload);
+    hasher.finalize().into()
+}
+
+fn split(serialized: &[u8]) -> (Signature, VerifyingKey) {
+    assert_eq!(serialized.len(), 97);
+    assert_eq!(serialized[0], 0x00, "Ed25519 flag");
+    let signature = Signature::from_bytes(serialized[1..65].try_into().unwrap());
+    let key = VerifyingKey::from_bytes(serialized[65..].try_into().unwrap()).unwrap();
+    (signature, key)
+}
+
+#[test]
+fn personal_message_signature_verifies_against_the_address() {
+    let challenge = b"onboarding challenge 7f3a";
+    let (serialized, public_key) = sign_personal_message_ed25519(challenge, KEY).unwrap();
+    let (signature, key) = split(&serialized);
+    assert_eq!(key.to_bytes(), public_key);
+
+    // The key behind the signature controls the trading address
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([0x00]);
+    hasher.update(public_key);
+    let address: [u8; 32] = hasher.finalize().into();
+    assert_eq!(address.as_slice(), ed25519_address(KEY).unwrap().as_ref());
+
+    // Personal messages are BCS byte vectors under intent scope 3
+    let payload = bcs::to_bytes(challenge.as_slice()).unwrap();
+    assert_eq!(payload[0] as usize, challenge.len());
+    key.verify(&intent_digest(0x03, &payload), &signature)
+        .expect("signature verifies");
+}
+
+#[test]
+fn message_signature_is_not_a_transaction_signature() {
+    let message = b"not a transaction";
+    let (personal, _) = sign_personal_message_ed25519(message, KEY).unwrap();
+    let (tx, _) = sign_tx_bcs_ed25519_to_serialized_signature(message, KEY).unwrap();
+    assert_ne!(personal, tx);
+
+    let (signature, key) = split(&personal);
+    assert!(key
+        .verify(&intent_digest(0x00, message), &signature)
+        .is_err());
+}
+
+#[test]
+fn secp256k1_keys_sign_personal_messages_for_their_own_address() {
+    let challenge = b"onboarding challenge 7f3a";
+    let (serialized, public_key) =
+        sign_personal_message(KeyScheme::Secp256k1, challenge, KEY).unwrap();
+    assert_eq!(serialized.len(), 1 + 64 + 33);
+    assert_eq!(serialized[0], 0x01, "secp256k1 flag");
+    assert_eq!(&serialized[65..], public_key.as_slice());
+
+    let signature = k256::ecdsa::Signature::from_slice(&serialized[1..65]).unwrap();
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key).unwrap();
+    let payload = bcs::to_bytes(challenge.as_slice()).unwrap();
+    k256::ecdsa::signature::Verifier::verify(&key, &intent_digest(0x03, &payload), &signature)
+        .expect("signature verifies");
+
+    // The address is derived from the secp256k1 key, not the Ed25519 reading of it
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update([0x01]);
+    hasher.update(&public_key);
+    let address: [u8; 32] = hasher.finalize().into();
+    let expected = address_for(KeyScheme::Secp256k1, KEY).unwrap();
+    assert_eq!(address.as_slice(), expected.as_ref());
+    assert_ne!(expected, ed25519_address(KEY).unwrap());
+}
+
+#[test]
+fn bearer_token_is_required() {
+    let token = "0123456789abcdef-token";
+    let mut headers = HeaderMap::new();
+    assert!(!bearer_token_matches(&headers, token));
+
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_static("Bearer wrong-token-of-len"),
+    );
+    assert!(!bearer_token_matches(&headers, token));
+
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_static("Bearer 0123456789abcdef-token"),
+    );
+    assert!(bearer_token_matches(&headers, token));
+    assert!(!bearer_token_matches(&headers, ""));
+}
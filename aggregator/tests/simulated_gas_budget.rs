@@ -0,0 +1,24 @@
+use ultra_aggr::router::execution::{simulated_gas_budget, MAX_GAS_BUDGET, MIN_GAS_BUDGET};
+
+#[test]
+fn budget_is_simulated_gas_times_safety_factor() {
+    assert_eq!(simulated_gas_budget(Some(10_000_000), 1.2), 12_000_000);
+    assert_eq!(simulated_gas_budget(Some(10_000_001), 1.5), 15_000_002);
+}
+
+#[test]
+fn budget_is_clamped_to_network_limits() {
+    assert_eq!(simulated_gas_budget(Some(1_000), 1.2), MIN_GAS_BUDGET);
+    assert_eq!(
+        simulated_gas_budget(Some(MAX_GAS_BUDGET), 1.2),
+        MAX_GAS_BUDGET
+    );
+}
+
+#[test]
+fn unsimulated_transactions_keep_the_static_budget() {
+    assert_eq!(
+        simulated_gas_budget(None, 1.2),
+        sui_deepbookv3::utils::config::GAS_BUDGET
+    );
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use axum::{routing::get, Router};
+use ultra_aggr::metrics::SLOW_REQUESTS;
+use ultra_aggr::router::middleware::with_slow_request_logging;
+
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test server");
+    let addr = listener.local_addr().expect("test server address");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn slow_handler_is_counted_and_fast_handler_is_not() {
+    let app = Router::new()
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                "slow"
+            }),
+        )
+        .route("/fast", get(|| async { "fast" }));
+    let base = serve(with_slow_request_logging(app, Duration::from_millis(20))).await;
+
+    let client = reqwest::Client::new();
+    client.get(format!("{base}/slow")).send().await.unwrap();
+    client.get(format!("{base}/fast")).send().await.unwrap();
+
+    assert_eq!(
+        SLOW_REQUESTS.with_label_values(&["GET", "/slow"]).get(),
+        1.0
+    );
+    assert_eq!(
+        SLOW_REQUESTS.with_label_values(&["GET", "/fast"]).get(),
+        0.0
+    );
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use sui_sdk::types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_sdk::types::digests::ObjectDigest;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{TransactionData, TransactionDataAPI, TransactionKind};
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::signing::LocalEd25519Signer;
+use ultra_aggr::sponsorship::{ensure_gas_coverage, AbuseConfig, SponsorshipManager};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const BUDGET: u64 = 10_000_000;
+
+fn coin(byte: u8, balance: u64) -> (ObjectRef, u64) {
+    let object_ref = (
+        ObjectID::from_single_byte(byte),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::random(),
+    );
+    (object_ref, balance)
+}
+
+fn small_coins() -> Vec<(ObjectRef, u64)> {
+    // None covers the budget alone; together they do
+    vec![coin(1, 4_000_000), coin(2, 3_000_000), coin(3, 3_000_000)]
+}
+
+fn empty_programmable() -> TransactionKind {
+    TransactionKind::programmable(ProgrammableTransactionBuilder::new().finish())
+}
+
+#[test]
+fn several_small_coins_cover_the_budget_together() {
+    assert!(ensure_gas_coverage(&small_coins(), BUDGET).is_ok());
+    assert!(ensure_gas_coverage(&small_coins()[..2], BUDGET).is_err());
+}
+
+#[test]
+fn shortfall_reports_available_and_required() {
+    let err = ensure_gas_coverage(&[coin(1, 1_000)], BUDGET).unwrap_err();
+    match err {
+        AggrError::InsufficientSponsorGas {
+            available,
+            required,
+        } => {
+            assert_eq!(available, 1_000);
+            assert_eq!(required, BUDGET);
+        }
+        other => panic!("unexpected error {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn sponsored_transaction_pays_with_every_coin() {
+    let sponsor_address = SuiAddress::random_for_testing_only();
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        sponsor_address,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+    let coins = small_coins();
+
+    let tx_bcs = sponsor
+        .build_sponsored_transaction_data(empty_programmable(), sender, coins.clone(), BUDGET)
+        .await
+        .unwrap();
+
+    let tx: TransactionData = bcs::from_bytes(&tx_bcs).unwrap();
+    let expected: Vec<ObjectRef> = coins.iter().map(|(object_ref, _)| *object_ref).collect();
+    assert_eq!(tx.gas(), expected.as_slice());
+    assert_eq!(tx.gas_owner(), sponsor_address);
+    assert_eq!(tx.sender(), sender);
+    assert_eq!(tx.gas_budget(), BUDGET);
+}
+
+#[tokio::test]
+async fn underfunded_coins_are_refused_before_building() {
+    let sponsor = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+
+    let err = sponsor
+        .build_sponsored_transaction_data(
+            empty_programmable(),
+            SuiAddress::ZERO,
+            small_coins()[..1].to_vec(),
+            BUDGET,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<AggrError>(),
+        Some(AggrError::InsufficientSponsorGas { .. })
+    ));
+}
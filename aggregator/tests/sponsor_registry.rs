@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use sui_sdk::types::base_types::SuiAddress;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::router::LimitOrderRequest;
+use ultra_aggr::signing::{sign_tx_bcs_ed25519_to_serialized_signature, LocalEd25519Signer};
+use ultra_aggr::sponsorship::{AbuseConfig, SponsorRegistry, SponsorshipManager};
+
+const KEY_A: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const KEY_B: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+
+fn sponsor(key: &str) -> Arc<SponsorshipManager> {
+    Arc::new(
+        SponsorshipManager::new(
+            Arc::new(LocalEd25519Signer::new(key)),
+            SuiAddress::ZERO,
+            1000,
+            AbuseConfig::default(),
+        )
+        .unwrap(),
+    )
+}
+
+fn public_key(key: &str) -> [u8; 32] {
+    sign_tx_bcs_ed25519_to_serialized_signature(b"probe", key)
+        .unwrap()
+        .1
+}
+
+#[tokio::test]
+async fn request_alias_selects_the_signing_sponsor() {
+    let registry = SponsorRegistry::new()
+        .with_sponsor("desk_a", sponsor(KEY_A))
+        .with_sponsor("desk_b", sponsor(KEY_B));
+    assert_eq!(registry.aliases(), vec!["desk_a", "desk_b"]);
+
+    let req: LimitOrderRequest = serde_json::from_value(serde_json::json!({
+        "pool": "SUI_USDC",
+        "price": 1.0,
+        "quantity": 10.0,
+        "is_bid": true,
+        "client_order_id": "1",
+        "sponsor": "desk_b"
+    }))
+    .unwrap();
+
+    let chosen = registry.get(req.sponsor.as_deref().unwrap()).unwrap();
+    let signature = chosen
+        .sign_sponsored_transaction(b"tx bytes")
+        .await
+        .unwrap();
+    // Serialized signatures end with the signer's public key
+    assert!(signature.ends_with(&public_key(KEY_B)));
+    assert!(!signature.ends_with(&public_key(KEY_A)));
+}
+
+#[test]
+fn unknown_alias_is_rejected() {
+    let registry = SponsorRegistry::new().with_sponsor("desk_a", sponsor(KEY_A));
+    assert!(registry.contains("desk_a"));
+    assert!(matches!(
+        registry.get("desk_c"),
+        Err(AggrError::UnknownSponsor(alias)) if alias == "desk_c"
+    ));
+}
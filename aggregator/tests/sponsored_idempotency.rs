@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+use sui_sdk::types::base_types::SuiAddress;
+use ultra_aggr::signing::LocalEd25519Signer;
+use ultra_aggr::sponsorship::{AbuseConfig, SponsorshipManager};
+
+const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const BUDGET: u64 = 1_000;
+
+async fn sponsor(user: SuiAddress) -> SponsorshipManager {
+    let manager = SponsorshipManager::new(
+        Arc::new(LocalEd25519Signer::new(KEY)),
+        SuiAddress::ZERO,
+        1000,
+        AbuseConfig::default(),
+    )
+    .unwrap();
+    manager.set_user_budget(user, BUDGET, BUDGET, None).await;
+    manager
+}
+
+#[tokio::test]
+async fn retried_sponsored_order_debits_the_budget_once() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user).await;
+
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-1"), user, None, 100)
+            .await
+    );
+    assert!(
+        !sponsor
+            .apply_spending_once(Some("order-1"), user, None, 100)
+            .await
+    );
+
+    assert_eq!(
+        sponsor.get_user_budget_remaining(user).await,
+        Some(BUDGET - 100)
+    );
+}
+
+#[tokio::test]
+async fn concurrent_retries_debit_the_budget_once() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = Arc::new(sponsor(user).await);
+
+    let attempts = (0..8).map(|_| {
+        let sponsor = Arc::clone(&sponsor);
+        tokio::spawn(async move {
+            sponsor
+                .apply_spending_once(Some("order-1"), user, None, 100)
+                .await
+        })
+    });
+    let mut debited = 0;
+    for attempt in attempts.collect::<Vec<_>>() {
+        if attempt.await.unwrap() {
+            debited += 1;
+        }
+    }
+
+    assert_eq!(debited, 1);
+    assert_eq!(
+        sponsor.get_user_budget_remaining(user).await,
+        Some(BUDGET - 100)
+    );
+}
+
+#[tokio::test]
+async fn distinct_or_missing_keys_are_debited_each_time() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user).await;
+
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-1"), user, None, 100)
+            .await
+    );
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-2"), user, None, 100)
+            .await
+    );
+    assert!(sponsor.apply_spending_once(None, user, None, 100).await);
+    assert!(sponsor.apply_spending_once(None, user, None, 100).await);
+
+    assert_eq!(
+        sponsor.get_user_budget_remaining(user).await,
+        Some(BUDGET - 400)
+    );
+}
+
+#[tokio::test]
+async fn keys_are_forgotten_after_the_ttl() {
+    let user = SuiAddress::random_for_testing_only();
+    let sponsor = sponsor(user)
+        .await
+        .with_debit_ttl(Duration::from_millis(50));
+
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-1"), user, None, 100)
+            .await
+    );
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(
+        sponsor
+            .apply_spending_once(Some("order-1"), user, None, 100)
+            .await
+    );
+
+    assert_eq!(
+        sponsor.get_user_budget_remaining(user).await,
+        Some(BUDGET - 200)
+    );
+}
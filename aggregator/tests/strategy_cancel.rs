@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::children::{
+    submit_children_until, ChildFailurePolicy, ChildSubmissionPolicy,
+};
+use ultra_aggr::router::execution::OrderHandle;
+use ultra_aggr::router::strategy_registry::{RestingOrder, StrategyRegistry, StrategyType};
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+fn twap_slices(count: usize) -> Vec<LimitReq> {
+    (0..count)
+        .map(|i| LimitReq {
+            pool: "SUI_USDC".to_string(),
+            price: 1.0,
+            quantity: 5.0,
+            is_bid: true,
+            client_order_id: format!("twap-{i}"),
+            pay_with_deep: false,
+            expiration_ms: None,
+            reduce_only: false,
+            post_only: None,
+            order_type: LimitOrderType::Gtc,
+            manager: None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn cancelling_twap_stops_further_slices() {
+    let registry = StrategyRegistry::new();
+    let handle = registry.start(StrategyType::Twap, 5).await;
+    let id = handle.id().to_string();
+    let submitted = AtomicUsize::new(0);
+
+    let report = submit_children_until(
+        twap_slices(5),
+        &ChildSubmissionPolicy {
+            max_parallel: 1,
+            on_failure: ChildFailurePolicy::Continue,
+        },
+        || handle.is_cancelled(),
+        |child| {
+            let registry = &registry;
+            let id = &id;
+            let submitted = &submitted;
+            let handle = &handle;
+            async move {
+                // Operator cancels while the second slice is in flight
+                if submitted.fetch_add(1, Ordering::SeqCst) == 1 {
+                    registry.cancel(id).await.unwrap();
+                }
+                handle.record_submitted(&[], None);
+                anyhow::Ok(child.client_order_id)
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(submitted.load(Ordering::SeqCst), 2);
+    assert_eq!(report.submitted.len(), 2);
+    assert_eq!(report.skipped, 3);
+
+    let listed = registry.list().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, id);
+    assert!(listed[0].cancelled);
+    assert_eq!(listed[0].submitted, 2);
+    assert_eq!(listed[0].total_children, 5);
+}
+
+#[tokio::test]
+async fn finished_strategies_leave_the_registry() {
+    let registry = StrategyRegistry::new();
+    let twap = registry.start(StrategyType::Twap, 3).await;
+    let iceberg = registry.start(StrategyType::Iceberg, 10).await;
+    assert_ne!(twap.id(), iceberg.id());
+    assert_eq!(registry.list().await.len(), 2);
+
+    registry.finish(twap.id()).await;
+
+    let listed = registry.list().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].kind, StrategyType::Iceberg);
+    assert!(matches!(
+        registry.cancel(twap.id()).await,
+        Err(AggrError::UnknownStrategy(_))
+    ));
+}
+
+#[tokio::test]
+async fn resting_children_remember_their_balance_manager() {
+    let registry = StrategyRegistry::new();
+    let handle = registry.start(StrategyType::Iceberg, 2).await;
+    let placed = |order_id| OrderHandle {
+        pool: "SUI_USDC".to_string(),
+        order_id,
+        client_order_id: None,
+    };
+
+    handle.record_submitted(&[placed(7)], Some("desk-b"));
+    handle.record_submitted(&[placed(8)], None);
+    assert_eq!(handle.progress().resting_orders, 2);
+
+    assert_eq!(
+        handle.take_resting_orders(),
+        vec![
+            RestingOrder {
+                pool: "SUI_USDC".to_string(),
+                order_id: 7,
+                manager: Some("desk-b".to_string()),
+            },
+            RestingOrder {
+                pool: "SUI_USDC".to_string(),
+                order_id: 8,
+                manager: None,
+            },
+        ]
+    );
+    assert!(handle.take_resting_orders().is_empty());
+}
+
+#[tokio::test]
+async fn aborted_strategy_tasks_leave_the_registry() {
+    let registry = StrategyRegistry::new();
+    let handle = registry.start(StrategyType::Twap, 4).await;
+    let finished = registry.finish_on_drop(handle.id());
+
+    let task = tokio::spawn(async move {
+        let _finished = finished;
+        std::future::pending::<()>().await;
+    });
+    tokio::task::yield_now().await;
+    assert_eq!(registry.list().await.len(), 1);
+
+    task.abort();
+    assert!(task.await.unwrap_err().is_cancelled());
+    assert!(registry.list().await.is_empty());
+    assert!(matches!(
+        registry.cancel(handle.id()).await,
+        Err(AggrError::UnknownStrategy(_))
+    ));
+}
+
+#[tokio::test]
+async fn waiting_for_cancellation_wakes_on_cancel() {
+    let registry = StrategyRegistry::new();
+    let handle = registry.start(StrategyType::Twap, 2).await;
+
+    let waiter = tokio::spawn({
+        let handle = handle.clone();
+        async move { handle.cancelled().await }
+    });
+    registry.cancel(handle.id()).await.unwrap();
+    tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+        .await
+        .expect("cancel did not wake the waiter")
+        .unwrap();
+
+    // Already cancelled strategies resolve immediately
+    handle.cancelled().await;
+}
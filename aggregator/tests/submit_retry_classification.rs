@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Context};
+use tonic::{Code, Status};
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::execution::{classify_submit_error, is_permanent_submit_error};
+
+fn grpc_failure(code: Code, message: &str) -> anyhow::Error {
+    anyhow::Error::from(Status::new(code, message)).context("gRPC execute transaction")
+}
+
+fn is_permanent(err: anyhow::Error) -> bool {
+    matches!(classify_submit_error(err), backoff::Error::Permanent(_))
+}
+
+#[test]
+fn rejected_arguments_and_preconditions_fail_fast() {
+    assert!(is_permanent(grpc_failure(
+        Code::InvalidArgument,
+        "invalid transaction bytes"
+    )));
+    assert!(is_permanent(grpc_failure(
+        Code::FailedPrecondition,
+        "gas object is not owned by the sender"
+    )));
+    assert!(is_permanent(grpc_failure(
+        Code::Unauthenticated,
+        "missing credentials"
+    )));
+}
+
+#[test]
+fn network_failures_are_retried() {
+    for code in [
+        Code::Unavailable,
+        Code::DeadlineExceeded,
+        Code::ResourceExhausted,
+        Code::Aborted,
+        Code::Internal,
+        Code::Unknown,
+    ] {
+        assert!(
+            !is_permanent(grpc_failure(code, "transport error")),
+            "{code:?} should be retried"
+        );
+    }
+    assert!(!is_permanent(
+        AggrError::Transport("connection reset by peer".to_string()).into()
+    ));
+}
+
+#[test]
+fn move_aborts_and_gas_shortfalls_fail_fast() {
+    assert!(is_permanent(grpc_failure(
+        Code::Internal,
+        "MoveAbort(MoveLocation { module: pool, function: 12 }, 5) in command 2"
+    )));
+    assert!(is_permanent(anyhow!(
+        "Error checking transaction input objects: GasBalanceTooLow"
+    )));
+    assert!(is_permanent(anyhow!("InsufficientGas")));
+}
+
+#[test]
+fn signing_and_quantization_errors_fail_fast() {
+    assert!(is_permanent(
+        AggrError::Signing("user signing failed".to_string()).into()
+    ));
+    let rejected: anyhow::Result<()> = Err(AggrError::Quantization {
+        constraint: ultra_aggr::quant::QuantConstraint::MinSize,
+        value: 0.5,
+        limit: 1.0,
+    }
+    .into());
+    assert!(is_permanent(rejected.context("build order").unwrap_err()));
+}
+
+#[test]
+fn version_conflicts_stay_permanent() {
+    let err = anyhow!(
+        "Transaction needs to be rebuilt because object 0x5 version 0x10 is unavailable for \
+         consumption"
+    );
+    assert!(is_permanent_submit_error(&err));
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use ultra_aggr::router::flow::{time_to_fill, TimeToFill, TradeFlow};
+use ultra_aggr::venues::book::queue_ahead;
+
+fn estimated_ms(estimate: TimeToFill) -> u64 {
+    match estimate {
+        TimeToFill::Expected { estimated_fill_ms } => estimated_fill_ms,
+        other => panic!("expected a fill estimate, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn more_aggressive_bids_fill_sooner() {
+    // Bids rest best-first below a mid of 1.00
+    let prices = [0.99, 0.98, 0.97, 0.96];
+    let quantities = [100.0, 200.0, 300.0, 400.0];
+
+    let flow = TradeFlow::new(Duration::from_secs(10));
+    flow.record_at_price("SUI_USDC", 400.0, 0.99).await;
+    flow.record_at_price("SUI_USDC", 300.0, 0.98).await;
+    flow.record_at_price("SUI_USDC", 200.0, 0.975).await;
+
+    let mut estimates = Vec::new();
+    for price in [0.995, 0.985, 0.975] {
+        let rate = flow
+            .rate_through_price_per_ms("SUI_USDC", price, true)
+            .await
+            .unwrap();
+        let ahead = queue_ahead(&prices, &quantities, price, true);
+        estimates.push(estimated_ms(time_to_fill(ahead, 50.0, rate, 60_000)));
+    }
+    assert!(
+        estimates.windows(2).all(|pair| pair[0] < pair[1]),
+        "fill time did not grow moving away from mid: {estimates:?}"
+    );
+}
+
+#[tokio::test]
+async fn only_flow_through_the_price_counts() {
+    let flow = TradeFlow::new(Duration::from_secs(10));
+    flow.record_at_price("SUI_USDC", 100.0, 0.99).await;
+
+    // Sells at 0.99 would have reached a bid at 0.99 but not one at 0.98
+    assert!(
+        flow.rate_through_price_per_ms("SUI_USDC", 0.99, true)
+            .await
+            .unwrap()
+            > 0.0
+    );
+    assert_eq!(
+        flow.rate_through_price_per_ms("SUI_USDC", 0.98, true).await,
+        Some(0.0)
+    );
+    // And buys at 0.99 lift an ask at 0.98
+    assert!(
+        flow.rate_through_price_per_ms("SUI_USDC", 0.98, false)
+            .await
+            .unwrap()
+            > 0.0
+    );
+}
+
+#[tokio::test]
+async fn unpriced_flow_falls_back_to_the_pool_rate() {
+    let flow = TradeFlow::new(Duration::from_secs(10));
+    flow.record("SUI_USDC", 100.0).await;
+    assert_eq!(
+        flow.rate_through_price_per_ms("SUI_USDC", 0.5, true).await,
+        flow.rate_per_ms("SUI_USDC").await
+    );
+    assert_eq!(
+        flow.rate_through_price_per_ms("DEEP_SUI", 0.5, true).await,
+        None
+    );
+}
+
+#[test]
+fn slow_flow_is_unlikely_within_horizon() {
+    assert_eq!(
+        time_to_fill(1_000.0, 100.0, 0.01, 5_000),
+        TimeToFill::UnlikelyWithinHorizon { horizon_ms: 5_000 }
+    );
+    assert_eq!(
+        time_to_fill(1_000.0, 100.0, 0.0, 5_000),
+        TimeToFill::UnlikelyWithinHorizon { horizon_ms: 5_000 }
+    );
+    assert_eq!(
+        time_to_fill(100.0, 100.0, 1.0, 5_000),
+        TimeToFill::Expected {
+            estimated_fill_ms: 200
+        }
+    );
+
+    let json = serde_json::to_value(time_to_fill(100.0, 100.0, 1.0, 5_000)).unwrap();
+    assert_eq!(json["status"], "expected");
+    assert_eq!(json["estimated_fill_ms"], 200);
+}
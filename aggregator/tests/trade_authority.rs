@@ -0,0 +1,68 @@
+use sui_sdk::types::base_types::SuiAddress;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::venues::managers::{TradeAuthority, TradeCapHolding};
+
+const MANAGER_ID: &str = "0x00ab";
+const CAP_ID: &str = "0xcafe";
+
+fn authority(owner: SuiAddress, trade_cap: Option<TradeCapHolding>) -> TradeAuthority {
+    TradeAuthority {
+        manager_id: MANAGER_ID.to_string(),
+        owner,
+        trade_cap,
+        allow_listed: Some(vec![CAP_ID.to_string()]),
+    }
+}
+
+fn cap(holder: SuiAddress, manager: &str) -> TradeCapHolding {
+    TradeCapHolding {
+        cap_id: CAP_ID.to_string(),
+        holder: Some(holder),
+        balance_manager_id: manager.to_string(),
+    }
+}
+
+#[test]
+fn missing_trade_cap_rejects_the_order_with_instructions() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owner = SuiAddress::random_for_testing_only();
+
+    let err = authority(owner, None)
+        .verify("MANAGER_1", sender)
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        AggrError::MissingTradeCap { manager, .. } if manager == "MANAGER_1"
+    ));
+    let message = err.to_string();
+    assert!(message.contains("no trade_cap is configured"));
+    assert!(message.contains("mint_trade_cap"));
+}
+
+#[test]
+fn owner_and_cap_holder_may_trade() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owner = SuiAddress::random_for_testing_only();
+
+    assert!(authority(sender, None).verify("MANAGER_1", sender).is_ok());
+    // Ids compare by value, not spelling
+    let held = authority(owner, Some(cap(sender, "0xAB")));
+    assert!(held.verify("MANAGER_1", sender).is_ok());
+}
+
+#[test]
+fn caps_held_elsewhere_minted_for_another_manager_or_revoked_are_rejected() {
+    let sender = SuiAddress::random_for_testing_only();
+    let owner = SuiAddress::random_for_testing_only();
+
+    let elsewhere = authority(owner, Some(cap(owner, MANAGER_ID)));
+    assert!(elsewhere.verify("MANAGER_1", sender).is_err());
+
+    let other_manager = authority(owner, Some(cap(sender, "0xbeef")));
+    assert!(other_manager.verify("MANAGER_1", sender).is_err());
+
+    let mut revoked = authority(owner, Some(cap(sender, MANAGER_ID)));
+    revoked.allow_listed = Some(Vec::new());
+    let err = revoked.verify("MANAGER_1", sender).unwrap_err();
+    assert!(err.to_string().contains("revoked"));
+}
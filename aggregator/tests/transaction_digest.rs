@@ -0,0 +1,62 @@
+use std::str::FromStr;
+
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::transaction::{TransactionData, TransactionKind};
+use ultra_aggr::router::execution::transaction_digest;
+
+const SENDER: &str = "0x0000000000000000000000000000000000000000000000000000000000000007";
+const GAS_PRICE: u64 = 1_000;
+const GAS_BUDGET: u64 = 50_000_000;
+
+/// BCS of an empty programmable transaction from SENDER paying its own gas
+const TX_HEX: &str = "000000000000000000000000000000000000000000000000000000000000000000000007\
+                      000000000000000000000000000000000000000000000000000000000000000007\
+                      e80300000000000080f0fa020000000000";
+/// Its digest as explorers print it
+const TX_DIGEST: &str = "B4mcSoYQj4NVmsmFy2U37ftKqfCfGxh7JHMTupkmtszw";
+
+fn sample_tx() -> TransactionData {
+    let sender = SuiAddress::from_str(SENDER).unwrap();
+    TransactionData::new_with_gas_coins_allow_sponsor(
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish()),
+        sender,
+        Vec::new(),
+        GAS_BUDGET,
+        GAS_PRICE,
+        sender,
+    )
+}
+
+#[test]
+fn fixture_is_the_bcs_of_the_sample_transaction() {
+    assert_eq!(hex::encode(bcs::to_bytes(&sample_tx()).unwrap()), TX_HEX);
+}
+
+#[test]
+fn digest_matches_the_known_sui_digest() {
+    let tx_bcs = hex::decode(TX_HEX).unwrap();
+    assert_eq!(transaction_digest(&tx_bcs).unwrap(), TX_DIGEST);
+    assert_eq!(sample_tx().digest().to_string(), TX_DIGEST);
+}
+
+#[test]
+fn digest_hashes_the_type_name_prefix_not_the_bare_bytes() {
+    let tx_bcs = hex::decode(TX_HEX).unwrap();
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(b"TransactionData::");
+    hasher.update(&tx_bcs);
+    let expected = TransactionDigest::new(hasher.finalize().into());
+    assert_eq!(transaction_digest(&tx_bcs).unwrap(), expected.to_string());
+
+    let bare = TransactionDigest::new(Blake2b::<U32>::digest(&tx_bcs).into());
+    assert_ne!(transaction_digest(&tx_bcs).unwrap(), bare.to_string());
+}
+
+#[test]
+fn bytes_that_are_not_transaction_data_are_rejected() {
+    assert!(transaction_digest(b"\x00\x00compiled transaction data").is_err());
+    assert!(transaction_digest(&[]).is_err());
+}
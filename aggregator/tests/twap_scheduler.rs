@@ -0,0 +1,286 @@
+use futures::future::BoxFuture;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::children::{
+    run_twap, twap_children, ChildFailurePolicy, SystemClock, TwapClock,
+};
+use ultra_aggr::router::routes::{Route, RoutePlan};
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+fn parent(quantity: f64) -> LimitReq {
+    LimitReq {
+        pool: "SUI_USDC".to_string(),
+        price: 1.5,
+        quantity,
+        is_bid: true,
+        client_order_id: "parent".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    }
+}
+
+/// Clock that jumps forward by each requested sleep instead of waiting
+#[derive(Default)]
+struct FakeClock {
+    now: AtomicU64,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl TwapClock for FakeClock {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'_, ()> {
+        self.sleeps.lock().unwrap().push(duration);
+        self.now
+            .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+        Box::pin(async {})
+    }
+}
+
+/// Executor that records what it was handed and when
+struct FakeExecutor<'a> {
+    clock: &'a FakeClock,
+    submissions: Mutex<Vec<(String, f64, u64)>>,
+}
+
+impl<'a> FakeExecutor<'a> {
+    fn new(clock: &'a FakeClock) -> Self {
+        Self {
+            clock,
+            submissions: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, child: &LimitReq) {
+        self.submissions.lock().unwrap().push((
+            child.client_order_id.clone(),
+            child.quantity,
+            self.clock.now_ms(),
+        ));
+    }
+}
+
+#[test]
+fn children_split_parent_quantity() {
+    let children = twap_children(&parent(10.0), 3);
+
+    assert_eq!(children.len(), 3);
+    let ids: Vec<&str> = children
+        .iter()
+        .map(|child| child.client_order_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["parent-0", "parent-1", "parent-2"]);
+    let total: f64 = children.iter().map(|child| child.quantity).sum();
+    assert!((total - 10.0).abs() < 1e-12);
+    assert!(children
+        .iter()
+        .all(|child| child.price == 1.5 && child.is_bid));
+}
+
+#[test]
+fn twap_route_targets_parent_pool() {
+    let plan = RoutePlan::twap(parent(10.0), 4, 500, 10_000_000);
+
+    assert_eq!(plan.route.venue(), "deepbook");
+    assert_eq!(plan.route.pool(), Some("SUI_USDC"));
+    assert_eq!(plan.expected_latency_ms, 1_500);
+    assert!(matches!(plan.route, Route::Twap { slices: 4, .. }));
+}
+
+#[tokio::test]
+async fn slices_are_submitted_one_interval_apart() {
+    let clock = FakeClock::default();
+    let executor = FakeExecutor::new(&clock);
+
+    let result = run_twap(
+        twap_children(&parent(8.0), 4),
+        Duration::from_millis(250),
+        &clock,
+        ChildFailurePolicy::Continue,
+        || false,
+        std::future::pending,
+        |child| {
+            executor.record(&child);
+            async move { Ok(child.client_order_id) }
+        },
+    )
+    .await;
+
+    let submissions = executor.submissions.lock().unwrap().clone();
+    assert_eq!(submissions.len(), 4);
+    let times: Vec<u64> = submissions.iter().map(|(_, _, at)| *at).collect();
+    assert_eq!(times, vec![0, 250, 500, 750]);
+    assert!(submissions.iter().all(|(_, qty, _)| *qty == 2.0));
+    // No wait before the first slice or after the last
+    assert_eq!(clock.sleeps.lock().unwrap().len(), 3);
+
+    assert_eq!(result.slices.len(), 4);
+    assert_eq!(result.skipped, 0);
+    assert!(result.aborted.is_none());
+    assert_eq!(result.failures(), 0);
+    let submitted_ms: Vec<u64> = result.slices.iter().map(|s| s.submitted_ms).collect();
+    assert_eq!(submitted_ms, times);
+    assert_eq!(result.slices[2].execution.as_deref(), Some("parent-2"));
+}
+
+#[tokio::test]
+async fn failed_slice_does_not_stop_the_rest() {
+    let clock = FakeClock::default();
+    let executor = FakeExecutor::new(&clock);
+
+    let result = run_twap(
+        twap_children(&parent(3.0), 3),
+        Duration::from_millis(100),
+        &clock,
+        ChildFailurePolicy::Continue,
+        || false,
+        std::future::pending,
+        |child| {
+            executor.record(&child);
+            async move {
+                if child.client_order_id == "parent-1" {
+                    anyhow::bail!("pre-trade validation failed");
+                }
+                Ok(())
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(executor.submissions.lock().unwrap().len(), 3);
+    assert_eq!(result.failures(), 1);
+    assert_eq!(result.skipped, 0);
+    assert!(result.aborted.is_none());
+}
+
+#[tokio::test]
+async fn open_breaker_aborts_remaining_slices() {
+    let clock = FakeClock::default();
+    let executor = FakeExecutor::new(&clock);
+
+    let result = run_twap(
+        twap_children(&parent(5.0), 5),
+        Duration::from_millis(100),
+        &clock,
+        ChildFailurePolicy::Continue,
+        || false,
+        std::future::pending,
+        |child| {
+            executor.record(&child);
+            async move {
+                if child.client_order_id == "parent-2" {
+                    let open = AggrError::CircuitOpen("deepbook:SUI_USDC".to_string());
+                    return Err(anyhow::Error::from(open));
+                }
+                Ok(())
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(executor.submissions.lock().unwrap().len(), 3);
+    assert_eq!(result.slices.len(), 3);
+    assert_eq!(result.skipped, 2);
+    assert!(result.aborted.unwrap().contains("deepbook:SUI_USDC"));
+    // Nothing waits for slices that will never go out
+    assert_eq!(clock.now_ms(), 200);
+}
+
+#[tokio::test]
+async fn cancellation_skips_slices_not_yet_due() {
+    let clock = FakeClock::default();
+    let executor = FakeExecutor::new(&clock);
+
+    let result = run_twap(
+        twap_children(&parent(4.0), 4),
+        Duration::from_millis(1_000),
+        &clock,
+        ChildFailurePolicy::Continue,
+        || clock.now_ms() >= 2_000,
+        std::future::pending,
+        |child| {
+            executor.record(&child);
+            async move { Ok(()) }
+        },
+    )
+    .await;
+
+    assert_eq!(executor.submissions.lock().unwrap().len(), 2);
+    assert_eq!(result.skipped, 2);
+    assert!(result.aborted.is_some());
+}
+
+#[tokio::test]
+async fn failed_slice_skips_the_rest_under_abort() {
+    let clock = FakeClock::default();
+    let executor = FakeExecutor::new(&clock);
+
+    let result = run_twap(
+        twap_children(&parent(4.0), 4),
+        Duration::from_millis(100),
+        &clock,
+        ChildFailurePolicy::Abort,
+        || false,
+        std::future::pending,
+        |child| {
+            executor.record(&child);
+            async move {
+                if child.client_order_id == "parent-1" {
+                    anyhow::bail!("pre-trade validation failed");
+                }
+                Ok(())
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(executor.submissions.lock().unwrap().len(), 2);
+    assert_eq!(result.failures(), 1);
+    assert_eq!(result.skipped, 2);
+    assert!(result.aborted.unwrap().contains("pre-trade validation"));
+}
+
+#[tokio::test]
+async fn cancellation_interrupts_the_wait_between_slices() {
+    let cancelled = AtomicBool::new(false);
+    let cancel = tokio::sync::Notify::new();
+    let submitted = AtomicU64::new(0);
+
+    let run = run_twap(
+        twap_children(&parent(3.0), 3),
+        Duration::from_secs(3_600),
+        &SystemClock,
+        ChildFailurePolicy::Continue,
+        || cancelled.load(Ordering::SeqCst),
+        || cancel.notified(),
+        |_child| {
+            submitted.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(()) }
+        },
+    );
+    let cancel_after_first = async {
+        while submitted.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        cancelled.store(true, Ordering::SeqCst);
+        cancel.notify_waiters();
+    };
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(run, cancel_after_first)
+    })
+    .await
+    .expect("cancel did not interrupt the hour-long interval");
+
+    assert_eq!(submitted.load(Ordering::SeqCst), 1);
+    assert_eq!(result.slices.len(), 1);
+    assert_eq!(result.skipped, 2);
+    assert_eq!(result.aborted.as_deref(), Some("strategy cancelled"));
+}
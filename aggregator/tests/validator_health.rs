@@ -0,0 +1,40 @@
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::ValidatorSelector;
+
+const ENDPOINT: &str = "https://fullnode.example:443";
+
+#[tokio::test]
+async fn failing_probes_remove_endpoint_and_success_restores_it() {
+    let selector = ValidatorSelector::default();
+    selector.register(ENDPOINT.to_string()).await;
+
+    assert!(selector.record_probe_result(ENDPOINT, false, 2).await);
+    assert_eq!(selector.select_best().await.as_deref(), Some(ENDPOINT));
+
+    assert!(!selector.record_probe_result(ENDPOINT, false, 2).await);
+    assert!(!selector.is_healthy(ENDPOINT).await);
+    assert_eq!(selector.select_best().await, None);
+
+    assert!(selector.record_probe_result(ENDPOINT, true, 2).await);
+    assert_eq!(selector.select_best().await.as_deref(), Some(ENDPOINT));
+}
+
+#[tokio::test]
+async fn orders_rejected_without_enough_healthy_validators() {
+    let selector = ValidatorSelector::default();
+    let err = selector.require_healthy(1).await.unwrap_err();
+    assert!(matches!(
+        err,
+        AggrError::NoHealthyValidators {
+            healthy: 0,
+            required: 1
+        }
+    ));
+
+    selector.register(ENDPOINT.to_string()).await;
+    assert_eq!(selector.require_healthy(1).await.unwrap(), 1);
+
+    selector.mark_unhealthy(ENDPOINT).await;
+    assert!(selector.require_healthy(1).await.is_err());
+    assert_eq!(selector.require_healthy(0).await.unwrap(), 0);
+}
@@ -0,0 +1,59 @@
+use ultra_aggr::metrics::VENUE_PANICS;
+use ultra_aggr::router::routes::RoutePlan;
+use ultra_aggr::router::selector::{evaluate_venue, gather_alternatives, VenueEvaluation};
+use ultra_aggr::venues::adapter::{LimitOrderType, LimitReq};
+
+fn plan(price: f64) -> RoutePlan {
+    let req = LimitReq {
+        pool: "SUI_USDC".to_string(),
+        price,
+        quantity: 10.0,
+        is_bid: true,
+        client_order_id: "1".to_string(),
+        pay_with_deep: false,
+        expiration_ms: None,
+        reduce_only: false,
+        post_only: None,
+        order_type: LimitOrderType::Gtc,
+        manager: None,
+    };
+    RoutePlan::deepbook_single(req, price, 0.0, 0.0, 400, 250, 0.0)
+}
+
+async fn quoting_venue(price: f64) -> anyhow::Result<RoutePlan> {
+    Ok(plan(price))
+}
+
+async fn panicking_venue(message: &'static str) -> anyhow::Result<RoutePlan> {
+    panic!("{message}");
+}
+
+#[tokio::test]
+async fn panicking_venue_does_not_drop_other_alternatives() {
+    let panics_before = VENUE_PANICS.with_label_values(&["broken"]).get();
+    let evaluations: Vec<VenueEvaluation<'_>> = vec![
+        ("healthy", Box::pin(quoting_venue(1.0))),
+        (
+            "broken",
+            Box::pin(panicking_venue("SDK invariant violated")),
+        ),
+        ("pricier", Box::pin(quoting_venue(1.1))),
+    ];
+
+    let alternatives = gather_alternatives(evaluations).await;
+    assert_eq!(alternatives.len(), 2);
+    assert_eq!(
+        VENUE_PANICS.with_label_values(&["broken"]).get(),
+        panics_before + 1.0
+    );
+}
+
+#[tokio::test]
+async fn panic_becomes_an_error_with_context() {
+    let err = evaluate_venue("broken", panicking_venue("lock poisoned"))
+        .await
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("broken"));
+    assert!(message.contains("lock poisoned"));
+}
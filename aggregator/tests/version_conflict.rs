@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use anyhow::anyhow;
+use ultra_aggr::router::execution::{is_version_conflict, retry_on_version_conflict};
+
+const CONFLICT: &str = "Transaction needs to be rebuilt because object 0x5 version 0x10 is \
+                        unavailable for consumption, current version: 0x11";
+
+#[tokio::test]
+async fn conflict_succeeds_after_rebuild() {
+    let builds = AtomicU32::new(0);
+    let result = retry_on_version_conflict(3, || async {
+        // Each call stands in for a fresh compile against current object versions
+        match builds.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(anyhow!(CONFLICT)),
+            n => Ok(n),
+        }
+    })
+    .await;
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(builds.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn rebuilds_are_capped() {
+    let builds = AtomicU32::new(0);
+    let result: anyhow::Result<()> = retry_on_version_conflict(2, || async {
+        builds.fetch_add(1, Ordering::SeqCst);
+        Err(anyhow!(CONFLICT))
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(builds.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn other_errors_are_not_rebuilt() {
+    let builds = AtomicU32::new(0);
+    let result: anyhow::Result<()> = retry_on_version_conflict(3, || async {
+        builds.fetch_add(1, Ordering::SeqCst);
+        Err(anyhow!("InsufficientGas"))
+    })
+    .await;
+    assert!(!is_version_conflict(&result.unwrap_err()));
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+}
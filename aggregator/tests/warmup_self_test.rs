@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use ultra_aggr::errors::AggrError;
+use ultra_aggr::router::execution::simulate_or_dry_run;
+use ultra_aggr::transport::grpc::sui::rpc::v2::{
+    ExecutedTransaction, ExecutionError, ExecutionStatus, TransactionEffects,
+};
+use ultra_aggr::transport::jsonrpc::DryRunResp;
+
+fn dry_run(status: serde_json::Value) -> DryRunResp {
+    DryRunResp {
+        effects: Some(serde_json::json!({
+            "status": status,
+            "gasUsed": { "computationCost": "1000000", "storageCost": "2000000" }
+        })),
+    }
+}
+
+fn simulated(success: bool) -> ExecutedTransaction {
+    ExecutedTransaction {
+        effects: Some(TransactionEffects {
+            status: Some(ExecutionStatus {
+                success: Some(success),
+                error: (!success).then(|| ExecutionError {
+                    description: Some("MoveAbort in command 0".to_string()),
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn rejection(err: &anyhow::Error) -> Option<&str> {
+    match err.downcast_ref::<AggrError>() {
+        Some(AggrError::SimulationRejected(reason)) => Some(reason),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn without_grpc_a_failing_dry_run_fails_the_self_test() {
+    let err = simulate_or_dry_run(
+        || async { Ok(None) },
+        || async {
+            Ok(dry_run(serde_json::json!({
+                "status": "failure",
+                "error": "InsufficientCoinBalance in command 0"
+            })))
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        rejection(&err),
+        Some("InsufficientCoinBalance in command 0")
+    );
+}
+
+#[tokio::test]
+async fn without_grpc_a_passing_dry_run_passes() {
+    let gas = simulate_or_dry_run(
+        || async { Ok(None) },
+        || async { Ok(dry_run(serde_json::json!({ "status": "success" }))) },
+    )
+    .await
+    .unwrap();
+    assert_eq!(gas, Some(3_000_000));
+}
+
+#[tokio::test]
+async fn unreachable_dry_run_fails_rather_than_passing() {
+    let err = simulate_or_dry_run(
+        || async { Ok(None) },
+        || async { Err(anyhow::anyhow!("connection refused")) },
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("connection refused"), "{err}");
+}
+
+#[tokio::test]
+async fn aborted_grpc_simulation_fails_without_a_dry_run() {
+    let dry_runs = AtomicU32::new(0);
+    let err = simulate_or_dry_run(
+        || async { Ok(Some(simulated(false))) },
+        || async {
+            dry_runs.fetch_add(1, Ordering::SeqCst);
+            Ok(dry_run(serde_json::json!({ "status": "success" })))
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(rejection(&err), Some("MoveAbort in command 0"));
+    assert_eq!(dry_runs.load(Ordering::SeqCst), 0);
+
+    assert!(simulate_or_dry_run(
+        || async { Ok(Some(simulated(true))) },
+        || async { Err(anyhow::anyhow!("no dry run after a gRPC simulation")) },
+    )
+    .await
+    .is_ok());
+}